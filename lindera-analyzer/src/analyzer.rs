@@ -8,7 +8,7 @@ use lindera_core::LinderaResult;
 use lindera_filter::character_filter::{correct_offset, BoxCharacterFilter, CharacterFilterLoader};
 use lindera_filter::token::Token;
 use lindera_filter::token_filter::{BoxTokenFilter, TokenFilterLoader};
-use lindera_tokenizer::tokenizer::Tokenizer;
+use lindera_tokenizer::tokenizer::{Tokenizer, TokenizerConfig};
 
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 pub struct AnalyzerConfig {
@@ -28,6 +28,12 @@ impl AnalyzerConfig {
 
         Ok(Self { inner: args })
     }
+
+    pub fn from_value(value: &Value) -> LinderaResult<Self> {
+        Ok(Self {
+            inner: value.clone(),
+        })
+    }
 }
 
 pub struct Analyzer {
@@ -66,8 +72,9 @@ impl Analyzer {
         let arg_bytes = serde_json::to_vec(args_value)
             .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))?;
 
-        let tokenizer_config = serde_json::from_slice(&arg_bytes)
+        let tokenizer_config: TokenizerConfig = serde_json::from_slice(&arg_bytes)
             .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))?;
+        let detail_schema = tokenizer_config.dictionary.detail_schema.clone();
         let tokenizer = Tokenizer::from_config(tokenizer_config)?;
 
         let mut token_filters: Vec<BoxTokenFilter> = Vec::new();
@@ -76,9 +83,22 @@ impl Analyzer {
             for token_filter_setting in token_filter_settings {
                 let token_filter_name = token_filter_setting["kind"].as_str();
                 if let Some(token_filter_name) = token_filter_name {
+                    // The detail schema of the dictionary is the default one of the token filters.
+                    let mut args = token_filter_setting["args"].clone();
+                    if let (Some(detail_schema), Some(args)) =
+                        (&detail_schema, args.as_object_mut())
+                    {
+                        if !args.contains_key("detail_schema") {
+                            args.insert(
+                                "detail_schema".to_string(),
+                                serde_json::to_value(detail_schema)
+                                    .map_err(|err| LinderaErrorKind::Serialize.with_error(err))?,
+                            );
+                        }
+                    }
                     token_filters.push(TokenFilterLoader::load_from_value(
                         token_filter_name,
-                        &token_filter_setting["args"],
+                        &args,
                     )?);
                 }
             }
@@ -87,6 +107,10 @@ impl Analyzer {
         Ok(Self::new(character_filters, tokenizer, token_filters))
     }
 
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        Self::from_config(&AnalyzerConfig::from_slice(data)?)
+    }
+
     pub fn new(
         character_filters: Vec<BoxCharacterFilter>,
         tokenizer: Tokenizer,
@@ -100,11 +124,31 @@ impl Analyzer {
     }
 
     pub fn analyze(&self, text: &str) -> LinderaResult<Vec<Token>> {
-        let mut normalized_text = text.to_string();
+        let (normalized_text, filtered_offsets) = self.apply_character_filters(text)?;
+
+        // Tokenize.
+        let tmp_tokens = self.tokenizer.tokenize(&normalized_text)?;
+
+        self.apply_token_filters(tmp_tokens, &filtered_offsets)
+    }
+
+    /// Analyze the text into the tokens of its `n` best segmentations, in increasing order of
+    /// cost. The character filters are applied once, and the token filters to each segmentation.
+    /// See `Tokenizer::n_best`.
+    pub fn analyze_n_best(&self, text: &str, n: usize) -> LinderaResult<Vec<Vec<Token>>> {
+        let (normalized_text, filtered_offsets) = self.apply_character_filters(text)?;
+
+        // Tokenize.
+        self.tokenizer
+            .n_best(&normalized_text, n)?
+            .into_iter()
+            .map(|tmp_tokens| self.apply_token_filters(tmp_tokens, &filtered_offsets))
+            .collect()
+    }
 
-        let mut text_len_vec: Vec<usize> = Vec::new();
-        let mut offsets_vec: Vec<Vec<usize>> = Vec::new();
-        let mut diffs_vec: Vec<Vec<i64>> = Vec::new();
+    fn apply_character_filters(&self, text: &str) -> LinderaResult<(String, FilteredOffsets)> {
+        let mut normalized_text = text.to_string();
+        let mut filtered_offsets = FilteredOffsets::default();
 
         // Appy character filters.
         for character_filter in &self.character_filters {
@@ -112,24 +156,37 @@ impl Analyzer {
 
             if !offsets.is_empty() {
                 // Record the offsets of each character filter.
-                offsets_vec.insert(0, offsets);
+                filtered_offsets.offsets_vec.insert(0, offsets);
 
                 // Record the diffs of each character filter.
-                diffs_vec.insert(0, diffs);
+                filtered_offsets.diffs_vec.insert(0, diffs);
 
                 // Record the length of the text after each character filter is applied.
-                text_len_vec.insert(0, new_text.len());
+                filtered_offsets.text_len_vec.insert(0, new_text.len());
             }
 
             normalized_text = new_text;
         }
 
-        // Tokenize.
-        let mut tmp_tokens = self.tokenizer.tokenize(&normalized_text)?;
+        Ok((normalized_text, filtered_offsets))
+    }
 
+    fn apply_token_filters(
+        &self,
+        mut tmp_tokens: Vec<lindera_tokenizer::token::Token>,
+        filtered_offsets: &FilteredOffsets,
+    ) -> LinderaResult<Vec<Token>> {
         // Make analyzed tokens.
         let mut tokens = Vec::new();
         for token in tmp_tokens.iter_mut() {
+            let details = token
+                .get_details()
+                .ok_or_else(|| {
+                    LinderaErrorKind::Content.with_error(anyhow::anyhow!("unknown error"))
+                })?
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<String>>();
             tokens.push(Token {
                 text: token.text.to_string(),
                 byte_start: token.byte_start,
@@ -137,14 +194,9 @@ impl Analyzer {
                 position: token.position,
                 position_length: token.position_length,
                 word_id: token.word_id,
-                details: token
-                    .get_details()
-                    .ok_or_else(|| {
-                        LinderaErrorKind::Content.with_error(anyhow::anyhow!("unknown error"))
-                    })?
-                    .iter()
-                    .map(|s| s.to_string())
-                    .collect::<Vec<String>>(),
+                details,
+                word_cost: token.word_cost,
+                connection_cost: token.connection_cost,
             });
         }
 
@@ -156,14 +208,22 @@ impl Analyzer {
         // Correct token offsets
         for token in tokens.iter_mut() {
             // Override details.
-            for (i, offsets) in offsets_vec.iter().enumerate() {
+            for (i, offsets) in filtered_offsets.offsets_vec.iter().enumerate() {
                 // Override start.
-                token.byte_start =
-                    correct_offset(token.byte_start, offsets, &diffs_vec[i], text_len_vec[i]);
+                token.byte_start = correct_offset(
+                    token.byte_start,
+                    offsets,
+                    &filtered_offsets.diffs_vec[i],
+                    filtered_offsets.text_len_vec[i],
+                );
 
                 // Override end.
-                token.byte_end =
-                    correct_offset(token.byte_end, offsets, &diffs_vec[i], text_len_vec[i]);
+                token.byte_end = correct_offset(
+                    token.byte_end,
+                    offsets,
+                    &filtered_offsets.diffs_vec[i],
+                    filtered_offsets.text_len_vec[i],
+                );
             }
         }
 
@@ -171,6 +231,15 @@ impl Analyzer {
     }
 }
 
+/// The offsets recorded by the character filters that changed the text, from the last applied
+/// to the first, to map the token offsets back onto the original text.
+#[derive(Default)]
+struct FilteredOffsets {
+    text_len_vec: Vec<usize>,
+    offsets_vec: Vec<Vec<usize>>,
+    diffs_vec: Vec<Vec<i64>>,
+}
+
 impl Clone for Analyzer {
     fn clone(&self) -> Self {
         let mut character_filters: Vec<BoxCharacterFilter> = Vec::new();
@@ -199,6 +268,9 @@ mod tests {
     ))]
     use crate::analyzer::{Analyzer, AnalyzerConfig};
 
+    #[cfg(all(feature = "ipadic", feature = "filter",))]
+    use std::path::PathBuf;
+
     #[test]
     #[cfg(all(feature = "ipadic", feature = "filter",))]
     fn test_analyzer_config_from_slice() {
@@ -715,4 +787,155 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    #[cfg(all(feature = "ipadic", feature = "filter",))]
+    fn test_ipadic_analyzer_analyze_unicode_normalize_offsets() {
+        let config_str = r#"
+        {
+            "character_filters": [
+                {
+                    "kind": "unicode_normalize",
+                    "args": {
+                        "kind": "nfkc"
+                    }
+                }
+            ],
+            "tokenizer": {
+                "dictionary": {
+                    "kind": "ipadic"
+                },
+                "mode": "normal"
+            }
+        }
+        "#;
+        let analyzer_config = AnalyzerConfig::from_slice(config_str.as_bytes()).unwrap();
+
+        let analyzer = Analyzer::from_config(&analyzer_config).unwrap();
+
+        let text = "ＡＢＣ";
+        let tokens = analyzer.analyze(text).unwrap();
+        assert_eq!(tokens.len(), 1);
+
+        // The text is normalized, but the offsets point at the original wide characters.
+        let token = &tokens[0];
+        assert_eq!(token.text, "ABC".to_string());
+        assert_eq!(token.byte_start, 0);
+        assert_eq!(token.byte_end, 9);
+        assert_eq!(&text[token.byte_start..token.byte_end], "ＡＢＣ");
+    }
+
+    #[test]
+    #[cfg(all(feature = "ipadic", feature = "filter",))]
+    fn test_ipadic_analyzer_from_file() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../resources")
+            .join("lindera_ipadic_mapping_keep_tags_conf.json");
+        let analyzer_config = AnalyzerConfig::from_file(&path).unwrap();
+
+        let analyzer = Analyzer::from_config(&analyzer_config).unwrap();
+
+        let text = "リンデラは形態素解析を行います。";
+        let tokens = analyzer.analyze(text).unwrap();
+        let texts = tokens
+            .iter()
+            .map(|token| token.text.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(texts, vec!["形態素", "解析"]);
+
+        // The offsets point at the text before the mapping is applied.
+        let token = &tokens[0];
+        assert_eq!(token.byte_start, 15);
+        assert_eq!(token.byte_end, 24);
+        assert_eq!(&text[token.byte_start..token.byte_end], "形態素");
+    }
+
+    #[test]
+    #[cfg(all(feature = "ipadic", feature = "filter",))]
+    fn test_ipadic_analyzer_from_slice() {
+        let config_str = r#"
+        {
+            "tokenizer": {
+                "dictionary": {
+                    "kind": "ipadic"
+                },
+                "mode": "normal"
+            }
+        }
+        "#;
+        let result = Analyzer::from_slice(config_str.as_bytes());
+
+        assert_eq!(true, result.is_ok());
+    }
+
+    #[test]
+    #[cfg(all(feature = "ipadic", feature = "filter",))]
+    fn test_analyzer_detail_schema_ipadic() {
+        // the detail schema of the dictionary is passed to the token filters
+        let config_str = r#"
+        {
+            "tokenizer": {
+                "dictionary": {
+                    "kind": "ipadic",
+                    "detail_schema": {
+                        "pos": [0, 1, 2, 3],
+                        "base_form": 7
+                    }
+                },
+                "mode": "normal"
+            },
+            "token_filters": [
+                {
+                    "kind": "japanese_base_form",
+                    "args": {
+                        "kind": "ipadic"
+                    }
+                }
+            ]
+        }
+        "#;
+        let analyzer = Analyzer::from_slice(config_str.as_bytes()).unwrap();
+
+        let tokens = analyzer.analyze("羽田空港").unwrap();
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(&tokens[0].text, "ハネダクウコウ");
+    }
+
+    #[test]
+    #[cfg(all(feature = "ipadic", feature = "filter",))]
+    fn test_analyzer_add_user_word_ipadic() {
+        use lindera_dictionary::DictionaryKind;
+
+        let config_str = r#"
+        {
+            "tokenizer": {
+                "dictionary": {
+                    "kind": "ipadic"
+                },
+                "mode": "normal"
+            }
+        }
+        "#;
+        let mut analyzer = Analyzer::from_slice(config_str.as_bytes()).unwrap();
+        analyzer.tokenizer.add_user_word(
+            "東京スカイツリー",
+            "カスタム名詞",
+            "トウキョウスカイツリー",
+            -10000,
+        );
+
+        // the accessors find the fields of the added word in the layout of IPADIC
+        let tokens = analyzer.analyze("東京スカイツリーの最寄り駅").unwrap();
+        assert_eq!(&tokens[0].text, "東京スカイツリー");
+        assert_eq!(tokens[0].pos(&DictionaryKind::IPADIC), Some("カスタム名詞"));
+        assert_eq!(
+            tokens[0].base_form(&DictionaryKind::IPADIC),
+            Some("東京スカイツリー")
+        );
+        assert_eq!(
+            tokens[0].reading(&DictionaryKind::IPADIC),
+            Some("トウキョウスカイツリー")
+        );
+        assert_eq!(tokens[0].pronunciation(&DictionaryKind::IPADIC), None);
+    }
 }
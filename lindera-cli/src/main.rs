@@ -1,21 +1,28 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fs::File;
-use std::io::{self, BufRead, BufReader};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
 use std::path::PathBuf;
 use std::str::FromStr;
+#[cfg(feature = "bench")]
+use std::time::{Duration, Instant};
 
 use std::path::Path;
 
 use clap::{Parser, Subcommand};
+use encoding_rs::{EncoderResult, Encoding, UTF_8};
+use encoding_rs_io::DecodeReaderBytesBuilder;
+use rayon::prelude::*;
 use serde_json::Value;
 
 use lindera::Analyzer;
 #[cfg(feature = "filter")]
-use lindera::{CharacterFilterLoader, TokenFilterLoader};
+use lindera::{AnalyzerConfig, CharacterFilterLoader, FilterDescription, TokenFilterLoader};
 
 use lindera::{
-    BoxCharacterFilter, BoxTokenFilter, DictionaryBuilderResolver, DictionaryConfig,
+    BoxCharacterFilter, BoxTokenFilter, Dictionary, DictionaryBuilderResolver, DictionaryConfig,
     DictionaryKind, DictionaryLoader, LinderaError, LinderaErrorKind, LinderaResult, Mode,
-    Tokenizer, UserDictionaryConfig,
+    Tokenizer, UserDictionaryConfig, ValidationReport,
 };
 
 #[derive(Debug, Parser)]
@@ -28,8 +35,14 @@ struct Args {
 #[derive(Debug, Subcommand)]
 enum Commands {
     List(ListArgs),
-    Tokenize(TokenizeArgs),
+    #[cfg(feature = "filter")]
+    Filters(FiltersArgs),
+    Tokenize(Box<TokenizeArgs>),
     Build(BuildArgs),
+    Info(InfoArgs),
+    DumpMatrix(DumpMatrixArgs),
+    #[cfg(feature = "bench")]
+    Bench(BenchArgs),
 }
 
 #[derive(Debug, clap::Args)]
@@ -40,6 +53,15 @@ enum Commands {
 )]
 struct ListArgs {}
 
+#[cfg(feature = "filter")]
+#[derive(Debug, clap::Args)]
+#[clap(
+    author,
+    about = "List the character filters and token filters with example arguments",
+    version
+)]
+struct FiltersArgs {}
+
 #[derive(Debug, clap::Args)]
 #[clap(
     author,
@@ -51,6 +73,11 @@ struct TokenizeArgs {
     dic_type: Option<DictionaryKind>,
     #[clap(short = 'd', long = "dic-dir", help = "Dictionary directory path")]
     dic_dir: Option<PathBuf>,
+    #[clap(
+        long = "dic-archive",
+        help = "Dictionary archive (.tar.gz) path. The archive is extracted to a temporary directory"
+    )]
+    dic_archive: Option<PathBuf>,
     #[clap(
         short = 'u',
         long = "user-dic-file",
@@ -60,23 +87,109 @@ struct TokenizeArgs {
     #[clap(
         short = 'm',
         long = "mode",
-        default_value = "normal",
-        help = "Tokenization mode. normal"
+        help = "Tokenization mode. normal, decompose (alias: search), or decompose:<json> to set the penalty of decompose mode. Defaults to the mode suited to the dictionary type"
     )]
-    mode: Mode,
+    mode: Option<Mode>,
     #[clap(
         short = 'o',
         long = "output-format",
         default_value = "mecab",
-        help = "Output format"
+        help = "Output format. mecab, wakati, json, jsonl, csv or concat"
     )]
     output_format: String,
     #[clap(short = 'C', long = "character-filter", help = "Character filter")]
     character_filters: Option<Vec<String>>,
     #[clap(short = 'T', long = "token-filter", help = "Token filter")]
     token_filters: Option<Vec<String>>,
-    #[clap(help = "Input text file path")]
-    input_file: Option<PathBuf>,
+    #[clap(
+        long = "filters-dir",
+        help = "Directory of filter JSON files, each with a stage (character or token), a type and args. The filters are applied in the order of the file names, before the filters of the -C and -T options"
+    )]
+    filters_dir: Option<PathBuf>,
+    #[clap(
+        long = "count",
+        help = "Output the frequency of each token text in the whole input instead of the tokens"
+    )]
+    count: bool,
+    #[clap(
+        long = "debug-costs",
+        help = "Output the word cost and the connection cost of each token in the json and jsonl formats. It also applies on top of the config file given with --config"
+    )]
+    debug_costs: bool,
+    #[clap(
+        long = "char-offsets",
+        help = "Output the character offsets (char_start, char_end) of each token instead of the byte offsets in the json and jsonl formats"
+    )]
+    char_offsets: bool,
+    #[clap(
+        long = "sentence-relative-offsets",
+        help = "Split each line into sentences after 。, ！, ？, ! and ?, and output the offsets of each token relative to the start of its sentence and the index of the sentence (sentence_index) in the json and jsonl formats"
+    )]
+    sentence_relative_offsets: bool,
+    #[clap(
+        long = "preserve-whitespace",
+        help = "Output runs of whitespace as tokens, and keep the whitespace at the start and end of each line. It also applies on top of the config file given with --config"
+    )]
+    preserve_whitespace: bool,
+    #[clap(
+        long = "wakati-escape",
+        help = "Escape the spaces and backslashes in the token text with a backslash in the wakati format, so that the output can be split back into the tokens"
+    )]
+    wakati_escape: bool,
+    #[clap(
+        long = "mecab-field-sep",
+        help = "Separator between the token text and the details in the mecab format. Defaults to a tab"
+    )]
+    mecab_field_sep: Option<String>,
+    #[clap(
+        long = "mecab-feature-sep",
+        help = "Separator between the details in the mecab format. Defaults to a comma"
+    )]
+    mecab_feature_sep: Option<String>,
+    #[clap(
+        short = 'c',
+        long = "config",
+        help = "Analyzer config file path. If specified, the dictionary, user dictionary, mode and filter options are ignored, while --debug-costs and --preserve-whitespace enable their setting on top of the config"
+    )]
+    config: Option<PathBuf>,
+    #[clap(
+        long = "threads",
+        default_value = "1",
+        help = "Number of threads used to tokenize the input files concurrently. The output of each file is kept together, in the order of the files"
+    )]
+    threads: usize,
+    #[clap(
+        short = 'N',
+        long = "nbest",
+        default_value = "1",
+        help = "Number of best segmentations to output for each line. The segmentations of a line are separated by a line of ---"
+    )]
+    nbest: usize,
+    #[clap(
+        long = "whole-file",
+        help = "Tokenize each input as one document instead of line by line. A line break between two Chinese or Japanese characters is removed, so that a word wrapped over two lines is tokenized as one word, and the offsets are those in the input"
+    )]
+    whole_file: bool,
+    #[clap(
+        long = "input-encoding",
+        default_value = "utf-8",
+        help = "Encoding of the input, such as shift_jis or euc-jp. The input is decoded to UTF-8 before it is tokenized"
+    )]
+    input_encoding: String,
+    #[clap(
+        long = "output-encoding",
+        default_value = "utf-8",
+        help = "Encoding of the output, such as shift_jis or euc-jp"
+    )]
+    output_encoding: String,
+    #[clap(
+        long = "output-fallback",
+        default_value = "?",
+        help = "Replacement of the characters that cannot be represented in the output encoding"
+    )]
+    output_fallback: String,
+    #[clap(help = "Input text file paths. If not specified, the text is read from stdin")]
+    input_files: Vec<PathBuf>,
 }
 
 #[derive(Debug, clap::Args)]
@@ -86,18 +199,92 @@ struct BuildArgs {
     build_user_dic: bool,
     #[clap(short = 't', long = "dic-type", help = "Dictionary type")]
     dic_type: DictionaryKind,
+    #[clap(
+        long = "skip-matrix",
+        help = "Reuse the connection cost matrix previously built in the destination path"
+    )]
+    skip_matrix: bool,
+    #[clap(
+        long = "dry-run",
+        help = "Parse and validate the source files without writing the dictionary"
+    )]
+    dry_run: bool,
     #[clap(help = "Dictionary source path")]
     src_path: PathBuf,
     #[clap(help = "Dictionary destination path")]
     dest_path: PathBuf,
 }
 
+#[derive(Debug, clap::Args)]
+#[clap(
+    author,
+    about = "Show information about a morphological analysis dictionary",
+    version
+)]
+struct InfoArgs {
+    #[clap(short = 't', long = "dic-type", help = "Dictionary type")]
+    dic_type: Option<DictionaryKind>,
+    #[clap(short = 'd', long = "dic-dir", help = "Dictionary directory path")]
+    dic_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, clap::Args)]
+#[clap(
+    author,
+    about = "Dump the connection cost matrix of a morphological analysis dictionary as CSV",
+    version
+)]
+struct DumpMatrixArgs {
+    #[clap(short = 't', long = "dic-type", help = "Dictionary type")]
+    dic_type: Option<DictionaryKind>,
+    #[clap(short = 'd', long = "dic-dir", help = "Dictionary directory path")]
+    dic_dir: Option<PathBuf>,
+}
+
+#[cfg(feature = "bench")]
+#[derive(Debug, clap::Args)]
+#[clap(
+    author,
+    about = "Measure the tokenization throughput and latency",
+    version
+)]
+struct BenchArgs {
+    #[clap(short = 't', long = "dic-type", help = "Dictionary type")]
+    dic_type: Option<DictionaryKind>,
+    #[clap(short = 'd', long = "dic-dir", help = "Dictionary directory path")]
+    dic_dir: Option<PathBuf>,
+    #[clap(
+        short = 'u',
+        long = "user-dic-file",
+        help = "User dictionary file path"
+    )]
+    user_dic_file: Option<PathBuf>,
+    #[clap(
+        short = 'm',
+        long = "mode",
+        help = "Tokenization mode. normal, decompose (alias: search), or decompose:<json> to set the penalty of decompose mode. Defaults to the mode suited to the dictionary type"
+    )]
+    mode: Option<Mode>,
+    #[clap(
+        short = 'n',
+        long = "iterations",
+        default_value = "10",
+        help = "Number of times the whole input is tokenized"
+    )]
+    iterations: usize,
+    #[clap(help = "Input text file path")]
+    input_file: PathBuf,
+}
+
 #[derive(Debug, Clone, Copy)]
 /// Formatter type
 pub enum Format {
     Mecab,
     Wakati,
     Json,
+    JsonL,
+    Csv,
+    Concat,
 }
 
 impl FromStr for Format {
@@ -108,6 +295,9 @@ impl FromStr for Format {
             "mecab" => Ok(Format::Mecab),
             "wakati" => Ok(Format::Wakati),
             "json" => Ok(Format::Json),
+            "jsonl" => Ok(Format::JsonL),
+            "csv" => Ok(Format::Csv),
+            "concat" => Ok(Format::Concat),
             _ => Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!("Invalid format: {}", s))),
         }
     }
@@ -118,8 +308,14 @@ fn main() -> LinderaResult<()> {
 
     match args.command {
         Commands::List(args) => list(args),
-        Commands::Tokenize(args) => tokenize(args),
-        Commands::Build(args) => build(args),
+        #[cfg(feature = "filter")]
+        Commands::Filters(args) => filters(args),
+        Commands::Tokenize(args) => tokenize(*args),
+        Commands::Build(args) => build(args, &mut io::stdout()),
+        Commands::Info(args) => info(args),
+        Commands::DumpMatrix(args) => dump_matrix(args),
+        #[cfg(feature = "bench")]
+        Commands::Bench(args) => bench(args),
     }
 }
 
@@ -130,7 +326,53 @@ fn list(_args: ListArgs) -> LinderaResult<()> {
     Ok(())
 }
 
-fn mecab_output(mut tokens: Vec<Value>) -> LinderaResult<()> {
+#[cfg(feature = "filter")]
+fn filters(_args: FiltersArgs) -> LinderaResult<()> {
+    let mut writer = BufWriter::new(io::stdout());
+    filters_output(&mut writer)?;
+    writer
+        .flush()
+        .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))
+}
+
+/// Write the name, the description and an example option of each filter the loaders can build,
+/// the character filters first.
+#[cfg(feature = "filter")]
+fn filters_output<W: Write>(writer: &mut W) -> LinderaResult<()> {
+    let sections: [(&str, &str, &[FilterDescription]); 2] = [
+        ("character filters", "-C", CharacterFilterLoader::FILTERS),
+        ("token filters", "-T", TokenFilterLoader::FILTERS),
+    ];
+
+    for (i, (title, option, filters)) in sections.iter().enumerate() {
+        if i > 0 {
+            writeln!(writer)
+                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+        }
+        writeln!(writer, "{}:", title)
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+        for filter in filters.iter() {
+            writeln!(writer, "  {}", filter.name)
+                .and_then(|_| writeln!(writer, "      {}", filter.description))
+                .and_then(|_| writeln!(writer, "      {} '{}'", option, filter.example_cli_flag()))
+                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The default separator between the token text and the details in the mecab format.
+const MECAB_FIELD_SEPARATOR: &str = "\t";
+/// The default separator between the details in the mecab format.
+const MECAB_FEATURE_SEPARATOR: &str = ",";
+
+fn mecab_output<W: Write>(
+    mut tokens: Vec<Value>,
+    field_sep: &str,
+    feature_sep: &str,
+    writer: &mut W,
+) -> LinderaResult<()> {
     for token in tokens.iter_mut() {
         let text = token["text"].as_str().ok_or_else(|| {
             LinderaErrorKind::Content.with_error(anyhow::anyhow!("failed to get text"))
@@ -143,155 +385,2338 @@ fn mecab_output(mut tokens: Vec<Value>) -> LinderaResult<()> {
             .iter()
             .map(|v| v.as_str().unwrap())
             .collect::<Vec<&str>>()
-            .join(",");
-        println!("{}\t{}", text, details);
+            .join(feature_sep);
+        writeln!(writer, "{}{}{}", text, field_sep, details)
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
     }
-    println!("EOS");
+    writeln!(writer, "EOS").map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
 
     Ok(())
 }
 
-fn json_output(tokens: Vec<Value>) -> LinderaResult<()> {
-    println!(
+fn json_output<W: Write>(tokens: Vec<Value>, writer: &mut W) -> LinderaResult<()> {
+    writeln!(
+        writer,
         "{}",
         serde_json::to_string_pretty(&tokens)
             .map_err(|err| { LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err)) })?
-    );
+    )
+    .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+
+    Ok(())
+}
+
+fn jsonl_output<W: Write>(tokens: Vec<Value>, writer: &mut W) -> LinderaResult<()> {
+    for token in tokens.iter() {
+        let line = serde_json::to_string(token)
+            .map_err(|err| LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err)))?;
+        writeln!(writer, "{}", line)
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+    }
+    // An empty line separates the tokens of each input line.
+    writeln!(writer).map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+
+    Ok(())
+}
+
+fn count_tokens(tokens: &[Value], counts: &mut HashMap<String, usize>) -> LinderaResult<()> {
+    for token in tokens.iter() {
+        let text = token["text"].as_str().ok_or_else(|| {
+            LinderaErrorKind::Content.with_error(anyhow::anyhow!("failed to get text"))
+        })?;
+        *counts.entry(text.to_string()).or_default() += 1;
+    }
+
+    Ok(())
+}
+
+fn count_output<W: Write>(counts: HashMap<String, usize>, writer: &mut W) -> LinderaResult<()> {
+    // Sort by count in descending order, then by text.
+    let mut counts = counts.into_iter().collect::<Vec<(String, usize)>>();
+    counts.sort_by(|(a_text, a_count), (b_text, b_count)| {
+        b_count.cmp(a_count).then_with(|| a_text.cmp(b_text))
+    });
+
+    for (text, count) in counts.iter() {
+        writeln!(writer, "{}\t{}", text, count)
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+    }
 
     Ok(())
 }
 
-fn wakati_output(tokens: Vec<Value>) -> LinderaResult<()> {
+fn wakati_output<W: Write>(tokens: Vec<Value>, escape: bool, writer: &mut W) -> LinderaResult<()> {
     let mut it = tokens.iter().peekable();
     while let Some(token) = it.next() {
         let text = token["text"].as_str().ok_or_else(|| {
             LinderaErrorKind::Content.with_error(anyhow::anyhow!("failed to get text"))
         })?;
+        let text = if escape {
+            Cow::Owned(escape_wakati(text))
+        } else {
+            Cow::Borrowed(text)
+        };
         if it.peek().is_some() {
-            print!("{} ", text);
+            write!(writer, "{} ", text)
         } else {
-            println!("{}", text);
+            writeln!(writer, "{}", text)
         }
+        .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
     }
 
     Ok(())
 }
 
-fn tokenize(args: TokenizeArgs) -> LinderaResult<()> {
-    // Dictionary config
-    let dictionary_conf = DictionaryConfig {
-        kind: args.dic_type.clone(),
-        path: args.dic_dir,
-    };
+/// Escape the backslashes and the spaces of the token text with a backslash, so that a space
+/// separating two tokens can be told apart from a space inside a token.
+fn escape_wakati(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c == '\\' || c == ' ' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
 
-    // User dictionary config
-    let user_dictionary_conf = match args.user_dic_file {
-        Some(path) => Some(UserDictionaryConfig {
-            kind: args.dic_type,
-            path,
-        }),
-        None => None,
-    };
+fn csv_output<W: Write>(tokens: Vec<Value>, header: bool, writer: &mut W) -> LinderaResult<()> {
+    let mut writer = csv::Writer::from_writer(writer);
 
-    // Dictionary
-    let dictionary = DictionaryLoader::load_dictionary_from_config(dictionary_conf)?;
+    if header {
+        writer
+            .write_record(["text", "byte_start", "byte_end", "word_id", "details"])
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+    }
 
-    // User dictionary
-    let user_dictionary = match user_dictionary_conf {
-        Some(ud_conf) => Some(DictionaryLoader::load_user_dictionary_from_config(ud_conf)?),
-        None => None,
-    };
-    let mode = args.mode;
+    for token in tokens.iter() {
+        let text = token["text"].as_str().ok_or_else(|| {
+            LinderaErrorKind::Content.with_error(anyhow::anyhow!("failed to get text"))
+        })?;
+        let byte_start = token["byte_start"].as_u64().ok_or_else(|| {
+            LinderaErrorKind::Content.with_error(anyhow::anyhow!("failed to get byte_start"))
+        })?;
+        let byte_end = token["byte_end"].as_u64().ok_or_else(|| {
+            LinderaErrorKind::Content.with_error(anyhow::anyhow!("failed to get byte_end"))
+        })?;
+        let word_id = token["word_id"][0].as_u64().ok_or_else(|| {
+            LinderaErrorKind::Content.with_error(anyhow::anyhow!("failed to get word_id"))
+        })?;
+        let details = token["details"]
+            .as_array()
+            .ok_or_else(|| {
+                LinderaErrorKind::Content.with_error(anyhow::anyhow!("failed to get details"))
+            })?
+            .iter()
+            .map(|v| {
+                v.as_str().ok_or_else(|| {
+                    LinderaErrorKind::Content.with_error(anyhow::anyhow!("failed to get detail"))
+                })
+            })
+            .collect::<LinderaResult<Vec<&str>>>()?
+            .join(";");
+
+        writer
+            .write_record([
+                text,
+                byte_start.to_string().as_str(),
+                byte_end.to_string().as_str(),
+                word_id.to_string().as_str(),
+                details.as_str(),
+            ])
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+    }
+
+    writer
+        .flush()
+        .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+
+    Ok(())
+}
 
-    // Tokenizer
-    let tokenizer = Tokenizer::new(dictionary, user_dictionary, mode);
+fn concat_output<W: Write>(tokens: Vec<Value>, writer: &mut W) -> LinderaResult<()> {
+    let mut line = String::new();
+    for token in tokens.iter() {
+        let text = token["text"].as_str().ok_or_else(|| {
+            LinderaErrorKind::Content.with_error(anyhow::anyhow!("failed to get text"))
+        })?;
+        line.push_str(text);
+    }
+    writeln!(writer, "{}", line)
+        .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
 
+    Ok(())
+}
+
+fn tokenize(args: TokenizeArgs) -> LinderaResult<()> {
     // output format
     let output_format = Format::from_str(args.output_format.as_str())?;
-
-    // Character flters
-    #[allow(unused_mut)]
-    let mut character_filters: Vec<BoxCharacterFilter> = Vec::new();
-    #[cfg(feature = "filter")]
-    for filter in args.character_filters.iter().flatten() {
-        let character_filter = CharacterFilterLoader::load_from_cli_flag(filter)?;
-        character_filters.push(character_filter);
+    if args.char_offsets && !matches!(output_format, Format::Json | Format::JsonL) {
+        return Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+            "the char-offsets option requires the json or jsonl output format"
+        )));
+    }
+    if args.sentence_relative_offsets && !matches!(output_format, Format::Json | Format::JsonL) {
+        return Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+            "the sentence-relative-offsets option requires the json or jsonl output format"
+        )));
     }
 
-    // Token filters
-    #[allow(unused_mut)]
-    let mut token_filters: Vec<BoxTokenFilter> = Vec::new();
-    #[cfg(feature = "filter")]
-    for filter in args.token_filters.iter().flatten() {
-        let token_filter = TokenFilterLoader::load_from_cli_flag(filter)?;
-        token_filters.push(token_filter);
+    if args.nbest == 0 {
+        return Err(LinderaErrorKind::Args
+            .with_error(anyhow::anyhow!("the nbest option must be at least 1")));
+    }
+    if args.count && args.nbest > 1 {
+        return Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+            "the count option cannot be used with the nbest option"
+        )));
+    }
+    if args.wakati_escape && !matches!(output_format, Format::Wakati) {
+        return Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+            "the wakati-escape option requires the wakati output format"
+        )));
+    }
+    if (args.mecab_field_sep.is_some() || args.mecab_feature_sep.is_some())
+        && !matches!(output_format, Format::Mecab)
+    {
+        return Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+            "the mecab-field-sep and mecab-feature-sep options require the mecab output format"
+        )));
     }
 
-    let analyzer = Analyzer::new(character_filters, tokenizer, token_filters);
+    let input_encoding = Encoding::for_label_no_replacement(args.input_encoding.as_bytes())
+        .ok_or_else(|| {
+            LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                "invalid input encoding: {}",
+                args.input_encoding
+            ))
+        })?;
 
-    // input file
-    let mut reader: Box<dyn BufRead> = if let Some(input_file) = args.input_file {
-        Box::new(BufReader::new(File::open(input_file).map_err(|err| {
-            LinderaErrorKind::Io.with_error(anyhow::anyhow!(err))
-        })?))
-    } else {
-        Box::new(BufReader::new(io::stdin()))
-    };
+    let output_encoding = Encoding::for_label_no_replacement(args.output_encoding.as_bytes())
+        .ok_or_else(|| {
+            LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                "invalid output encoding: {}",
+                args.output_encoding
+            ))
+        })?;
 
-    loop {
-        // read the text to be tokenized from stdin
-        let mut text = String::new();
-        let size = reader
-            .read_line(&mut text)
-            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
-        if size == 0 {
-            // EOS
-            break;
+    let analyzer = match args.config {
+        #[cfg(feature = "filter")]
+        Some(config_path) => {
+            // Analyzer config
+            let analyzer_config = AnalyzerConfig::from_file(&config_path)?;
+            let mut analyzer = Analyzer::from_config(&analyzer_config)?;
+
+            // The output options enable their setting on top of the config.
+            if args.debug_costs {
+                analyzer.tokenizer.debug_costs = true;
+            }
+            if args.preserve_whitespace {
+                analyzer.tokenizer.preserve_whitespace = true;
+            }
+
+            analyzer
+        }
+        #[cfg(not(feature = "filter"))]
+        Some(_) => {
+            return Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                "the config option requires the filter feature"
+            )));
         }
+        None => {
+            let dictionary_kind = args.dic_type.clone();
 
-        let mut tokens = Vec::new();
-
-        let mut tmp_tokens = analyzer.analyze(text.trim())?;
-        for token in tmp_tokens.iter_mut() {
-            let token_info = serde_json::json!({
-                "text": token.text,
-                "details": token.details,
-                "byte_start": token.byte_start,
-                "byte_end": token.byte_end,
-                "word_id": token.word_id,
-            });
-            tokens.push(token_info);
+            // Dictionary config
+            let dictionary_conf = DictionaryConfig {
+                kind: args.dic_type.clone(),
+                path: args.dic_dir,
+                ..Default::default()
+            };
+
+            // User dictionary config
+            let user_dictionary_conf = match args.user_dic_file {
+                Some(path) => Some(UserDictionaryConfig {
+                    kind: args.dic_type,
+                    path,
+                }),
+                None => None,
+            };
+
+            // Dictionary
+            let dictionary = match args.dic_archive {
+                Some(archive_path) => DictionaryLoader::load_dictionary_from_archive(archive_path)?,
+                None => DictionaryLoader::load_dictionary_from_config(dictionary_conf)?,
+            };
+
+            // User dictionary
+            let user_dictionary = match user_dictionary_conf {
+                Some(ud_conf) => Some(DictionaryLoader::load_user_dictionary_from_config(ud_conf)?),
+                None => None,
+            };
+            let mode = resolve_mode(args.mode, dictionary_kind.as_ref());
+
+            // Tokenizer
+            let mut tokenizer = Tokenizer::new(dictionary, user_dictionary, mode);
+            tokenizer.decompose_compounds = dictionary_kind
+                .as_ref()
+                .is_some_and(DictionaryKind::has_compounds);
+            tokenizer.dictionary_kind = dictionary_kind;
+            tokenizer.debug_costs = args.debug_costs;
+            tokenizer.preserve_whitespace = args.preserve_whitespace;
+
+            // Filters in the filters directory
+            #[cfg(feature = "filter")]
+            let (mut character_filters, mut token_filters) = match args.filters_dir {
+                Some(filters_dir) => load_filters_dir(&filters_dir)?,
+                None => (Vec::new(), Vec::new()),
+            };
+            #[cfg(not(feature = "filter"))]
+            let (character_filters, token_filters): (
+                Vec<BoxCharacterFilter>,
+                Vec<BoxTokenFilter>,
+            ) = match args.filters_dir {
+                Some(_) => {
+                    return Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                        "the filters-dir option requires the filter feature"
+                    )));
+                }
+                None => (Vec::new(), Vec::new()),
+            };
+
+            // Character flters
+            #[cfg(feature = "filter")]
+            for filter in args.character_filters.iter().flatten() {
+                let character_filter = CharacterFilterLoader::load_from_cli_flag(filter)?;
+                character_filters.push(character_filter);
+            }
+
+            // Token filters
+            #[cfg(feature = "filter")]
+            for filter in args.token_filters.iter().flatten() {
+                let token_filter = TokenFilterLoader::load_from_cli_flag(filter)?;
+                token_filters.push(token_filter);
+            }
+
+            Analyzer::new(character_filters, tokenizer, token_filters)
         }
+    };
+
+    // The dictionary kind is used to output dictionary specific fields. It is the kind of the
+    // dictionary of the config if one is given.
+    let dictionary_kind = analyzer.tokenizer.dictionary_kind.clone();
+
+    // The options to output the tokens of each line.
+    let options = OutputOptions {
+        format: output_format,
+        char_offsets: args.char_offsets,
+        sentence_relative_offsets: args.sentence_relative_offsets,
+        count: args.count,
+        nbest: args.nbest,
+        whole_file: args.whole_file,
+        input_encoding,
+        wakati_escape: args.wakati_escape,
+        mecab_field_sep: args
+            .mecab_field_sep
+            .unwrap_or_else(|| MECAB_FIELD_SEPARATOR.to_string()),
+        mecab_feature_sep: args
+            .mecab_feature_sep
+            .unwrap_or_else(|| MECAB_FEATURE_SEPARATOR.to_string()),
+        dictionary_kind,
+    };
+
+    let mut writer = EncodeWriter::new(
+        BufWriter::new(io::stdout().lock()),
+        output_encoding,
+        &args.output_fallback,
+    )?;
+
+    // input files
+    let counts = if args.input_files.is_empty() {
+        let mut counts = HashMap::new();
+        // The output of each line is flushed as soon as it is written, so that a stream piped
+        // into stdin is tokenized line by line.
+        tokenize_input(
+            &analyzer,
+            decode_input(io::stdin(), options.input_encoding),
+            &mut writer,
+            &options,
+            true,
+            true,
+            &mut counts,
+        )?;
+        counts
+    } else {
+        tokenize_files(
+            &analyzer,
+            &args.input_files,
+            args.threads,
+            &options,
+            &mut writer,
+        )?
+    };
+
+    if args.count {
+        count_output(counts, &mut writer)?;
+    }
+
+    writer
+        .finish()
+        .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+
+    Ok(())
+}
+
+/// Return the mode given with `--mode`, or the default mode of the dictionary type if it is
+/// not given. Normal mode is used for a dictionary without a type, such as an archive.
+fn resolve_mode(mode: Option<Mode>, dictionary_kind: Option<&DictionaryKind>) -> Mode {
+    mode.unwrap_or_else(|| dictionary_kind.map_or(Mode::Normal, |kind| kind.default_mode()))
+}
+
+/// The options to output the tokens.
+struct OutputOptions {
+    format: Format,
+    char_offsets: bool,
+    /// Whether the offsets are relative to the start of the sentence containing the token.
+    sentence_relative_offsets: bool,
+    count: bool,
+    /// The number of best segmentations to output for each line.
+    nbest: usize,
+    /// Whether each input is tokenized as one document instead of line by line.
+    whole_file: bool,
+    /// The encoding of the input, which is decoded to UTF-8 before it is tokenized.
+    input_encoding: &'static Encoding,
+    /// Whether the spaces in the token text are escaped in the wakati format.
+    wakati_escape: bool,
+    /// The separator between the token text and the details in the mecab format.
+    mecab_field_sep: String,
+    /// The separator between the details in the mecab format.
+    mecab_feature_sep: String,
+    /// The dictionary kind is used to output dictionary specific fields.
+    dictionary_kind: Option<DictionaryKind>,
+}
+
+/// Load the filters described by the `*.json` files of the directory, in the order of the file
+/// names. Each file has a `stage`, `character` or `token`, and the `type` and `args` of the
+/// filter, e.g. `{"stage": "token", "type": "lowercase", "args": {}}`.
+#[cfg(feature = "filter")]
+fn load_filters_dir(dir: &Path) -> LinderaResult<(Vec<BoxCharacterFilter>, Vec<BoxTokenFilter>)> {
+    let mut paths = std::fs::read_dir(dir)
+        .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?
+        .map(|entry| {
+            entry
+                .map(|entry| entry.path())
+                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))
+        })
+        .collect::<LinderaResult<Vec<_>>>()?;
+    paths.retain(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "json"));
+    paths.sort();
+
+    let mut character_filters: Vec<BoxCharacterFilter> = Vec::new();
+    let mut token_filters: Vec<BoxTokenFilter> = Vec::new();
+    for path in paths {
+        let data = std::fs::read(&path)
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+        let value = serde_json::from_slice::<Value>(&data)
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(anyhow::anyhow!(err)))?;
 
-        match output_format {
-            Format::Mecab => {
-                mecab_output(tokens)?;
+        let kind = value["type"].as_str().ok_or_else(|| {
+            LinderaErrorKind::Deserialize
+                .with_error(anyhow::anyhow!("type is required in {}", path.display()))
+        })?;
+        match value["stage"].as_str() {
+            Some("character") => {
+                character_filters.push(CharacterFilterLoader::load_from_value(
+                    kind,
+                    &value["args"],
+                )?);
             }
-            Format::Json => {
-                json_output(tokens)?;
+            Some("token") => {
+                token_filters.push(TokenFilterLoader::load_from_value(kind, &value["args"])?);
             }
-            Format::Wakati => {
-                wakati_output(tokens)?;
+            _ => {
+                return Err(LinderaErrorKind::Deserialize.with_error(anyhow::anyhow!(
+                    "stage must be character or token in {}",
+                    path.display()
+                )));
             }
         }
     }
 
-    Ok(())
+    Ok((character_filters, token_filters))
 }
 
-fn build(args: BuildArgs) -> LinderaResult<()> {
-    let builder = DictionaryBuilderResolver::resolve_builder(args.dic_type)?;
+/// The line separating the segmentations of a line when more than one is output.
+const NBEST_SEPARATOR: &str = "---";
 
-    if args.build_user_dic {
-        let output_file = if let Some(filename) = args.src_path.file_name() {
-            let mut output_file = Path::new(&args.dest_path).join(filename);
-            output_file.set_extension("bin");
-            output_file
-        } else {
-            return Err(LinderaErrorKind::Io.with_error(anyhow::anyhow!("failed to get filename")));
-        };
-        builder.build_user_dictionary(&args.src_path, &output_file)
-    } else {
-        builder.build_dictionary(&args.src_path, &args.dest_path)
+/// Tokenize the input files and write the output of each file in the order of the files.
+/// If `threads` is greater than 1, the files are tokenized concurrently on a thread pool and
+/// the output of each file is buffered, so that the output of the files is not interleaved.
+/// Returns the token frequencies of all the files.
+fn tokenize_files<W: Write>(
+    analyzer: &Analyzer,
+    input_files: &[PathBuf],
+    threads: usize,
+    options: &OutputOptions,
+    writer: &mut W,
+) -> LinderaResult<HashMap<String, usize>> {
+    let open = |input_file: &PathBuf| -> LinderaResult<Box<dyn BufRead>> {
+        let file = File::open(input_file)
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+        Ok(decode_input(file, options.input_encoding))
+    };
+
+    let mut counts = HashMap::new();
+
+    if threads <= 1 {
+        for (index, input_file) in input_files.iter().enumerate() {
+            // The CSV header is written only once, before the first row of the first file.
+            tokenize_input(
+                analyzer,
+                open(input_file)?,
+                writer,
+                options,
+                index == 0,
+                false,
+                &mut counts,
+            )?;
+        }
+
+        return Ok(counts);
+    }
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .map_err(|err| LinderaErrorKind::Args.with_error(anyhow::anyhow!(err)))?;
+
+    let outputs = pool.install(|| {
+        input_files
+            .par_iter()
+            .enumerate()
+            .map(|(index, input_file)| {
+                let mut buffer = Vec::new();
+                let mut file_counts = HashMap::new();
+                tokenize_input(
+                    analyzer,
+                    open(input_file)?,
+                    &mut buffer,
+                    options,
+                    index == 0,
+                    false,
+                    &mut file_counts,
+                )?;
+                Ok((buffer, file_counts))
+            })
+            .collect::<LinderaResult<Vec<_>>>()
+    })?;
+
+    for (buffer, file_counts) in outputs {
+        writer
+            .write_all(&buffer)
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+        for (text, count) in file_counts {
+            *counts.entry(text).or_default() += count;
+        }
+    }
+
+    Ok(counts)
+}
+
+/// Wrap the input in a buffered reader that decodes it to UTF-8 from the given encoding.
+/// A UTF-8 input is read as it is, so that an invalid byte sequence is reported as an error.
+fn decode_input<'a, R: Read + 'a>(reader: R, encoding: &'static Encoding) -> Box<dyn BufRead + 'a> {
+    if encoding == UTF_8 {
+        return Box::new(BufReader::new(reader));
+    }
+
+    Box::new(BufReader::new(
+        DecodeReaderBytesBuilder::new()
+            .encoding(Some(encoding))
+            .build(reader),
+    ))
+}
+
+/// A writer that encodes the UTF-8 output to the given encoding before writing it.
+/// The characters that cannot be represented in the encoding are replaced with the fallback.
+struct EncodeWriter<W: Write> {
+    inner: W,
+    encoding: &'static Encoding,
+    /// The fallback, encoded in the encoding.
+    fallback: Vec<u8>,
+    /// The first bytes of a character whose remaining bytes have not been written yet.
+    pending: Vec<u8>,
+}
+
+impl<W: Write> EncodeWriter<W> {
+    fn new(inner: W, encoding: &'static Encoding, fallback: &str) -> LinderaResult<Self> {
+        let (encoded_fallback, _, unmappable) = encoding.encode(fallback);
+        if unmappable {
+            return Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                "the output fallback {:?} cannot be represented in {}",
+                fallback,
+                encoding.name()
+            )));
+        }
+
+        Ok(Self {
+            inner,
+            encoding,
+            fallback: encoded_fallback.into_owned(),
+            pending: Vec::new(),
+        })
+    }
+
+    /// Finish the output: the incomplete character kept from the last write cannot be
+    /// encoded, so it is replaced by the fallback rather than dropped, and the inner writer is
+    /// flushed. This is done on drop too, where the errors are ignored.
+    fn finish(&mut self) -> io::Result<()> {
+        if !self.pending.is_empty() {
+            self.inner.write_all(&self.fallback)?;
+            self.pending.clear();
+        }
+
+        self.inner.flush()
+    }
+}
+
+impl<W: Write> Write for EncodeWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.encoding == UTF_8 {
+            return self.inner.write(buf);
+        }
+
+        // A character may be split over two writes, so only the complete characters are
+        // encoded and the rest is kept for the next write. The pending bytes are only replaced
+        // once the write succeeded, so that a failed write can be retried.
+        let mut bytes = Vec::with_capacity(self.pending.len() + buf.len());
+        bytes.extend_from_slice(&self.pending);
+        bytes.extend_from_slice(buf);
+        let (text, rest) = match std::str::from_utf8(&bytes) {
+            Ok(text) => (text, &[][..]),
+            Err(err) if err.error_len().is_none() => {
+                let (valid, rest) = bytes.split_at(err.valid_up_to());
+                // The prefix up to `valid_up_to` is valid UTF-8.
+                let text = std::str::from_utf8(valid)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+                (text, rest)
+            }
+            Err(err) => return Err(io::Error::new(io::ErrorKind::InvalidData, err)),
+        };
+
+        // Each write is encoded by a new encoder, so that a stateful encoding such as
+        // ISO-2022-JP is back to its initial state at the end of each write.
+        let mut encoder = self.encoding.new_encoder();
+        let mut encoded = Vec::new();
+        let mut remaining = text;
+        loop {
+            let (result, read) =
+                encoder.encode_from_utf8_to_vec_without_replacement(remaining, &mut encoded, true);
+            remaining = &remaining[read..];
+            match result {
+                EncoderResult::InputEmpty => break,
+                EncoderResult::OutputFull => {
+                    let additional = encoder
+                        .max_buffer_length_from_utf8_without_replacement(remaining.len())
+                        .ok_or_else(|| {
+                            io::Error::new(io::ErrorKind::InvalidInput, "the output is too long")
+                        })?;
+                    encoded.reserve(additional);
+                }
+                EncoderResult::Unmappable(_) => encoded.extend_from_slice(&self.fallback),
+            }
+        }
+
+        self.inner.write_all(&encoded)?;
+        self.pending = rest.to_vec();
+
+        Ok(buf.len())
+    }
+
+    /// Flush the inner writer. An incomplete character is kept, as its remaining bytes may
+    /// still be written, e.g. when the output is flushed after each line.
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: Write> Drop for EncodeWriter<W> {
+    fn drop(&mut self) {
+        // Errors cannot be reported on drop, as with `BufWriter`.
+        let _ = self.finish();
+    }
+}
+
+/// Tokenize the input line by line and write the tokens of each line.
+/// If the count option is enabled, the token frequencies are added to `counts` instead.
+/// If `flush_lines` is true, the writer is flushed after the output of each line.
+fn tokenize_input<R: BufRead, W: Write>(
+    analyzer: &Analyzer,
+    mut reader: R,
+    writer: &mut W,
+    options: &OutputOptions,
+    mut csv_header: bool,
+    flush_lines: bool,
+    counts: &mut HashMap<String, usize>,
+) -> LinderaResult<()> {
+    loop {
+        // read the text to be tokenized
+        let mut text = String::new();
+        let size = if options.whole_file {
+            reader.read_to_string(&mut text)
+        } else {
+            reader.read_line(&mut text)
+        }
+        .map_err(|err| match err.kind() {
+            io::ErrorKind::InvalidData => LinderaErrorKind::Decode.with_error(anyhow::anyhow!(
+                "the input is not valid UTF-8, specify its encoding with --input-encoding"
+            )),
+            _ => LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)),
+        })?;
+        if size == 0 {
+            // EOS
+            break;
+        }
+
+        // The whole input is one document: the lines wrapped in the middle of a CJK text are
+        // joined so that a word or a sentence wrapped over several lines is tokenized as it would
+        // be on a single line, and the offsets of the tokens are mapped back onto the input.
+        let (line, line_starts) = if options.whole_file {
+            let (line, line_starts) = join_lines(&text, !analyzer.tokenizer.preserve_whitespace);
+            (Cow::Owned(line), Some(line_starts))
+        } else if analyzer.tokenizer.preserve_whitespace {
+            // Only the line terminator is removed when whitespace is preserved.
+            (Cow::Borrowed(text.trim_end_matches(['\r', '\n'])), None)
+        } else {
+            (Cow::Borrowed(text.trim()), None)
+        };
+        let line = line.as_ref();
+
+        // The character offsets are computed once per line and looked up for each token.
+        // They are those of the input in whole-file mode, like the byte offsets.
+        let line_char_offsets = if options.char_offsets {
+            Some(char_offsets(if options.whole_file { &text } else { line }))
+        } else {
+            None
+        };
+
+        // The sentences are split by the sentence splitter of the tokenizer, and the start of
+        // each sentence is looked up for each token.
+        let sentence_starts = if options.sentence_relative_offsets {
+            let mut sentence_start = 0;
+            let mut sentence_starts = Vec::new();
+            for sentence in analyzer.tokenizer.sentence_splitter.split(line) {
+                sentence_starts.push(match &line_starts {
+                    Some(line_starts) => input_offset(line_starts, sentence_start, false),
+                    None => sentence_start,
+                });
+                sentence_start += sentence.len();
+            }
+            Some(sentence_starts)
+        } else {
+            None
+        };
+
+        let segmentations = if options.nbest > 1 {
+            analyzer.analyze_n_best(line, options.nbest)?
+        } else {
+            vec![analyzer.analyze(line)?]
+        };
+
+        for (rank, mut tmp_tokens) in segmentations.into_iter().enumerate() {
+            let mut tokens = Vec::new();
+            for token in tmp_tokens.iter_mut() {
+                if let Some(line_starts) = &line_starts {
+                    let is_empty = token.byte_start == token.byte_end;
+                    token.byte_start = input_offset(line_starts, token.byte_start, false);
+                    token.byte_end = if is_empty {
+                        token.byte_start
+                    } else {
+                        input_offset(line_starts, token.byte_end, true)
+                    };
+                }
+
+                let (sentence_index, sentence_start) = match &sentence_starts {
+                    Some(sentence_starts) => {
+                        let index = sentence_starts
+                            .partition_point(|&start| start <= token.byte_start)
+                            .saturating_sub(1);
+                        (Some(index), sentence_starts[index])
+                    }
+                    None => (None, 0),
+                };
+
+                let mut token_info = serde_json::json!({
+                    "text": token.text,
+                    "details": token.details,
+                    "byte_start": token.byte_start - sentence_start,
+                    "byte_end": token.byte_end - sentence_start,
+                    "word_id": token.word_id,
+                    "is_known": token.word_id.is_known(),
+                });
+                if options.dictionary_kind == Some(DictionaryKind::CcCedict) {
+                    token_info["pinyin"] =
+                        serde_json::json!(token.reading(&DictionaryKind::CcCedict));
+                }
+                if let Some(line_char_offsets) = &line_char_offsets {
+                    if let Some(token_info) = token_info.as_object_mut() {
+                        token_info.remove("byte_start");
+                        token_info.remove("byte_end");
+                        let sentence_char_start = line_char_offsets[sentence_start];
+                        token_info.insert(
+                            "char_start".to_string(),
+                            serde_json::json!(
+                                line_char_offsets[token.byte_start] - sentence_char_start
+                            ),
+                        );
+                        token_info.insert(
+                            "char_end".to_string(),
+                            serde_json::json!(
+                                line_char_offsets[token.byte_end] - sentence_char_start
+                            ),
+                        );
+                    }
+                }
+                if let Some(sentence_index) = sentence_index {
+                    token_info["sentence_index"] = serde_json::json!(sentence_index);
+                }
+                if let Some(word_cost) = token.word_cost {
+                    token_info["word_cost"] = serde_json::json!(word_cost);
+                }
+                if let Some(connection_cost) = token.connection_cost {
+                    token_info["connection_cost"] = serde_json::json!(connection_cost);
+                }
+                tokens.push(token_info);
+            }
+
+            if options.count {
+                count_tokens(&tokens, counts)?;
+                continue;
+            }
+
+            if rank > 0 {
+                writeln!(writer, "{}", NBEST_SEPARATOR)
+                    .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+            }
+
+            match options.format {
+                Format::Mecab => {
+                    mecab_output(
+                        tokens,
+                        &options.mecab_field_sep,
+                        &options.mecab_feature_sep,
+                        writer,
+                    )?;
+                }
+                Format::Json => {
+                    json_output(tokens, writer)?;
+                }
+                Format::JsonL => {
+                    jsonl_output(tokens, writer)?;
+                }
+                Format::Wakati => {
+                    wakati_output(tokens, options.wakati_escape, writer)?;
+                }
+                Format::Csv => {
+                    csv_output(tokens, csv_header, writer)?;
+                    csv_header = false;
+                }
+                Format::Concat => {
+                    concat_output(tokens, writer)?;
+                }
+            }
+        }
+
+        if flush_lines {
+            writer
+                .flush()
+                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Join the lines of a whole-file input that are wrapped in the middle of a CJK text, and
+/// remove its leading and trailing whitespace if `trim` is set.
+/// A line break is removed only if the characters before and after it are both CJK, so that
+/// the words of a space-delimited text are not merged and the blank lines are kept.
+/// Returns the joined text and, for the start of each joined line in it, the number of bytes of
+/// the input removed before that line, from which `input_offset` maps the offsets back onto the
+/// input.
+fn join_lines(text: &str, trim: bool) -> (String, Vec<(usize, usize)>) {
+    let (content, mut removed) = if trim {
+        let trimmed = text.trim_start();
+        (trimmed.trim_end(), text.len() - trimmed.len())
+    } else {
+        (text, 0)
+    };
+
+    let mut joined = String::with_capacity(content.len());
+    let mut line_starts = vec![(0, removed)];
+    let mut lines = content.split_inclusive('\n').peekable();
+    while let Some(line) = lines.next() {
+        let line_content = match line.strip_suffix('\n') {
+            Some(line) => line.strip_suffix('\r').unwrap_or(line),
+            None => line,
+        };
+        joined.push_str(line_content);
+
+        let is_wrapped = match (
+            line_content.chars().next_back(),
+            lines.peek().and_then(|next_line| next_line.chars().next()),
+        ) {
+            (Some(last), Some(first)) => is_cjk(last) && is_cjk(first),
+            _ => false,
+        };
+        if is_wrapped {
+            removed += line.len() - line_content.len();
+            line_starts.push((joined.len(), removed));
+        } else {
+            joined.push_str(&line[line_content.len()..]);
+        }
+    }
+
+    (joined, line_starts)
+}
+
+/// Check if a character is a Chinese or Japanese character, which are written without spaces
+/// between the words. Hangul is not included, as Korean is written with spaces.
+fn is_cjk(c: char) -> bool {
+    matches!(
+        c,
+        '\u{3000}'..='\u{30FF}'
+            | '\u{31F0}'..='\u{31FF}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{FF01}'..='\u{FF9F}'
+            | '\u{20000}'..='\u{2FA1F}'
+    )
+}
+
+/// Map a byte offset of the text joined by `join_lines` back onto the input.
+/// An offset at a removed line break is placed after it for the start of a token, and before it
+/// for the end of a token, so that the token does not cover the line break unless it was
+/// wrapped over it.
+fn input_offset(line_starts: &[(usize, usize)], offset: usize, is_end: bool) -> usize {
+    let index = line_starts
+        .partition_point(|&(start, _)| {
+            if is_end {
+                start < offset
+            } else {
+                start <= offset
+            }
+        })
+        .saturating_sub(1);
+    offset + line_starts[index].1
+}
+
+/// Map each byte offset of the text, including the end of the text, to its character offset.
+/// Offsets within a multi-byte character map to the character containing them.
+fn char_offsets(text: &str) -> Vec<usize> {
+    let mut offsets = Vec::with_capacity(text.len() + 1);
+    for (char_offset, c) in text.chars().enumerate() {
+        offsets.extend(std::iter::repeat_n(char_offset, c.len_utf8()));
+    }
+    offsets.push(text.chars().count());
+    offsets
+}
+
+/// Build the dictionary. With `--dry-run`, the summary of the validation is written to the
+/// writer instead.
+fn build<W: Write>(args: BuildArgs, writer: &mut W) -> LinderaResult<()> {
+    let builder = DictionaryBuilderResolver::resolve_builder(args.dic_type)?;
+
+    if args.dry_run {
+        if args.build_user_dic {
+            // The user dictionary is built in memory only.
+            let user_dict = builder.build_user_dict(&args.src_path)?;
+            writeln!(
+                writer,
+                "{:<14}{}",
+                "entries",
+                user_dict.words_idx_data.len() / 4
+            )
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+            return Ok(());
+        }
+
+        let report = builder.validate_dictionary(&args.src_path)?;
+        validation_output(&report, writer)?;
+        if !report.is_valid() {
+            return Err(LinderaErrorKind::Content.with_error(anyhow::anyhow!(
+                "{} error(s) found in {}",
+                report.errors.len(),
+                args.src_path.display()
+            )));
+        }
+        return Ok(());
+    }
+
+    if args.build_user_dic {
+        let output_file = if let Some(filename) = args.src_path.file_name() {
+            let mut output_file = Path::new(&args.dest_path).join(filename);
+            output_file.set_extension("bin");
+            output_file
+        } else {
+            return Err(LinderaErrorKind::Io.with_error(anyhow::anyhow!("failed to get filename")));
+        };
+        builder.build_user_dictionary(&args.src_path, &output_file)
+    } else if args.skip_matrix {
+        builder.build_dictionary_without_cost_matrix(&args.src_path, &args.dest_path)
+    } else {
+        builder.build_dictionary(&args.src_path, &args.dest_path)
+    }
+}
+
+/// Write the summary of the validation of the dictionary source files, followed by the warnings
+/// and the errors.
+fn validation_output<W: Write>(report: &ValidationReport, writer: &mut W) -> LinderaResult<()> {
+    writeln!(writer, "{:<14}{}", "entries", report.entries)
+        .and_then(|_| writeln!(writer, "{:<14}{}", "unknown", report.unknown_entries))
+        .and_then(|_| writeln!(writer, "{:<14}{}", "categories", report.categories))
+        .and_then(|_| {
+            writeln!(
+                writer,
+                "{:<14}{} x {}",
+                "contexts", report.forward_size, report.backward_size
+            )
+        })
+        .and_then(|_| writeln!(writer, "{:<14}{}", "warnings", report.warnings.len()))
+        .and_then(|_| writeln!(writer, "{:<14}{}", "errors", report.errors.len()))
+        .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+
+    for warning in &report.warnings {
+        writeln!(writer, "warning: {}", warning)
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+    }
+    for error in &report.errors {
+        writeln!(writer, "error: {}", error)
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+    }
+
+    Ok(())
+}
+
+fn info(args: InfoArgs) -> LinderaResult<()> {
+    let (info, compression) = match args.dic_dir {
+        // Only the parts of the dictionary needed for the information are read.
+        Some(dic_dir) => {
+            let mut info = DictionaryLoader::load_dictionary_info(dic_dir.clone())?;
+            match (info.kind.as_deref(), args.dic_type) {
+                (Some(kind), Some(dic_type)) if kind != dic_type.as_str() => {
+                    return Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                        "the dictionary in {} is {}, not {}",
+                        dic_dir.display(),
+                        kind,
+                        dic_type.as_str()
+                    )));
+                }
+                (Some(_), _) => {}
+                (None, Some(dic_type)) => info.kind = Some(dic_type.as_str().to_string()),
+                (None, None) => {
+                    return Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                        "the kind of the dictionary in {} is not recorded. Specify it with --dic-type",
+                        dic_dir.display()
+                    )));
+                }
+            }
+            let compression = match DictionaryLoader::compression_algorithm(dic_dir)? {
+                Some(algorithm) => format!("{:?}", algorithm).to_lowercase(),
+                None => "none".to_string(),
+            };
+            (info, compression)
+        }
+        None => match args.dic_type {
+            Some(dic_type) => {
+                let mut info =
+                    DictionaryLoader::load_dictionary_from_kind(dic_type.clone())?.info();
+                info.kind = Some(dic_type.as_str().to_string());
+                // The self-contained dictionaries are decompressed when they are loaded.
+                (info, "none".to_string())
+            }
+            None => {
+                return Err(LinderaErrorKind::Args
+                    .with_error(anyhow::anyhow!("Dictionary must be specified")));
+            }
+        },
+    };
+
+    println!("{:<14}{}", "kind", info.kind.unwrap_or_default());
+    println!("{:<14}{}", "entries", info.entries);
+    println!("{:<14}{}", "fields", info.fields);
+    println!(
+        "{:<14}{} x {}",
+        "contexts", info.forward_size, info.backward_size
+    );
+    println!("{:<14}{}", "compression", compression);
+
+    Ok(())
+}
+
+fn dump_matrix(args: DumpMatrixArgs) -> LinderaResult<()> {
+    if args.dic_type.is_none() && args.dic_dir.is_none() {
+        return Err(
+            LinderaErrorKind::Args.with_error(anyhow::anyhow!("Dictionary must be specified"))
+        );
+    }
+
+    let dictionary = DictionaryLoader::load_dictionary_from_config(DictionaryConfig {
+        kind: args.dic_type,
+        path: args.dic_dir,
+        ..Default::default()
+    })?;
+
+    let mut writer = BufWriter::new(io::stdout().lock());
+    matrix_output(&dictionary, &mut writer)?;
+    writer
+        .flush()
+        .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+
+    Ok(())
+}
+
+/// Write a `left_id,right_id,cost` row for each pair of context IDs of the connection cost
+/// matrix, in the order of the columns of `matrix.def`.
+fn matrix_output<W: Write>(dictionary: &Dictionary, writer: &mut W) -> LinderaResult<()> {
+    let (left_size, right_size) = dictionary.matrix_size();
+    for left_id in 0..left_size as u32 {
+        for right_id in 0..right_size as u32 {
+            writeln!(
+                writer,
+                "{},{},{}",
+                left_id,
+                right_id,
+                dictionary.connection_cost(left_id, right_id)
+            )
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "bench")]
+fn bench(args: BenchArgs) -> LinderaResult<()> {
+    if args.iterations == 0 {
+        return Err(
+            LinderaErrorKind::Args.with_error(anyhow::anyhow!("iterations must be greater than 0"))
+        );
+    }
+
+    let mode = resolve_mode(args.mode, args.dic_type.as_ref());
+    let decompose_compounds = args
+        .dic_type
+        .as_ref()
+        .is_some_and(DictionaryKind::has_compounds);
+
+    // Dictionary config
+    let dictionary_conf = DictionaryConfig {
+        kind: args.dic_type.clone(),
+        path: args.dic_dir,
+        ..Default::default()
+    };
+
+    // User dictionary config
+    let user_dictionary_conf = match args.user_dic_file {
+        Some(path) => Some(UserDictionaryConfig {
+            kind: args.dic_type,
+            path,
+        }),
+        None => None,
+    };
+
+    // The dictionaries are loaded only once, outside of the measurement.
+    let dictionary = DictionaryLoader::load_dictionary_from_config(dictionary_conf)?;
+    let user_dictionary = match user_dictionary_conf {
+        Some(ud_conf) => Some(DictionaryLoader::load_user_dictionary_from_config(ud_conf)?),
+        None => None,
+    };
+    let mut tokenizer = Tokenizer::new(dictionary, user_dictionary, mode);
+    tokenizer.decompose_compounds = decompose_compounds;
+    let analyzer = Analyzer::new(Vec::new(), tokenizer, Vec::new());
+
+    let input = std::fs::read_to_string(&args.input_file)
+        .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+    let lines = input
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        return Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!("the input file is empty")));
+    }
+
+    let mut latencies = Vec::with_capacity(lines.len() * args.iterations);
+    let mut token_count = 0_usize;
+    let mut char_count = 0_usize;
+
+    let start = Instant::now();
+    for _ in 0..args.iterations {
+        for line in lines.iter() {
+            let line_start = Instant::now();
+            let tokens = analyzer.analyze(line)?;
+            latencies.push(line_start.elapsed());
+
+            token_count += tokens.len();
+            char_count += line.chars().count();
+        }
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+
+    latencies.sort_unstable();
+
+    println!("{:<14}{:>16}", "iterations", args.iterations);
+    println!("{:<14}{:>16}", "lines", lines.len());
+    println!("{:<14}{:>16}", "tokens", token_count);
+    println!("{:<14}{:>16.3}", "elapsed (s)", elapsed);
+    println!("{:<14}{:>16.0}", "tokens/sec", token_count as f64 / elapsed);
+    println!("{:<14}{:>16.0}", "chars/sec", char_count as f64 / elapsed);
+    println!(
+        "{:<14}{:>16.1}",
+        "p50 (us)",
+        percentile(&latencies, 0.50).as_secs_f64() * 1_000_000.0
+    );
+    println!(
+        "{:<14}{:>16.1}",
+        "p99 (us)",
+        percentile(&latencies, 0.99).as_secs_f64() * 1_000_000.0
+    );
+
+    Ok(())
+}
+
+/// Returns the value at the given percentile (0.0 to 1.0) of the sorted latencies,
+/// using the nearest-rank method.
+#[cfg(feature = "bench")]
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use crate::{
+        char_offsets, count_output, count_tokens, csv_output, input_offset, join_lines,
+        jsonl_output, mecab_output, resolve_mode, wakati_output, MECAB_FEATURE_SEPARATOR,
+        MECAB_FIELD_SEPARATOR,
+    };
+
+    #[test]
+    fn test_jsonl_output() {
+        let tokens = vec![
+            json!({
+                "text": "東京",
+                "details": ["名詞", "固有名詞"],
+                "byte_start": 0,
+                "byte_end": 6,
+                "word_id": [1, true],
+            }),
+            json!({
+                "text": "都",
+                "details": ["名詞", "接尾"],
+                "byte_start": 6,
+                "byte_end": 9,
+                "word_id": [2, true],
+            }),
+        ];
+
+        let mut output = Vec::new();
+        jsonl_output(tokens, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            concat!(
+                r#"{"byte_end":6,"byte_start":0,"details":["名詞","固有名詞"],"text":"東京","word_id":[1,true]}"#,
+                "\n",
+                r#"{"byte_end":9,"byte_start":6,"details":["名詞","接尾"],"text":"都","word_id":[2,true]}"#,
+                "\n",
+                "\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_mecab_output() {
+        let tokens = vec![
+            json!({"text": "東京", "details": ["名詞", "固有名詞"]}),
+            json!({"text": "都", "details": ["名詞", "接尾"]}),
+        ];
+
+        let mut output = Vec::new();
+        mecab_output(
+            tokens.clone(),
+            MECAB_FIELD_SEPARATOR,
+            MECAB_FEATURE_SEPARATOR,
+            &mut output,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "東京\t名詞,固有名詞\n都\t名詞,接尾\nEOS\n"
+        );
+
+        let mut output = Vec::new();
+        mecab_output(tokens, ",", "|", &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "東京,名詞|固有名詞\n都,名詞|接尾\nEOS\n"
+        );
+    }
+
+    #[test]
+    fn test_csv_output() {
+        let tokens = vec![
+            json!({
+                "text": "東京",
+                "details": ["名詞", "固有名詞"],
+                "byte_start": 0,
+                "byte_end": 6,
+                "word_id": [1, true],
+            }),
+            json!({
+                "text": "a,\"b\"\nc",
+                "details": ["記号", "一般"],
+                "byte_start": 6,
+                "byte_end": 13,
+                "word_id": [2, false],
+            }),
+        ];
+
+        let mut output = Vec::new();
+        csv_output(tokens.clone(), true, &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            concat!(
+                "text,byte_start,byte_end,word_id,details\n",
+                "東京,0,6,1,名詞;固有名詞\n",
+                "\"a,\"\"b\"\"\nc\",6,13,2,記号;一般\n",
+            )
+        );
+
+        // the fields with commas, quotes or newlines are quoted, so they can be read back
+        let mut output = Vec::new();
+        csv_output(tokens, false, &mut output).unwrap();
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(output.as_slice());
+        let records = reader
+            .records()
+            .map(|record| record.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(records.len(), 2);
+        assert_eq!(&records[1][0], "a,\"b\"\nc");
+        assert_eq!(&records[1][4], "記号;一般");
+
+        // the details must be strings
+        let tokens = vec![json!({
+            "text": "東京",
+            "details": ["名詞", 1],
+            "byte_start": 0,
+            "byte_end": 6,
+            "word_id": [1, true],
+        })];
+        let mut output = Vec::new();
+        assert!(csv_output(tokens, false, &mut output).is_err());
+    }
+
+    #[test]
+    fn test_wakati_output_escape() {
+        let tokens = vec![
+            json!({"text": "New York"}),
+            json!({"text": "に"}),
+            json!({"text": "a\\b"}),
+        ];
+
+        let mut output = Vec::new();
+        wakati_output(tokens.clone(), false, &mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "New York に a\\b\n");
+
+        let mut output = Vec::new();
+        wakati_output(tokens, true, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output, "New\\ York に a\\\\b\n");
+
+        // the output can be split back into the tokens at the unescaped spaces
+        let mut texts = vec![String::new()];
+        let mut chars = output.trim_end_matches('\n').chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => texts.last_mut().unwrap().extend(chars.next()),
+                ' ' => texts.push(String::new()),
+                _ => texts.last_mut().unwrap().push(c),
+            }
+        }
+        assert_eq!(texts, vec!["New York", "に", "a\\b"]);
+    }
+
+    #[test]
+    fn test_resolve_mode() {
+        use std::str::FromStr;
+
+        use lindera::{DictionaryKind, Mode};
+
+        // the dictionary type gives the default mode
+        let ipadic_mode = resolve_mode(None, Some(&DictionaryKind::IPADIC));
+        let ko_dic_mode = resolve_mode(None, Some(&DictionaryKind::KoDic));
+        assert_eq!(ipadic_mode, Mode::Normal);
+        assert!(ko_dic_mode.is_search());
+        assert_ne!(ipadic_mode, ko_dic_mode);
+        assert_eq!(resolve_mode(None, None), Mode::Normal);
+
+        // an explicit mode always overrides the default mode
+        let normal = Mode::from_str("normal").unwrap();
+        let decompose = Mode::from_str("decompose").unwrap();
+        for kind in DictionaryKind::variants() {
+            assert_eq!(resolve_mode(Some(normal.clone()), Some(&kind)), normal);
+            assert_eq!(
+                resolve_mode(Some(decompose.clone()), Some(&kind)),
+                decompose
+            );
+        }
+        assert_eq!(resolve_mode(Some(decompose.clone()), None), decompose);
+    }
+
+    #[test]
+    fn test_char_offsets() {
+        let text = "東京はrain";
+        let offsets = char_offsets(text);
+
+        assert_eq!(offsets.len(), text.len() + 1);
+        // "東京" is 6 bytes but 2 characters
+        assert_eq!(offsets[0], 0);
+        assert_eq!(offsets[3], 1);
+        assert_eq!(offsets[6], 2);
+        assert_eq!(offsets[9], 3);
+        assert_eq!(offsets[text.len()], 7);
+
+        assert_eq!(char_offsets(""), vec![0]);
+    }
+
+    #[test]
+    fn test_join_lines() {
+        let text = "  東京\r\n都\n\n大阪\n";
+        let (joined, line_starts) = join_lines(text, true);
+        assert_eq!(joined, "東京都\n\n大阪");
+
+        // "東京都" is wrapped over the first line break, "大阪" follows a blank line.
+        assert_eq!(input_offset(&line_starts, 0, false), 2);
+        assert_eq!(input_offset(&line_starts, 9, true), 13);
+        assert_eq!(&text[2..13], "東京\r\n都");
+        assert_eq!(input_offset(&line_starts, 6, true), 8);
+        assert_eq!(input_offset(&line_starts, 6, false), 10);
+        assert_eq!(input_offset(&line_starts, 11, false), 15);
+        assert_eq!(input_offset(&line_starts, 17, true), 21);
+        assert_eq!(&text[15..21], "大阪");
+
+        // The whitespace is kept if it is not trimmed.
+        let (joined, line_starts) = join_lines(text, false);
+        assert_eq!(joined, "  東京都\n\n大阪\n");
+        assert_eq!(input_offset(&line_starts, 19, true), 21);
+        assert_eq!(input_offset(&line_starts, 20, false), 22);
+
+        let (joined, line_starts) = join_lines(" \n ", true);
+        assert_eq!(joined, "");
+        assert_eq!(input_offset(&line_starts, 0, false), 3);
+    }
+
+    #[test]
+    fn test_join_lines_space_delimited() {
+        // The words of a space-delimited text are not merged, and the blank line is kept.
+        let text = "hello\nworld\r\n\n안녕\n하세요\n";
+        let (joined, line_starts) = join_lines(text, true);
+        assert_eq!(joined, "hello\nworld\r\n\n안녕\n하세요");
+        assert_eq!(input_offset(&line_starts, 6, false), 6);
+        assert_eq!(input_offset(&line_starts, 11, true), 11);
+
+        // A line break between a CJK and a Latin character is kept too.
+        let (joined, _) = join_lines("東京\nTokyo\n", true);
+        assert_eq!(joined, "東京\nTokyo");
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_concat_output_ipadic() {
+        use lindera::{DictionaryConfig, DictionaryKind, DictionaryLoader, Mode, Tokenizer};
+
+        use crate::concat_output;
+
+        let dictionary = DictionaryLoader::load_dictionary_from_config(DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        })
+        .unwrap();
+        let mut tokenizer = Tokenizer::new(dictionary, None, Mode::Normal);
+        tokenizer.preserve_whitespace = true;
+
+        let text = " 今日は  晴れ、明日は 雨。";
+        let tokens = tokenizer
+            .tokenize(text)
+            .unwrap()
+            .iter()
+            .map(|token| json!({"text": token.text}))
+            .collect::<Vec<_>>();
+
+        let mut output = Vec::new();
+        concat_output(tokens, &mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), format!("{}\n", text));
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_files_threads_ipadic() {
+        use std::fs;
+
+        use encoding_rs::UTF_8;
+        use lindera::{
+            Analyzer, DictionaryConfig, DictionaryKind, DictionaryLoader, Mode, Tokenizer,
+        };
+
+        use crate::{
+            tokenize_files, Format, OutputOptions, MECAB_FEATURE_SEPARATOR, MECAB_FIELD_SEPARATOR,
+        };
+
+        let dictionary = DictionaryLoader::load_dictionary_from_config(DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        })
+        .unwrap();
+        let tokenizer = Tokenizer::new(dictionary, None, Mode::Normal);
+        let analyzer = Analyzer::new(Vec::new(), tokenizer, Vec::new());
+
+        let dir = std::env::temp_dir().join(format!("lindera-cli-threads-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let first_file = dir.join("first.txt");
+        fs::write(&first_file, "すもももももももものうち\n東京スカイツリー\n").unwrap();
+        let second_file = dir.join("second.txt");
+        fs::write(&second_file, "関西国際空港限定トートバッグ\n").unwrap();
+        let input_files = vec![first_file, second_file];
+
+        let options = OutputOptions {
+            format: Format::Wakati,
+            char_offsets: false,
+            sentence_relative_offsets: false,
+            count: false,
+            nbest: 1,
+            whole_file: false,
+            input_encoding: UTF_8,
+            wakati_escape: false,
+            mecab_field_sep: MECAB_FIELD_SEPARATOR.to_string(),
+            mecab_feature_sep: MECAB_FEATURE_SEPARATOR.to_string(),
+            dictionary_kind: None,
+        };
+
+        let mut output = Vec::new();
+        tokenize_files(&analyzer, &input_files, 2, &options, &mut output).unwrap();
+
+        // The output of each file is kept together, in the order of the files.
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            concat!(
+                "すもも も もも も もも の うち\n",
+                "東京 スカイ ツリー\n",
+                "関西国際空港 限定 トートバッグ\n",
+            )
+        );
+
+        let mut serial_output = Vec::new();
+        tokenize_files(&analyzer, &input_files, 1, &options, &mut serial_output).unwrap();
+        let mut output = Vec::new();
+        tokenize_files(&analyzer, &input_files, 2, &options, &mut output).unwrap();
+        assert_eq!(output, serial_output);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_input_nbest_ipadic() {
+        use std::io::Cursor;
+
+        use encoding_rs::UTF_8;
+        use lindera::{
+            Analyzer, DictionaryConfig, DictionaryKind, DictionaryLoader, Mode, Tokenizer,
+        };
+
+        use crate::{
+            tokenize_input, Format, OutputOptions, MECAB_FEATURE_SEPARATOR, MECAB_FIELD_SEPARATOR,
+            NBEST_SEPARATOR,
+        };
+
+        let dictionary = DictionaryLoader::load_dictionary_from_config(DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        })
+        .unwrap();
+        let tokenizer = Tokenizer::new(dictionary, None, Mode::Normal);
+        let analyzer = Analyzer::new(Vec::new(), tokenizer, Vec::new());
+
+        let mut options = OutputOptions {
+            format: Format::Wakati,
+            char_offsets: false,
+            sentence_relative_offsets: false,
+            count: false,
+            nbest: 2,
+            whole_file: false,
+            input_encoding: UTF_8,
+            wakati_escape: false,
+            mecab_field_sep: MECAB_FIELD_SEPARATOR.to_string(),
+            mecab_feature_sep: MECAB_FEATURE_SEPARATOR.to_string(),
+            dictionary_kind: None,
+        };
+
+        // "すもももももももものうち" can be segmented in many ways
+        let text = "すもももももももものうち\n";
+        let mut output = Vec::new();
+        tokenize_input(
+            &analyzer,
+            Cursor::new(text),
+            &mut output,
+            &options,
+            false,
+            false,
+            &mut HashMap::new(),
+        )
+        .unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "すもも も もも も もも の うち");
+        assert_eq!(lines[1], NBEST_SEPARATOR);
+        assert_ne!(lines[2], lines[0]);
+        assert_eq!(lines[2].replace(' ', ""), text.trim());
+
+        // the default of 1 outputs the best segmentation only
+        options.nbest = 1;
+        let mut output = Vec::new();
+        tokenize_input(
+            &analyzer,
+            Cursor::new(text),
+            &mut output,
+            &options,
+            false,
+            false,
+            &mut HashMap::new(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "すもも も もも も もも の うち\n"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_input_sentence_relative_offsets_ipadic() {
+        use std::io::Cursor;
+
+        use encoding_rs::UTF_8;
+        use lindera::{
+            Analyzer, DictionaryConfig, DictionaryKind, DictionaryLoader, Mode, Tokenizer,
+        };
+        use serde_json::Value;
+
+        use crate::{
+            tokenize_input, Format, OutputOptions, MECAB_FEATURE_SEPARATOR, MECAB_FIELD_SEPARATOR,
+        };
+
+        let dictionary = DictionaryLoader::load_dictionary_from_config(DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        })
+        .unwrap();
+        let tokenizer = Tokenizer::new(dictionary, None, Mode::Normal);
+        let analyzer = Analyzer::new(Vec::new(), tokenizer, Vec::new());
+
+        let mut options = OutputOptions {
+            format: Format::JsonL,
+            char_offsets: false,
+            sentence_relative_offsets: true,
+            count: false,
+            nbest: 1,
+            whole_file: false,
+            input_encoding: UTF_8,
+            wakati_escape: false,
+            mecab_field_sep: MECAB_FIELD_SEPARATOR.to_string(),
+            mecab_feature_sep: MECAB_FEATURE_SEPARATOR.to_string(),
+            dictionary_kind: None,
+        };
+
+        let text = "関西国際空港限定トートバッグ。すもももももももものうち。\n";
+        let tokenize_text = |options: &OutputOptions| {
+            let mut output = Vec::new();
+            tokenize_input(
+                &analyzer,
+                Cursor::new(text),
+                &mut output,
+                options,
+                false,
+                false,
+                &mut HashMap::new(),
+            )
+            .unwrap();
+            String::from_utf8(output)
+                .unwrap()
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(|line| serde_json::from_str::<Value>(line).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        let tokens = tokenize_text(&options);
+        let offsets = tokens
+            .iter()
+            .map(|token| {
+                (
+                    token["text"].as_str().unwrap().to_string(),
+                    token["byte_start"].as_u64().unwrap(),
+                    token["byte_end"].as_u64().unwrap(),
+                    token["sentence_index"].as_u64().unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        // the offsets are reset at the start of the second sentence
+        assert_eq!(offsets[0], ("関西国際空港".to_string(), 0, 18, 0));
+        assert_eq!(offsets[3], ("。".to_string(), 42, 45, 0));
+        assert_eq!(offsets[4], ("すもも".to_string(), 0, 9, 1));
+        assert_eq!(offsets.last().unwrap(), &("。".to_string(), 36, 39, 1));
+
+        // the character offsets are relative to the sentence as well
+        options.char_offsets = true;
+        let tokens = tokenize_text(&options);
+        assert_eq!(tokens[4]["text"], "すもも");
+        assert_eq!(tokens[4]["char_start"], 0);
+        assert_eq!(tokens[4]["char_end"], 3);
+        assert_eq!(tokens[4]["sentence_index"], 1);
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_files_whole_file_ipadic() {
+        use std::fs;
+
+        use encoding_rs::UTF_8;
+        use lindera::{
+            Analyzer, DictionaryConfig, DictionaryKind, DictionaryLoader, Mode, Tokenizer,
+        };
+        use serde_json::Value;
+
+        use crate::{
+            tokenize_files, Format, OutputOptions, MECAB_FEATURE_SEPARATOR, MECAB_FIELD_SEPARATOR,
+        };
+
+        let dictionary = DictionaryLoader::load_dictionary_from_config(DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        })
+        .unwrap();
+        let tokenizer = Tokenizer::new(dictionary, None, Mode::Normal);
+        let analyzer = Analyzer::new(Vec::new(), tokenizer, Vec::new());
+
+        let dir =
+            std::env::temp_dir().join(format!("lindera-cli-whole-file-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        // "関西国際空港" is wrapped over two lines, after some leading whitespace.
+        let input = "  関西国際\n空港限定トートバッグ\n";
+        let input_file = dir.join("wrapped.txt");
+        fs::write(&input_file, input).unwrap();
+        let input_files = vec![input_file];
+
+        let mut options = OutputOptions {
+            format: Format::Wakati,
+            char_offsets: false,
+            sentence_relative_offsets: false,
+            count: false,
+            nbest: 1,
+            whole_file: true,
+            input_encoding: UTF_8,
+            wakati_escape: false,
+            mecab_field_sep: MECAB_FIELD_SEPARATOR.to_string(),
+            mecab_feature_sep: MECAB_FEATURE_SEPARATOR.to_string(),
+            dictionary_kind: None,
+        };
+
+        let tokenize = |options: &OutputOptions| {
+            let mut output = Vec::new();
+            tokenize_files(&analyzer, &input_files, 1, options, &mut output).unwrap();
+            String::from_utf8(output).unwrap()
+        };
+        // Return the number of token streams and the offsets of the token.
+        let offsets = |options: &OutputOptions, text: &str| {
+            let output = tokenize(options);
+            let stream_count = output.lines().filter(|line| line.is_empty()).count();
+            let token = output
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(|line| serde_json::from_str::<Value>(line).unwrap())
+                .find(|token| token["text"] == text)
+                .unwrap();
+            (
+                stream_count,
+                token["byte_start"].as_u64().unwrap() as usize,
+                token["byte_end"].as_u64().unwrap() as usize,
+            )
+        };
+
+        // The file is output as one token stream, in which the wrapped word is one token.
+        assert_eq!(tokenize(&options), "関西国際空港 限定 トートバッグ\n");
+
+        // The offsets are those in the file: the wrapped word covers the line break, and the
+        // leading whitespace is counted.
+        options.format = Format::JsonL;
+        assert_eq!(offsets(&options, "関西国際空港"), (1, 2, 21));
+        assert_eq!(offsets(&options, "限定"), (1, 21, 27));
+        assert_eq!(&input[21..27], "限定");
+
+        // Line by line, the word is split at the line break, and each line is a token stream
+        // with its own offsets.
+        options.whole_file = false;
+        let output = tokenize(&options);
+        assert!(!output.contains("関西国際空港"));
+        assert_eq!(offsets(&options, "限定").0, 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_files_input_encoding_ipadic() {
+        use std::path::PathBuf;
+
+        use encoding_rs::{SHIFT_JIS, UTF_8};
+        use lindera::{
+            Analyzer, DictionaryConfig, DictionaryKind, DictionaryLoader, Mode, Tokenizer,
+        };
+
+        use crate::{
+            tokenize_files, Format, OutputOptions, MECAB_FEATURE_SEPARATOR, MECAB_FIELD_SEPARATOR,
+        };
+
+        let dictionary = DictionaryLoader::load_dictionary_from_config(DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        })
+        .unwrap();
+        let tokenizer = Tokenizer::new(dictionary, None, Mode::Normal);
+        let analyzer = Analyzer::new(Vec::new(), tokenizer, Vec::new());
+
+        let input_files = vec![PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../resources")
+            .join("shift_jis_input.txt")];
+
+        let mut options = OutputOptions {
+            format: Format::Wakati,
+            char_offsets: false,
+            sentence_relative_offsets: false,
+            count: false,
+            nbest: 1,
+            whole_file: false,
+            input_encoding: SHIFT_JIS,
+            wakati_escape: false,
+            mecab_field_sep: MECAB_FIELD_SEPARATOR.to_string(),
+            mecab_feature_sep: MECAB_FEATURE_SEPARATOR.to_string(),
+            dictionary_kind: None,
+        };
+
+        let mut output = Vec::new();
+        tokenize_files(&analyzer, &input_files, 1, &options, &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            concat!(
+                "関西国際空港 限定 トートバッグ\n",
+                "すもも も もも も もも の うち\n",
+            )
+        );
+
+        // the Shift-JIS input is not valid UTF-8
+        options.input_encoding = UTF_8;
+        let mut output = Vec::new();
+        assert!(tokenize_files(&analyzer, &input_files, 1, &options, &mut output).is_err());
+    }
+
+    #[test]
+    fn test_encode_writer() {
+        use std::io::Write;
+
+        use encoding_rs::{EUC_JP, SHIFT_JIS, UTF_8};
+
+        use crate::EncodeWriter;
+
+        let mut output = Vec::new();
+        let mut writer = EncodeWriter::new(&mut output, SHIFT_JIS, "?").unwrap();
+        // "東" is split over two writes
+        let text = "東京\n";
+        writer.write_all(&text.as_bytes()[..1]).unwrap();
+        writer.write_all(&text.as_bytes()[1..]).unwrap();
+        // Hangul cannot be represented in Shift-JIS
+        writer.write_all("한국\n".as_bytes()).unwrap();
+        writer.flush().unwrap();
+        drop(writer);
+
+        assert_eq!(SHIFT_JIS.decode(&output).0, "東京\n??\n");
+
+        let mut output = Vec::new();
+        let mut writer = EncodeWriter::new(&mut output, EUC_JP, "〓").unwrap();
+        writer.write_all("한국\n".as_bytes()).unwrap();
+        drop(writer);
+
+        assert_eq!(EUC_JP.decode(&output).0, "〓〓\n");
+
+        // a flush between the bytes of a character does not break it
+        let mut output = Vec::new();
+        let mut writer = EncodeWriter::new(&mut output, SHIFT_JIS, "?").unwrap();
+        writer.write_all(&text.as_bytes()[..4]).unwrap();
+        writer.flush().unwrap();
+        writer.write_all(&text.as_bytes()[4..]).unwrap();
+        writer.finish().unwrap();
+        drop(writer);
+
+        assert_eq!(SHIFT_JIS.decode(&output).0, "東京\n");
+
+        // an incomplete character left at the end is written as the fallback on finish
+        let mut output = Vec::new();
+        let mut writer = EncodeWriter::new(&mut output, SHIFT_JIS, "?").unwrap();
+        writer.write_all(&text.as_bytes()[..4]).unwrap();
+        writer.finish().unwrap();
+        drop(writer);
+
+        assert_eq!(SHIFT_JIS.decode(&output).0, "東?");
+
+        // and on drop
+        let mut output = Vec::new();
+        let mut writer = EncodeWriter::new(&mut output, SHIFT_JIS, "?").unwrap();
+        writer.write_all(&text.as_bytes()[..2]).unwrap();
+        writer.write_all(&text.as_bytes()[2..5]).unwrap();
+        drop(writer);
+
+        assert_eq!(SHIFT_JIS.decode(&output).0, "東?");
+
+        // an invalid byte sequence is rejected without being kept for the next write
+        let mut output = Vec::new();
+        let mut writer = EncodeWriter::new(&mut output, SHIFT_JIS, "?").unwrap();
+        writer.write_all(&text.as_bytes()[..1]).unwrap();
+        let err = writer.write(&[0xFF]).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        writer.write_all(&text.as_bytes()[1..]).unwrap();
+        drop(writer);
+
+        assert_eq!(SHIFT_JIS.decode(&output).0, "東京\n");
+
+        // the pending bytes are kept when the inner writer fails
+        struct FailingWriter {
+            output: Vec<u8>,
+            fail: bool,
+        }
+
+        impl Write for FailingWriter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                if self.fail {
+                    return Err(std::io::Error::other("failed"));
+                }
+                self.output.write(buf)
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut writer = EncodeWriter::new(
+            FailingWriter {
+                output: Vec::new(),
+                fail: false,
+            },
+            SHIFT_JIS,
+            "?",
+        )
+        .unwrap();
+        writer.write_all(&text.as_bytes()[..4]).unwrap();
+        writer.inner.fail = true;
+        assert!(writer.write(&text.as_bytes()[4..6]).is_err());
+        writer.inner.fail = false;
+        writer.write_all(&text.as_bytes()[4..]).unwrap();
+        writer.finish().unwrap();
+
+        assert_eq!(SHIFT_JIS.decode(&writer.inner.output).0, "東京\n");
+
+        // the fallback must be representable in the encoding
+        assert!(EncodeWriter::new(Vec::new(), SHIFT_JIS, "�").is_err());
+        assert!(EncodeWriter::new(Vec::new(), UTF_8, "�").is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_input_output_encoding_ipadic() {
+        use std::io::Cursor;
+
+        use encoding_rs::{EUC_JP, UTF_8};
+        use lindera::{
+            Analyzer, DictionaryConfig, DictionaryKind, DictionaryLoader, Mode, Tokenizer,
+        };
+
+        use crate::{
+            tokenize_input, EncodeWriter, Format, OutputOptions, MECAB_FEATURE_SEPARATOR,
+            MECAB_FIELD_SEPARATOR,
+        };
+
+        let dictionary = DictionaryLoader::load_dictionary_from_config(DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        })
+        .unwrap();
+        let tokenizer = Tokenizer::new(dictionary, None, Mode::Normal);
+        let analyzer = Analyzer::new(Vec::new(), tokenizer, Vec::new());
+
+        let options = OutputOptions {
+            format: Format::Wakati,
+            char_offsets: false,
+            sentence_relative_offsets: false,
+            count: false,
+            nbest: 1,
+            whole_file: false,
+            input_encoding: UTF_8,
+            wakati_escape: false,
+            mecab_field_sep: MECAB_FIELD_SEPARATOR.to_string(),
+            mecab_feature_sep: MECAB_FEATURE_SEPARATOR.to_string(),
+            dictionary_kind: None,
+        };
+
+        let mut output = Vec::new();
+        let mut writer = EncodeWriter::new(&mut output, EUC_JP, "?").unwrap();
+        tokenize_input(
+            &analyzer,
+            Cursor::new("関西国際空港限定トートバッグ\n"),
+            &mut writer,
+            &options,
+            false,
+            false,
+            &mut HashMap::new(),
+        )
+        .unwrap();
+        drop(writer);
+
+        // the output is EUC-JP, which decodes back to the UTF-8 output
+        let expected = "関西国際空港 限定 トートバッグ\n";
+        assert_ne!(output, expected.as_bytes());
+        let (decoded, _, had_errors) = EUC_JP.decode(&output);
+        assert!(!had_errors);
+        assert_eq!(decoded, expected);
+    }
+
+    #[test]
+    #[cfg(all(feature = "ipadic", feature = "filter"))]
+    fn test_load_filters_dir_ipadic() {
+        use std::fs;
+
+        use lindera::{
+            Analyzer, DictionaryConfig, DictionaryKind, DictionaryLoader, Mode, Tokenizer,
+        };
+
+        use crate::load_filters_dir;
+
+        let dir =
+            std::env::temp_dir().join(format!("lindera-cli-filters-dir-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("01_mapping.json"),
+            r#"{"stage": "character", "type": "mapping", "args": {"mapping": {"とうきょう": "東京"}}}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("02_keep_tags.json"),
+            r#"{"stage": "token", "type": "japanese_keep_tags", "args": {"tags": ["名詞,固有名詞"], "prefix_match": true}}"#,
+        )
+        .unwrap();
+        // the files other than JSON are ignored
+        fs::write(dir.join("README.txt"), "filters of the pipeline").unwrap();
+
+        let (character_filters, token_filters) = load_filters_dir(&dir).unwrap();
+        assert_eq!(
+            character_filters
+                .iter()
+                .map(|filter| filter.name())
+                .collect::<Vec<_>>(),
+            vec!["mapping"]
+        );
+        assert_eq!(
+            token_filters
+                .iter()
+                .map(|filter| filter.name())
+                .collect::<Vec<_>>(),
+            vec!["japanese_keep_tags"]
+        );
+
+        let dictionary = DictionaryLoader::load_dictionary_from_config(DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        })
+        .unwrap();
+        let tokenizer = Tokenizer::new(dictionary, None, Mode::Normal);
+        let analyzer = Analyzer::new(character_filters, tokenizer, token_filters);
+
+        let tokens = analyzer.analyze("とうきょうの空港").unwrap();
+        assert_eq!(
+            tokens.iter().map(|t| t.text.as_str()).collect::<Vec<_>>(),
+            vec!["東京"]
+        );
+
+        // a filter without a valid stage is rejected
+        fs::write(
+            dir.join("03_lowercase.json"),
+            r#"{"stage": "tokenizer", "type": "lowercase", "args": {}}"#,
+        )
+        .unwrap();
+        assert!(load_filters_dir(&dir).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_count_output() {
+        let mut counts = HashMap::new();
+
+        // tokens of the first line
+        let tokens = vec![
+            json!({"text": "すもも"}),
+            json!({"text": "も"}),
+            json!({"text": "もも"}),
+            json!({"text": "も"}),
+            json!({"text": "もも"}),
+        ];
+        count_tokens(&tokens, &mut counts).unwrap();
+
+        // tokens of the second line
+        let tokens = vec![json!({"text": "もも"}), json!({"text": "かき"})];
+        count_tokens(&tokens, &mut counts).unwrap();
+
+        let mut output = Vec::new();
+        count_output(counts, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "もも\t3\nも\t2\nかき\t1\nすもも\t1\n"
+        );
+    }
+
+    #[test]
+    fn test_build_dry_run() {
+        use std::fs;
+
+        use lindera::DictionaryKind;
+
+        use crate::{build, BuildArgs};
+
+        let base_dir = std::env::temp_dir().join(format!(
+            "lindera-cli-test-build-dry-run-{}",
+            std::process::id()
+        ));
+        let src_path = base_dir.join("src");
+        let dest_path = base_dir.join("dest");
+        fs::create_dir_all(&src_path).unwrap();
+        fs::write(
+            src_path.join("char.def"),
+            "DEFAULT 0 1 0\nALPHA 1 1 0\n0x0041..0x005A ALPHA\n0x0061..0x007A ALPHA\n",
+        )
+        .unwrap();
+        fs::write(
+            src_path.join("unk.def"),
+            "DEFAULT,0,0,1000,SYMBOL,*,*,*,*,*,*\nALPHA,1,1,1000,NOUN,*,*,*,*,*,*\n",
+        )
+        .unwrap();
+        fs::write(
+            src_path.join("matrix.def"),
+            "2 2\n0 0 0\n0 1 0\n1 0 0\n1 1 0\n",
+        )
+        .unwrap();
+        // the second row has too few fields and the third one an invalid word cost
+        fs::write(
+            src_path.join("lex.csv"),
+            "tokyo,1,1,100,NOUN,PROPER,*,*,*,*,tokyo,TOKYO,TOKYO\nrun,1,1,200\nosaka,1,1,x,NOUN,PROPER,*,*,*,*,osaka,OSAKA,OSAKA\n",
+        )
+        .unwrap();
+
+        let mut output = Vec::new();
+        let result = build(
+            BuildArgs {
+                build_user_dic: false,
+                dic_type: DictionaryKind::IPADIC,
+                skip_matrix: false,
+                dry_run: true,
+                src_path: src_path.clone(),
+                dest_path: dest_path.clone(),
+            },
+            &mut output,
+        );
+
+        assert!(result.is_err());
+        // nothing is written
+        assert!(!dest_path.exists());
+
+        let lex_path = src_path.join("lex.csv");
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            format!(
+                "entries       3
+unknown       2
+categories    2
+contexts      2 x 2
+warnings      0
+errors        2
+error: {0}:2: expected 13 fields, got 4
+error: {0}:3: failed to parse word_cost \"x\": invalid digit found in string
+",
+                lex_path.display()
+            )
+        );
+
+        fs::remove_dir_all(&base_dir).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "filter")]
+    fn test_filters_output() {
+        use crate::filters_output;
+
+        let mut output = Vec::new();
+        filters_output(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.starts_with("character filters:\n"));
+        assert!(output.contains("\ntoken filters:\n"));
+        assert!(output.contains("\n  unicode_normalize\n"));
+        assert!(output.contains("      -C 'unicode_normalize:{\"kind\":\"nfkc\"}'\n"));
+        assert!(output.contains("\n  japanese_stop_tags\n"));
+        assert!(output.contains("      -T 'uppercase'\n"));
+    }
+
+    #[test]
+    fn test_validation_output() {
+        use lindera::ValidationReport;
+
+        use crate::validation_output;
+
+        let report = ValidationReport {
+            entries: 3,
+            unknown_entries: 2,
+            categories: 2,
+            forward_size: 2,
+            backward_size: 2,
+            warnings: vec!["lex.csv:1: empty surface".to_string()],
+            errors: vec![
+                "lex.csv:2: expected 13 fields, got 4".to_string(),
+                "lex.csv:3: failed to parse word_cost".to_string(),
+            ],
+        };
+        assert!(!report.is_valid());
+
+        let mut output = Vec::new();
+        validation_output(&report, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "entries       3
+unknown       2
+categories    2
+contexts      2 x 2
+warnings      1
+errors        2
+warning: lex.csv:1: empty surface
+error: lex.csv:2: expected 13 fields, got 4
+error: lex.csv:3: failed to parse word_cost
+"
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "compress"))]
+    fn test_matrix_output() {
+        use std::fs;
+
+        use lindera::{DictionaryBuilderResolver, DictionaryKind, DictionaryLoader};
+
+        use crate::matrix_output;
+
+        let base_dir = std::env::temp_dir().join(format!(
+            "lindera-cli-test-matrix-output-{}",
+            std::process::id()
+        ));
+        let src_path = base_dir.join("src");
+        let dest_path = base_dir.join("dest");
+        fs::create_dir_all(&src_path).unwrap();
+        fs::write(
+            src_path.join("char.def"),
+            "DEFAULT 0 1 0\nALPHA 1 1 0\n0x0041..0x005A ALPHA\n0x0061..0x007A ALPHA\n",
+        )
+        .unwrap();
+        fs::write(
+            src_path.join("unk.def"),
+            "DEFAULT,0,0,1000,SYMBOL,*,*,*,*,*,*\nALPHA,1,1,1000,NOUN,*,*,*,*,*,*\n",
+        )
+        .unwrap();
+        // 2 right context IDs of the word on the left by 3 left context IDs of the word on the right
+        fs::write(
+            src_path.join("matrix.def"),
+            "2 3\n0 0 0\n0 1 -5\n0 2 10\n1 0 7\n1 1 3\n1 2 -1\n",
+        )
+        .unwrap();
+        fs::write(
+            src_path.join("lex.csv"),
+            "tokyo,1,1,100,NOUN,PROPER,*,*,*,*,tokyo,TOKYO,TOKYO\n",
+        )
+        .unwrap();
+
+        DictionaryBuilderResolver::resolve_builder(DictionaryKind::IPADIC)
+            .unwrap()
+            .build_dictionary(&src_path, &dest_path)
+            .unwrap();
+        let dictionary = DictionaryLoader::load_dictionary(dest_path).unwrap();
+
+        assert_eq!(dictionary.matrix_size(), (2, 3));
+        assert_eq!(dictionary.connection_cost(1, 0), 7);
+
+        let mut output = Vec::new();
+        matrix_output(&dictionary, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "0,0,0\n0,1,-5\n0,2,10\n1,0,7\n1,1,3\n1,2,-1\n"
+        );
+
+        fs::remove_dir_all(&base_dir).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "bench")]
+    fn test_percentile() {
+        use std::time::Duration;
+
+        use crate::percentile;
+
+        let latencies = (1..=100).map(Duration::from_micros).collect::<Vec<_>>();
+
+        assert_eq!(percentile(&latencies, 0.50), Duration::from_micros(50));
+        assert_eq!(percentile(&latencies, 0.99), Duration::from_micros(99));
+        assert_eq!(percentile(&latencies, 1.0), Duration::from_micros(100));
+        assert_eq!(percentile(&latencies, 0.0), Duration::from_micros(1));
+        assert_eq!(percentile(&[], 0.50), Duration::ZERO);
     }
 }
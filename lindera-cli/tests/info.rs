@@ -0,0 +1,75 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+/// Build a small IPADIC format dictionary with the `build` subcommand.
+fn build_dictionary(base_dir: &Path) -> PathBuf {
+    let input_dir = base_dir.join("input");
+    let output_dir = base_dir.join("output");
+    fs::create_dir_all(&input_dir).unwrap();
+    fs::write(
+        input_dir.join("char.def"),
+        "DEFAULT 0 1 0\nALPHA 1 1 0\n0x0041..0x005A ALPHA\n0x0061..0x007A ALPHA\n",
+    )
+    .unwrap();
+    fs::write(
+        input_dir.join("unk.def"),
+        "DEFAULT,0,0,1000,SYMBOL,*,*,*,*,*,*\nALPHA,1,1,1000,NOUN,*,*,*,*,*,*\n",
+    )
+    .unwrap();
+    fs::write(
+        input_dir.join("matrix.def"),
+        "2 2\n0 0 0\n0 1 0\n1 0 0\n1 1 0\n",
+    )
+    .unwrap();
+    fs::write(
+        input_dir.join("lex.csv"),
+        "tokyo,1,1,100,NOUN,PROPER,*,*,*,*,tokyo,TOKYO,TOKYO\nrun,1,1,200,VERB,*,*,*,*,*,run,RUN,RUN\n",
+    )
+    .unwrap();
+
+    let status = Command::new(env!("CARGO_BIN_EXE_lindera"))
+        .args(["build", "--dic-type", "ipadic"])
+        .arg(&input_dir)
+        .arg(&output_dir)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    output_dir
+}
+
+fn info(dictionary_dir: &Path, dic_type: Option<&str>) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_lindera"));
+    command.args(["info", "--dic-dir"]).arg(dictionary_dir);
+    if let Some(dic_type) = dic_type {
+        command.args(["--dic-type", dic_type]);
+    }
+    command.output().unwrap()
+}
+
+#[test]
+fn test_info_dictionary_kind() {
+    let base_dir =
+        std::env::temp_dir().join(format!("lindera-cli-test-info-{}", std::process::id()));
+    let dictionary_dir = build_dictionary(&base_dir);
+
+    // The kind is recorded in the dictionary by the build.
+    let output = info(&dictionary_dir, None);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("kind          ipadic\nentries       2\n"));
+
+    assert!(!info(&dictionary_dir, Some("unidic")).status.success());
+
+    // A dictionary without the kind needs `--dic-type`.
+    fs::remove_file(dictionary_dir.join("kind.bin")).unwrap();
+    assert!(!info(&dictionary_dir, None).status.success());
+    let output = info(&dictionary_dir, Some("ipadic"));
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .starts_with("kind          ipadic\n"));
+
+    fs::remove_dir_all(&base_dir).unwrap();
+}
@@ -0,0 +1,44 @@
+#![cfg(all(feature = "cc-cedict", feature = "filter"))]
+
+use std::fs;
+use std::process::Command;
+
+use serde_json::Value;
+
+#[test]
+fn test_tokenize_config_cc_cedict_outputs_pinyin() {
+    let base_dir = std::env::temp_dir().join(format!(
+        "lindera-cli-test-tokenize-config-{}",
+        std::process::id()
+    ));
+    fs::create_dir_all(&base_dir).unwrap();
+    let config_file = base_dir.join("lindera.json");
+    fs::write(
+        &config_file,
+        r#"{"tokenizer": {"dictionary": {"kind": "cc-cedict"}, "mode": "normal"}}"#,
+    )
+    .unwrap();
+    let input_file = base_dir.join("input.txt");
+    fs::write(&input_file, "中国\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_lindera"))
+        .args(["tokenize", "--output-format", "jsonl", "--config"])
+        .arg(&config_file)
+        .arg(&input_file)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    // The dictionary kind of the config enables the pinyin field of cc-cedict.
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let tokens = stdout
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| serde_json::from_str::<Value>(line).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(tokens.len(), 1);
+    assert_eq!(tokens[0]["text"], "中国");
+    assert!(tokens[0]["pinyin"].is_string());
+
+    fs::remove_dir_all(&base_dir).unwrap();
+}
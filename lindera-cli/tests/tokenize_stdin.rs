@@ -0,0 +1,102 @@
+// The dictionary directory is loaded as it is, so the dictionary must be built uncompressed.
+#![cfg(not(feature = "compress"))]
+
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// Build a small IPADIC format dictionary with the `build` subcommand.
+fn build_dictionary(base_dir: &Path) -> std::path::PathBuf {
+    let input_dir = base_dir.join("input");
+    let output_dir = base_dir.join("output");
+    fs::create_dir_all(&input_dir).unwrap();
+    fs::write(
+        input_dir.join("char.def"),
+        "DEFAULT 0 1 0\nALPHA 1 1 0\n0x0041..0x005A ALPHA\n0x0061..0x007A ALPHA\n",
+    )
+    .unwrap();
+    fs::write(
+        input_dir.join("unk.def"),
+        "DEFAULT,0,0,1000,SYMBOL,*,*,*,*,*,*\nALPHA,1,1,1000,NOUN,*,*,*,*,*,*\n",
+    )
+    .unwrap();
+    fs::write(
+        input_dir.join("matrix.def"),
+        "2 2\n0 0 0\n0 1 0\n1 0 0\n1 1 0\n",
+    )
+    .unwrap();
+    fs::write(
+        input_dir.join("lex.csv"),
+        "tokyo,1,1,100,NOUN,PROPER,*,*,*,*,tokyo,TOKYO,TOKYO\nrun,1,1,200,VERB,*,*,*,*,*,run,RUN,RUN\n",
+    )
+    .unwrap();
+
+    let status = Command::new(env!("CARGO_BIN_EXE_lindera"))
+        .args(["build", "--dic-type", "ipadic"])
+        .arg(&input_dir)
+        .arg(&output_dir)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    output_dir
+}
+
+#[test]
+fn test_tokenize_stdin_flushes_each_line() {
+    let base_dir = std::env::temp_dir().join(format!(
+        "lindera-cli-test-tokenize-stdin-{}",
+        std::process::id()
+    ));
+    let dictionary_dir = build_dictionary(&base_dir);
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_lindera"))
+        .args(["tokenize", "--dic-dir"])
+        .arg(&dictionary_dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    let stdout = child.stdout.take().unwrap();
+
+    // The output lines are read on another thread, so that they can be waited for with a timeout.
+    let (sender, receiver) = mpsc::channel();
+    let reader = thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            if sender.send(line.unwrap()).is_err() {
+                break;
+            }
+        }
+    });
+
+    writeln!(stdin, "tokyo").unwrap();
+    stdin.flush().unwrap();
+
+    // The output of the first line is emitted while the second line has not been written yet.
+    let timeout = Duration::from_secs(30);
+    assert_eq!(
+        receiver.recv_timeout(timeout).unwrap(),
+        "tokyo\tNOUN,PROPER,*,*,*,*,tokyo,TOKYO,TOKYO"
+    );
+    assert_eq!(receiver.recv_timeout(timeout).unwrap(), "EOS");
+
+    thread::sleep(Duration::from_millis(100));
+    writeln!(stdin, "run").unwrap();
+    drop(stdin);
+
+    assert_eq!(
+        receiver.recv_timeout(timeout).unwrap(),
+        "run\tVERB,*,*,*,*,*,run,RUN,RUN"
+    );
+    assert_eq!(receiver.recv_timeout(timeout).unwrap(), "EOS");
+
+    assert!(child.wait().unwrap().success());
+    reader.join().unwrap();
+
+    fs::remove_dir_all(&base_dir).unwrap();
+}
@@ -31,6 +31,11 @@ pub fn compress(data: &[u8], algorithm: Algorithm) -> anyhow::Result<CompressedD
             Ok(CompressedData::new(algorithm, e.finish()?))
         }
         Algorithm::Raw => Ok(CompressedData::new(algorithm, data.to_vec())),
+        Algorithm::Zstd => {
+            // Level 0 selects the default compression level of zstd.
+            let compressed = zstd::stream::encode_all(data, 0)?;
+            Ok(CompressedData::new(algorithm, compressed))
+        }
     }
 }
 
@@ -58,4 +63,23 @@ mod tests {
 
         assert_eq!(&buf, &data);
     }
+
+    #[test]
+    fn compress_decompress_zstd() {
+        let mut rng = rand::thread_rng();
+        let mut buf = Vec::new();
+
+        for _i in 0..10000 {
+            buf.push(rng.gen())
+        }
+        for _i in 0..10000 {
+            buf.push(0)
+        }
+
+        let compress_data = compress(&buf, Algorithm::Zstd).unwrap();
+
+        let data = decompress(compress_data).unwrap();
+
+        assert_eq!(&buf, &data);
+    }
 }
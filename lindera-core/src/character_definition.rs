@@ -4,7 +4,7 @@ use byteorder::{ByteOrder, LittleEndian};
 use encoding_rs::UTF_16LE;
 use serde::{Deserialize, Serialize};
 
-use crate::{error::LinderaErrorKind, LinderaResult};
+use crate::{dictionary::strip_dictionary_header, error::LinderaErrorKind, LinderaResult};
 
 const DEFAULT_CATEGORY_NAME: &str = "DEFAULT";
 
@@ -82,7 +82,7 @@ impl CharacterDefinitions {
     }
 
     pub fn load(char_def_data: &[u8]) -> LinderaResult<CharacterDefinitions> {
-        bincode::deserialize(char_def_data)
+        bincode::deserialize(strip_dictionary_header(char_def_data)?)
             .map_err(|err| LinderaErrorKind::Deserialize.with_error(anyhow::anyhow!(err)))
     }
 
@@ -246,7 +246,11 @@ impl CharacterDefinitionsBuilder {
 
 #[cfg(test)]
 mod tests {
-    use crate::character_definition::LookupTable;
+    use crate::character_definition::{
+        CharacterDefinitions, CharacterDefinitionsBuilder, LookupTable,
+    };
+    use crate::dictionary::{dictionary_header, DICTIONARY_FORMAT_VERSION, DICTIONARY_MAGIC};
+    use crate::error::LinderaErrorKind;
 
     #[test]
     fn test_lookup_table() {
@@ -265,6 +269,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_load_format_version() {
+        let mut char_definitions_builder = CharacterDefinitionsBuilder::default();
+        char_definitions_builder
+            .parse("DEFAULT 0 1 0\nKANJI 0 0 2\n0x4E00..0x9FA5 KANJI\n")
+            .unwrap();
+        let char_definitions = char_definitions_builder.build();
+        let data = bincode::serialize(&char_definitions).unwrap();
+
+        let mut current = dictionary_header();
+        current.extend_from_slice(&data);
+        let loaded = CharacterDefinitions::load(&current).unwrap();
+        assert_eq!(loaded.categories(), &["DEFAULT", "KANJI"]);
+
+        // a dictionary built by an incompatible version
+        let mut bogus = DICTIONARY_MAGIC.to_vec();
+        bogus.push(DICTIONARY_FORMAT_VERSION + 1);
+        bogus.extend_from_slice(&data);
+        let err = CharacterDefinitions::load(&bogus).err().unwrap();
+        assert_eq!(err.kind(), LinderaErrorKind::IncompatibleDictionary);
+        let message = err.to_string();
+        assert!(message.contains(&format!("version {}", DICTIONARY_FORMAT_VERSION + 1)));
+        assert!(message.contains(&format!("version {}", DICTIONARY_FORMAT_VERSION)));
+
+        // a dictionary built by a version without the magic bytes
+        let mut old = vec![DICTIONARY_FORMAT_VERSION];
+        old.extend_from_slice(&data);
+        let err = CharacterDefinitions::load(&old).err().unwrap();
+        assert_eq!(err.kind(), LinderaErrorKind::IncompatibleDictionary);
+        assert!(err.to_string().contains("not a lindera dictionary file"));
+    }
+
     //    #[test]
     //    fn test_bisa() {
     //        let char_definitions = CharacterDefinitions::load();
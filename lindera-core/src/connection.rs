@@ -26,6 +26,14 @@ impl ConnectionCostMatrix {
         }
     }
 
+    /// The number of forward IDs, i.e. the rows of the matrix.
+    pub fn forward_size(&self) -> u32 {
+        if self.backward_size == 0 {
+            return 0;
+        }
+        (self.costs_data.len() / 2) as u32 / self.backward_size
+    }
+
     pub fn cost(&self, forward_id: u32, backward_id: u32) -> i32 {
         let cost_id = (backward_id + forward_id * self.backward_size) as usize;
         LittleEndian::read_i16(&self.costs_data[cost_id * 2..]) as i32
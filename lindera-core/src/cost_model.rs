@@ -0,0 +1,58 @@
+use std::fmt;
+
+use crate::connection::ConnectionCostMatrix;
+use crate::viterbi::Edge;
+
+/// The costs used by the Viterbi search to choose the best path through the lattice.
+/// The path with the lowest sum of word costs and connection costs is chosen.
+///
+/// The default methods return the costs of the dictionary, so a cost model only needs to
+/// override the costs it changes, e.g. to experiment with learned costs.
+pub trait CostModel: 'static + Send + Sync + CostModelClone {
+    /// Return the cost of the word of the edge.
+    fn word_cost(&self, edge: &Edge) -> i32 {
+        edge.word_entry.word_cost as i32
+    }
+
+    /// Return the cost of the connection from the left edge to the right edge.
+    fn connection_cost(
+        &self,
+        cost_matrix: &ConnectionCostMatrix,
+        left_edge: &Edge,
+        right_edge: &Edge,
+    ) -> i32 {
+        cost_matrix.cost(
+            left_edge.word_entry.right_id(),
+            right_edge.word_entry.left_id(),
+        )
+    }
+}
+
+pub trait CostModelClone {
+    fn box_clone(&self) -> Box<dyn CostModel>;
+}
+
+impl<T: CostModel + Clone> CostModelClone for T {
+    fn box_clone(&self) -> Box<dyn CostModel> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn CostModel> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+impl fmt::Debug for dyn CostModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CostModel")
+    }
+}
+
+/// The cost model of the dictionary: the word costs of the lexicon and the connection costs
+/// of the connection cost matrix.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DictionaryCostModel;
+
+impl CostModel for DictionaryCostModel {}
@@ -1,15 +1,70 @@
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::str;
 
 use byteorder::{ByteOrder, LittleEndian};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    character_definition::CharacterDefinitions, connection::ConnectionCostMatrix,
-    error::LinderaErrorKind, prefix_dict::PrefixDict, unknown_dictionary::UnknownDictionary,
+    character_definition::CharacterDefinitions,
+    connection::ConnectionCostMatrix,
+    error::LinderaErrorKind,
+    prefix_dict::PrefixDict,
+    unknown_dictionary::UnknownDictionary,
+    word_entry::{WordEntry, WordId},
     LinderaResult,
 };
 
+/// The magic bytes at the head of every dictionary file, followed by the format version.
+pub const DICTIONARY_MAGIC: [u8; 7] = *b"LINDERA";
+
+/// The version of the binary format of the dictionary files, written after the magic bytes.
+/// It must be incremented whenever the layout of the dictionary files changes, so that a
+/// dictionary built by an incompatible version of lindera is rejected when it is loaded.
+pub const DICTIONARY_FORMAT_VERSION: u8 = 1;
+
+/// The file in the directory of a built dictionary that records the kind of the dictionary, such
+/// as `ipadic`, after the dictionary header.
+pub const DICTIONARY_KIND_FILE: &str = "kind.bin";
+
+/// Get the header written at the head of every dictionary file: the magic bytes and the format
+/// version.
+pub fn dictionary_header() -> Vec<u8> {
+    let mut header = DICTIONARY_MAGIC.to_vec();
+    header.push(DICTIONARY_FORMAT_VERSION);
+    header
+}
+
+/// Check the header at the head of a dictionary file and return the data after it.
+pub fn strip_dictionary_header(data: &[u8]) -> LinderaResult<&[u8]> {
+    if data.is_empty() {
+        return Err(
+            LinderaErrorKind::Deserialize.with_error(anyhow::anyhow!("empty dictionary data"))
+        );
+    }
+    let rest = data.strip_prefix(&DICTIONARY_MAGIC[..]).ok_or_else(|| {
+        LinderaErrorKind::IncompatibleDictionary.with_error(anyhow::anyhow!(
+            "not a lindera dictionary file, or built by a version of lindera without a format \
+             header. Rebuild the dictionary from its source files with `lindera build` of this \
+             version (with `--build-user-dic` for a user dictionary)."
+        ))
+    })?;
+    match rest.split_first() {
+        Some((&DICTIONARY_FORMAT_VERSION, rest)) => Ok(rest),
+        Some((&version, _)) => Err(LinderaErrorKind::IncompatibleDictionary.with_error(
+            anyhow::anyhow!(
+                "dictionary format version {} is not supported, expected version {}. \
+                 Rebuild the dictionary from its source files with `lindera build` of this \
+                 version (with `--build-user-dic` for a user dictionary).",
+                version,
+                DICTIONARY_FORMAT_VERSION
+            ),
+        )),
+        None => Err(LinderaErrorKind::Deserialize
+            .with_error(anyhow::anyhow!("missing dictionary format version"))),
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Dictionary {
     pub dict: PrefixDict<Vec<u8>>,
@@ -22,22 +77,97 @@ pub struct Dictionary {
 
 impl Dictionary {
     pub fn word_details(&self, word_id: usize) -> Option<Vec<String>> {
-        if 4 * word_id >= self.words_idx_data.len() {
-            return None;
-        }
-        let idx: usize = LittleEndian::read_u32(&self.words_idx_data[4 * word_id..][..4])
-            .try_into()
-            .ok()?;
-        let data = &self.words_data[idx..];
-        let joined_details_len: usize = LittleEndian::read_u32(data).try_into().ok()?;
-        let joined_details_bytes: &[u8] = &self.words_data[idx + 4..idx + 4 + joined_details_len];
-
-        let mut details = Vec::new();
-        for bytes in joined_details_bytes.split(|&b| b == 0) {
-            let detail = str::from_utf8(bytes).ok()?.to_string();
-            details.push(detail);
+        word_details(&self.words_idx_data, &self.words_data, word_id)
+    }
+
+    /// Get a single field of the details of the word, without decoding the other fields.
+    pub fn word_detail(&self, word_id: usize, index: usize) -> Option<&str> {
+        joined_word_details(&self.words_idx_data, &self.words_data, word_id)?
+            .split(|&b| b == 0)
+            .nth(index)
+            .and_then(|bytes| str::from_utf8(bytes).ok())
+    }
+
+    /// Get summary information about the dictionary.
+    pub fn info(&self) -> DictionaryInfo {
+        DictionaryInfo::from_parts(&self.words_idx_data, &self.words_data, &self.cost_matrix)
+    }
+
+    /// Get the connection cost from a word whose right context ID is `left_id` to the following
+    /// word whose left context ID is `right_id`, in the order of the columns of `matrix.def`.
+    pub fn connection_cost(&self, left_id: u32, right_id: u32) -> i32 {
+        self.cost_matrix.cost(left_id, right_id)
+    }
+
+    /// Get the dimensions of the connection cost matrix, as the number of `left_id` and the
+    /// number of `right_id` accepted by `connection_cost`.
+    pub fn matrix_size(&self) -> (usize, usize) {
+        (
+            self.cost_matrix.forward_size() as usize,
+            self.cost_matrix.backward_size as usize,
+        )
+    }
+}
+
+fn word_details(words_idx_data: &[u8], words_data: &[u8], word_id: usize) -> Option<Vec<String>> {
+    let joined_details_bytes = joined_word_details(words_idx_data, words_data, word_id)?;
+
+    let mut details = Vec::new();
+    for bytes in joined_details_bytes.split(|&b| b == 0) {
+        let detail = str::from_utf8(bytes).ok()?.to_string();
+        details.push(detail);
+    }
+    Some(details)
+}
+
+/// Get the details of the word, joined with NUL bytes.
+fn joined_word_details<'a>(
+    words_idx_data: &[u8],
+    words_data: &'a [u8],
+    word_id: usize,
+) -> Option<&'a [u8]> {
+    if 4 * word_id >= words_idx_data.len() {
+        return None;
+    }
+    let idx: usize = LittleEndian::read_u32(&words_idx_data[4 * word_id..][..4])
+        .try_into()
+        .ok()?;
+    let data = &words_data[idx..];
+    let joined_details_len: usize = LittleEndian::read_u32(data).try_into().ok()?;
+
+    Some(&words_data[idx + 4..idx + 4 + joined_details_len])
+}
+
+/// Summary information about a system dictionary.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DictionaryInfo {
+    /// The kind of the dictionary, such as `ipadic`, if it is recorded.
+    pub kind: Option<String>,
+    /// The number of word entries.
+    pub entries: usize,
+    /// The number of detail fields of a word entry, such as part-of-speech tags and readings.
+    pub fields: usize,
+    /// The number of right context IDs (rows) of the connection cost matrix.
+    pub forward_size: usize,
+    /// The number of left context IDs (columns) of the connection cost matrix.
+    pub backward_size: usize,
+}
+
+impl DictionaryInfo {
+    /// Compute the information from the word index, the word details and the connection cost matrix,
+    /// without the other parts of the dictionary. The kind is not known from them.
+    pub fn from_parts(
+        words_idx_data: &[u8],
+        words_data: &[u8],
+        cost_matrix: &ConnectionCostMatrix,
+    ) -> Self {
+        DictionaryInfo {
+            kind: None,
+            entries: words_idx_data.len() / 4,
+            fields: word_details(words_idx_data, words_data, 0).map_or(0, |details| details.len()),
+            forward_size: cost_matrix.forward_size() as usize,
+            backward_size: cost_matrix.backward_size as usize,
         }
-        Some(details)
     }
 }
 
@@ -50,7 +180,7 @@ pub struct UserDictionary {
 
 impl UserDictionary {
     pub fn load(user_dict_data: &[u8]) -> LinderaResult<UserDictionary> {
-        bincode::deserialize(user_dict_data)
+        bincode::deserialize(strip_dictionary_header(user_dict_data)?)
             .map_err(|err| LinderaErrorKind::Deserialize.with_error(anyhow::anyhow!(err)))
     }
 
@@ -66,3 +196,93 @@ impl UserDictionary {
         }
     }
 }
+
+/// The first word ID of the words in a `UserWordOverlay`.
+/// The IDs are above the IDs of the user dictionary, so that both can be used together.
+pub const USER_WORD_OVERLAY_ID_BASE: u32 = 1 << 31;
+
+/// Words added at runtime on top of the system and user dictionaries.
+///
+/// The words are kept in memory only; they are not persisted to the user dictionary.
+/// It is meant for a small number of words: it is looked up with a hash map for each prefix
+/// of the text up to the length of the longest word.
+#[derive(Clone, Debug, Default)]
+pub struct UserWordOverlay {
+    /// The word entry of each surface.
+    entries: HashMap<String, WordEntry>,
+    /// The details of the words, indexed by word ID minus `USER_WORD_OVERLAY_ID_BASE`.
+    details: Vec<Vec<String>>,
+    /// The length of the longest surface in bytes.
+    max_len: usize,
+}
+
+impl UserWordOverlay {
+    /// Add a word. If the surface is already registered, the previous word is replaced.
+    pub fn insert(
+        &mut self,
+        surface: &str,
+        word_cost: i16,
+        context_id: u16,
+        details: Vec<String>,
+    ) -> WordId {
+        let word_id = WordId(USER_WORD_OVERLAY_ID_BASE + self.details.len() as u32, false);
+        self.details.push(details);
+        self.entries.insert(
+            surface.to_string(),
+            WordEntry {
+                word_id,
+                word_cost,
+                left_id: context_id,
+                right_id: context_id,
+            },
+        );
+        self.max_len = self.max_len.max(surface.len());
+
+        word_id
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Get the word entry of the surface, if it is registered.
+    pub fn get(&self, surface: &str) -> Option<WordEntry> {
+        self.entries.get(surface).copied()
+    }
+
+    /// Return the surface and the word entry of the words whose surface starts with the prefix.
+    pub fn predictive<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> impl Iterator<Item = (&'a str, WordEntry)> + 'a {
+        self.entries
+            .iter()
+            .filter(move |(surface, _)| surface.starts_with(prefix))
+            .map(|(surface, word_entry)| (surface.as_str(), *word_entry))
+    }
+
+    /// Return the length in bytes and the word entry of the words that are a prefix of the text.
+    pub fn prefix<'a>(&'a self, text: &'a str) -> impl Iterator<Item = (usize, WordEntry)> + 'a {
+        text.char_indices()
+            .skip(1)
+            .map(|(index, _)| index)
+            .chain(std::iter::once(text.len()))
+            .take_while(move |&len| len <= self.max_len)
+            .filter_map(move |len| {
+                self.entries
+                    .get(&text[..len])
+                    .map(|word_entry| (len, *word_entry))
+            })
+    }
+
+    /// Get the details of a word of the overlay.
+    /// Returns `None` if the word ID does not belong to the overlay.
+    pub fn word_details(&self, word_id: WordId) -> Option<Vec<String>> {
+        if word_id.is_system() || word_id.0 < USER_WORD_OVERLAY_ID_BASE {
+            return None;
+        }
+        self.details
+            .get((word_id.0 - USER_WORD_OVERLAY_ID_BASE) as usize)
+            .cloned()
+    }
+}
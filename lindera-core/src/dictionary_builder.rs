@@ -1,11 +1,73 @@
 use std::path::Path;
 
+use csv::StringRecord;
+
 use crate::{
-    character_definition::CharacterDefinitions, dictionary::UserDictionary, LinderaResult,
+    character_definition::CharacterDefinitions, dictionary::UserDictionary,
+    error::LinderaErrorKind, LinderaResult,
 };
 
+/// The result of validating the source files of a dictionary without building it.
+#[derive(Clone, Debug, Default)]
+pub struct ValidationReport {
+    /// The number of entries in the lexicon files.
+    pub entries: usize,
+    /// The number of entries in `unk.def`.
+    pub unknown_entries: usize,
+    /// The number of character categories defined in `char.def`.
+    pub categories: usize,
+    /// The size of the connection cost matrix declared in `matrix.def`.
+    pub forward_size: usize,
+    pub backward_size: usize,
+    /// Problems that do not prevent the dictionary from being built, prefixed with their location.
+    pub warnings: Vec<String>,
+    /// Problems that make the build fail or produce a broken dictionary, prefixed with their
+    /// location.
+    pub errors: Vec<String>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
 pub trait DictionaryBuilder {
     fn build_dictionary(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()>;
+
+    /// Build the dictionary, reusing the connection cost matrix (`matrix.mtx`) previously built
+    /// in the output directory instead of building it again from `matrix.def`.
+    /// This is useful when only the lexicon has changed, since building the matrix can take long.
+    fn build_dictionary_without_cost_matrix(
+        &self,
+        input_dir: &Path,
+        output_dir: &Path,
+    ) -> LinderaResult<()> {
+        let matrix_path = output_dir.join("matrix.mtx");
+        if !matrix_path.is_file() {
+            return Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                "{} does not exist. Build the dictionary with the cost matrix first.",
+                matrix_path.display()
+            )));
+        }
+
+        let chardef = self.build_chardef(input_dir, output_dir)?;
+        self.build_unk(input_dir, &chardef, output_dir)?;
+        self.build_dict(input_dir, output_dir)?;
+
+        Ok(())
+    }
+
+    /// Run the parsing and validation passes of `build_dictionary` over all the source files
+    /// without writing anything, collecting the problems instead of stopping at the first one.
+    /// Builders that do not support the validation return an error.
+    fn validate_dictionary(&self, input_dir: &Path) -> LinderaResult<ValidationReport> {
+        Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+            "validating the sources in {} is not supported by this dictionary builder",
+            input_dir.display()
+        )))
+    }
+
     fn build_user_dictionary(&self, input_path: &Path, output_path: &Path) -> LinderaResult<()>;
     fn build_chardef(
         &self,
@@ -21,4 +83,12 @@ pub trait DictionaryBuilder {
     fn build_dict(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()>;
     fn build_cost_matrix(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()>;
     fn build_user_dict(&self, input_file: &Path) -> LinderaResult<UserDictionary>;
+
+    /// Build a user dictionary from rows already read from user dictionary CSV files, such as the
+    /// rows of several files merged together.
+    fn build_user_dict_from_rows(&self, rows: Vec<StringRecord>) -> LinderaResult<UserDictionary>;
+
+    /// Build the details of a word from a simple row of a user dictionary CSV, i.e. its
+    /// surface, part-of-speech and reading, in the layout of the details of the dictionary.
+    fn simple_userdic_details(&self, surface: &str, pos: &str, reading: &str) -> Vec<String>;
 }
@@ -1,11 +1,13 @@
 pub mod character_definition;
 pub mod connection;
+pub mod cost_model;
 pub mod dictionary;
 pub mod dictionary_builder;
 pub mod error;
 pub mod file_util;
 pub mod mode;
 pub mod prefix_dict;
+pub mod sentence_splitter;
 pub mod unknown_dictionary;
 pub mod viterbi;
 pub mod word_entry;
@@ -7,11 +7,19 @@ use crate::{
     viterbi::Edge,
 };
 
+/// Length-based cost penalty applied to long words in decompose (search) mode,
+/// in the same way as kuromoji's search mode.
+/// The fields missing from a deserialized penalty take their default values.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
 pub struct Penalty {
+    /// Words consisting only of kanji longer than this number of characters are penalized.
     pub kanji_penalty_length_threshold: usize,
+    /// Penalty added per kanji character beyond the threshold.
     pub kanji_penalty_length_penalty: i32,
+    /// Other words longer than this number of characters are penalized.
     pub other_penalty_length_threshold: usize,
+    /// Penalty added per character beyond the threshold.
     pub other_penalty_length_penalty: i32,
 }
 
@@ -67,12 +75,41 @@ impl Mode {
     }
 }
 
+/// Parse a mode from `normal`, `decompose` (alias: `search`) with the default penalty, or
+/// `decompose:<penalty>` with the penalty given as a JSON object of the `Penalty` fields,
+/// e.g. `search:{"kanji_penalty_length_threshold":3}`. The fields not given keep their
+/// default values.
+/// The JSON form of the mode itself, such as `{"decompose":{...}}`, is also accepted.
 impl FromStr for Mode {
     type Err = LinderaError;
     fn from_str(mode: &str) -> Result<Mode, Self::Err> {
-        match mode {
-            "normal" => Ok(Mode::Normal),
-            "decompose" => Ok(Mode::Decompose(Penalty::default())),
+        let mode = mode.trim();
+        if mode.starts_with('{') || mode.starts_with('"') {
+            return serde_json::from_str::<Mode>(mode).map_err(|err| {
+                LinderaErrorKind::ModeError.with_error(anyhow::anyhow!(
+                    "Invalid mode: {}: {}",
+                    mode,
+                    err
+                ))
+            });
+        }
+
+        let (name, params) = match mode.split_once(':') {
+            Some((name, params)) => (name, Some(params)),
+            None => (mode, None),
+        };
+        match (name, params) {
+            ("normal", None) => Ok(Mode::Normal),
+            ("decompose" | "search", None) => Ok(Mode::Decompose(Penalty::default())),
+            ("decompose" | "search", Some(params)) => serde_json::from_str::<Penalty>(params)
+                .map(Mode::Decompose)
+                .map_err(|err| {
+                    LinderaErrorKind::ModeError.with_error(anyhow::anyhow!(
+                        "Invalid mode parameters: {}: {}",
+                        params,
+                        err
+                    ))
+                }),
             _ => {
                 Err(LinderaErrorKind::ModeError
                     .with_error(anyhow::anyhow!("Invalid mode: {}", mode)))
@@ -80,3 +117,104 @@ impl FromStr for Mode {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::mode::{Mode, Penalty};
+    use crate::viterbi::Edge;
+
+    fn edge(num_chars: usize, kanji_only: bool) -> Edge {
+        Edge {
+            start_index: 0,
+            stop_index: (num_chars * 3) as u32,
+            kanji_only,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_penalty_kanji() {
+        let penalty = Penalty::default();
+
+        assert_eq!(penalty.penalty(&edge(2, true)), 0);
+        assert_eq!(penalty.penalty(&edge(3, true)), 3000);
+        assert_eq!(penalty.penalty(&edge(6, true)), 12000);
+    }
+
+    #[test]
+    fn test_penalty_other() {
+        let penalty = Penalty::default();
+
+        assert_eq!(penalty.penalty(&edge(7, false)), 0);
+        assert_eq!(penalty.penalty(&edge(8, false)), 1700);
+        assert_eq!(penalty.penalty(&edge(10, false)), 5100);
+    }
+
+    #[test]
+    fn test_mode_penalty_cost() {
+        assert_eq!(Mode::Normal.penalty_cost(&edge(6, true)), 0);
+        assert_eq!(
+            Mode::Decompose(Penalty::default()).penalty_cost(&edge(6, true)),
+            12000
+        );
+    }
+
+    #[test]
+    fn test_mode_from_str() {
+        assert_eq!(Mode::from_str("normal").unwrap(), Mode::Normal);
+        assert_eq!(
+            Mode::from_str("decompose").unwrap(),
+            Mode::Decompose(Penalty::default())
+        );
+        assert_eq!(
+            Mode::from_str("search").unwrap(),
+            Mode::Decompose(Penalty::default())
+        );
+        assert!(Mode::from_str("invalid").is_err());
+        // normal mode has no parameters
+        assert!(Mode::from_str("normal:{}").is_err());
+    }
+
+    #[test]
+    fn test_mode_from_str_with_params() {
+        assert_eq!(
+            Mode::from_str("search:{}").unwrap(),
+            Mode::Decompose(Penalty::default())
+        );
+        assert_eq!(
+            Mode::from_str(
+                r#"search:{"kanji_penalty_length_threshold":3,"kanji_penalty_length_penalty":10}"#
+            )
+            .unwrap(),
+            Mode::Decompose(Penalty {
+                kanji_penalty_length_threshold: 3,
+                kanji_penalty_length_penalty: 10,
+                ..Penalty::default()
+            })
+        );
+        assert_eq!(
+            Mode::from_str(r#"decompose:{"other_penalty_length_penalty":500}"#).unwrap(),
+            Mode::Decompose(Penalty {
+                other_penalty_length_penalty: 500,
+                ..Penalty::default()
+            })
+        );
+        assert!(Mode::from_str("search:{").is_err());
+        assert!(Mode::from_str(r#"search:{"kanji_penalty_length_penalty":"high"}"#).is_err());
+    }
+
+    #[test]
+    fn test_mode_from_str_json() {
+        assert_eq!(Mode::from_str(r#""normal""#).unwrap(), Mode::Normal);
+        assert_eq!(
+            Mode::from_str(r#"{"decompose":{"kanji_penalty_length_threshold":4}}"#).unwrap(),
+            Mode::Decompose(Penalty {
+                kanji_penalty_length_threshold: 4,
+                ..Penalty::default()
+            })
+        );
+        assert!(Mode::from_str(r#"{"search":{}}"#).is_err());
+    }
+}
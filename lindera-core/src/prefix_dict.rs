@@ -1,5 +1,6 @@
 use std::ops::Deref;
 
+use byteorder::{ByteOrder, LittleEndian};
 use serde::{Deserialize, Serialize};
 use yada::DoubleArray;
 
@@ -56,24 +57,260 @@ impl<D: Deref<Target = [u8]>> PrefixDict<D> {
     /// Find `WordEntry`s with surface
     pub fn find_surface(&self, surface: &str) -> Vec<WordEntry> {
         match self.da.exact_match_search(surface) {
-            Some(offset_len) => {
-                let offset = offset_len >> 5u32;
-                let offset_bytes = (offset as usize) * WordEntry::SERIALIZED_LEN;
-                let data: &[u8] = &self.vals_data[offset_bytes..];
-                let len = offset_len & ((1u32 << 5) - 1u32);
-                (0..len as usize)
-                    .map(|i| {
-                        WordEntry::deserialize(
-                            &data[WordEntry::SERIALIZED_LEN * i..],
-                            self.is_system,
-                        )
-                    })
-                    .collect::<Vec<WordEntry>>()
-            }
+            Some(offset_len) => self.word_entries(offset_len).collect::<Vec<WordEntry>>(),
             None => vec![],
         }
     }
+
+    /// Find the `WordEntry`s of the words whose surface starts with `prefix`, along with their
+    /// surface, up to `limit` entries. The words are in the byte order of their surface, and
+    /// the entries of a surface in the order of the dictionary. The traversal of the double
+    /// array stops as soon as `limit` entries are found.
+    pub fn predictive_search(&self, prefix: &str, limit: usize) -> Vec<(String, WordEntry)> {
+        let mut words = Vec::new();
+        if limit == 0 {
+            return words;
+        }
+
+        // Follow the prefix from the root.
+        let mut node_pos = 0;
+        let mut unit = match self.unit(node_pos) {
+            Some(unit) => unit,
+            None => return words,
+        };
+        for &label in prefix.as_bytes() {
+            node_pos ^= unit_offset(unit) ^ label as usize;
+            unit = match self.unit(node_pos) {
+                Some(child) if unit_label(child) == label as u32 => child,
+                _ => return words,
+            };
+        }
+
+        let mut key = prefix.as_bytes().to_vec();
+        self.collect_words(node_pos, unit, &mut key, limit, &mut words);
+
+        words
+    }
+
+    /// Collect the words of the subtree of the node, whose key is `key`, depth first and in
+    /// the order of the labels, until there are `limit` words.
+    fn collect_words(
+        &self,
+        node_pos: usize,
+        unit: u32,
+        key: &mut Vec<u8>,
+        limit: usize,
+        words: &mut Vec<(String, WordEntry)>,
+    ) {
+        let offset = unit_offset(unit);
+
+        // The leaf of the key is the child with the label 0, which comes before the others.
+        if unit_has_leaf(unit) {
+            if let (Some(leaf), Ok(surface)) =
+                (self.unit(node_pos ^ offset), std::str::from_utf8(key))
+            {
+                words.extend(
+                    self.word_entries(unit_value(leaf))
+                        .take(limit - words.len())
+                        .map(|word_entry| (surface.to_string(), word_entry)),
+                );
+            }
+        }
+
+        // The surfaces are UTF-8, so only the bytes that may follow the key are probed.
+        for label in next_labels(key) {
+            if words.len() >= limit {
+                return;
+            }
+
+            let child_pos = node_pos ^ offset ^ label as usize;
+            if let Some(child) = self.unit(child_pos) {
+                if unit_label(child) == label as u32 {
+                    key.push(label);
+                    self.collect_words(child_pos, child, key, limit, words);
+                    key.pop();
+                }
+            }
+        }
+    }
+
+    /// Get the unit of the double array at the index, if any.
+    fn unit(&self, index: usize) -> Option<u32> {
+        self.da
+            .0
+            .get(index * UNIT_SIZE..(index + 1) * UNIT_SIZE)
+            .map(LittleEndian::read_u32)
+    }
+
+    /// Get the `WordEntry`s of the value stored in the double array: the offset of the first
+    /// entry in `vals_data` and the number of entries.
+    fn word_entries(&self, offset_len: u32) -> impl Iterator<Item = WordEntry> + '_ {
+        let offset = offset_len >> 5u32;
+        let offset_bytes = (offset as usize) * WordEntry::SERIALIZED_LEN;
+        let data: &[u8] = &self.vals_data[offset_bytes..];
+        let len = offset_len & ((1u32 << 5) - 1u32);
+        (0..len as usize).map(move |i| {
+            WordEntry::deserialize(&data[WordEntry::SERIALIZED_LEN * i..], self.is_system)
+        })
+    }
+}
+
+// The double array of `yada` is made of the units of darts-clone, each a little-endian `u32`.
+// `yada` does not expose its units, so they are decoded here to walk the double array.
+
+/// The size of a unit of the double array in bytes.
+const UNIT_SIZE: usize = 4;
+
+/// Whether the node has a leaf, i.e. its key is a word.
+fn unit_has_leaf(unit: u32) -> bool {
+    (unit >> 8) & 1 == 1
+}
+
+/// The value of a leaf.
+fn unit_value(unit: u32) -> u32 {
+    unit & ((1 << 31) - 1)
+}
+
+/// The label of a node. The label of a leaf has the highest bit set, so it matches no byte.
+fn unit_label(unit: u32) -> u32 {
+    unit & ((1 << 31) | 0xFF)
+}
+
+/// The offset of the children of a node: the position of a child is the position of the node
+/// XOR the offset XOR the label of the child.
+fn unit_offset(unit: u32) -> usize {
+    ((unit >> 10) << ((unit & (1 << 9)) >> 6)) as usize
+}
+
+/// Return the bytes that may follow the key in a UTF-8 string, in increasing order: the
+/// continuation bytes if the key ends inside a character, and the first bytes of a character
+/// otherwise. The NUL byte is left out, as it is the label of the leaves.
+fn next_labels(key: &[u8]) -> impl Iterator<Item = u8> {
+    let continuation_len = key
+        .iter()
+        .rev()
+        .take_while(|&&byte| byte & 0xC0 == 0x80)
+        .count();
+    let inside_char = match key.len().checked_sub(continuation_len + 1) {
+        Some(first_index) => continuation_len + 1 < utf8_char_len(key[first_index]),
+        None => false,
+    };
+
+    if inside_char {
+        (0x80..=0xBF).chain(None.into_iter().flatten())
+    } else {
+        (0x01..=0x7F).chain(Some(0xC2..=0xF4).into_iter().flatten())
+    }
+}
+
+/// The length of the UTF-8 character starting with the byte.
+fn utf8_char_len(first_byte: u8) -> usize {
+    match first_byte {
+        0x00..=0x7F => 1,
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        _ => 4,
+    }
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use yada::builder::DoubleArrayBuilder;
+    use yada::DoubleArray;
+
+    use crate::prefix_dict::{next_labels, PrefixDict};
+    use crate::word_entry::{WordEntry, WordId};
+
+    /// Build a dictionary of the surfaces, with an entry of word ID `i` for the i-th surface and
+    /// a second entry for `東京`.
+    fn build_prefix_dict(surfaces: &[&str]) -> PrefixDict {
+        let mut sorted_surfaces = surfaces.to_vec();
+        sorted_surfaces.sort();
+
+        let mut keyset: Vec<(&[u8], u32)> = Vec::new();
+        let mut vals_data = Vec::new();
+        let mut id = 0u32;
+        for surface in sorted_surfaces {
+            let mut word_ids = vec![surfaces.iter().position(|s| *s == surface).unwrap() as u32];
+            if surface == "東京" {
+                word_ids.push(100);
+            }
+            for word_id in word_ids.iter() {
+                WordEntry {
+                    word_id: WordId(*word_id, true),
+                    word_cost: 0,
+                    left_id: 0,
+                    right_id: 0,
+                }
+                .serialize(&mut vals_data)
+                .unwrap();
+            }
+            keyset.push((surface.as_bytes(), (id << 5) | word_ids.len() as u32));
+            id += word_ids.len() as u32;
+        }
+
+        PrefixDict {
+            da: DoubleArray::new(DoubleArrayBuilder::build(&keyset).unwrap()),
+            vals_data,
+            is_system: true,
+        }
+    }
+
+    #[test]
+    fn test_predictive_search() {
+        let prefix_dict =
+            build_prefix_dict(&["東京都", "東", "東京", "大阪", "東京タワー", "東西"]);
+
+        let words = prefix_dict
+            .predictive_search("東京", 10)
+            .into_iter()
+            .map(|(surface, word_entry)| (surface, word_entry.word_id.0))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            words,
+            vec![
+                ("東京".to_string(), 2),
+                ("東京".to_string(), 100),
+                ("東京タワー".to_string(), 4),
+                ("東京都".to_string(), 0),
+            ]
+        );
+
+        // The words are in the byte order of their surface.
+        let surfaces = prefix_dict
+            .predictive_search("", 10)
+            .into_iter()
+            .map(|(surface, _)| surface)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            surfaces,
+            vec!["大阪", "東", "東京", "東京", "東京タワー", "東京都", "東西"]
+        );
+
+        // The search stops at the limit, even in the middle of the entries of a surface.
+        let surfaces = prefix_dict
+            .predictive_search("東", 3)
+            .into_iter()
+            .map(|(surface, _)| surface)
+            .collect::<Vec<_>>();
+        assert_eq!(surfaces, vec!["東", "東京", "東京"]);
+        assert_eq!(prefix_dict.predictive_search("東京", 1).len(), 1);
+        assert!(prefix_dict.predictive_search("東京", 0).is_empty());
+
+        assert!(prefix_dict.predictive_search("京都", 10).is_empty());
+    }
+
+    #[test]
+    fn test_next_labels() {
+        let first_bytes = next_labels(b"").collect::<Vec<u8>>();
+        assert_eq!(first_bytes.first(), Some(&0x01));
+        assert_eq!(first_bytes.last(), Some(&0xF4));
+        assert!(!first_bytes.contains(&0x80));
+        assert_eq!(next_labels("東".as_bytes()).count(), first_bytes.len());
+
+        // `東` is E6 9D B1.
+        let continuation_bytes = next_labels(&[0xE6, 0x9D]).collect::<Vec<u8>>();
+        assert_eq!(continuation_bytes, (0x80..=0xBF).collect::<Vec<u8>>());
+        assert_eq!(next_labels(&[0xE6]).count(), 64);
+    }
+}
@@ -0,0 +1,212 @@
+/// The default characters that terminate a sentence.
+pub const DEFAULT_SENTENCE_TERMINATORS: [char; 5] = ['。', '！', '？', '!', '?'];
+
+/// Splits a text into sentences after the terminator characters.
+///
+/// The terminator belongs to the sentence it ends, so the sentences can be concatenated back
+/// into the original text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SentenceSplitter {
+    /// The characters that terminate a sentence.
+    terminators: Vec<char>,
+
+    /// Whether a newline also terminates a sentence.
+    split_on_newline: bool,
+}
+
+impl SentenceSplitter {
+    /// Create a builder to configure the terminators of a sentence splitter.
+    pub fn builder() -> SentenceSplitterBuilder {
+        SentenceSplitterBuilder::default()
+    }
+
+    /// Split the text into sentences.
+    /// If the text contains no terminator, the whole text is returned as one sentence.
+    ///
+    /// # Arguments
+    ///
+    /// * `text`: The text to be split.
+    ///
+    /// returns: Vec<&str>
+    ///
+    pub fn split<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        text.split_inclusive(|c: char| self.is_terminator(c))
+            .collect()
+    }
+
+    /// Split the text into sentences lazily, in the same way as `split`.
+    ///
+    /// # Arguments
+    ///
+    /// * `text`: The text to be split.
+    ///
+    /// returns: Sentences
+    ///
+    pub fn sentences<'a>(&'a self, text: &'a str) -> Sentences<'a> {
+        Sentences {
+            splitter: self,
+            rest: text,
+        }
+    }
+
+    /// Return the byte offset of the end of the last sentence terminator of the text, or `None`
+    /// if the text contains no terminator.
+    ///
+    /// # Arguments
+    ///
+    /// * `text`: The text to be searched.
+    ///
+    /// returns: Option<usize>
+    ///
+    pub fn last_sentence_end(&self, text: &str) -> Option<usize> {
+        text.char_indices()
+            .rev()
+            .find(|&(_, c)| self.is_terminator(c))
+            .map(|(start, c)| start + c.len_utf8())
+    }
+
+    /// Return the byte offset of the end of the first sentence terminator of the text.
+    fn first_sentence_end(&self, text: &str) -> Option<usize> {
+        text.char_indices()
+            .find(|&(_, c)| self.is_terminator(c))
+            .map(|(start, c)| start + c.len_utf8())
+    }
+
+    fn is_terminator(&self, c: char) -> bool {
+        (self.split_on_newline && c == '\n') || self.terminators.contains(&c)
+    }
+}
+
+/// The iterator returned by `SentenceSplitter::sentences`.
+#[derive(Clone, Debug)]
+pub struct Sentences<'a> {
+    splitter: &'a SentenceSplitter,
+    /// The part of the text that has not been split yet.
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Sentences<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+
+        let len = self
+            .splitter
+            .first_sentence_end(self.rest)
+            .unwrap_or(self.rest.len());
+        let (sentence, rest) = self.rest.split_at(len);
+        self.rest = rest;
+
+        Some(sentence)
+    }
+}
+
+impl Default for SentenceSplitter {
+    fn default() -> Self {
+        SentenceSplitterBuilder::default().build()
+    }
+}
+
+/// Builder of `SentenceSplitter`.
+/// By default, the sentences are terminated by `。`, `！`, `？`, `!`, `?` and newlines.
+#[derive(Clone, Debug)]
+pub struct SentenceSplitterBuilder {
+    terminators: Vec<char>,
+    split_on_newline: bool,
+}
+
+impl SentenceSplitterBuilder {
+    /// Replace the terminator characters.
+    pub fn terminators(mut self, terminators: &[char]) -> Self {
+        self.terminators = terminators.to_vec();
+        self
+    }
+
+    /// Add a terminator character.
+    pub fn terminator(mut self, terminator: char) -> Self {
+        if !self.terminators.contains(&terminator) {
+            self.terminators.push(terminator);
+        }
+        self
+    }
+
+    /// Set whether a newline terminates a sentence.
+    pub fn split_on_newline(mut self, split_on_newline: bool) -> Self {
+        self.split_on_newline = split_on_newline;
+        self
+    }
+
+    pub fn build(self) -> SentenceSplitter {
+        SentenceSplitter {
+            terminators: self.terminators,
+            split_on_newline: self.split_on_newline,
+        }
+    }
+}
+
+impl Default for SentenceSplitterBuilder {
+    fn default() -> Self {
+        Self {
+            terminators: DEFAULT_SENTENCE_TERMINATORS.to_vec(),
+            split_on_newline: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sentence_splitter::SentenceSplitter;
+
+    #[test]
+    fn test_split_default() {
+        let splitter = SentenceSplitter::default();
+
+        assert_eq!(
+            splitter.split("東京へ行きます。京都は遠いですか？\n大阪"),
+            vec!["東京へ行きます。", "京都は遠いですか？", "\n", "大阪"]
+        );
+        assert_eq!(splitter.split("東京へ行きます"), vec!["東京へ行きます"]);
+        assert!(splitter.split("").is_empty());
+    }
+
+    #[test]
+    fn test_sentences() {
+        let splitter = SentenceSplitter::default();
+
+        let mut sentences = splitter.sentences("東京へ行きます！京都");
+        assert_eq!(sentences.next(), Some("東京へ行きます！"));
+        assert_eq!(sentences.next(), Some("京都"));
+        assert_eq!(sentences.next(), None);
+    }
+
+    #[test]
+    fn test_last_sentence_end() {
+        let splitter = SentenceSplitter::default();
+
+        assert_eq!(
+            splitter.last_sentence_end("東京。京都？大阪"),
+            Some("東京。京都？".len())
+        );
+        assert_eq!(splitter.last_sentence_end("東京\n京都"), Some(7));
+        assert_eq!(splitter.last_sentence_end("東京、京都"), None);
+        assert_eq!(splitter.last_sentence_end(""), None);
+    }
+
+    #[test]
+    fn test_split_custom_terminators() {
+        let splitter = SentenceSplitter::builder()
+            .terminators(&['。'])
+            .terminator('!')
+            .terminator('?')
+            .split_on_newline(false)
+            .build();
+
+        assert_eq!(
+            splitter.split("Hello! 東京へ行きます。Really?\nYes！"),
+            vec!["Hello!", " 東京へ行きます。", "Really?", "\nYes！"]
+        );
+    }
+}
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     character_definition::CategoryId,
+    dictionary::strip_dictionary_header,
     error::LinderaErrorKind,
     word_entry::{WordEntry, WordId},
     LinderaResult,
@@ -18,7 +19,7 @@ pub struct UnknownDictionary {
 
 impl UnknownDictionary {
     pub fn load(unknown_data: &[u8]) -> LinderaResult<UnknownDictionary> {
-        bincode::deserialize(unknown_data)
+        bincode::deserialize(strip_dictionary_header(unknown_data)?)
             .map_err(|err| LinderaErrorKind::Deserialize.with_error(anyhow::anyhow!(err)))
     }
 
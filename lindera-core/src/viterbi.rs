@@ -1,15 +1,23 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
 use std::u32;
 
 use crate::{
     character_definition::{CategoryId, CharacterDefinitions},
     connection::ConnectionCostMatrix,
+    cost_model::CostModel,
+    dictionary::UserWordOverlay,
     mode::Mode,
     prefix_dict::PrefixDict,
     unknown_dictionary::UnknownDictionary,
     word_entry::{WordEntry, WordId},
 };
 
-const EOS_NODE: EdgeId = EdgeId(1u32);
+/// The edge at the beginning of the text.
+pub const BOS_NODE: EdgeId = EdgeId(0u32);
+
+/// The edge at the end of the text.
+pub const EOS_NODE: EdgeId = EdgeId(1u32);
 
 #[derive(Clone, Copy, Debug, Default)]
 pub enum EdgeType {
@@ -82,22 +90,37 @@ impl Lattice {
         }
     }
 
+    /// Build the lattice of the text.
+    ///
+    /// The words of the overlay are looked up in addition to the user dictionary.
+    /// Grouped unknown words are split every `max_unknown_length` characters.
+    /// The word cost of the unknown words of a character category in `unknown_cost_overrides`
+    /// replaces the cost of the unknown dictionary.
     #[inline(never)]
+    #[allow(clippy::too_many_arguments)]
     pub fn set_text(
         &mut self,
         dict: &PrefixDict,
         user_dict: &Option<&PrefixDict>,
+        user_words: &UserWordOverlay,
         char_definitions: &CharacterDefinitions,
         unknown_dictionary: &UnknownDictionary,
         text: &str,
         search_mode: &Mode,
+        max_unknown_length: usize,
+        unknown_cost_overrides: &HashMap<String, i16>,
     ) {
         let len = text.len();
         self.set_capacity(len);
 
         let start_edge_id = self.add_edge(Edge::default());
-        let end_edge_id = self.add_edge(Edge::default());
+        let end_edge_id = self.add_edge(Edge {
+            start_index: len as u32,
+            stop_index: len as u32,
+            ..Edge::default()
+        });
 
+        assert_eq!(BOS_NODE, start_edge_id);
         assert_eq!(EOS_NODE, end_edge_id);
         self.ends_at[0].push(start_edge_id);
         self.starts_at[len].push(end_edge_id);
@@ -116,6 +139,23 @@ impl Lattice {
 
             let mut found: bool = false;
 
+            // lookup the words added at runtime
+            if !user_words.is_empty() {
+                for (prefix_len, word_entry) in user_words.prefix(suffix) {
+                    let edge = Edge {
+                        edge_type: EdgeType::USER,
+                        word_entry,
+                        left_edge: None,
+                        start_index: start as u32,
+                        stop_index: (start + prefix_len) as u32,
+                        path_cost: i32::max_value(),
+                        kanji_only: is_kanji_only(&suffix[..prefix_len]),
+                    };
+                    self.add_edge_in_lattice(edge);
+                    found = true;
+                }
+            }
+
             // lookup user dictionary
             if user_dict.is_some() {
                 let dict = user_dict.as_ref().unwrap();
@@ -166,6 +206,8 @@ impl Lattice {
                             start,
                             suffix,
                             found,
+                            max_unknown_length,
+                            unknown_cost_overrides,
                         );
                     }
                 }
@@ -184,6 +226,8 @@ impl Lattice {
         start: usize,
         suffix: &str,
         found: bool,
+        max_unknown_length: usize,
+        unknown_cost_overrides: &HashMap<String, i16>,
     ) -> Option<usize> {
         let mut unknown_word_num_chars: usize = 0;
         let category_data = char_definitions.lookup_definition(category);
@@ -191,6 +235,9 @@ impl Lattice {
             unknown_word_num_chars = 1;
             if category_data.group {
                 for c in suffix.chars().skip(1) {
+                    if unknown_word_num_chars >= max_unknown_length {
+                        break;
+                    }
                     let categories = char_definitions.lookup_categories(c);
                     if categories.len() > category_ord && categories[category_ord] == category {
                         unknown_word_num_chars += 1;
@@ -206,8 +253,14 @@ impl Lattice {
                 .chars()
                 .take(unknown_word_num_chars)
                 .collect::<String>();
+            let cost_override = unknown_cost_overrides
+                .get(char_definitions.category_name(category))
+                .copied();
             for &word_id in unknown_dictionary.lookup_word_ids(category) {
-                let word_entry = unknown_dictionary.word_entry(word_id);
+                let mut word_entry = unknown_dictionary.word_entry(word_id);
+                if let Some(word_cost) = cost_override {
+                    word_entry.word_cost = word_cost;
+                }
                 let edge = Edge {
                     edge_type: EdgeType::UNKNOWN,
                     word_entry,
@@ -242,30 +295,53 @@ impl Lattice {
         &self.edges[edge_id.0 as usize]
     }
 
+    /// Returns all the edges of the lattice, indexed by their `EdgeId`.
+    /// The first two edges are `BOS_NODE` and `EOS_NODE`.
+    pub fn edges(&self) -> &[Edge] {
+        &self.edges
+    }
+
+    /// Returns the edges starting at the byte index of the text.
+    pub fn edges_starting_at(&self, index: usize) -> &[EdgeId] {
+        self.starts_at.get(index).map_or(&[], |edge_ids| edge_ids)
+    }
+
+    /// Returns the edges ending at the byte index of the text.
+    pub fn edges_ending_at(&self, index: usize) -> &[EdgeId] {
+        self.ends_at.get(index).map_or(&[], |edge_ids| edge_ids)
+    }
+
+    /// Compute the cost of the best path from the beginning of the text to each edge, with the
+    /// word costs and the connection costs given by the cost model.
     #[inline(never)]
-    pub fn calculate_path_costs(&mut self, cost_matrix: &ConnectionCostMatrix, mode: &Mode) {
+    pub fn calculate_path_costs(
+        &mut self,
+        cost_matrix: &ConnectionCostMatrix,
+        mode: &Mode,
+        cost_model: &dyn CostModel,
+    ) {
         let text_len = self.starts_at.len();
         for i in 0..text_len {
             let left_edge_ids = &self.ends_at[i];
             let right_edge_ids = &self.starts_at[i];
             for &right_edge_id in right_edge_ids {
-                let right_word_entry = self.edge(right_edge_id).word_entry;
+                let right_edge = self.edge(right_edge_id);
                 let best_path = left_edge_ids
                     .iter()
                     .cloned()
                     .map(|left_edge_id| {
                         let left_edge = self.edge(left_edge_id);
                         let mut path_cost = left_edge.path_cost
-                            + cost_matrix
-                                .cost(left_edge.word_entry.right_id(), right_word_entry.left_id());
+                            + cost_model.connection_cost(cost_matrix, left_edge, right_edge);
                         path_cost += mode.penalty_cost(left_edge);
                         (path_cost, left_edge_id)
                     })
                     .min_by_key(|&(cost, _)| cost);
                 if let Some((best_cost, best_left)) = best_path {
+                    let word_cost = cost_model.word_cost(right_edge);
                     let edge = &mut self.edges[right_edge_id.0 as usize];
                     edge.left_edge = Some(best_left);
-                    edge.path_cost = right_word_entry.word_cost as i32 + best_cost;
+                    edge.path_cost = word_cost + best_cost;
                 }
             }
         }
@@ -288,4 +364,172 @@ impl Lattice {
         offsets.pop();
         offsets
     }
+
+    /// Returns the best path, without `BOS_NODE` and `EOS_NODE`.
+    pub fn best_path(&self) -> Vec<EdgeId> {
+        let mut path = Vec::new();
+        let mut edge_id = EOS_NODE;
+        while let Some(left_edge_id) = self.edge(edge_id).left_edge {
+            if left_edge_id != BOS_NODE {
+                path.push(left_edge_id);
+            }
+            edge_id = left_edge_id;
+        }
+        path.reverse();
+        path
+    }
+
+    /// Returns up to `n` paths from the beginning to the end of the text together with their
+    /// cost, in increasing order of cost. The first path is the best path.
+    /// A path is the list of its edges, without `BOS_NODE` and `EOS_NODE`.
+    ///
+    /// `calculate_path_costs` must have been called with the same cost matrix, mode and cost
+    /// model.
+    /// The paths are enumerated by an A* search from the end of the text, which uses the cost
+    /// of the best path to each edge computed by `calculate_path_costs` as the heuristic.
+    pub fn nbest_paths(
+        &self,
+        cost_matrix: &ConnectionCostMatrix,
+        mode: &Mode,
+        cost_model: &dyn CostModel,
+        n: usize,
+    ) -> Vec<(i32, Vec<EdgeId>)> {
+        struct Hypothesis {
+            edge_id: EdgeId,
+            // The cost from the end of the edge to the end of the text.
+            backward_cost: i32,
+            // The index of the hypothesis of the next edge in the path.
+            next: Option<usize>,
+        }
+
+        let mut paths = Vec::new();
+        if n == 0 || self.edge(EOS_NODE).left_edge.is_none() {
+            return paths;
+        }
+
+        // The best path is taken from `calculate_path_costs` so that ties are broken in the
+        // same way as `tokens_offset`, and it is skipped when the search finds it.
+        let best_path = self.best_path();
+        paths.push((self.edge(EOS_NODE).path_cost, best_path.clone()));
+        let mut best_path_found = false;
+
+        let mut hypotheses = vec![Hypothesis {
+            edge_id: EOS_NODE,
+            backward_cost: 0,
+            next: None,
+        }];
+        let mut heap = BinaryHeap::new();
+        heap.push(Reverse((self.edge(EOS_NODE).path_cost, 0)));
+
+        while let Some(Reverse((cost, index))) = heap.pop() {
+            if paths.len() >= n {
+                break;
+            }
+
+            let edge_id = hypotheses[index].edge_id;
+            if edge_id == BOS_NODE {
+                let mut path = Vec::new();
+                let mut next = hypotheses[index].next;
+                while let Some(next_index) = next {
+                    if hypotheses[next_index].edge_id != EOS_NODE {
+                        path.push(hypotheses[next_index].edge_id);
+                    }
+                    next = hypotheses[next_index].next;
+                }
+
+                if !best_path_found && path == best_path {
+                    best_path_found = true;
+                } else {
+                    paths.push((cost, path));
+                }
+                continue;
+            }
+
+            let edge = self.edge(edge_id);
+            let backward_cost = hypotheses[index].backward_cost + cost_model.word_cost(edge);
+            for &left_edge_id in self.edges_ending_at(edge.start_index as usize) {
+                let left_edge = self.edge(left_edge_id);
+                // The edge cannot be reached from the beginning of the text.
+                if left_edge_id != BOS_NODE && left_edge.left_edge.is_none() {
+                    continue;
+                }
+
+                let left_backward_cost = backward_cost
+                    + cost_model.connection_cost(cost_matrix, left_edge, edge)
+                    + mode.penalty_cost(left_edge);
+                hypotheses.push(Hypothesis {
+                    edge_id: left_edge_id,
+                    backward_cost: left_backward_cost,
+                    next: Some(index),
+                });
+                heap.push(Reverse((
+                    left_edge.path_cost + left_backward_cost,
+                    hypotheses.len() - 1,
+                )));
+            }
+        }
+
+        paths
+    }
+
+    /// Returns the byte offset and the word ID of each edge of the path, in the same form as
+    /// `tokens_offset`.
+    pub fn path_offsets(&self, path: &[EdgeId]) -> Vec<(usize, WordId)> {
+        path.iter()
+            .map(|&edge_id| {
+                let edge = self.edge(edge_id);
+                (edge.start_index as usize, edge.word_entry.word_id)
+            })
+            .collect()
+    }
+
+    /// Returns the word cost of each edge of the path and the connection cost from the
+    /// previous edge, in the same form as `tokens_costs`.
+    pub fn path_costs(
+        &self,
+        path: &[EdgeId],
+        cost_matrix: &ConnectionCostMatrix,
+        cost_model: &dyn CostModel,
+    ) -> Vec<(i32, i32)> {
+        let mut left_edge = self.edge(BOS_NODE);
+        path.iter()
+            .map(|&edge_id| {
+                let edge = self.edge(edge_id);
+                let costs = (
+                    cost_model.word_cost(edge),
+                    cost_model.connection_cost(cost_matrix, left_edge, edge),
+                );
+                left_edge = edge;
+                costs
+            })
+            .collect()
+    }
+
+    /// Returns the word cost of each token of the best path and the connection cost from the
+    /// previous token (the beginning of the text for the first token), in the same order as
+    /// `tokens_offset`.
+    pub fn tokens_costs(
+        &self,
+        cost_matrix: &ConnectionCostMatrix,
+        cost_model: &dyn CostModel,
+    ) -> Vec<(i32, i32)> {
+        let mut costs = Vec::new();
+        let mut edge_id = EOS_NODE;
+        loop {
+            let edge = self.edge(edge_id);
+            if let Some(left_edge_id) = edge.left_edge {
+                let left_edge = self.edge(left_edge_id);
+                costs.push((
+                    cost_model.word_cost(edge),
+                    cost_model.connection_cost(cost_matrix, left_edge, edge),
+                ));
+                edge_id = left_edge_id;
+            } else {
+                break;
+            }
+        }
+        costs.reverse();
+        costs.pop();
+        costs
+    }
 }
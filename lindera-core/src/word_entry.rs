@@ -10,6 +10,11 @@ impl WordId {
     pub fn is_unknown(&self) -> bool {
         self.0 == std::u32::MAX
     }
+    /// Returns true if the word is registered in the system or user dictionary,
+    /// false if it was produced by the unknown word processing.
+    pub fn is_known(&self) -> bool {
+        !self.is_unknown()
+    }
     pub fn is_system(&self) -> bool {
         self.1
     }
@@ -66,6 +71,14 @@ impl WordEntry {
 mod tests {
     use crate::word_entry::{WordEntry, WordId};
 
+    #[test]
+    fn test_word_id_is_known() {
+        assert!(WordId(1u32, true).is_known());
+        assert!(WordId(1u32, false).is_known());
+        assert!(!WordId(u32::MAX, true).is_known());
+        assert!(!WordId::default().is_known());
+    }
+
     #[test]
     fn test_word_entry() {
         let mut buffer = Vec::new();
@@ -3,12 +3,17 @@ use std::io::Read;
 use flate2::read::{DeflateDecoder, GzDecoder, ZlibDecoder};
 use serde::{Deserialize, Serialize};
 
+/// The compression algorithm of a `CompressedData`.
+///
+/// The algorithm is stored by its index in the header of the compressed data, so that the data
+/// can be decompressed without knowing how it was built. New variants must be added at the end.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum Algorithm {
     Deflate,
     Zlib,
     Gzip,
     Raw,
+    Zstd,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,5 +49,6 @@ pub fn decompress(data: CompressedData) -> anyhow::Result<Vec<u8>> {
             Ok(output_data)
         }
         Algorithm::Raw => Ok(data.data),
+        Algorithm::Zstd => Ok(zstd::stream::decode_all(data.data.as_slice())?),
     }
 }
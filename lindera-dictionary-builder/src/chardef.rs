@@ -6,12 +6,14 @@ use std::path::Path;
 
 use derive_builder::Builder;
 use lindera_core::character_definition::{CharacterDefinitions, CharacterDefinitionsBuilder};
+use lindera_core::dictionary_builder::ValidationReport;
 use lindera_core::error::LinderaErrorKind;
 use lindera_core::LinderaResult;
 use lindera_decompress::Algorithm;
 use log::debug;
 
 use crate::utils::{compress_write, read_file_with_encoding};
+use crate::validator::{error_message, read_text};
 
 #[derive(Builder, Debug)]
 #[builder(name = "CharDefBuilderOptions")]
@@ -55,4 +57,34 @@ impl CharDefBuilder {
 
         Ok(char_definitions)
     }
+
+    /// Parse `char.def` the same way as `build` without writing anything, recording the problems
+    /// in the report. Returns the names of the categories defined in it.
+    pub fn validate(
+        &self,
+        input_dir: &Path,
+        report: &mut ValidationReport,
+    ) -> LinderaResult<Vec<String>> {
+        let char_def_path = input_dir.join("char.def");
+        let char_def = match read_text(&char_def_path, &self.encoding, report)? {
+            Some(char_def) => char_def,
+            None => return Ok(Vec::new()),
+        };
+
+        let mut char_definitions_builder = CharacterDefinitionsBuilder::default();
+        for (i, line) in char_def.lines().enumerate() {
+            if let Err(err) = char_definitions_builder.parse(line) {
+                report.errors.push(format!(
+                    "{}:{}: {}",
+                    char_def_path.display(),
+                    i + 1,
+                    error_message(&err)
+                ));
+            }
+        }
+
+        let categories = char_definitions_builder.build().categories().to_vec();
+        report.categories = categories.len();
+        Ok(categories)
+    }
 }
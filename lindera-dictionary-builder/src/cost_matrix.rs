@@ -7,12 +7,14 @@ use std::str::FromStr;
 
 use byteorder::{LittleEndian, WriteBytesExt};
 use derive_builder::Builder;
+use lindera_core::dictionary_builder::ValidationReport;
 use lindera_core::error::LinderaErrorKind;
 use lindera_core::LinderaResult;
 use lindera_decompress::Algorithm;
 use log::debug;
 
 use crate::utils::{compress_write, read_file_with_encoding};
+use crate::validator::{error_message, read_text};
 
 #[derive(Builder, Debug)]
 #[builder(name = "CostMatrixBuilderOptions")]
@@ -30,30 +32,15 @@ impl CostMatrixBuilder {
         debug!("reading {:?}", matrix_data_path);
         let matrix_data = read_file_with_encoding(&matrix_data_path, &self.encoding)?;
 
-        let mut lines = Vec::new();
-        for line in matrix_data.lines() {
-            let fields: Vec<i32> = line
-                .split_whitespace()
-                .map(i32::from_str)
-                .collect::<Result<_, _>>()
-                .map_err(|err| LinderaErrorKind::Parse.with_error(anyhow::anyhow!(err)))?;
-            lines.push(fields);
-        }
-        let mut lines_it = lines.into_iter();
-        let header = lines_it.next().ok_or_else(|| {
-            LinderaErrorKind::Content.with_error(anyhow::anyhow!("unknown error"))
-        })?;
-        let forward_size = header[0] as u32;
-        let backward_size = header[1] as u32;
-        let len = 2 + (forward_size * backward_size) as usize;
+        let mut lines = matrix_data.lines();
+        let (forward_size, backward_size) = parse_header(lines.next().unwrap_or_default())?;
+        let len = 2 + forward_size * backward_size;
         let mut costs = vec![i16::MAX; len];
         costs[0] = forward_size as i16;
         costs[1] = backward_size as i16;
-        for fields in lines_it {
-            let forward_id = fields[0] as u32;
-            let backward_id = fields[1] as u32;
-            let cost = fields[2] as u16;
-            costs[2 + (backward_id + forward_id * backward_size) as usize] = cost as i16;
+        for line in lines {
+            let (forward_id, backward_id, cost) = parse_row(line, forward_size, backward_size)?;
+            costs[2 + backward_id + forward_id * backward_size] = cost;
         }
 
         let wtr_matrix_mtx_path = output_dir.join(Path::new("matrix.mtx"));
@@ -80,4 +67,160 @@ impl CostMatrixBuilder {
 
         Ok(())
     }
+
+    /// Parse `matrix.def` the same way as `build` without writing anything, recording the
+    /// problems in the report. Returns the forward and backward sizes declared in its header.
+    pub fn validate(
+        &self,
+        input_dir: &Path,
+        report: &mut ValidationReport,
+    ) -> LinderaResult<Option<(usize, usize)>> {
+        let matrix_data_path = input_dir.join("matrix.def");
+        let matrix_data = match read_text(&matrix_data_path, &self.encoding, report)? {
+            Some(matrix_data) => matrix_data,
+            None => return Ok(None),
+        };
+
+        let mut lines = matrix_data.lines().enumerate();
+        let header = lines.next().map_or("", |(_, line)| line);
+        let (forward_size, backward_size) = match parse_header(header) {
+            Ok(sizes) => sizes,
+            Err(err) => {
+                report.errors.push(format!(
+                    "{}:1: {}",
+                    matrix_data_path.display(),
+                    error_message(&err)
+                ));
+                return Ok(None);
+            }
+        };
+        report.forward_size = forward_size;
+        report.backward_size = backward_size;
+
+        for (i, line) in lines {
+            if let Err(err) = parse_row(line, forward_size, backward_size) {
+                report.errors.push(format!(
+                    "{}:{}: {}",
+                    matrix_data_path.display(),
+                    i + 1,
+                    error_message(&err)
+                ));
+            }
+        }
+
+        Ok(Some((forward_size, backward_size)))
+    }
+}
+
+/// Parse the header of `matrix.def`: the forward and backward sizes of the matrix.
+/// Any field after the sizes is ignored.
+fn parse_header(line: &str) -> LinderaResult<(usize, usize)> {
+    let sizes = line
+        .split_whitespace()
+        .map(usize::from_str)
+        .collect::<Result<Vec<_>, _>>();
+    match sizes {
+        Ok(sizes) if sizes.len() >= 2 => Ok((sizes[0], sizes[1])),
+        _ => Err(LinderaErrorKind::Content.with_error(anyhow::anyhow!(
+            "expected the forward and backward sizes, got {:?}",
+            line
+        ))),
+    }
+}
+
+/// Parse a row of `matrix.def`: the forward ID, the backward ID and the connection cost.
+/// The IDs must be within the sizes declared in the header, and the cost must fit in an `i16`.
+fn parse_row(
+    line: &str,
+    forward_size: usize,
+    backward_size: usize,
+) -> LinderaResult<(usize, usize, i16)> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() != 3 {
+        return Err(LinderaErrorKind::Content
+            .with_error(anyhow::anyhow!("expected 3 fields, got {}", fields.len())));
+    }
+
+    let (forward_id, backward_id, cost) = match (
+        usize::from_str(fields[0]),
+        usize::from_str(fields[1]),
+        i32::from_str(fields[2]),
+    ) {
+        (Ok(forward_id), Ok(backward_id), Ok(cost)) => (forward_id, backward_id, cost),
+        _ => {
+            return Err(
+                LinderaErrorKind::Parse.with_error(anyhow::anyhow!("failed to parse {:?}", line))
+            )
+        }
+    };
+    if forward_id >= forward_size || backward_id >= backward_size {
+        return Err(LinderaErrorKind::Content.with_error(anyhow::anyhow!(
+            "context ID out of range: {} {} (the matrix is {} x {})",
+            forward_id,
+            backward_id,
+            forward_size,
+            backward_size
+        )));
+    }
+    let cost = i16::try_from(cost).map_err(|_| {
+        LinderaErrorKind::Content.with_error(anyhow::anyhow!("cost out of range: {}", cost))
+    })?;
+
+    Ok((forward_id, backward_id, cost))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{self, File};
+    use std::io::Write;
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    use crate::cost_matrix::CostMatrixBuilderOptions;
+
+    const MATRIX_DEF: &str = "2 3
+0 0 0
+0 1 -100
+0 2 200
+1 0 300
+1 1 -400
+1 2 500
+";
+
+    #[test]
+    fn test_build_gzipped_matrix_def() {
+        let base_dir = std::env::temp_dir().join("lindera-dictionary-builder-test-gzipped-matrix");
+        let plain_input_dir = base_dir.join("plain_input");
+        let gz_input_dir = base_dir.join("gz_input");
+        let plain_output_dir = base_dir.join("plain_output");
+        let gz_output_dir = base_dir.join("gz_output");
+        for dir in [
+            &plain_input_dir,
+            &gz_input_dir,
+            &plain_output_dir,
+            &gz_output_dir,
+        ] {
+            fs::create_dir_all(dir).unwrap();
+        }
+
+        fs::write(plain_input_dir.join("matrix.def"), MATRIX_DEF).unwrap();
+        let mut encoder = GzEncoder::new(
+            File::create(gz_input_dir.join("matrix.def.gz")).unwrap(),
+            Compression::default(),
+        );
+        encoder.write_all(MATRIX_DEF.as_bytes()).unwrap();
+        encoder.finish().unwrap();
+
+        let builder = CostMatrixBuilderOptions::default().builder().unwrap();
+        builder.build(&plain_input_dir, &plain_output_dir).unwrap();
+        builder.build(&gz_input_dir, &gz_output_dir).unwrap();
+
+        assert_eq!(
+            fs::read(plain_output_dir.join("matrix.mtx")).unwrap(),
+            fs::read(gz_output_dir.join("matrix.mtx")).unwrap()
+        );
+
+        fs::remove_dir_all(&base_dir).unwrap();
+    }
 }
@@ -3,7 +3,7 @@ use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::Write;
 use std::io::{self, Read};
-use std::path::{Path, PathBuf};
+use std::path::Path;
 use std::str::FromStr;
 
 use anyhow::anyhow;
@@ -12,16 +12,21 @@ use csv::StringRecord;
 use derive_builder::Builder;
 use encoding_rs::{Encoding, UTF_8};
 use encoding_rs_io::DecodeReaderBytesBuilder;
-use glob::glob;
 use log::{debug, warn};
 use yada::builder::DoubleArrayBuilder;
 
+use lindera_core::dictionary_builder::ValidationReport;
 use lindera_core::error::LinderaErrorKind;
 use lindera_core::word_entry::{WordEntry, WordId};
 use lindera_core::LinderaResult;
 use lindera_decompress::Algorithm;
 
-use crate::utils::compress_write;
+use crate::utils::{compress_write, lexicon_files, open_source_file};
+use crate::validator::{check_context_ids, error_message, read_text};
+
+/// The fields of a row before the details: surface, left context ID, right context ID and word
+/// cost.
+const LEADING_FIELDS_NUM: usize = 4;
 
 #[derive(Builder, Debug)]
 #[builder(name = "DictBuilderOptions")]
@@ -42,30 +47,7 @@ pub struct DictBuilder {
 
 impl DictBuilder {
     pub fn build(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()> {
-        let pattern = if let Some(path) = input_dir.to_str() {
-            format!("{}/*.csv", path)
-        } else {
-            return Err(
-                LinderaErrorKind::Io.with_error(anyhow::anyhow!("Failed to convert path to &str."))
-            );
-        };
-
-        let mut filenames: Vec<PathBuf> = Vec::new();
-        for entry in
-            glob(&pattern).map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?
-        {
-            match entry {
-                Ok(path) => {
-                    if let Some(filename) = path.file_name() {
-                        filenames.push(Path::new(input_dir).join(filename));
-                    } else {
-                        return Err(LinderaErrorKind::Io
-                            .with_error(anyhow::anyhow!("failed to get filename")));
-                    };
-                }
-                Err(err) => return Err(LinderaErrorKind::Content.with_error(anyhow!(err))),
-            }
-        }
+        let filenames = lexicon_files(input_dir)?;
 
         let encoding = Encoding::for_label_no_replacement(self.encoding.as_bytes());
         let encoding = encoding.ok_or_else(|| {
@@ -76,10 +58,9 @@ impl DictBuilder {
         for filename in filenames {
             debug!("reading {:?}", filename);
 
-            let file = File::open(filename)
-                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+            let file = open_source_file(&filename)?;
             let reader: Box<dyn Read> = if encoding == UTF_8 {
-                Box::new(file)
+                file
             } else {
                 Box::new(
                     DecodeReaderBytesBuilder::new()
@@ -87,10 +68,7 @@ impl DictBuilder {
                         .build(file),
                 )
             };
-            let mut rdr = csv::ReaderBuilder::new()
-                .has_headers(false)
-                .flexible(self.flexible_csv)
-                .from_reader(reader);
+            let mut rdr = self.csv_reader(reader);
 
             for result in rdr.records() {
                 let record =
@@ -99,6 +77,28 @@ impl DictBuilder {
             }
         }
 
+        self.build_from_records(rows, output_dir)
+    }
+
+    /// Build the dictionary from rows held in memory, in the same layout as the lexicon CSV
+    /// files: surface, left context ID, right context ID, word cost, followed by the details.
+    /// No source file is read, so a dictionary can be built without CSV files on disk.
+    pub fn build_from_rows(&self, rows: Vec<Vec<String>>, output_dir: &Path) -> LinderaResult<()> {
+        let records = rows.into_iter().map(StringRecord::from).collect();
+        self.build_from_records(records, output_dir)
+    }
+
+    /// Build the dictionary from CSV records in the same layout as the lexicon CSV files.
+    /// See `build_from_rows`.
+    pub fn build_from_records(
+        &self,
+        mut rows: Vec<StringRecord>,
+        output_dir: &Path,
+    ) -> LinderaResult<()> {
+        for row in rows.iter() {
+            check_leading_fields(row)?;
+        }
+
         if self.normalize_details {
             rows.sort_by_key(|row| normalize(&row[0]));
         } else {
@@ -120,39 +120,14 @@ impl DictBuilder {
         let mut word_entry_map: BTreeMap<String, Vec<WordEntry>> = BTreeMap::new();
 
         for (row_id, row) in rows.iter().enumerate() {
-            let word_cost = match i16::from_str(row[3].trim()) {
-                Ok(wc) => wc,
-                Err(_err) => {
+            let (word_cost, left_id, right_id) = match parse_row(row) {
+                Ok(fields) => fields,
+                Err(err) => {
                     if self.skip_invalid_cost_or_id {
-                        warn!("failed to parse word_cost: {:?}", row);
+                        warn!("{}: {:?}", error_message(&err), row);
                         continue;
                     } else {
-                        return Err(LinderaErrorKind::Parse
-                            .with_error(anyhow::anyhow!("failed to parse word_cost")));
-                    }
-                }
-            };
-            let left_id = match u16::from_str(row[1].trim()) {
-                Ok(lid) => lid,
-                Err(_err) => {
-                    if self.skip_invalid_cost_or_id {
-                        warn!("failed to parse left_id: {:?}", row);
-                        continue;
-                    } else {
-                        return Err(LinderaErrorKind::Parse
-                            .with_error(anyhow::anyhow!("failed to parse left_id")));
-                    }
-                }
-            };
-            let right_id = match u16::from_str(row[2].trim()) {
-                Ok(rid) => rid,
-                Err(_err) => {
-                    if self.skip_invalid_cost_or_id {
-                        warn!("failed to parse right_id: {:?}", row);
-                        continue;
-                    } else {
-                        return Err(LinderaErrorKind::Parse
-                            .with_error(anyhow::anyhow!("failed to parse right_id")));
+                        return Err(err);
                     }
                 }
             };
@@ -192,13 +167,13 @@ impl DictBuilder {
             let joined_details = if self.normalize_details {
                 row.iter()
                     .skip(4)
-                    .map(|item| normalize(item))
+                    .map(normalize)
                     .collect::<Vec<String>>()
                     .join("\0")
             } else {
                 row.iter().skip(4).collect::<Vec<&str>>().join("\0")
             };
-            let joined_details_len = u32::try_from(joined_details.as_bytes().len())
+            let joined_details_len = u32::try_from(joined_details.len())
                 .map_err(|err| LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err)))?;
             words_buffer
                 .write_u32::<LittleEndian>(joined_details_len)
@@ -255,8 +230,313 @@ impl DictBuilder {
 
         Ok(())
     }
+
+    /// Parse the lexicon files the same way as `build` without writing anything, recording the
+    /// problems in the report. The context IDs are checked against the size of the connection
+    /// cost matrix if it is known.
+    pub fn validate(
+        &self,
+        input_dir: &Path,
+        matrix_size: Option<(usize, usize)>,
+        report: &mut ValidationReport,
+    ) -> LinderaResult<()> {
+        let filenames = lexicon_files(input_dir)?;
+        if filenames.is_empty() {
+            report.warnings.push(format!(
+                "{}: no lexicon file (*.csv) found",
+                input_dir.display()
+            ));
+        }
+
+        for filename in filenames {
+            let text = match read_text(&filename, &self.encoding, report)? {
+                Some(text) => text,
+                None => continue,
+            };
+            let mut rdr = self.csv_reader(text.as_bytes());
+
+            for result in rdr.records() {
+                report.entries += 1;
+                let row = match result {
+                    Ok(row) => row,
+                    Err(err) => {
+                        let line = err.position().map_or(0, |position| position.line());
+                        let message = match err.kind() {
+                            csv::ErrorKind::UnequalLengths {
+                                expected_len, len, ..
+                            } => format!("expected {} fields, got {}", expected_len, len),
+                            _ => err.to_string(),
+                        };
+                        report
+                            .errors
+                            .push(format!("{}:{}: {}", filename.display(), line, message));
+                        continue;
+                    }
+                };
+                let line = row.position().map_or(0, |position| position.line());
+                let location = format!("{}:{}", filename.display(), line);
+
+                if let Err(err) = check_leading_fields(&row) {
+                    report
+                        .errors
+                        .push(format!("{}: {}", location, error_message(&err)));
+                    continue;
+                }
+                if row[0].is_empty() {
+                    report.warnings.push(format!("{}: empty surface", location));
+                }
+
+                match parse_row(&row) {
+                    Ok((_, left_id, right_id)) => {
+                        if let Some(message) = check_context_ids(left_id, right_id, matrix_size) {
+                            report.errors.push(format!("{}: {}", location, message));
+                        }
+                    }
+                    // the build skips the row instead of failing
+                    Err(err) if self.skip_invalid_cost_or_id => {
+                        report
+                            .warnings
+                            .push(format!("{}: {}", location, error_message(&err)))
+                    }
+                    Err(err) => {
+                        report
+                            .errors
+                            .push(format!("{}: {}", location, error_message(&err)))
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn csv_reader<R: Read>(&self, reader: R) -> csv::Reader<R> {
+        csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(self.flexible_csv)
+            .from_reader(reader)
+    }
+}
+
+/// Check that a row has the leading fields: surface, left context ID, right context ID and word
+/// cost.
+fn check_leading_fields(row: &StringRecord) -> LinderaResult<()> {
+    if row.len() < LEADING_FIELDS_NUM {
+        return Err(LinderaErrorKind::Content.with_error(anyhow::anyhow!(
+            "expected at least {} fields, got {}: {:?}",
+            LEADING_FIELDS_NUM,
+            row.len(),
+            row
+        )));
+    }
+    Ok(())
+}
+
+/// Parse the word cost, the left context ID and the right context ID of a row with the leading
+/// fields.
+fn parse_row(row: &StringRecord) -> LinderaResult<(i16, u16, u16)> {
+    let word_cost = i16::from_str(row[3].trim()).map_err(|err| {
+        LinderaErrorKind::Parse.with_error(anyhow::anyhow!(
+            "failed to parse word_cost {:?}: {}",
+            &row[3],
+            err
+        ))
+    })?;
+    let left_id = u16::from_str(row[1].trim()).map_err(|err| {
+        LinderaErrorKind::Parse.with_error(anyhow::anyhow!(
+            "failed to parse left_id {:?}: {}",
+            &row[1],
+            err
+        ))
+    })?;
+    let right_id = u16::from_str(row[2].trim()).map_err(|err| {
+        LinderaErrorKind::Parse.with_error(anyhow::anyhow!(
+            "failed to parse right_id {:?}: {}",
+            &row[2],
+            err
+        ))
+    })?;
+    Ok((word_cost, left_id, right_id))
 }
 
 fn normalize(text: &str) -> String {
     text.to_string().replace('―', "—").replace('～', "〜")
 }
+
+#[cfg(test)]
+#[cfg(not(feature = "compress"))]
+mod tests {
+    use std::borrow::Cow;
+    use std::collections::HashMap;
+    use std::fs;
+    use std::path::Path;
+
+    use lindera_core::character_definition::CharacterDefinitions;
+    use lindera_core::connection::ConnectionCostMatrix;
+    use lindera_core::cost_model::DictionaryCostModel;
+    use lindera_core::dictionary::{strip_dictionary_header, Dictionary, UserWordOverlay};
+    use lindera_core::mode::Mode;
+    use lindera_core::prefix_dict::PrefixDict;
+    use lindera_core::unknown_dictionary::UnknownDictionary;
+    use lindera_core::viterbi::Lattice;
+
+    use crate::chardef::CharDefBuilderOptions;
+    use crate::cost_matrix::CostMatrixBuilderOptions;
+    use crate::dict::DictBuilderOptions;
+    use crate::unk::UnkBuilderOptions;
+
+    const CHAR_DEF: &str = "DEFAULT 0 1 0
+KANJI 0 0 2
+0x4E00..0x9FA5 KANJI
+";
+
+    const UNK_DEF: &str = "DEFAULT,0,0,1000,記号,一般,*,*,*,*,*
+KANJI,1,1,2000,名詞,一般,*,*,*,*,*
+";
+
+    const MATRIX_DEF: &str = "2 2
+0 0 0
+0 1 0
+1 0 0
+1 1 0
+";
+
+    fn row(fields: &[&str]) -> Vec<String> {
+        fields.iter().map(|field| field.to_string()).collect()
+    }
+
+    fn load_dictionary(dir: &Path) -> Dictionary {
+        Dictionary {
+            dict: PrefixDict::from_static_slice(
+                strip_dictionary_header(&fs::read(dir.join("dict.da")).unwrap()).unwrap(),
+                strip_dictionary_header(&fs::read(dir.join("dict.vals")).unwrap()).unwrap(),
+            ),
+            cost_matrix: ConnectionCostMatrix::load(
+                strip_dictionary_header(&fs::read(dir.join("matrix.mtx")).unwrap()).unwrap(),
+            ),
+            char_definitions: CharacterDefinitions::load(
+                &fs::read(dir.join("char_def.bin")).unwrap(),
+            )
+            .unwrap(),
+            unknown_dictionary: UnknownDictionary::load(&fs::read(dir.join("unk.bin")).unwrap())
+                .unwrap(),
+            words_idx_data: Cow::Owned(
+                strip_dictionary_header(&fs::read(dir.join("dict.wordsidx")).unwrap())
+                    .unwrap()
+                    .to_vec(),
+            ),
+            words_data: Cow::Owned(
+                strip_dictionary_header(&fs::read(dir.join("dict.words")).unwrap())
+                    .unwrap()
+                    .to_vec(),
+            ),
+        }
+    }
+
+    fn tokenize<'a>(dictionary: &Dictionary, text: &'a str) -> Vec<(&'a str, Vec<String>)> {
+        let mut lattice = Lattice::default();
+        lattice.set_text(
+            &dictionary.dict,
+            &None,
+            &UserWordOverlay::default(),
+            &dictionary.char_definitions,
+            &dictionary.unknown_dictionary,
+            text,
+            &Mode::Normal,
+            1024,
+            &HashMap::new(),
+        );
+        lattice.calculate_path_costs(&dictionary.cost_matrix, &Mode::Normal, &DictionaryCostModel);
+
+        let offsets = lattice.tokens_offset();
+        offsets
+            .iter()
+            .enumerate()
+            .map(|(i, &(start, word_id))| {
+                let end = offsets.get(i + 1).map_or(text.len(), |&(end, _)| end);
+                let details = dictionary.word_details(word_id.0 as usize).unwrap();
+                (&text[start..end], details)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_build_from_rows() {
+        let base_dir = std::env::temp_dir().join("lindera-dictionary-builder-test-build-from-rows");
+        let input_dir = base_dir.join("input");
+        let output_dir = base_dir.join("output");
+        fs::create_dir_all(&input_dir).unwrap();
+        fs::create_dir_all(&output_dir).unwrap();
+        fs::write(input_dir.join("char.def"), CHAR_DEF).unwrap();
+        fs::write(input_dir.join("unk.def"), UNK_DEF).unwrap();
+        fs::write(input_dir.join("matrix.def"), MATRIX_DEF).unwrap();
+
+        let char_definitions = CharDefBuilderOptions::default()
+            .builder()
+            .unwrap()
+            .build(&input_dir, &output_dir)
+            .unwrap();
+        UnkBuilderOptions::default()
+            .unk_fields_num(11)
+            .builder()
+            .unwrap()
+            .build(&input_dir, &char_definitions, &output_dir)
+            .unwrap();
+        CostMatrixBuilderOptions::default()
+            .builder()
+            .unwrap()
+            .build(&input_dir, &output_dir)
+            .unwrap();
+
+        // no lexicon file is written, the rows are given as they are
+        DictBuilderOptions::default()
+            .builder()
+            .unwrap()
+            .build_from_rows(
+                vec![
+                    row(&["東京", "1", "1", "100", "名詞", "固有名詞", "トウキョウ"]),
+                    row(&["京都", "1", "1", "100", "名詞", "固有名詞", "キョウト"]),
+                    row(&["都", "1", "1", "200", "名詞", "接尾", "ト"]),
+                ],
+                &output_dir,
+            )
+            .unwrap();
+
+        let dictionary = load_dictionary(&output_dir);
+
+        assert_eq!(
+            tokenize(&dictionary, "東京都"),
+            vec![
+                ("東京", row(&["名詞", "固有名詞", "トウキョウ"])),
+                ("都", row(&["名詞", "接尾", "ト"])),
+            ]
+        );
+        assert_eq!(
+            tokenize(&dictionary, "京都"),
+            vec![("京都", row(&["名詞", "固有名詞", "キョウト"]))]
+        );
+
+        fs::remove_dir_all(&base_dir).unwrap();
+    }
+
+    #[test]
+    fn test_build_from_rows_too_few_fields() {
+        let output_dir =
+            std::env::temp_dir().join("lindera-dictionary-builder-test-build-from-rows-too-few");
+        fs::create_dir_all(&output_dir).unwrap();
+
+        let result = DictBuilderOptions::default()
+            .builder()
+            .unwrap()
+            .build_from_rows(
+                vec![
+                    row(&["東京", "1", "1", "100", "名詞"]),
+                    row(&["都", "1", "1"]),
+                ],
+                &output_dir,
+            );
+        assert!(result.is_err());
+
+        fs::remove_dir_all(&output_dir).unwrap();
+    }
+}
@@ -7,12 +7,17 @@
 pub mod chardef;
 pub mod cost_matrix;
 pub mod dict;
+pub mod mecab_compiled;
 pub mod unk;
 pub mod user_dict;
 pub mod utils;
+pub mod validator;
 
-pub use chardef::CharDefBuilderOptions;
-pub use cost_matrix::CostMatrixBuilderOptions;
-pub use dict::DictBuilderOptions;
-pub use unk::UnkBuilderOptions;
-pub use user_dict::{build_user_dictionary, UserDictBuilderOptions};
+pub use chardef::{CharDefBuilder, CharDefBuilderOptions};
+pub use cost_matrix::{CostMatrixBuilder, CostMatrixBuilderOptions};
+pub use dict::{DictBuilder, DictBuilderOptions};
+pub use mecab_compiled::{from_mecab_compiled, MecabCompiledBuilderOptions};
+pub use unk::{UnkBuilder, UnkBuilderOptions};
+pub use user_dict::{build_user_dictionary, UserDictBuilder, UserDictBuilderOptions};
+pub use utils::write_dictionary_kind;
+pub use validator::validate_source;
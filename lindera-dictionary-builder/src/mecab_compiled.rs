@@ -0,0 +1,780 @@
+//! Transcode a compiled MeCab dictionary into the lindera dictionary format.
+//!
+//! The following files are read from the input directory. All integers are little-endian,
+//! as written by MeCab on x86 and ARM hosts.
+//!
+//! `sys.dic` and `unk.dic`:
+//!
+//! | Size            | Content                                                          |
+//! |-----------------|------------------------------------------------------------------|
+//! | u32             | magic number, the file size XOR `0xef718f77`                     |
+//! | u32             | version, `102`                                                   |
+//! | u32             | type, `0` for `sys.dic` and `2` for `unk.dic`                    |
+//! | u32             | number of tokens                                                 |
+//! | u32             | number of left context IDs                                       |
+//! | u32             | number of right context IDs                                      |
+//! | u32             | `dsize`, size of the double array in bytes                       |
+//! | u32             | `tsize`, size of the tokens in bytes                             |
+//! | u32             | `fsize`, size of the features in bytes                           |
+//! | u32             | reserved                                                         |
+//! | 32 bytes        | charset of the surfaces and features, NUL-terminated             |
+//! | `dsize` bytes   | Darts double array, units of `i32` base and `u32` check          |
+//! | `tsize` bytes   | tokens, 16 bytes each (see below)                                |
+//! | `fsize` bytes   | features, NUL-terminated comma separated strings                 |
+//!
+//! A token is `u16` left context ID, `u16` right context ID, `u16` part-of-speech ID,
+//! `i16` word cost, `u32` offset of its feature and `u32` compound information.
+//! The double array maps a surface (the category name in `unk.dic`) to
+//! `(index of the first token << 8) | number of tokens`.
+//!
+//! `matrix.bin`: `u16` number of left context IDs `lsize`, `u16` number of right context IDs,
+//! followed by the `i16` connection costs, the cost between a right context ID `l` of the
+//! preceding word and a left context ID `r` of the following word being at `l + lsize * r`.
+//!
+//! `char.bin`: `u32` number of categories, the category names as 32 bytes NUL-terminated
+//! strings, followed by a `u32` for each UCS-2 code point from `0x0000` to `0xfffe`.
+//! The bits of this value are, from the least significant one: 18 bits of categories
+//! (a bit set indexed by category), 8 bits of the default category, 4 bits of length,
+//! 1 bit of group and 1 bit of invoke. The length, group and invoke flags are those of the
+//! default category.
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+
+use byteorder::{ByteOrder, LittleEndian, WriteBytesExt};
+use csv::StringRecord;
+use derive_builder::Builder;
+use encoding_rs::Encoding;
+use log::debug;
+
+use lindera_core::character_definition::{
+    CategoryData, CategoryId, CharacterDefinitions, LookupTable,
+};
+use lindera_core::error::LinderaErrorKind;
+use lindera_core::unknown_dictionary::UnknownDictionary;
+use lindera_core::word_entry::{WordEntry, WordId};
+use lindera_core::LinderaResult;
+use lindera_decompress::Algorithm;
+
+use crate::dict::DictBuilderOptions;
+use crate::utils::compress_write;
+
+const DICTIONARY_MAGIC_ID: u32 = 0xef718f77;
+const DICTIONARY_VERSION: u32 = 102;
+const DICTIONARY_HEADER_SIZE: usize = 4 * 10 + 32;
+const TOKEN_SIZE: usize = 16;
+const CATEGORY_NAME_SIZE: usize = 32;
+const CHAR_INFO_NUM: usize = 0xffff;
+
+#[derive(Builder, Debug)]
+#[builder(name = "MecabCompiledBuilderOptions")]
+#[builder(build_fn(name = "builder"))]
+pub struct MecabCompiledBuilder {
+    #[builder(default = "Algorithm::Deflate")]
+    compress_algorithm: Algorithm,
+}
+
+impl MecabCompiledBuilder {
+    /// Transcode `sys.dic`, `unk.dic`, `matrix.bin` and `char.bin` in the input directory
+    /// into the lindera dictionary files in the output directory.
+    pub fn build(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()> {
+        fs::create_dir_all(output_dir)
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+
+        let char_definitions = self.build_chardef(input_dir, output_dir)?;
+        self.build_unk(input_dir, &char_definitions, output_dir)?;
+        self.build_dict(input_dir, output_dir)?;
+        self.build_cost_matrix(input_dir, output_dir)?;
+
+        Ok(())
+    }
+
+    fn build_chardef(
+        &self,
+        input_dir: &Path,
+        output_dir: &Path,
+    ) -> LinderaResult<CharacterDefinitions> {
+        let data = read_file(&input_dir.join("char.bin"))?;
+        let char_definitions = parse_char_bin(&data)?;
+
+        let mut chardef_buffer = Vec::new();
+        bincode::serialize_into(&mut chardef_buffer, &char_definitions)
+            .map_err(|err| LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err)))?;
+        self.write(&chardef_buffer, &output_dir.join("char_def.bin"))?;
+
+        Ok(char_definitions)
+    }
+
+    fn build_unk(
+        &self,
+        input_dir: &Path,
+        char_definitions: &CharacterDefinitions,
+        output_dir: &Path,
+    ) -> LinderaResult<()> {
+        let data = read_file(&input_dir.join("unk.dic"))?;
+        let dictionary = CompiledDictionary::parse(&data)?;
+
+        let mut costs = Vec::new();
+        let mut category_references = vec![Vec::new(); char_definitions.categories().len()];
+        for (category_name, tokens) in dictionary.entries()? {
+            let category_id = char_definitions
+                .categories()
+                .iter()
+                .position(|name| *name == category_name)
+                .ok_or_else(|| {
+                    LinderaErrorKind::Content.with_error(anyhow::anyhow!(
+                        "unknown category in unk.dic: {}",
+                        category_name
+                    ))
+                })?;
+            for token in tokens {
+                category_references[category_id].push(costs.len() as u32);
+                costs.push(WordEntry {
+                    word_id: WordId(u32::MAX, true),
+                    word_cost: token.word_cost,
+                    left_id: token.left_id,
+                    right_id: token.right_id,
+                });
+            }
+        }
+
+        let mut unk_buffer = Vec::new();
+        bincode::serialize_into(
+            &mut unk_buffer,
+            &UnknownDictionary {
+                category_references,
+                costs,
+            },
+        )
+        .map_err(|err| LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err)))?;
+        self.write(&unk_buffer, &output_dir.join("unk.bin"))
+    }
+
+    fn build_dict(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()> {
+        let data = read_file(&input_dir.join("sys.dic"))?;
+        let dictionary = CompiledDictionary::parse(&data)?;
+
+        let mut rows = Vec::new();
+        for (surface, tokens) in dictionary.entries()? {
+            for token in tokens {
+                let mut row = StringRecord::from(vec![
+                    surface.clone(),
+                    token.left_id.to_string(),
+                    token.right_id.to_string(),
+                    token.word_cost.to_string(),
+                ]);
+                // The feature is a CSV line, whose fields may be quoted.
+                let mut rdr = csv::ReaderBuilder::new()
+                    .has_headers(false)
+                    .flexible(true)
+                    .from_reader(token.feature.as_bytes());
+                if let Some(record) = rdr.records().next() {
+                    let record = record.map_err(|err| {
+                        LinderaErrorKind::Content.with_error(anyhow::anyhow!(err))
+                    })?;
+                    row.extend(record.iter());
+                }
+                rows.push(row);
+            }
+        }
+
+        DictBuilderOptions::default()
+            .compress_algorithm(self.compress_algorithm)
+            .builder()
+            .unwrap()
+            .build_from_records(rows, output_dir)
+    }
+
+    fn build_cost_matrix(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()> {
+        let data = read_file(&input_dir.join("matrix.bin"))?;
+        if data.len() < 4 {
+            return Err(
+                LinderaErrorKind::Content.with_error(anyhow::anyhow!("matrix.bin is too short"))
+            );
+        }
+        let forward_size = LittleEndian::read_u16(&data[0..2]) as usize;
+        let backward_size = LittleEndian::read_u16(&data[2..4]) as usize;
+        if data.len() != 4 + forward_size * backward_size * 2 {
+            return Err(LinderaErrorKind::Content.with_error(anyhow::anyhow!(
+                "invalid size of matrix.bin: {}",
+                data.len()
+            )));
+        }
+
+        let mut matrix_mtx_buffer = Vec::with_capacity(data.len());
+        for size in [forward_size, backward_size] {
+            matrix_mtx_buffer
+                .write_i16::<LittleEndian>(size as i16)
+                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+        }
+        // MeCab stores the costs column by column, lindera row by row.
+        for forward_id in 0..forward_size {
+            for backward_id in 0..backward_size {
+                let offset = 4 + (forward_id + forward_size * backward_id) * 2;
+                matrix_mtx_buffer
+                    .write_i16::<LittleEndian>(LittleEndian::read_i16(&data[offset..]))
+                    .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+            }
+        }
+
+        self.write(&matrix_mtx_buffer, &output_dir.join("matrix.mtx"))
+    }
+
+    fn write(&self, buffer: &[u8], path: &Path) -> LinderaResult<()> {
+        let mut wtr = io::BufWriter::new(
+            File::create(path)
+                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?,
+        );
+        compress_write(buffer, self.compress_algorithm, &mut wtr)?;
+        wtr.flush()
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+
+        Ok(())
+    }
+}
+
+/// Transcode a compiled MeCab dictionary in the input directory into the lindera dictionary
+/// files in the output directory, with the default options.
+pub fn from_mecab_compiled(input_dir: &Path, output_dir: &Path) -> LinderaResult<()> {
+    MecabCompiledBuilderOptions::default()
+        .builder()
+        .unwrap()
+        .build(input_dir, output_dir)
+}
+
+fn read_file(path: &Path) -> LinderaResult<Vec<u8>> {
+    debug!("reading {:?}", path);
+    fs::read(path).map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))
+}
+
+fn read_cstr(data: &[u8]) -> &[u8] {
+    let len = data.iter().position(|&b| b == 0).unwrap_or(data.len());
+    &data[..len]
+}
+
+fn parse_char_bin(data: &[u8]) -> LinderaResult<CharacterDefinitions> {
+    if data.len() < 4 {
+        return Err(LinderaErrorKind::Content.with_error(anyhow::anyhow!("char.bin is too short")));
+    }
+    let category_num = LittleEndian::read_u32(&data[0..4]) as usize;
+    let char_infos_offset = 4 + category_num * CATEGORY_NAME_SIZE;
+    if data.len() != char_infos_offset + CHAR_INFO_NUM * 4 {
+        return Err(LinderaErrorKind::Content
+            .with_error(anyhow::anyhow!("invalid size of char.bin: {}", data.len())));
+    }
+
+    let category_names = (0..category_num)
+        .map(|i| {
+            let offset = 4 + i * CATEGORY_NAME_SIZE;
+            String::from_utf8(read_cstr(&data[offset..offset + CATEGORY_NAME_SIZE]).to_vec())
+                .map_err(|err| LinderaErrorKind::Decode.with_error(anyhow::anyhow!(err)))
+        })
+        .collect::<LinderaResult<Vec<String>>>()?;
+    let char_infos = (0..CHAR_INFO_NUM)
+        .map(|c| LittleEndian::read_u32(&data[char_infos_offset + c * 4..]))
+        .collect::<Vec<u32>>();
+
+    // The category definitions are copied into the characters of which it is the default category.
+    let mut category_definitions = vec![
+        CategoryData {
+            invoke: false,
+            group: false,
+            length: 0,
+        };
+        category_num
+    ];
+    for &char_info in char_infos.iter() {
+        if let Some(category_data) = category_definitions.get_mut(default_category(char_info)) {
+            *category_data = CategoryData {
+                invoke: (char_info >> 31) & 1 == 1,
+                group: (char_info >> 30) & 1 == 1,
+                length: (char_info >> 26) & 0xf,
+            };
+        }
+    }
+
+    // The default category comes first, as in char.def.
+    let categories = |char_info: u32| -> Vec<CategoryId> {
+        let default_category = default_category(char_info);
+        let mut categories = vec![CategoryId(default_category)];
+        categories.extend(
+            (0..category_num.min(18))
+                .filter(|&i| i != default_category && (char_info >> i) & 1 == 1)
+                .map(CategoryId),
+        );
+        categories
+    };
+
+    let mut boundaries = vec![0];
+    for c in 1..CHAR_INFO_NUM {
+        if char_infos[c] != char_infos[c - 1] {
+            boundaries.push(c as u32);
+        }
+    }
+    // The characters out of the UCS-2 range belong to the default category of U+0000.
+    boundaries.push(CHAR_INFO_NUM as u32);
+    let mapping = LookupTable::from_fn(boundaries, &|c, buff| {
+        let char_info = if (c as usize) < CHAR_INFO_NUM {
+            char_infos[c as usize]
+        } else {
+            char_infos[0]
+        };
+        buff.extend(categories(char_info));
+    });
+
+    Ok(CharacterDefinitions {
+        category_definitions,
+        category_names,
+        mapping,
+    })
+}
+
+fn default_category(char_info: u32) -> usize {
+    ((char_info >> 18) & 0xff) as usize
+}
+
+struct CompiledToken {
+    left_id: u16,
+    right_id: u16,
+    word_cost: i16,
+    feature: String,
+}
+
+struct CompiledDictionary<'a> {
+    encoding: &'static Encoding,
+    double_array: &'a [u8],
+    tokens: &'a [u8],
+    features: &'a [u8],
+}
+
+impl<'a> CompiledDictionary<'a> {
+    fn parse(data: &'a [u8]) -> LinderaResult<Self> {
+        if data.len() < DICTIONARY_HEADER_SIZE {
+            return Err(LinderaErrorKind::Content
+                .with_error(anyhow::anyhow!("dictionary file is too short")));
+        }
+        let header = |i: usize| LittleEndian::read_u32(&data[i * 4..]);
+        if header(0) ^ DICTIONARY_MAGIC_ID != data.len() as u32 {
+            return Err(LinderaErrorKind::Content.with_error(anyhow::anyhow!(
+                "invalid magic number of the dictionary file"
+            )));
+        }
+        if header(1) != DICTIONARY_VERSION {
+            return Err(LinderaErrorKind::Content.with_error(anyhow::anyhow!(
+                "unsupported dictionary version: {}",
+                header(1)
+            )));
+        }
+        let dsize = header(6) as usize;
+        let tsize = header(7) as usize;
+        let fsize = header(8) as usize;
+        if data.len() != DICTIONARY_HEADER_SIZE + dsize + tsize + fsize {
+            return Err(LinderaErrorKind::Content
+                .with_error(anyhow::anyhow!("invalid size of the dictionary file")));
+        }
+
+        let charset = String::from_utf8_lossy(read_cstr(&data[40..DICTIONARY_HEADER_SIZE]));
+        let encoding = Encoding::for_label_no_replacement(charset.as_bytes()).ok_or_else(|| {
+            LinderaErrorKind::Decode.with_error(anyhow::anyhow!("Invalid encoding: {}", charset))
+        })?;
+
+        let tokens_offset = DICTIONARY_HEADER_SIZE + dsize;
+        let features_offset = tokens_offset + tsize;
+        Ok(Self {
+            encoding,
+            double_array: &data[DICTIONARY_HEADER_SIZE..tokens_offset],
+            tokens: &data[tokens_offset..features_offset],
+            features: &data[features_offset..],
+        })
+    }
+
+    /// Returns the keys of the double array with their tokens.
+    fn entries(&self) -> LinderaResult<Vec<(String, Vec<CompiledToken>)>> {
+        let mut keys = Vec::new();
+        let root_base = self.base(0).ok_or_else(|| {
+            LinderaErrorKind::Content.with_error(anyhow::anyhow!("empty double array"))
+        })?;
+        self.collect_keys(root_base, &mut Vec::new(), &mut keys);
+
+        keys.into_iter()
+            .map(|(key, value)| {
+                let surface = self
+                    .encoding
+                    .decode_without_bom_handling(&key)
+                    .0
+                    .into_owned();
+                let offset = (value >> 8) as usize;
+                let tokens = (offset..offset + (value & 0xff) as usize)
+                    .map(|i| self.token(i))
+                    .collect::<LinderaResult<Vec<_>>>()?;
+                Ok((surface, tokens))
+            })
+            .collect()
+    }
+
+    fn unit_num(&self) -> usize {
+        self.double_array.len() / 8
+    }
+
+    fn base(&self, index: usize) -> Option<i32> {
+        (index < self.unit_num()).then(|| LittleEndian::read_i32(&self.double_array[index * 8..]))
+    }
+
+    fn check(&self, index: usize) -> Option<u32> {
+        (index < self.unit_num())
+            .then(|| LittleEndian::read_u32(&self.double_array[index * 8 + 4..]))
+    }
+
+    /// Walk the double array depth first. A node is identified by its base `b`, the child by
+    /// the byte `c` is at `b + c + 1` and the value of the key ending at the node is at `b`,
+    /// encoded as `-value - 1`. In both cases, the check of the unit is `b`.
+    fn collect_keys(&self, base: i32, key: &mut Vec<u8>, keys: &mut Vec<(Vec<u8>, u32)>) {
+        let b = base as usize;
+        if let (Some(value), Some(check)) = (self.base(b), self.check(b)) {
+            if check == base as u32 && value < 0 {
+                keys.push((key.clone(), (-value - 1) as u32));
+            }
+        }
+        for c in 0..=255u8 {
+            let p = b + c as usize + 1;
+            match (self.base(p), self.check(p)) {
+                (Some(child_base), Some(check)) if check == base as u32 && child_base > 0 => {
+                    key.push(c);
+                    self.collect_keys(child_base, key, keys);
+                    key.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn token(&self, index: usize) -> LinderaResult<CompiledToken> {
+        let offset = index * TOKEN_SIZE;
+        if offset + TOKEN_SIZE > self.tokens.len() {
+            return Err(LinderaErrorKind::Content
+                .with_error(anyhow::anyhow!("token index out of range: {}", index)));
+        }
+        let token = &self.tokens[offset..offset + TOKEN_SIZE];
+        let feature_offset = LittleEndian::read_u32(&token[8..12]) as usize;
+        let feature = self.features.get(feature_offset..).ok_or_else(|| {
+            LinderaErrorKind::Content.with_error(anyhow::anyhow!(
+                "feature offset out of range: {}",
+                feature_offset
+            ))
+        })?;
+
+        Ok(CompiledToken {
+            left_id: LittleEndian::read_u16(&token[0..2]),
+            right_id: LittleEndian::read_u16(&token[2..4]),
+            word_cost: LittleEndian::read_i16(&token[6..8]),
+            feature: self
+                .encoding
+                .decode_without_bom_handling(read_cstr(feature))
+                .0
+                .into_owned(),
+        })
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(feature = "compress"))]
+mod tests {
+    use std::borrow::Cow;
+    use std::fs;
+    use std::path::Path;
+
+    use byteorder::{LittleEndian, WriteBytesExt};
+
+    use lindera_core::character_definition::CharacterDefinitions;
+    use lindera_core::connection::ConnectionCostMatrix;
+    use lindera_core::dictionary::{strip_dictionary_header, Dictionary};
+    use lindera_core::prefix_dict::PrefixDict;
+    use lindera_core::unknown_dictionary::UnknownDictionary;
+
+    use crate::mecab_compiled::{from_mecab_compiled, DICTIONARY_MAGIC_ID};
+
+    /// Build a Darts double array of the sorted keys.
+    fn build_double_array(keys: &[(&[u8], i32)]) -> Vec<(i32, u32)> {
+        fn place(
+            units: &mut Vec<(i32, u32)>,
+            used_bases: &mut Vec<bool>,
+            keys: &[(&[u8], i32)],
+            depth: usize,
+        ) -> i32 {
+            // (code, range of the keys) of the children, 0 being the end of a key.
+            let mut children: Vec<(usize, usize, usize)> = Vec::new();
+            for (i, (key, _)) in keys.iter().enumerate() {
+                let code = key.get(depth).map_or(0, |&c| c as usize + 1);
+                match children.last_mut() {
+                    Some(child) if child.0 == code => child.2 = i + 1,
+                    _ => children.push((code, i, i + 1)),
+                }
+            }
+
+            let base = (1..)
+                .find(|&b: &usize| {
+                    !used_bases.get(b).copied().unwrap_or(false)
+                        && children
+                            .iter()
+                            .all(|&(code, _, _)| units.get(b + code).is_none_or(|unit| unit.1 == 0))
+                })
+                .unwrap();
+            let max = base + children.last().unwrap().0 + 1;
+            if units.len() < max {
+                units.resize(max, (0, 0));
+                used_bases.resize(max, false);
+            }
+            used_bases[base] = true;
+            for &(code, _, _) in children.iter() {
+                units[base + code].1 = base as u32;
+            }
+
+            for &(code, start, end) in children.iter() {
+                units[base + code].0 = if code == 0 {
+                    -keys[start].1 - 1
+                } else {
+                    place(units, used_bases, &keys[start..end], depth + 1)
+                };
+            }
+
+            base as i32
+        }
+
+        let mut units = vec![(0, 0)];
+        let mut used_bases = vec![true];
+        units[0].0 = place(&mut units, &mut used_bases, keys, 0);
+        units
+    }
+
+    /// A key of a compiled dictionary and its tokens (left_id, right_id, cost, feature).
+    type Entry<'a> = (&'a str, Vec<(u16, u16, i16, &'a str)>);
+
+    /// Build a compiled dictionary of the entries.
+    fn build_compiled_dictionary(dictionary_type: u32, entries: &[Entry]) -> Vec<u8> {
+        let mut keys = Vec::new();
+        let mut tokens = Vec::new();
+        let mut features = Vec::new();
+        let mut token_num = 0;
+        for (key, key_tokens) in entries.iter() {
+            keys.push((key.as_bytes(), (token_num << 8) | key_tokens.len() as i32));
+            token_num += key_tokens.len() as i32;
+            for &(left_id, right_id, cost, feature) in key_tokens.iter() {
+                tokens.write_u16::<LittleEndian>(left_id).unwrap();
+                tokens.write_u16::<LittleEndian>(right_id).unwrap();
+                tokens.write_u16::<LittleEndian>(0).unwrap();
+                tokens.write_i16::<LittleEndian>(cost).unwrap();
+                tokens
+                    .write_u32::<LittleEndian>(features.len() as u32)
+                    .unwrap();
+                tokens.write_u32::<LittleEndian>(0).unwrap();
+                features.extend_from_slice(feature.as_bytes());
+                features.push(0);
+            }
+        }
+
+        let mut double_array = Vec::new();
+        for (base, check) in build_double_array(&keys) {
+            double_array.write_i32::<LittleEndian>(base).unwrap();
+            double_array.write_u32::<LittleEndian>(check).unwrap();
+        }
+
+        let size = 4 * 10 + 32 + double_array.len() + tokens.len() + features.len();
+        let mut data = Vec::new();
+        for value in [
+            size as u32 ^ DICTIONARY_MAGIC_ID,
+            102,
+            dictionary_type,
+            token_num as u32,
+            2,
+            2,
+            double_array.len() as u32,
+            tokens.len() as u32,
+            features.len() as u32,
+            0,
+        ] {
+            data.write_u32::<LittleEndian>(value).unwrap();
+        }
+        let mut charset = b"UTF-8".to_vec();
+        charset.resize(32, 0);
+        data.extend(charset);
+        data.extend(double_array);
+        data.extend(tokens);
+        data.extend(features);
+        data
+    }
+
+    fn build_char_bin() -> Vec<u8> {
+        let mut data = Vec::new();
+        data.write_u32::<LittleEndian>(2).unwrap();
+        for name in ["DEFAULT", "KANJI"] {
+            let mut name = name.as_bytes().to_vec();
+            name.resize(32, 0);
+            data.extend(name);
+        }
+        for c in 0..0xffff {
+            // DEFAULT: invoke 0, group 1, length 0 / KANJI: invoke 0, group 0, length 2
+            let char_info: u32 = if (0x4e00..=0x9fa5).contains(&c) {
+                (1 << 1) | (1 << 18) | (2 << 26)
+            } else {
+                1 | (1 << 30)
+            };
+            data.write_u32::<LittleEndian>(char_info).unwrap();
+        }
+        data
+    }
+
+    fn build_matrix_bin() -> Vec<u8> {
+        let mut data = Vec::new();
+        data.write_u16::<LittleEndian>(2).unwrap();
+        data.write_u16::<LittleEndian>(2).unwrap();
+        // cost(l, r) = 10 * l + r + 1, stored at l + 2 * r
+        for r in 0..2 {
+            for l in 0..2 {
+                data.write_i16::<LittleEndian>(10 * l + r + 1).unwrap();
+            }
+        }
+        data
+    }
+
+    fn load_dictionary(dir: &Path) -> Dictionary {
+        Dictionary {
+            dict: PrefixDict::from_static_slice(
+                strip_dictionary_header(&fs::read(dir.join("dict.da")).unwrap()).unwrap(),
+                strip_dictionary_header(&fs::read(dir.join("dict.vals")).unwrap()).unwrap(),
+            ),
+            cost_matrix: ConnectionCostMatrix::load(
+                strip_dictionary_header(&fs::read(dir.join("matrix.mtx")).unwrap()).unwrap(),
+            ),
+            char_definitions: CharacterDefinitions::load(
+                &fs::read(dir.join("char_def.bin")).unwrap(),
+            )
+            .unwrap(),
+            unknown_dictionary: UnknownDictionary::load(&fs::read(dir.join("unk.bin")).unwrap())
+                .unwrap(),
+            words_idx_data: Cow::Owned(
+                strip_dictionary_header(&fs::read(dir.join("dict.wordsidx")).unwrap())
+                    .unwrap()
+                    .to_vec(),
+            ),
+            words_data: Cow::Owned(
+                strip_dictionary_header(&fs::read(dir.join("dict.words")).unwrap())
+                    .unwrap()
+                    .to_vec(),
+            ),
+        }
+    }
+
+    #[test]
+    fn test_from_mecab_compiled() {
+        let base_dir = std::env::temp_dir().join("lindera-dictionary-builder-test-mecab-compiled");
+        let input_dir = base_dir.join("input");
+        let output_dir = base_dir.join("output");
+        fs::create_dir_all(&input_dir).unwrap();
+
+        fs::write(
+            input_dir.join("sys.dic"),
+            build_compiled_dictionary(
+                0,
+                &[
+                    (
+                        "東京",
+                        vec![
+                            (
+                                1,
+                                1,
+                                100,
+                                "名詞,固有名詞,地域,一般,*,*,東京,トウキョウ,トーキョー",
+                            ),
+                            (
+                                1,
+                                1,
+                                300,
+                                "名詞,固有名詞,人名,姓,*,*,東京,トウキョウ,トーキョー",
+                            ),
+                        ],
+                    ),
+                    ("都", vec![(0, 1, 200, "名詞,接尾,地域,*,*,*,都,ト,ト")]),
+                ],
+            ),
+        )
+        .unwrap();
+        fs::write(
+            input_dir.join("unk.dic"),
+            build_compiled_dictionary(
+                2,
+                &[
+                    ("DEFAULT", vec![(0, 0, 1000, "記号,一般,*,*,*,*,*")]),
+                    ("KANJI", vec![(1, 1, 2000, "名詞,一般,*,*,*,*,*")]),
+                ],
+            ),
+        )
+        .unwrap();
+        fs::write(input_dir.join("char.bin"), build_char_bin()).unwrap();
+        fs::write(input_dir.join("matrix.bin"), build_matrix_bin()).unwrap();
+
+        from_mecab_compiled(&input_dir, &output_dir).unwrap();
+
+        let dictionary = load_dictionary(&output_dir);
+
+        let entries = dictionary.dict.find_surface("東京");
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| entry.word_cost)
+                .collect::<Vec<_>>(),
+            vec![100, 300]
+        );
+        assert_eq!(
+            dictionary
+                .word_details(entries[0].word_id.0 as usize)
+                .unwrap(),
+            vec![
+                "名詞",
+                "固有名詞",
+                "地域",
+                "一般",
+                "*",
+                "*",
+                "東京",
+                "トウキョウ",
+                "トーキョー"
+            ]
+        );
+        let entries = dictionary.dict.find_surface("都");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].left_id, 0);
+        assert_eq!(entries[0].right_id, 1);
+
+        let char_definitions = &dictionary.char_definitions;
+        assert_eq!(char_definitions.categories(), &["DEFAULT", "KANJI"]);
+        let categories = char_definitions.lookup_categories('東');
+        assert_eq!(char_definitions.category_name(categories[0]), "KANJI");
+        let definition = char_definitions.lookup_definition(categories[0]);
+        assert_eq!(
+            (definition.invoke, definition.group, definition.length),
+            (false, false, 2)
+        );
+        let categories = char_definitions.lookup_categories('a');
+        assert_eq!(char_definitions.category_name(categories[0]), "DEFAULT");
+        let definition = char_definitions.lookup_definition(categories[0]);
+        assert_eq!(
+            (definition.invoke, definition.group, definition.length),
+            (false, true, 0)
+        );
+
+        let unknown_dictionary = &dictionary.unknown_dictionary;
+        let word_ids = unknown_dictionary.lookup_word_ids(categories[0]);
+        assert_eq!(word_ids.len(), 1);
+        assert_eq!(unknown_dictionary.word_entry(word_ids[0]).word_cost, 1000);
+
+        for l in 0..2 {
+            for r in 0..2 {
+                assert_eq!(dictionary.cost_matrix.cost(l, r), (10 * l + r + 1) as i32);
+            }
+        }
+
+        fs::remove_dir_all(&base_dir).unwrap();
+    }
+}
@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::collections::HashSet;
 use std::fs::File;
 use std::io;
 use std::io::Write;
@@ -6,6 +7,7 @@ use std::path::Path;
 
 use derive_builder::Builder;
 use lindera_core::character_definition::CharacterDefinitions;
+use lindera_core::dictionary_builder::ValidationReport;
 use lindera_core::error::LinderaErrorKind;
 use lindera_core::unknown_dictionary::parse_unk;
 use lindera_core::LinderaResult;
@@ -13,6 +15,7 @@ use lindera_decompress::Algorithm;
 use log::debug;
 
 use crate::utils::{compress_write, read_file_with_encoding};
+use crate::validator::{check_context_ids, error_message, read_text};
 
 #[derive(Builder, Debug)]
 #[builder(name = "UnkBuilderOptions")]
@@ -56,4 +59,64 @@ impl UnkBuilder {
 
         Ok(())
     }
+
+    /// Parse `unk.def` the same way as `build` without writing anything, recording the problems
+    /// in the report. The categories are the ones defined in `char.def`, and the context IDs are
+    /// checked against the size of the connection cost matrix if it is known.
+    pub fn validate(
+        &self,
+        input_dir: &Path,
+        categories: &[String],
+        matrix_size: Option<(usize, usize)>,
+        report: &mut ValidationReport,
+    ) -> LinderaResult<()> {
+        let unk_data_path = input_dir.join("unk.def");
+        let unk_data = match read_text(&unk_data_path, &self.encoding, report)? {
+            Some(unk_data) => unk_data,
+            None => return Ok(()),
+        };
+
+        let mut unk_categories = HashSet::new();
+        for (i, line) in unk_data.lines().enumerate() {
+            let location = format!("{}:{}", unk_data_path.display(), i + 1);
+            report.unknown_entries += 1;
+
+            let unknown_dictionary = match parse_unk(categories, line, self.unk_fields_num) {
+                Ok(unknown_dictionary) => unknown_dictionary,
+                Err(err) => {
+                    report
+                        .errors
+                        .push(format!("{}: {}", location, error_message(&err)));
+                    continue;
+                }
+            };
+            let word_entry = unknown_dictionary.costs[0];
+            if let Some(message) =
+                check_context_ids(word_entry.left_id, word_entry.right_id, matrix_size)
+            {
+                report.errors.push(format!("{}: {}", location, message));
+            }
+
+            let category = line.split(',').next().unwrap_or_default();
+            if !categories.iter().any(|c| c == category) {
+                report.warnings.push(format!(
+                    "{}: category {} is not defined in char.def",
+                    location, category
+                ));
+            }
+            unk_categories.insert(category.to_string());
+        }
+
+        for category in categories {
+            if !unk_categories.contains(category) {
+                report.warnings.push(format!(
+                    "{}: no unknown word entry for category {}",
+                    unk_data_path.display(),
+                    category
+                ));
+            }
+        }
+
+        Ok(())
+    }
 }
@@ -8,7 +8,7 @@ use std::path::Path;
 use byteorder::{LittleEndian, WriteBytesExt};
 use csv::StringRecord;
 use derive_builder::Builder;
-use lindera_core::dictionary::UserDictionary;
+use lindera_core::dictionary::{dictionary_header, UserDictionary};
 use lindera_core::error::LinderaErrorKind;
 use lindera_core::prefix_dict::PrefixDict;
 use lindera_core::word_entry::{WordEntry, WordId};
@@ -53,30 +53,64 @@ impl UserDictBuilder {
                 result.map_err(|err| LinderaErrorKind::Content.with_error(anyhow::anyhow!(err)))?;
             rows.push(record);
         }
+
+        self.build_from_rows(rows)
+    }
+
+    /// Build a user dictionary from rows already read from user dictionary CSV files, such as the
+    /// rows of several files merged together.
+    pub fn build_from_rows(&self, mut rows: Vec<StringRecord>) -> LinderaResult<UserDictionary> {
         rows.sort_by_key(|row| row[0].to_string());
 
         let mut word_entry_map: BTreeMap<String, Vec<WordEntry>> = BTreeMap::new();
 
         for (row_id, row) in rows.iter().enumerate() {
+            if !self.is_simple_row(row) && row.len() < self.detailed_userdic_fields_num {
+                return Err(LinderaErrorKind::Content.with_error(anyhow::anyhow!(
+                    "user dictionary should be a CSV with {} or {}+ fields: {}",
+                    self.simple_userdic_fields_num,
+                    self.detailed_userdic_fields_num,
+                    row_location(row)
+                )));
+            }
+
             let surface = row[0].to_string();
             let word_cost = if row.len() == self.simple_userdic_fields_num {
                 self.simple_word_cost
+            } else if self.is_simple_row(row) {
+                // The optional column following the simple fields overrides the default word cost.
+                row[self.simple_userdic_fields_num]
+                    .trim()
+                    .parse::<i16>()
+                    .map_err(|_err| {
+                        LinderaErrorKind::Parse.with_error(anyhow::anyhow!(
+                            "failed to parse word cost: {}",
+                            row_location(row)
+                        ))
+                    })?
             } else {
                 row[3].parse::<i16>().map_err(|_err| {
-                    LinderaErrorKind::Parse.with_error(anyhow::anyhow!("failed to parse word cost"))
+                    LinderaErrorKind::Parse.with_error(anyhow::anyhow!(
+                        "failed to parse word cost: {}",
+                        row_location(row)
+                    ))
                 })?
             };
-            let (left_id, right_id) = if row.len() == self.simple_userdic_fields_num {
+            let (left_id, right_id) = if self.is_simple_row(row) {
                 (self.simple_context_id, self.simple_context_id)
             } else {
                 (
                     row[1].parse::<u16>().map_err(|_err| {
-                        LinderaErrorKind::Parse
-                            .with_error(anyhow::anyhow!("failed to parse left context id"))
+                        LinderaErrorKind::Parse.with_error(anyhow::anyhow!(
+                            "failed to parse left context id: {}",
+                            row_location(row)
+                        ))
                     })?,
                     row[2].parse::<u16>().map_err(|_err| {
-                        LinderaErrorKind::Parse
-                            .with_error(anyhow::anyhow!("failed to parse left context id"))
+                        LinderaErrorKind::Parse.with_error(anyhow::anyhow!(
+                            "failed to parse right context id: {}",
+                            row_location(row)
+                        ))
                     })?,
                 )
             };
@@ -92,11 +126,12 @@ impl UserDictBuilder {
         let mut words_data = Vec::<u8>::new();
         let mut words_idx_data = Vec::<u8>::new();
         for row in rows.iter() {
-            let word_detail = if row.len() == self.simple_userdic_fields_num {
+            let word_detail = if self.is_simple_row(row) {
                 if let Some(handler) = &self.simple_userdic_details_handler {
                     handler(row)?
                 } else {
                     row.iter()
+                        .take(self.simple_userdic_fields_num)
                         .skip(1)
                         .map(|s| s.to_string())
                         .collect::<Vec<String>>()
@@ -109,9 +144,10 @@ impl UserDictBuilder {
                 tmp_word_detail
             } else {
                 return Err(LinderaErrorKind::Content.with_error(anyhow::anyhow!(
-                    "user dictionary should be a CSV with {} or {}+ fields",
+                    "user dictionary should be a CSV with {} or {}+ fields: {}",
                     self.simple_userdic_fields_num,
-                    self.detailed_userdic_fields_num
+                    self.detailed_userdic_fields_num,
+                    row_location(row)
                 )));
             };
 
@@ -159,6 +195,23 @@ impl UserDictBuilder {
             words_data,
         })
     }
+
+    /// A simple row has the simple fields, optionally followed by a word cost.
+    fn is_simple_row(&self, row: &StringRecord) -> bool {
+        row.len() == self.simple_userdic_fields_num
+            || (row.len() == self.simple_userdic_fields_num + 1
+                && row.len() < self.detailed_userdic_fields_num)
+    }
+}
+
+/// Describe where a row is in the user dictionary file, with its 1-based line number and raw text.
+fn row_location(row: &StringRecord) -> String {
+    let line = row.position().map_or(0, |position| position.line());
+    format!(
+        "line {}: {}",
+        line,
+        row.iter().collect::<Vec<&str>>().join(",")
+    )
 }
 
 pub fn build_user_dictionary(user_dict: UserDictionary, output_file: &Path) -> LinderaResult<()> {
@@ -177,6 +230,8 @@ pub fn build_user_dictionary(user_dict: UserDictionary, output_file: &Path) -> L
         File::create(output_file)
             .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?,
     );
+    wtr.write_all(&dictionary_header())
+        .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
     bincode::serialize_into(&mut wtr, &user_dict)
         .map_err(|err| LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err)))?;
     wtr.flush()
@@ -184,3 +239,116 @@ pub fn build_user_dictionary(user_dict: UserDictionary, output_file: &Path) -> L
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use lindera_core::dictionary::UserDictionary;
+
+    use crate::user_dict::UserDictBuilderOptions;
+
+    fn build_user_dict(name: &str, content: &str) -> UserDictionary {
+        let base_dir = std::env::temp_dir().join(name);
+        fs::create_dir_all(&base_dir).unwrap();
+        let input_file = base_dir.join("userdic.csv");
+        fs::write(&input_file, content).unwrap();
+
+        let result = UserDictBuilderOptions::default()
+            .simple_userdic_fields_num(3)
+            .detailed_userdic_fields_num(13)
+            .builder()
+            .unwrap()
+            .build(&input_file);
+
+        fs::remove_dir_all(&base_dir).unwrap();
+
+        result.unwrap()
+    }
+
+    fn build_user_dict_error(name: &str, content: &str) -> String {
+        let base_dir = std::env::temp_dir().join(name);
+        fs::create_dir_all(&base_dir).unwrap();
+        let input_file = base_dir.join("userdic.csv");
+        fs::write(&input_file, content).unwrap();
+
+        let result = UserDictBuilderOptions::default()
+            .simple_userdic_fields_num(3)
+            .detailed_userdic_fields_num(13)
+            .builder()
+            .unwrap()
+            .build(&input_file);
+
+        fs::remove_dir_all(&base_dir).unwrap();
+
+        result.err().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_build_user_dict_invalid_fields_num() {
+        let message = build_user_dict_error(
+            "lindera-dictionary-builder-test-user-dict-invalid-fields-num",
+            "東京スカイツリー,カスタム名詞,トウキョウスカイツリー
+とうきょうスカイツリー駅,カスタム名詞,トウキョウスカイツリーエキ
+東武スカイツリーライン,カスタム名詞
+",
+        );
+        assert!(message.contains("line 3: 東武スカイツリーライン,カスタム名詞"));
+    }
+
+    #[test]
+    fn test_build_user_dict_invalid_cost() {
+        let message = build_user_dict_error(
+            "lindera-dictionary-builder-test-user-dict-invalid-cost",
+            "東京スカイツリー,カスタム名詞,トウキョウスカイツリー
+とうきょうスカイツリー駅,カスタム名詞,トウキョウスカイツリーエキ
+東武スカイツリーライン,1288,1288,cost,名詞,固有名詞,一般,*,*,*,東武スカイツリーライン,トウブスカイツリーライン,トウブスカイツリーライン
+",
+        );
+        assert!(message.contains("failed to parse word cost"));
+        assert!(message.contains("line 3: 東武スカイツリーライン,1288,1288,cost,"));
+    }
+
+    #[test]
+    fn test_build_user_dict_simple_word_cost() {
+        let user_dict = build_user_dict(
+            "lindera-dictionary-builder-test-user-dict-simple-word-cost",
+            "東京スカイツリー,カスタム名詞,トウキョウスカイツリー
+とうきょうスカイツリー駅,カスタム名詞,トウキョウスカイツリーエキ,-5000
+",
+        );
+
+        // without the cost column, the default cost is used
+        let entries = user_dict.dict.find_surface("東京スカイツリー");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].word_cost, -10000);
+
+        // with the cost column, the given cost is used
+        let entries = user_dict.dict.find_surface("とうきょうスカイツリー駅");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].word_cost, -5000);
+        assert_eq!(entries[0].left_id, 0);
+        assert_eq!(entries[0].right_id, 0);
+
+        // the cost column is not a part of the details
+        assert_eq!(
+            user_dict
+                .word_details(entries[0].word_id.0 as usize)
+                .unwrap(),
+            vec!["カスタム名詞", "トウキョウスカイツリーエキ"]
+        );
+    }
+
+    #[test]
+    fn test_build_user_dict_invalid_simple_word_cost() {
+        let message = build_user_dict_error(
+            "lindera-dictionary-builder-test-user-dict-invalid-simple-word-cost",
+            "東京スカイツリー,カスタム名詞,トウキョウスカイツリー,cost
+",
+        );
+        assert!(message.contains("failed to parse word cost"));
+        assert!(
+            message.contains("line 1: 東京スカイツリー,カスタム名詞,トウキョウスカイツリー,cost")
+        );
+    }
+}
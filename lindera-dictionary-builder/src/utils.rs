@@ -1,22 +1,28 @@
-use std::io::Write;
-use std::path::Path;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
 
 use anyhow::anyhow;
 use encoding_rs::Encoding;
+use flate2::read::GzDecoder;
+use glob::glob;
 #[cfg(feature = "compress")]
 use lindera_compress::compress;
+use lindera_core::dictionary::{dictionary_header, DICTIONARY_KIND_FILE};
 use lindera_core::error::LinderaErrorKind;
-use lindera_core::file_util::read_file;
 use lindera_core::LinderaResult;
 use lindera_decompress::Algorithm;
 
+/// Write a dictionary file, prefixed with the dictionary header, compressed with `algorithm`.
 #[cfg(feature = "compress")]
 pub fn compress_write<W: Write>(
     buffer: &[u8],
     algorithm: Algorithm,
     writer: &mut W,
 ) -> LinderaResult<()> {
-    let compressed = compress(buffer, algorithm)
+    let mut data = dictionary_header();
+    data.extend_from_slice(buffer);
+    let compressed = compress(&data, algorithm)
         .map_err(|err| LinderaErrorKind::Compress.with_error(anyhow::anyhow!(err)))?;
     bincode::serialize_into(writer, &compressed)
         .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
@@ -24,6 +30,7 @@ pub fn compress_write<W: Write>(
     Ok(())
 }
 
+/// Write a dictionary file, prefixed with the dictionary header.
 #[cfg(not(feature = "compress"))]
 pub fn compress_write<W: Write>(
     buffer: &[u8],
@@ -31,18 +38,103 @@ pub fn compress_write<W: Write>(
     writer: &mut W,
 ) -> LinderaResult<()> {
     writer
-        .write_all(buffer)
+        .write_all(&dictionary_header())
+        .and_then(|_| writer.write_all(buffer))
         .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
 
     Ok(())
 }
 
+/// Write the file recording the kind of the dictionary, such as `ipadic`, in the output directory.
+/// It is prefixed with the dictionary header and never compressed.
+pub fn write_dictionary_kind(output_dir: &Path, kind: &str) -> LinderaResult<()> {
+    let mut data = dictionary_header();
+    data.extend_from_slice(kind.as_bytes());
+    fs::write(output_dir.join(DICTIONARY_KIND_FILE), data)
+        .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))
+}
+
 pub fn read_file_with_encoding(filepath: &Path, encoding_name: &str) -> LinderaResult<String> {
     let encoding = Encoding::for_label_no_replacement(encoding_name.as_bytes());
     let encoding = encoding.ok_or_else(|| {
         LinderaErrorKind::Decode.with_error(anyhow!("Invalid encoding: {}", encoding_name))
     })?;
 
-    let buffer = read_file(&filepath)?;
+    let buffer = read_source_file(filepath)?;
     Ok(encoding.decode(&buffer).0.into_owned())
 }
+
+/// Returns true if the file is gzip-compressed, judging by its `.gz` extension.
+pub fn is_gzipped(filepath: &Path) -> bool {
+    filepath.extension().is_some_and(|ext| ext == "gz")
+}
+
+/// Open a dictionary source file.
+///
+/// Files ending in `.gz` are decompressed transparently.
+/// If the file does not exist but a gzip-compressed `<filename>.gz` does, that one is opened instead.
+pub fn open_source_file(filepath: &Path) -> LinderaResult<Box<dyn Read>> {
+    let filepath = if !filepath.exists() && !is_gzipped(filepath) {
+        let mut gz_filepath = filepath.as_os_str().to_owned();
+        gz_filepath.push(".gz");
+        let gz_filepath = PathBuf::from(gz_filepath);
+        if gz_filepath.exists() {
+            gz_filepath
+        } else {
+            filepath.to_path_buf()
+        }
+    } else {
+        filepath.to_path_buf()
+    };
+
+    let file =
+        File::open(&filepath).map_err(|err| LinderaErrorKind::Io.with_error(anyhow!(err)))?;
+    if is_gzipped(&filepath) {
+        Ok(Box::new(GzDecoder::new(file)))
+    } else {
+        Ok(Box::new(file))
+    }
+}
+
+/// Read a dictionary source file, decompressing it if it is gzip-compressed.
+/// See `open_source_file`.
+pub fn read_source_file(filepath: &Path) -> LinderaResult<Vec<u8>> {
+    let mut buffer = Vec::new();
+    open_source_file(filepath)?
+        .read_to_end(&mut buffer)
+        .map_err(|err| LinderaErrorKind::Io.with_error(anyhow!(err)))?;
+    Ok(buffer)
+}
+
+/// List the lexicon files (`*.csv`) in the input directory.
+/// gzip-compressed CSV files (`*.csv.gz`) are also listed.
+pub fn lexicon_files(input_dir: &Path) -> LinderaResult<Vec<PathBuf>> {
+    let path = if let Some(path) = input_dir.to_str() {
+        path
+    } else {
+        return Err(
+            LinderaErrorKind::Io.with_error(anyhow::anyhow!("Failed to convert path to &str."))
+        );
+    };
+
+    let mut filenames: Vec<PathBuf> = Vec::new();
+    for pattern in [format!("{}/*.csv", path), format!("{}/*.csv.gz", path)] {
+        for entry in
+            glob(&pattern).map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?
+        {
+            match entry {
+                Ok(path) => {
+                    if let Some(filename) = path.file_name() {
+                        filenames.push(Path::new(input_dir).join(filename));
+                    } else {
+                        return Err(LinderaErrorKind::Io
+                            .with_error(anyhow::anyhow!("failed to get filename")));
+                    };
+                }
+                Err(err) => return Err(LinderaErrorKind::Content.with_error(anyhow!(err))),
+            }
+        }
+    }
+
+    Ok(filenames)
+}
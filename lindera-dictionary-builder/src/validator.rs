@@ -0,0 +1,237 @@
+use std::error::Error;
+use std::path::Path;
+
+use anyhow::anyhow;
+use encoding_rs::Encoding;
+use log::debug;
+
+use lindera_core::dictionary_builder::ValidationReport;
+use lindera_core::error::{LinderaError, LinderaErrorKind};
+use lindera_core::LinderaResult;
+
+use crate::chardef::CharDefBuilder;
+use crate::cost_matrix::CostMatrixBuilder;
+use crate::dict::DictBuilder;
+use crate::unk::UnkBuilder;
+use crate::utils::read_source_file;
+
+/// Run the parsing passes of the builders over `matrix.def`, `char.def`, `unk.def` and the
+/// lexicon files in the input directory without writing anything.
+/// The problems found are collected in the report instead of stopping at the first one.
+/// Pass the builders configured as for the build, so that the sources are read the same way.
+pub fn validate_source(
+    input_dir: &Path,
+    chardef_builder: &CharDefBuilder,
+    unk_builder: &UnkBuilder,
+    dict_builder: &DictBuilder,
+    cost_matrix_builder: &CostMatrixBuilder,
+) -> LinderaResult<ValidationReport> {
+    let mut report = ValidationReport::default();
+
+    // The context IDs of the other files are checked against the size of the matrix.
+    let matrix_size = cost_matrix_builder.validate(input_dir, &mut report)?;
+    let categories = chardef_builder.validate(input_dir, &mut report)?;
+    unk_builder.validate(input_dir, &categories, matrix_size, &mut report)?;
+    dict_builder.validate(input_dir, matrix_size, &mut report)?;
+
+    Ok(report)
+}
+
+/// Read a source file and decode it, recording the error if it cannot be read or is not valid
+/// in the encoding.
+pub(crate) fn read_text(
+    path: &Path,
+    encoding_name: &str,
+    report: &mut ValidationReport,
+) -> LinderaResult<Option<String>> {
+    let encoding = Encoding::for_label_no_replacement(encoding_name.as_bytes());
+    let encoding = encoding.ok_or_else(|| {
+        LinderaErrorKind::Decode.with_error(anyhow!("Invalid encoding: {}", encoding_name))
+    })?;
+
+    debug!("reading {:?}", path);
+    let buffer = match read_source_file(path) {
+        Ok(buffer) => buffer,
+        Err(err) => {
+            report
+                .errors
+                .push(format!("{}: {}", path.display(), error_message(&err)));
+            return Ok(None);
+        }
+    };
+
+    let (text, _, had_errors) = encoding.decode(&buffer);
+    if had_errors {
+        report.errors.push(format!(
+            "{}: invalid {} byte sequence",
+            path.display(),
+            encoding.name()
+        ));
+    }
+    Ok(Some(text.into_owned()))
+}
+
+/// Check that the context IDs of an entry are within the connection cost matrix.
+/// The right ID is the forward ID of the matrix, and the left ID is the backward ID.
+pub(crate) fn check_context_ids(
+    left_id: u16,
+    right_id: u16,
+    matrix_size: Option<(usize, usize)>,
+) -> Option<String> {
+    let (forward_size, backward_size) = matrix_size?;
+    if right_id as usize >= forward_size || left_id as usize >= backward_size {
+        Some(format!(
+            "context ID out of range: left_id {} right_id {} (the matrix is {} x {})",
+            left_id, right_id, forward_size, backward_size
+        ))
+    } else {
+        None
+    }
+}
+
+/// The message of an error without its kind.
+pub(crate) fn error_message(err: &LinderaError) -> String {
+    err.source()
+        .map_or_else(|| err.to_string(), |source| source.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use lindera_core::dictionary_builder::ValidationReport;
+
+    use crate::chardef::CharDefBuilderOptions;
+    use crate::cost_matrix::CostMatrixBuilderOptions;
+    use crate::dict::DictBuilderOptions;
+    use crate::unk::UnkBuilderOptions;
+    use crate::validator::validate_source;
+
+    const CHAR_DEF: &str = "DEFAULT 0 1 0
+ALPHA 1 1 0
+0x0041..0x005A ALPHA
+0x0061..0x007A ALPHA
+";
+
+    const UNK_DEF: &str = "DEFAULT,0,0,1000,SYMBOL,*,*,*,*,*,*
+ALPHA,1,1,1000,NOUN,*,*,*,*,*,*
+";
+
+    const MATRIX_DEF: &str = "2 2
+0 0 0
+0 1 0
+1 0 0
+1 1 0
+";
+
+    const LEX_CSV: &str = "tokyo,1,1,100,NOUN,PROPER,*,*,*,*,tokyo,TOKYO,TOKYO
+run,1,1,200,VERB,*,*,*,*,*,run,RUN,RUN
+";
+
+    fn write_source(input_dir: &Path, lex_csv: &str, matrix_def: &str) {
+        fs::create_dir_all(input_dir).unwrap();
+        fs::write(input_dir.join("char.def"), CHAR_DEF).unwrap();
+        fs::write(input_dir.join("unk.def"), UNK_DEF).unwrap();
+        fs::write(input_dir.join("matrix.def"), matrix_def).unwrap();
+        fs::write(input_dir.join("lex.csv"), lex_csv).unwrap();
+    }
+
+    fn validate(
+        input_dir: &Path,
+        encoding: &'static str,
+        skip_invalid_cost_or_id: bool,
+    ) -> ValidationReport {
+        validate_source(
+            input_dir,
+            &CharDefBuilderOptions::default()
+                .encoding(encoding)
+                .builder()
+                .unwrap(),
+            &UnkBuilderOptions::default()
+                .encoding(encoding)
+                .builder()
+                .unwrap(),
+            &DictBuilderOptions::default()
+                .encoding(encoding)
+                .flexible_csv(false)
+                .skip_invalid_cost_or_id(skip_invalid_cost_or_id)
+                .builder()
+                .unwrap(),
+            &CostMatrixBuilderOptions::default()
+                .encoding(encoding)
+                .builder()
+                .unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validate() {
+        let base_dir = std::env::temp_dir().join("lindera-dictionary-builder-test-validate");
+        write_source(&base_dir, LEX_CSV, MATRIX_DEF);
+
+        let report = validate(&base_dir, "UTF-8", false);
+
+        assert!(report.is_valid(), "{:?}", report.errors);
+        assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+        assert_eq!(report.entries, 2);
+        assert_eq!(report.unknown_entries, 2);
+        assert_eq!(report.categories, 2);
+        assert_eq!((report.forward_size, report.backward_size), (2, 2));
+
+        fs::remove_dir_all(&base_dir).unwrap();
+    }
+
+    #[test]
+    fn test_validate_malformed() {
+        let base_dir =
+            std::env::temp_dir().join("lindera-dictionary-builder-test-validate-malformed");
+        let lex_csv = "tokyo,1,1,100,NOUN,PROPER,*,*,*,*,tokyo,TOKYO,TOKYO
+run,1,1,200,VERB
+osaka,1,5,100,NOUN,PROPER,*,*,*,*,osaka,OSAKA,OSAKA
+kyoto,1,1,high,NOUN,PROPER,*,*,*,*,kyoto,KYOTO,KYOTO
+";
+        let matrix_def = "2 2
+0 0 0
+0 1 0
+1 0 40000
+1 2 0
+";
+        write_source(&base_dir, lex_csv, matrix_def);
+        // an invalid EUC-JP byte sequence
+        fs::write(base_dir.join("extra.csv"), b"tok\xffyo,1,1,100\n").unwrap();
+
+        let report = validate(&base_dir, "EUC-JP", false);
+
+        assert!(!report.is_valid());
+        assert_eq!(report.entries, 5);
+        let expected = [
+            "matrix.def:4: cost out of range: 40000",
+            "matrix.def:5: context ID out of range",
+            "extra.csv: invalid EUC-JP byte sequence",
+            "lex.csv:2: expected 13 fields, got 5",
+            "lex.csv:3: context ID out of range",
+            "lex.csv:4: failed to parse word_cost",
+        ];
+        for message in expected {
+            assert!(
+                report.errors.iter().any(|error| error.contains(message)),
+                "{} not in {:?}",
+                message,
+                report.errors
+            );
+        }
+        assert_eq!(report.errors.len(), expected.len());
+
+        // with skip_invalid_cost_or_id, the build skips the rows with an invalid cost or ID, so
+        // they are only warned about
+        let report = validate(&base_dir, "EUC-JP", true);
+
+        assert_eq!(report.errors.len(), expected.len() - 1);
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report.warnings[0].contains("lex.csv:4: failed to parse word_cost"));
+
+        fs::remove_dir_all(&base_dir).unwrap();
+    }
+}
@@ -1,21 +1,31 @@
 use std::borrow::Cow;
-use std::fs;
-use std::path::PathBuf;
-use std::str::FromStr;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::str::{self, FromStr};
+use std::time::{SystemTime, UNIX_EPOCH};
 
+use csv::StringRecord;
+use flate2::read::GzDecoder;
 use serde::{Deserialize, Serialize};
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
+use tar::Archive;
 
 use lindera_cc_cedict_builder::cc_cedict_builder::CcCedictBuilder;
 use lindera_core::character_definition::CharacterDefinitions;
 use lindera_core::connection::ConnectionCostMatrix;
-use lindera_core::dictionary::{Dictionary, UserDictionary};
+use lindera_core::dictionary::{
+    strip_dictionary_header, Dictionary, DictionaryInfo, UserDictionary, DICTIONARY_KIND_FILE,
+};
 use lindera_core::dictionary_builder::DictionaryBuilder;
 use lindera_core::error::{LinderaError, LinderaErrorKind};
+use lindera_core::mode::{Mode, Penalty};
 use lindera_core::prefix_dict::PrefixDict;
 use lindera_core::unknown_dictionary::UnknownDictionary;
 use lindera_core::LinderaResult;
+use lindera_decompress::{decompress, Algorithm, CompressedData};
 use lindera_ipadic_builder::ipadic_builder::IpadicBuilder;
 use lindera_ipadic_neologd_builder::ipadic_neologd_builder::IpadicNeologdBuilder;
 use lindera_ko_dic_builder::ko_dic_builder::KoDicBuilder;
@@ -36,10 +46,13 @@ pub enum DictionaryKind {
 }
 
 impl DictionaryKind {
+    /// Return all kinds of dictionaries.
     pub fn variants() -> Vec<DictionaryKind> {
         DictionaryKind::iter().collect::<Vec<_>>()
     }
 
+    /// Return the kinds of dictionaries contained in the binary,
+    /// i.e. those whose feature flag is enabled at compile time.
     pub fn contained_variants() -> Vec<DictionaryKind> {
         DictionaryKind::variants()
             .into_iter()
@@ -62,19 +75,92 @@ impl DictionaryKind {
             DictionaryKind::CcCedict => "cc-cedict",
         }
     }
+
+    /// Return the layout of the word details of the dictionary.
+    pub fn detail_schema(&self) -> DetailSchema {
+        match self {
+            DictionaryKind::IPADIC | DictionaryKind::IPADICNEologd => DetailSchema::ipadic(),
+            DictionaryKind::UniDic => DetailSchema::unidic(),
+            DictionaryKind::KoDic => DetailSchema::ko_dic(),
+            DictionaryKind::CcCedict => DetailSchema::cc_cedict(),
+        }
+    }
+
+    /// Return the number of fields of the word details of the dictionary,
+    /// i.e. the number of columns of the lexicon after the surface, the IDs and the cost.
+    /// Unknown words have a single `UNK` field instead.
+    pub fn detail_field_count(&self) -> usize {
+        match self {
+            DictionaryKind::IPADIC | DictionaryKind::IPADICNEologd => 9,
+            DictionaryKind::UniDic => 17,
+            DictionaryKind::KoDic => 8,
+            DictionaryKind::CcCedict => 8,
+        }
+    }
+
+    /// Build the details of a word from its surface, part-of-speech and reading, like those of
+    /// a simple row of a user dictionary CSV for the dictionary.
+    pub fn simple_userdic_details(&self, surface: &str, pos: &str, reading: &str) -> Vec<String> {
+        match self {
+            DictionaryKind::IPADIC => {
+                IpadicBuilder::new().simple_userdic_details(surface, pos, reading)
+            }
+            DictionaryKind::IPADICNEologd => {
+                IpadicNeologdBuilder::new().simple_userdic_details(surface, pos, reading)
+            }
+            DictionaryKind::UniDic => {
+                UnidicBuilder::new().simple_userdic_details(surface, pos, reading)
+            }
+            DictionaryKind::KoDic => {
+                KoDicBuilder::new().simple_userdic_details(surface, pos, reading)
+            }
+            DictionaryKind::CcCedict => {
+                CcCedictBuilder::new().simple_userdic_details(surface, pos, reading)
+            }
+        }
+    }
+
+    /// Return whether the word details of the dictionary record the parts of compound words,
+    /// which the tokenizer splits in decompose mode. Only ko-dic does.
+    pub fn has_compounds(&self) -> bool {
+        matches!(self, DictionaryKind::KoDic)
+    }
+
+    /// Return the tokenization mode suited to the dictionary, used when no mode is specified.
+    /// ko-dic records the parts of compound words, which are split in decompose mode, while
+    /// the other dictionaries use normal mode.
+    pub fn default_mode(&self) -> Mode {
+        match self {
+            DictionaryKind::KoDic => Mode::Decompose(Penalty::default()),
+            DictionaryKind::IPADIC
+            | DictionaryKind::IPADICNEologd
+            | DictionaryKind::UniDic
+            | DictionaryKind::CcCedict => Mode::Normal,
+        }
+    }
 }
 
 impl FromStr for DictionaryKind {
     type Err = LinderaError;
     fn from_str(input: &str) -> Result<DictionaryKind, Self::Err> {
-        match input {
+        // Accept any case (e.g. `IPADIC`) and some common aliases.
+        match input.to_lowercase().as_str() {
             "ipadic" => Ok(DictionaryKind::IPADIC),
             "ipadic-neologd" => Ok(DictionaryKind::IPADICNEologd),
             "unidic" => Ok(DictionaryKind::UniDic),
-            "ko-dic" => Ok(DictionaryKind::KoDic),
-            "cc-cedict" => Ok(DictionaryKind::CcCedict),
-            _ => Err(LinderaErrorKind::DictionaryKindError
-                .with_error(anyhow::anyhow!("Invalid dictionary kind: {}", input))),
+            "ko-dic" | "ko" => Ok(DictionaryKind::KoDic),
+            "cc-cedict" | "cedict" => Ok(DictionaryKind::CcCedict),
+            _ => Err(
+                LinderaErrorKind::DictionaryKindError.with_error(anyhow::anyhow!(
+                    "Invalid dictionary kind: {}. Valid kinds are: {}",
+                    input,
+                    DictionaryKind::variants()
+                        .iter()
+                        .map(|kind| kind.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )),
+            ),
         }
     }
 }
@@ -84,13 +170,93 @@ impl FromStr for DictionaryKind {
 /// Use this if you want to use a dictionary when tokenizing.
 ///
 /// Either `kind` or `path` must be specified.
+/// Fill in the other fields with `..Default::default()`.
 ///
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
 pub struct DictionaryConfig {
     /// Specify the kind of dictionary (IPADIC, UniDic, ko-dic, CC-CEDICT) if a self-contained dictionary is used for tokenization.
     pub kind: Option<DictionaryKind>,
     /// Specifies the path to a pre-built external dictionary if one is used.
     pub path: Option<PathBuf>,
+    /// Specifies the layout of the word details if it differs from the one of `kind`.
+    /// It is used as the default schema of the token filters that read the details.
+    #[serde(default)]
+    pub detail_schema: Option<DetailSchema>,
+}
+
+/// Layout of the word details of a dictionary
+///
+/// Names the index of the details holding each piece of information,
+/// so that dictionaries with a non-standard column order can be used with the token filters.
+///
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct DetailSchema {
+    /// Indices of the part-of-speech levels, from the major part-of-speech to the most detailed one.
+    #[serde(default)]
+    pub pos: Vec<usize>,
+    /// Index of the base form.
+    #[serde(default)]
+    pub base_form: Option<usize>,
+    /// Index of the reading.
+    #[serde(default)]
+    pub reading: Option<usize>,
+    /// Index of the pronunciation.
+    #[serde(default)]
+    pub pronunciation: Option<usize>,
+}
+
+impl DetailSchema {
+    /// The layout of IPADIC and IPADIC NEologd.
+    pub fn ipadic() -> Self {
+        Self {
+            pos: vec![0, 1, 2, 3],
+            base_form: Some(6),
+            reading: Some(7),
+            pronunciation: Some(8),
+        }
+    }
+
+    /// The layout of UniDic.
+    pub fn unidic() -> Self {
+        Self {
+            pos: vec![0, 1, 2, 3],
+            base_form: Some(10),
+            reading: Some(6),
+            pronunciation: Some(9),
+        }
+    }
+
+    /// The layout of ko-dic.
+    pub fn ko_dic() -> Self {
+        Self {
+            pos: vec![0],
+            base_form: None,
+            reading: Some(3),
+            pronunciation: None,
+        }
+    }
+
+    /// The layout of CC-CEDICT. The reading is the pinyin.
+    pub fn cc_cedict() -> Self {
+        Self {
+            pos: Vec::new(),
+            base_form: None,
+            reading: Some(4),
+            pronunciation: None,
+        }
+    }
+}
+
+impl From<&DictionaryKind> for DetailSchema {
+    fn from(kind: &DictionaryKind) -> Self {
+        kind.detail_schema()
+    }
+}
+
+impl From<&DetailSchema> for DetailSchema {
+    fn from(schema: &DetailSchema) -> Self {
+        schema.clone()
+    }
 }
 
 /// User dictionary config
@@ -130,10 +296,10 @@ impl DictionaryLoader {
 
     pub fn prefix_dict(dir: PathBuf) -> LinderaResult<PrefixDict> {
         let unidic_data_path = dir.join("dict.da");
-        let unidic_data = Self::read_file(unidic_data_path)?;
+        let unidic_data = Self::read_dictionary_file(unidic_data_path)?;
 
         let unidic_vals_path = dir.join("dict.vals");
-        let unidic_vals = Self::read_file(unidic_vals_path)?;
+        let unidic_vals = Self::read_dictionary_file(unidic_vals_path)?;
 
         Ok(PrefixDict::from_static_slice(
             unidic_data.as_slice(),
@@ -143,7 +309,7 @@ impl DictionaryLoader {
 
     pub fn connection(dir: PathBuf) -> LinderaResult<ConnectionCostMatrix> {
         let path = dir.join("matrix.mtx");
-        let data = Self::read_file(path)?;
+        let data = Self::read_dictionary_file(path)?;
 
         Ok(ConnectionCostMatrix::load(data.as_slice()))
     }
@@ -164,12 +330,12 @@ impl DictionaryLoader {
 
     pub fn words_idx_data(dir: PathBuf) -> LinderaResult<Vec<u8>> {
         let path = dir.join("dict.wordsidx");
-        Self::read_file(path)
+        Self::read_dictionary_file(path)
     }
 
     pub fn words_data(dir: PathBuf) -> LinderaResult<Vec<u8>> {
         let path = dir.join("dict.words");
-        Self::read_file(path)
+        Self::read_dictionary_file(path)
     }
 
     pub fn load_dictionary(path: PathBuf) -> LinderaResult<Dictionary> {
@@ -183,6 +349,213 @@ impl DictionaryLoader {
         })
     }
 
+    /// Get summary information about a pre-built dictionary.
+    ///
+    /// Only the kind, the word index, the word details and the connection cost matrix are read,
+    /// and they are decompressed if the dictionary was built with the `compress` feature.
+    pub fn load_dictionary_info(dir: PathBuf) -> LinderaResult<DictionaryInfo> {
+        let words_idx_data = Self::read_dictionary_file(dir.join("dict.wordsidx"))?;
+        let words_data = Self::read_dictionary_file(dir.join("dict.words"))?;
+        let cost_matrix =
+            ConnectionCostMatrix::load(&Self::read_dictionary_file(dir.join("matrix.mtx"))?);
+
+        let mut info = DictionaryInfo::from_parts(&words_idx_data, &words_data, &cost_matrix);
+        info.kind = Self::dictionary_kind(dir)?.map(|kind| kind.as_str().to_string());
+        Ok(info)
+    }
+
+    /// Get the kind of a pre-built dictionary recorded by the dictionary builder.
+    ///
+    /// Returns `None` if the kind is not recorded, e.g. for a dictionary built from a compiled
+    /// MeCab dictionary.
+    pub fn dictionary_kind(dir: PathBuf) -> LinderaResult<Option<DictionaryKind>> {
+        let path = dir.join(DICTIONARY_KIND_FILE);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let data = Self::read_file(path)?;
+        let kind = str::from_utf8(strip_dictionary_header(&data)?)
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(anyhow::anyhow!(err)))?;
+        DictionaryKind::from_str(kind).map(Some)
+    }
+
+    /// Get the compression algorithm of a pre-built dictionary, reading only the header of
+    /// the word details file.
+    ///
+    /// Returns `None` if the dictionary is not compressed.
+    pub fn compression_algorithm(dir: PathBuf) -> LinderaResult<Option<Algorithm>> {
+        let path = dir.join("dict.words");
+        let file_len = fs::metadata(&path)
+            .map_err(|err| LinderaErrorKind::Io.with_error(err))?
+            .len();
+        let mut header = Vec::with_capacity(12);
+        File::open(&path)
+            .map_err(|err| LinderaErrorKind::Io.with_error(err))?
+            .take(12)
+            .read_to_end(&mut header)
+            .map_err(|err| LinderaErrorKind::Io.with_error(err))?;
+
+        Ok(Self::compressed_header_algorithm(&header, file_len))
+    }
+
+    /// A compressed file is a bincode-serialized `CompressedData`: the algorithm as a `u32`
+    /// followed by the length of the compressed data as a `u64`, which must match the file size.
+    fn compressed_header_algorithm(header: &[u8], file_len: u64) -> Option<Algorithm> {
+        if header.len() < 12 {
+            return None;
+        }
+        let algorithm = u32::from_le_bytes(header[0..4].try_into().ok()?);
+        let data_len = u64::from_le_bytes(header[4..12].try_into().ok()?);
+        if data_len != file_len - 12 {
+            return None;
+        }
+
+        match algorithm {
+            0 => Some(Algorithm::Deflate),
+            1 => Some(Algorithm::Zlib),
+            2 => Some(Algorithm::Gzip),
+            3 => Some(Algorithm::Raw),
+            4 => Some(Algorithm::Zstd),
+            _ => None,
+        }
+    }
+
+    /// Read a dictionary file, decompressing it if it is compressed, and return the data after
+    /// the dictionary header.
+    fn read_dictionary_file(path: PathBuf) -> LinderaResult<Vec<u8>> {
+        let data = Self::read_file(path)?;
+        match Self::decompress_data(&data)? {
+            Some(decompressed) => Self::without_header(decompressed),
+            None => Self::without_header(data),
+        }
+    }
+
+    /// Check the dictionary header of the data and remove it.
+    fn without_header(mut data: Vec<u8>) -> LinderaResult<Vec<u8>> {
+        let header_len = data.len() - strip_dictionary_header(&data)?.len();
+        data.drain(..header_len);
+        Ok(data)
+    }
+
+    /// Decompress the contents of a dictionary file if it is compressed, and return the data
+    /// after the dictionary header. The data is borrowed if it is not compressed.
+    fn dictionary_data(data: &'static [u8]) -> LinderaResult<Cow<'static, [u8]>> {
+        match Self::decompress_data(data)? {
+            Some(decompressed) => Ok(Cow::Owned(Self::without_header(decompressed)?)),
+            None => Ok(Cow::Borrowed(strip_dictionary_header(data)?)),
+        }
+    }
+
+    /// Decompress the data if it has the header of a compressed file.
+    ///
+    /// Returns `None` if the data is not compressed.
+    fn decompress_data(data: &[u8]) -> LinderaResult<Option<Vec<u8>>> {
+        if Self::compressed_header_algorithm(data, data.len() as u64).is_none() {
+            return Ok(None);
+        }
+
+        let compressed_data: CompressedData = bincode::deserialize(data)
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(anyhow::anyhow!(err)))?;
+        decompress(compressed_data)
+            .map(Some)
+            .map_err(|err| LinderaErrorKind::DictionaryLoadError.with_error(anyhow::anyhow!(err)))
+    }
+
+    /// Load a pre-built dictionary from the contents of its files, such as the files embedded
+    /// in the binary with `include_bytes!`, without touching the filesystem.
+    ///
+    /// The arguments are the contents of `dict.da`, `dict.vals`, `dict.wordsidx`, `dict.words`,
+    /// `matrix.mtx`, `char_def.bin` and `unk.bin`.
+    /// The files are decompressed if the dictionary was built with the `compress` feature.
+    /// Otherwise the connection cost matrix, the word index and the word details are borrowed
+    /// instead of being copied.
+    pub fn load_dictionary_from_bytes(
+        dict_da: &'static [u8],
+        dict_vals: &'static [u8],
+        words_idx: &'static [u8],
+        words: &'static [u8],
+        connection: &'static [u8],
+        char_def: &'static [u8],
+        unk: &'static [u8],
+    ) -> LinderaResult<Dictionary> {
+        let dict = PrefixDict::from_static_slice(
+            &Self::dictionary_data(dict_da)?,
+            &Self::dictionary_data(dict_vals)?,
+        );
+
+        let cost_matrix = match Self::dictionary_data(connection)? {
+            Cow::Owned(data) => ConnectionCostMatrix::load(&data),
+            Cow::Borrowed(data) => ConnectionCostMatrix::load_static(data),
+        };
+
+        let char_definitions = CharacterDefinitions::load(
+            &Self::decompress_data(char_def)?.map_or(Cow::Borrowed(char_def), Cow::Owned),
+        )?;
+
+        let unknown_dictionary = UnknownDictionary::load(
+            &Self::decompress_data(unk)?.map_or(Cow::Borrowed(unk), Cow::Owned),
+        )?;
+
+        Ok(Dictionary {
+            dict,
+            cost_matrix,
+            char_definitions,
+            unknown_dictionary,
+            words_idx_data: Self::dictionary_data(words_idx)?,
+            words_data: Self::dictionary_data(words)?,
+        })
+    }
+
+    /// Load a pre-built dictionary from a `.tar.gz` archive.
+    ///
+    /// The archive is extracted into a temporary directory, which is removed once the
+    /// dictionary has been loaded. The dictionary files may be placed either at the root of
+    /// the archive or in a single top-level directory.
+    pub fn load_dictionary_from_archive(path: PathBuf) -> LinderaResult<Dictionary> {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|err| LinderaErrorKind::Io.with_error(err))?
+            .as_nanos();
+        let temp_dir = std::env::temp_dir().join(format!(
+            "lindera-dictionary-{}-{}",
+            std::process::id(),
+            nanos
+        ));
+
+        let result = Self::extract_archive(&path, &temp_dir).and_then(Self::load_dictionary);
+
+        if temp_dir.exists() {
+            fs::remove_dir_all(&temp_dir).map_err(|err| LinderaErrorKind::Io.with_error(err))?;
+        }
+
+        result
+    }
+
+    fn extract_archive(path: &Path, output_dir: &Path) -> LinderaResult<PathBuf> {
+        let file = File::open(path).map_err(|err| LinderaErrorKind::Io.with_error(err))?;
+        Archive::new(GzDecoder::new(file))
+            .unpack(output_dir)
+            .map_err(|err| LinderaErrorKind::Io.with_error(err))?;
+
+        if output_dir.join("dict.da").exists() {
+            return Ok(output_dir.to_path_buf());
+        }
+
+        for entry in fs::read_dir(output_dir).map_err(|err| LinderaErrorKind::Io.with_error(err))? {
+            let entry = entry.map_err(|err| LinderaErrorKind::Io.with_error(err))?;
+            if entry.path().join("dict.da").exists() {
+                return Ok(entry.path());
+            }
+        }
+
+        Err(
+            LinderaErrorKind::DictionaryNotFound.with_error(anyhow::anyhow!(
+                "No dictionary found in the archive: {:?}",
+                path
+            )),
+        )
+    }
+
     pub fn load_dictionary_from_kind(kind: DictionaryKind) -> LinderaResult<Dictionary> {
         // The dictionary specified by the feature flag will be loaded.
         match kind {
@@ -262,4 +635,630 @@ impl DictionaryLoader {
                 .with_error(anyhow::anyhow!("Invalid user dictionary source file"))),
         }
     }
+
+    /// Load several user dictionaries as a single user dictionary.
+    ///
+    /// The entries of all the CSV files are built into one user dictionary.
+    /// If the same surface form appears in several files, only the entries of the last file are kept,
+    /// so that later files override earlier ones. All the files must have the same dictionary type.
+    /// A single config may also point to a pre-built `.bin` user dictionary.
+    pub fn load_user_dictionaries_from_configs(
+        dictionary_configs: Vec<UserDictionaryConfig>,
+    ) -> LinderaResult<UserDictionary> {
+        if dictionary_configs.len() == 1 {
+            return Self::load_user_dictionary_from_config(
+                dictionary_configs.into_iter().next().unwrap(),
+            );
+        }
+
+        let mut kind: Option<DictionaryKind> = None;
+        let mut files_rows = Vec::with_capacity(dictionary_configs.len());
+        for dictionary_config in dictionary_configs.iter() {
+            if dictionary_config
+                .path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                != Some("csv")
+            {
+                return Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                    "Only CSV user dictionaries can be merged: {:?}",
+                    dictionary_config.path
+                )));
+            }
+            let file_kind = dictionary_config.kind.clone().ok_or_else(|| {
+                LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                    "Dictionary type must be specified if CSV file specified"
+                ))
+            })?;
+            if kind.as_ref().is_some_and(|kind| *kind != file_kind) {
+                return Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                    "All the user dictionaries must have the same dictionary type"
+                )));
+            }
+            kind = Some(file_kind);
+
+            let mut rdr = csv::ReaderBuilder::new()
+                .has_headers(false)
+                .flexible(true)
+                .from_path(&dictionary_config.path)
+                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+            let mut rows = Vec::new();
+            for result in rdr.records() {
+                rows.push(
+                    result.map_err(|err| {
+                        LinderaErrorKind::Content.with_error(anyhow::anyhow!(err))
+                    })?,
+                );
+            }
+            files_rows.push(rows);
+        }
+        let kind = kind.ok_or_else(|| {
+            LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                "At least one user dictionary must be specified"
+            ))
+        })?;
+
+        // The last file that defines each surface form.
+        let mut surface_files: HashMap<String, usize> = HashMap::new();
+        for (file_idx, rows) in files_rows.iter().enumerate() {
+            for row in rows.iter().filter(|row| !row.is_empty()) {
+                surface_files.insert(row[0].to_string(), file_idx);
+            }
+        }
+        let merged_rows = files_rows
+            .into_iter()
+            .enumerate()
+            .flat_map(|(file_idx, rows)| {
+                let surface_files = &surface_files;
+                rows.into_iter().filter(move |row| {
+                    !row.is_empty() && surface_files.get(&row[0]) == Some(&file_idx)
+                })
+            })
+            .collect::<Vec<StringRecord>>();
+
+        // The merged entries are built with the regular user dictionary builder.
+        let builder = DictionaryBuilderResolver::resolve_builder(kind)?;
+        builder
+            .build_user_dict_from_rows(merged_rows)
+            .map_err(|err| LinderaErrorKind::DictionaryBuildError.with_error(err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    #[cfg(not(feature = "compress"))]
+    use std::fs::File;
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    #[cfg(not(feature = "compress"))]
+    use flate2::{write::GzEncoder, Compression};
+
+    use lindera_core::dictionary::DICTIONARY_KIND_FILE;
+    use lindera_core::dictionary_builder::DictionaryBuilder;
+    use lindera_core::mode::{Mode, Penalty};
+    use lindera_ipadic_builder::ipadic_builder::IpadicBuilder;
+    #[cfg(not(feature = "compress"))]
+    use lindera_unidic_builder::unidic_builder::UnidicBuilder;
+
+    use crate::{DictionaryKind, DictionaryLoader, UserDictionaryConfig};
+
+    #[test]
+    fn test_dictionary_kind_contained_variants() {
+        let contained_variants = DictionaryKind::contained_variants();
+
+        assert_eq!(
+            contained_variants.contains(&DictionaryKind::IPADIC),
+            cfg!(feature = "ipadic")
+        );
+        assert_eq!(
+            contained_variants.contains(&DictionaryKind::IPADICNEologd),
+            cfg!(feature = "ipadic-neologd")
+        );
+        assert_eq!(
+            contained_variants.contains(&DictionaryKind::UniDic),
+            cfg!(feature = "unidic")
+        );
+        assert_eq!(
+            contained_variants.contains(&DictionaryKind::KoDic),
+            cfg!(feature = "ko-dic")
+        );
+        assert_eq!(
+            contained_variants.contains(&DictionaryKind::CcCedict),
+            cfg!(feature = "cc-cedict")
+        );
+    }
+
+    #[test]
+    #[cfg(not(any(
+        feature = "ipadic",
+        feature = "ipadic-neologd",
+        feature = "unidic",
+        feature = "ko-dic",
+        feature = "cc-cedict"
+    )))]
+    fn test_dictionary_kind_contained_variants_empty() {
+        assert!(DictionaryKind::contained_variants().is_empty());
+    }
+
+    #[test]
+    fn test_dictionary_kind_from_str() {
+        assert_eq!(
+            DictionaryKind::from_str("ipadic").unwrap(),
+            DictionaryKind::IPADIC
+        );
+        assert_eq!(
+            DictionaryKind::from_str("ipadic-neologd").unwrap(),
+            DictionaryKind::IPADICNEologd
+        );
+        assert_eq!(
+            DictionaryKind::from_str("unidic").unwrap(),
+            DictionaryKind::UniDic
+        );
+        assert_eq!(
+            DictionaryKind::from_str("ko-dic").unwrap(),
+            DictionaryKind::KoDic
+        );
+        assert_eq!(
+            DictionaryKind::from_str("cc-cedict").unwrap(),
+            DictionaryKind::CcCedict
+        );
+    }
+
+    #[test]
+    fn test_dictionary_kind_from_str_case_insensitive() {
+        assert_eq!(
+            DictionaryKind::from_str("IPADIC").unwrap(),
+            DictionaryKind::IPADIC
+        );
+        assert_eq!(
+            DictionaryKind::from_str("ipaDic").unwrap(),
+            DictionaryKind::IPADIC
+        );
+        assert_eq!(
+            DictionaryKind::from_str("IPADIC-NEologd").unwrap(),
+            DictionaryKind::IPADICNEologd
+        );
+        assert_eq!(
+            DictionaryKind::from_str("UniDic").unwrap(),
+            DictionaryKind::UniDic
+        );
+        assert_eq!(
+            DictionaryKind::from_str("Ko-Dic").unwrap(),
+            DictionaryKind::KoDic
+        );
+        assert_eq!(
+            DictionaryKind::from_str("CC-CEDICT").unwrap(),
+            DictionaryKind::CcCedict
+        );
+    }
+
+    #[test]
+    fn test_dictionary_kind_from_str_alias() {
+        assert_eq!(
+            DictionaryKind::from_str("ko").unwrap(),
+            DictionaryKind::KoDic
+        );
+        assert_eq!(
+            DictionaryKind::from_str("KO").unwrap(),
+            DictionaryKind::KoDic
+        );
+        assert_eq!(
+            DictionaryKind::from_str("cedict").unwrap(),
+            DictionaryKind::CcCedict
+        );
+        assert_eq!(
+            DictionaryKind::from_str("CEDICT").unwrap(),
+            DictionaryKind::CcCedict
+        );
+    }
+
+    #[test]
+    fn test_dictionary_kind_from_str_invalid() {
+        let result = DictionaryKind::from_str("mecab");
+        assert!(result.is_err());
+
+        let message = result.unwrap_err().to_string();
+        assert!(message.contains("mecab"));
+        for kind in DictionaryKind::variants() {
+            assert!(message.contains(kind.as_str()));
+        }
+    }
+
+    #[test]
+    fn test_dictionary_kind_as_str() {
+        assert_eq!(DictionaryKind::from_str("KO").unwrap().as_str(), "ko-dic");
+        assert_eq!(
+            DictionaryKind::from_str("cedict").unwrap().as_str(),
+            "cc-cedict"
+        );
+    }
+
+    #[test]
+    fn test_dictionary_kind_default_mode() {
+        assert_eq!(DictionaryKind::IPADIC.default_mode(), Mode::Normal);
+        assert_eq!(
+            DictionaryKind::KoDic.default_mode(),
+            Mode::Decompose(Penalty::default())
+        );
+        assert_ne!(
+            DictionaryKind::KoDic.default_mode(),
+            DictionaryKind::IPADIC.default_mode()
+        );
+    }
+
+    #[test]
+    fn test_dictionary_kind_detail_field_count() {
+        assert_eq!(DictionaryKind::IPADIC.detail_field_count(), 9);
+        assert_eq!(DictionaryKind::UniDic.detail_field_count(), 17);
+        assert_eq!(DictionaryKind::CcCedict.detail_field_count(), 8);
+
+        // the fields of the built-in schemas exist
+        for kind in DictionaryKind::variants() {
+            let schema = kind.detail_schema();
+            let indices = schema
+                .pos
+                .iter()
+                .chain(schema.base_form.iter())
+                .chain(schema.reading.iter())
+                .chain(schema.pronunciation.iter());
+            for index in indices {
+                assert!(*index < kind.detail_field_count(), "{}", kind.as_str());
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "compress"))]
+    fn test_load_dictionary_from_archive() {
+        let base_dir = std::env::temp_dir().join("lindera-dictionary-test-load-from-archive");
+        let input_dir = base_dir.join("input");
+        let output_dir = base_dir.join("output");
+        fs::create_dir_all(&input_dir).unwrap();
+        fs::write(
+            input_dir.join("char.def"),
+            "DEFAULT 0 1 0\nKANJI 0 0 2\n0x4E00..0x9FA5 KANJI\n",
+        )
+        .unwrap();
+        fs::write(
+            input_dir.join("unk.def"),
+            "DEFAULT,1,1,1000,補助記号,一般,*,*,*,*\nKANJI,1,1,1000,名詞,普通名詞,一般,*,*,*\n",
+        )
+        .unwrap();
+        fs::write(
+            input_dir.join("matrix.def"),
+            "2 2\n0 0 0\n0 1 0\n1 0 0\n1 1 0\n",
+        )
+        .unwrap();
+        fs::write(
+            input_dir.join("lex.csv"),
+            "東京,1,1,100,名詞,固有名詞,地名,一般,*,*,トウキョウ,トウキョウ,東京,トーキョー,東京,トーキョー,固,*,*,*,*\n",
+        )
+        .unwrap();
+        UnidicBuilder::new()
+            .build_dictionary(&input_dir, &output_dir)
+            .unwrap();
+
+        // pack the built dictionary into a top-level directory of the archive
+        let archive_path = base_dir.join("dictionary.tar.gz");
+        let mut archive = tar::Builder::new(GzEncoder::new(
+            File::create(&archive_path).unwrap(),
+            Compression::default(),
+        ));
+        archive.append_dir_all("unidic", &output_dir).unwrap();
+        archive.into_inner().unwrap().finish().unwrap();
+
+        let dictionary = DictionaryLoader::load_dictionary_from_archive(archive_path).unwrap();
+
+        let entries = dictionary.dict.find_surface("東京");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].word_cost, 100);
+        assert_eq!(
+            dictionary
+                .word_details(entries[0].word_id.0 as usize)
+                .unwrap()[0],
+            "名詞"
+        );
+
+        let result =
+            DictionaryLoader::load_dictionary_from_archive(base_dir.join("missing.tar.gz"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_load_user_dictionaries_from_configs() {
+        let base_dir = std::env::temp_dir().join("lindera-dictionary-test-merge-user-dictionaries");
+        fs::create_dir_all(&base_dir).unwrap();
+        let first_path = base_dir.join("first.csv");
+        fs::write(
+            &first_path,
+            "東京スカイツリー,カスタム名詞,トウキョウスカイツリー\n東武スカイツリーライン,カスタム名詞,トウブスカイツリーライン\n",
+        )
+        .unwrap();
+        let second_path = base_dir.join("second.csv");
+        fs::write(
+            &second_path,
+            "東京スカイツリー,固有名詞,トーキョースカイツリー\n",
+        )
+        .unwrap();
+
+        let user_dictionary = DictionaryLoader::load_user_dictionaries_from_configs(vec![
+            UserDictionaryConfig {
+                path: first_path,
+                kind: Some(DictionaryKind::IPADIC),
+            },
+            UserDictionaryConfig {
+                path: second_path,
+                kind: Some(DictionaryKind::IPADIC),
+            },
+        ])
+        .unwrap();
+
+        // the entry of the later file overrides the earlier one
+        let entries = user_dictionary.dict.find_surface("東京スカイツリー");
+        assert_eq!(entries.len(), 1);
+        let details = user_dictionary
+            .word_details(entries[0].word_id.0 as usize)
+            .unwrap();
+        assert_eq!(details[0], "固有名詞");
+        assert_eq!(details[7], "トーキョースカイツリー");
+
+        // the entries without conflict are kept
+        let entries = user_dictionary.dict.find_surface("東武スカイツリーライン");
+        assert_eq!(entries.len(), 1);
+
+        // a pre-built user dictionary cannot be merged
+        let result = DictionaryLoader::load_user_dictionaries_from_configs(vec![
+            UserDictionaryConfig {
+                path: base_dir.join("first.csv"),
+                kind: Some(DictionaryKind::IPADIC),
+            },
+            UserDictionaryConfig {
+                path: base_dir.join("userdic.bin"),
+                kind: None,
+            },
+        ]);
+        assert!(result.is_err());
+
+        // an invalid row is reported with its line in its own file
+        let invalid_path = base_dir.join("invalid.csv");
+        fs::write(&invalid_path, "東武スカイツリーライン,カスタム名詞\n").unwrap();
+        let result = DictionaryLoader::load_user_dictionaries_from_configs(vec![
+            UserDictionaryConfig {
+                path: base_dir.join("first.csv"),
+                kind: Some(DictionaryKind::IPADIC),
+            },
+            UserDictionaryConfig {
+                path: invalid_path,
+                kind: Some(DictionaryKind::IPADIC),
+            },
+        ]);
+        let message = result.err().unwrap().to_string();
+        assert!(message.contains("line 1: 東武スカイツリーライン,カスタム名詞"));
+
+        fs::remove_dir_all(&base_dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_dictionary_from_bytes() {
+        let base_dir = std::env::temp_dir().join("lindera-dictionary-test-load-from-bytes");
+        let input_dir = base_dir.join("input");
+        let output_dir = base_dir.join("output");
+        fs::create_dir_all(&input_dir).unwrap();
+        fs::write(
+            input_dir.join("char.def"),
+            "DEFAULT 0 1 0\nALPHA 1 1 0\n0x0041..0x005A ALPHA\n0x0061..0x007A ALPHA\n",
+        )
+        .unwrap();
+        fs::write(
+            input_dir.join("unk.def"),
+            "DEFAULT,0,0,1000,SYMBOL,*,*,*,*,*,*\nALPHA,1,1,1000,NOUN,*,*,*,*,*,*\n",
+        )
+        .unwrap();
+        fs::write(
+            input_dir.join("matrix.def"),
+            "2 2\n0 0 0\n0 1 0\n1 0 0\n1 1 0\n",
+        )
+        .unwrap();
+        fs::write(
+            input_dir.join("lex.csv"),
+            "tokyo,1,1,100,NOUN,PROPER,*,*,*,*,tokyo,TOKYO,TOKYO\nrun,1,1,200,VERB,*,*,*,*,*,run,RUN,RUN\n",
+        )
+        .unwrap();
+        IpadicBuilder::new()
+            .build_dictionary(&input_dir, &output_dir)
+            .unwrap();
+
+        // the contents of the files, as they would be embedded with `include_bytes!`
+        let read = |name: &str| -> &'static [u8] {
+            Box::leak(fs::read(output_dir.join(name)).unwrap().into_boxed_slice())
+        };
+        let dictionary = DictionaryLoader::load_dictionary_from_bytes(
+            read("dict.da"),
+            read("dict.vals"),
+            read("dict.wordsidx"),
+            read("dict.words"),
+            read("matrix.mtx"),
+            read("char_def.bin"),
+            read("unk.bin"),
+        )
+        .unwrap();
+
+        let entries = dictionary.dict.find_surface("tokyo");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].word_cost, 100);
+        assert_eq!(
+            dictionary
+                .word_details(entries[0].word_id.0 as usize)
+                .unwrap(),
+            vec!["NOUN", "PROPER", "*", "*", "*", "*", "tokyo", "TOKYO", "TOKYO"]
+        );
+        assert_eq!(dictionary.cost_matrix.cost(1, 1), 0);
+        assert!(dictionary.dict.find_surface("osaka").is_empty());
+
+        fs::remove_dir_all(&base_dir).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "compress")]
+    fn test_load_dictionary_zstd() {
+        use std::collections::HashMap;
+
+        use lindera_core::cost_model::DictionaryCostModel;
+        use lindera_core::dictionary::{Dictionary, UserWordOverlay};
+        use lindera_core::viterbi::Lattice;
+        use lindera_decompress::Algorithm;
+
+        let base_dir = std::env::temp_dir().join("lindera-dictionary-test-load-zstd");
+        let input_dir = base_dir.join("input");
+        fs::create_dir_all(&input_dir).unwrap();
+        fs::write(
+            input_dir.join("char.def"),
+            "DEFAULT 0 1 0\nALPHA 1 1 0\n0x0041..0x005A ALPHA\n0x0061..0x007A ALPHA\n",
+        )
+        .unwrap();
+        fs::write(
+            input_dir.join("unk.def"),
+            "DEFAULT,0,0,1000,SYMBOL,*,*,*,*,*,*\nALPHA,1,1,1000,NOUN,*,*,*,*,*,*\n",
+        )
+        .unwrap();
+        fs::write(
+            input_dir.join("matrix.def"),
+            "2 2\n0 0 0\n0 1 0\n1 0 0\n1 1 0\n",
+        )
+        .unwrap();
+        fs::write(
+            input_dir.join("lex.csv"),
+            "tokyo,1,1,100,NOUN,PROPER,*,*,*,*,tokyo,TOKYO,TOKYO\nrun,1,1,200,VERB,*,*,*,*,*,run,RUN,RUN\n",
+        )
+        .unwrap();
+
+        let deflate_dir = base_dir.join("deflate");
+        IpadicBuilder::new()
+            .build_dictionary(&input_dir, &deflate_dir)
+            .unwrap();
+        let zstd_dir = base_dir.join("zstd");
+        IpadicBuilder::new()
+            .compress_algorithm(Algorithm::Zstd)
+            .build_dictionary(&input_dir, &zstd_dir)
+            .unwrap();
+
+        // the algorithm is detected from the header of the files
+        assert!(matches!(
+            DictionaryLoader::compression_algorithm(deflate_dir.clone()).unwrap(),
+            Some(Algorithm::Deflate)
+        ));
+        assert!(matches!(
+            DictionaryLoader::compression_algorithm(zstd_dir.clone()).unwrap(),
+            Some(Algorithm::Zstd)
+        ));
+
+        let tokenize = |dictionary: &Dictionary, text: &str| -> Vec<(usize, Vec<String>)> {
+            let mut lattice = Lattice::default();
+            lattice.set_text(
+                &dictionary.dict,
+                &None,
+                &UserWordOverlay::default(),
+                &dictionary.char_definitions,
+                &dictionary.unknown_dictionary,
+                text,
+                &Mode::Normal,
+                1024,
+                &HashMap::new(),
+            );
+            lattice.calculate_path_costs(
+                &dictionary.cost_matrix,
+                &Mode::Normal,
+                &DictionaryCostModel,
+            );
+            lattice
+                .tokens_offset()
+                .into_iter()
+                .map(|(offset, word_id)| {
+                    let details = if word_id.is_unknown() {
+                        vec!["UNK".to_string()]
+                    } else {
+                        dictionary
+                            .word_details(word_id.0 as usize)
+                            .unwrap()
+                            .iter()
+                            .map(|detail| detail.to_string())
+                            .collect()
+                    };
+                    (offset, details)
+                })
+                .collect()
+        };
+
+        let deflate_dictionary = DictionaryLoader::load_dictionary(deflate_dir).unwrap();
+        let zstd_dictionary = DictionaryLoader::load_dictionary(zstd_dir).unwrap();
+        let text = "tokyo run osaka";
+        let tokens = tokenize(&zstd_dictionary, text);
+        assert_eq!(tokens, tokenize(&deflate_dictionary, text));
+        assert_eq!(tokens[0].0, 0);
+        assert_eq!(tokens[0].1[0], "NOUN");
+
+        fs::remove_dir_all(&base_dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_user_dictionary_from_bin() {
+        for kind in ["ipadic", "unidic", "ko-dic", "cc-cedict"] {
+            let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("../resources")
+                .join(format!("{}_simple_userdic.bin", kind));
+            let user_dict = DictionaryLoader::load_user_dictionary_from_bin(path).unwrap();
+            assert!(!user_dict.words_idx_data.is_empty(), "{}", kind);
+        }
+    }
+
+    #[test]
+    fn test_load_dictionary_info() {
+        let base_dir = std::env::temp_dir().join("lindera-dictionary-test-load-dictionary-info");
+        let input_dir = base_dir.join("input");
+        let output_dir = base_dir.join("output");
+        fs::create_dir_all(&input_dir).unwrap();
+        fs::write(
+            input_dir.join("char.def"),
+            "DEFAULT 0 1 0\nALPHA 1 1 0\n0x0041..0x005A ALPHA\n0x0061..0x007A ALPHA\n",
+        )
+        .unwrap();
+        fs::write(
+            input_dir.join("unk.def"),
+            "DEFAULT,0,0,1000,SYMBOL,*,*,*,*,*,*\nALPHA,1,1,1000,NOUN,*,*,*,*,*,*\n",
+        )
+        .unwrap();
+        fs::write(
+            input_dir.join("matrix.def"),
+            "2 2\n0 0 0\n0 1 0\n1 0 0\n1 1 0\n",
+        )
+        .unwrap();
+        fs::write(
+            input_dir.join("lex.csv"),
+            "tokyo,1,1,100,NOUN,PROPER,*,*,*,*,tokyo,TOKYO,TOKYO\nosaka,1,1,100,NOUN,PROPER,*,*,*,*,osaka,OSAKA,OSAKA\nrun,1,1,200,VERB,*,*,*,*,*,run,RUN,RUN\n",
+        )
+        .unwrap();
+        IpadicBuilder::new()
+            .build_dictionary(&input_dir, &output_dir)
+            .unwrap();
+
+        let info = DictionaryLoader::load_dictionary_info(output_dir.clone()).unwrap();
+        assert_eq!(info.kind.as_deref(), Some("ipadic"));
+        assert_eq!(info.entries, 3);
+        assert_eq!(info.fields, 9);
+        assert_eq!(info.forward_size, 2);
+        assert_eq!(info.backward_size, 2);
+
+        let algorithm = DictionaryLoader::compression_algorithm(output_dir.clone()).unwrap();
+        assert_eq!(algorithm.is_some(), cfg!(feature = "compress"));
+
+        // the kind is not recorded in a dictionary built by other tools
+        fs::remove_file(output_dir.join(DICTIONARY_KIND_FILE)).unwrap();
+        let info = DictionaryLoader::load_dictionary_info(output_dir).unwrap();
+        assert_eq!(info.kind, None);
+        assert_eq!(info.entries, 3);
+
+        fs::remove_dir_all(&base_dir).unwrap();
+    }
 }
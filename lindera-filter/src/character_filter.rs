@@ -1,6 +1,8 @@
+pub mod combining_sound_mark;
 pub mod japanese_iteration_mark;
 pub mod mapping;
 pub mod regex;
+pub mod trim_whitespace;
 pub mod unicode_normalize;
 
 use serde_json::Value;
@@ -9,6 +11,9 @@ use std::ops::Deref;
 use lindera_core::error::LinderaErrorKind;
 use lindera_core::LinderaResult;
 
+use crate::character_filter::combining_sound_mark::{
+    CombiningSoundMarkCharacterFilter, COMBINING_SOUND_MARK_CHARACTER_FILTER_NAME,
+};
 use crate::character_filter::japanese_iteration_mark::{
     JapaneseIterationMarkCharacterFilter, JapaneseIterationMarkCharacterFilterConfig,
     JAPANESE_ITERATION_MARK_CHARACTER_FILTER_NAME,
@@ -19,11 +24,14 @@ use crate::character_filter::mapping::{
 use crate::character_filter::regex::{
     RegexCharacterFilter, RegexCharacterFilterConfig, REGEX_CHARACTER_FILTER_NAME,
 };
+use crate::character_filter::trim_whitespace::{
+    TrimWhitespaceCharacterFilter, TRIM_WHITESPACE_CHARACTER_FILTER_NAME,
+};
 use crate::character_filter::unicode_normalize::{
     UnicodeNormalizeCharacterFilter, UnicodeNormalizeCharacterFilterConfig,
     UNICODE_NORMALIZE_CHARACTER_FILTER_NAME,
 };
-use crate::parse_cli_flag;
+use crate::{filter_args, parse_cli_flag, FilterDescription};
 
 pub trait CharacterFilter: 'static + Send + Sync + CharacterFilterClone {
     fn name(&self) -> &str;
@@ -106,8 +114,48 @@ pub fn correct_offset(offset: usize, offsets: &[usize], diffs: &[i64], text_len:
 pub struct CharacterFilterLoader {}
 
 impl CharacterFilterLoader {
+    /// The character filters that `load_from_value` can build, in alphabetical order.
+    pub const FILTERS: &'static [FilterDescription] = &[
+        FilterDescription {
+            name: COMBINING_SOUND_MARK_CHARACTER_FILTER_NAME,
+            description: "Compose a kana followed by a voiced or semi-voiced sound mark into the precomposed kana",
+            example_args: "",
+        },
+        FilterDescription {
+            name: JAPANESE_ITERATION_MARK_CHARACTER_FILTER_NAME,
+            description: "Expand the Japanese iteration marks (odoriji) of kanji and kana",
+            example_args: r#"{"normalize_kanji":true,"normalize_kana":true}"#,
+        },
+        FilterDescription {
+            name: MAPPING_CHARACTER_FILTER_NAME,
+            description: "Replace characters with the specified character mappings",
+            example_args: r#"{"mapping":{"ｱ":"ア","ｲ":"イ"}}"#,
+        },
+        FilterDescription {
+            name: REGEX_CHARACTER_FILTER_NAME,
+            description: "Replace the matches of a regular expression with the replacement",
+            example_args: r#"{"pattern":"\\s{2,}","replacement":" "}"#,
+        },
+        FilterDescription {
+            name: TRIM_WHITESPACE_CHARACTER_FILTER_NAME,
+            description: "Trim the whitespace at both ends of the text and collapse runs of whitespace",
+            example_args: "",
+        },
+        FilterDescription {
+            name: UNICODE_NORMALIZE_CHARACTER_FILTER_NAME,
+            description: "Normalize the text with the Unicode normalization form nfc, nfd, nfkc or nfkd",
+            example_args: r#"{"kind":"nfkc"}"#,
+        },
+    ];
+
     pub fn load_from_value(kind: &str, value: &Value) -> LinderaResult<BoxCharacterFilter> {
+        let args = filter_args(value);
+        let value = args.as_ref();
+
         let character_filter = match kind {
+            COMBINING_SOUND_MARK_CHARACTER_FILTER_NAME => {
+                BoxCharacterFilter::from(CombiningSoundMarkCharacterFilter::new())
+            }
             JAPANESE_ITERATION_MARK_CHARACTER_FILTER_NAME => {
                 BoxCharacterFilter::from(JapaneseIterationMarkCharacterFilter::new(
                     JapaneseIterationMarkCharacterFilterConfig::from_value(value)?,
@@ -121,6 +169,9 @@ impl CharacterFilterLoader {
                 let config = RegexCharacterFilterConfig::from_value(value)?;
                 BoxCharacterFilter::from(RegexCharacterFilter::new(config)?)
             }
+            TRIM_WHITESPACE_CHARACTER_FILTER_NAME => {
+                BoxCharacterFilter::from(TrimWhitespaceCharacterFilter::new())
+            }
             UNICODE_NORMALIZE_CHARACTER_FILTER_NAME => {
                 let config = UnicodeNormalizeCharacterFilterConfig::from_value(value)?;
                 BoxCharacterFilter::from(UnicodeNormalizeCharacterFilter::new(config))
@@ -145,6 +196,24 @@ impl CharacterFilterLoader {
 
 #[cfg(test)]
 mod tests {
+    use crate::character_filter::CharacterFilterLoader;
+
+    #[test]
+    fn test_character_filter_loader_filters() {
+        for filter in CharacterFilterLoader::FILTERS {
+            let character_filter =
+                CharacterFilterLoader::load_from_cli_flag(&filter.example_cli_flag()).unwrap();
+            assert_eq!(character_filter.name(), filter.name);
+        }
+
+        // Every character filter module is listed.
+        let modules = include_str!("character_filter.rs")
+            .lines()
+            .filter(|line| line.starts_with("pub mod "))
+            .count();
+        assert_eq!(CharacterFilterLoader::FILTERS.len(), modules);
+    }
+
     #[test]
     fn test_correct_offset() {
         let text = "ABCDEFG";
@@ -0,0 +1,161 @@
+use unicode_normalization::char::compose;
+
+use lindera_core::LinderaResult;
+
+use crate::character_filter::{add_offset_diff, CharacterFilter};
+use crate::check_args;
+
+pub const COMBINING_SOUND_MARK_CHARACTER_FILTER_NAME: &str = "combining_sound_mark";
+
+/// Return the combining form of a voiced (dakuten) or semi-voiced (handakuten) sound mark,
+/// or `None` if the character is not a sound mark.
+fn combining_sound_mark(c: char) -> Option<char> {
+    match c {
+        '\u{3099}' | '\u{309B}' => Some('\u{3099}'),
+        '\u{309A}' | '\u{309C}' => Some('\u{309A}'),
+        _ => None,
+    }
+}
+
+/// Character filter that composes a kana followed by a voiced or semi-voiced sound mark into
+/// the precomposed kana, e.g. `か゛` into `が` or `は゜` into `ぱ`.
+/// Both the combining marks (U+3099, U+309A) and their spacing forms (U+309B, U+309C) are
+/// composed. A sound mark that cannot be composed with the preceding character is kept as is.
+///
+#[derive(Clone, Debug)]
+pub struct CombiningSoundMarkCharacterFilter {}
+
+impl CombiningSoundMarkCharacterFilter {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        check_args(data)?;
+
+        Ok(Self::new())
+    }
+}
+
+impl Default for CombiningSoundMarkCharacterFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CharacterFilter for CombiningSoundMarkCharacterFilter {
+    fn name(&self) -> &'static str {
+        COMBINING_SOUND_MARK_CHARACTER_FILTER_NAME
+    }
+
+    fn apply(&self, text: &str) -> LinderaResult<(String, Vec<usize>, Vec<i64>)> {
+        let mut offsets: Vec<usize> = Vec::new();
+        let mut diffs: Vec<i64> = Vec::new();
+        let mut new_text = String::with_capacity(text.len());
+
+        let mut char_indices = text.char_indices().peekable();
+        while let Some((start, c)) = char_indices.next() {
+            let composed = char_indices
+                .peek()
+                .and_then(|&(_, next_c)| combining_sound_mark(next_c))
+                .and_then(|mark| compose(c, mark));
+
+            let composed = match composed {
+                Some(composed) => composed,
+                None => {
+                    new_text.push(c);
+                    continue;
+                }
+            };
+
+            // Consume the sound mark.
+            let (mark_start, mark) = char_indices.next().unwrap();
+            let end = mark_start + mark.len_utf8();
+            new_text.push(composed);
+
+            let diff_len = (end - start - composed.len_utf8()) as i64;
+            let prev_diff = *diffs.last().unwrap_or(&0);
+            let offset = (end as i64 - diff_len - prev_diff) as usize;
+            add_offset_diff(&mut offsets, &mut diffs, offset, prev_diff + diff_len);
+        }
+
+        Ok((new_text, offsets, diffs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::character_filter::combining_sound_mark::CombiningSoundMarkCharacterFilter;
+    use crate::character_filter::{correct_offset, CharacterFilter};
+
+    #[test]
+    fn test_combining_sound_mark_character_filter_from_slice() {
+        let config_str = r#"
+        {}
+        "#;
+        let result = CombiningSoundMarkCharacterFilter::from_slice(config_str.as_bytes());
+        assert_eq!(true, result.is_ok());
+    }
+
+    #[test]
+    fn test_combining_sound_mark_character_filter_apply() {
+        let filter = CombiningSoundMarkCharacterFilter::new();
+
+        {
+            let text = "か゛き";
+            let (filterd_text, offsets, diffs) = filter.apply(text).unwrap();
+            assert_eq!("がき", filterd_text);
+            assert_eq!(vec![3], offsets);
+            assert_eq!(vec![3], diffs);
+            let start = 0;
+            let end = 3;
+            assert_eq!("が", &filterd_text[start..end]);
+            let correct_start = correct_offset(start, &offsets, &diffs, filterd_text.len());
+            let correct_end = correct_offset(end, &offsets, &diffs, filterd_text.len());
+            assert_eq!(0, correct_start);
+            assert_eq!(6, correct_end);
+            assert_eq!("か゛", &text[correct_start..correct_end]);
+
+            let start = 3;
+            let end = 6;
+            assert_eq!("き", &filterd_text[start..end]);
+            let correct_start = correct_offset(start, &offsets, &diffs, filterd_text.len());
+            let correct_end = correct_offset(end, &offsets, &diffs, filterd_text.len());
+            assert_eq!(6, correct_start);
+            assert_eq!(9, correct_end);
+            assert_eq!("き", &text[correct_start..correct_end]);
+        }
+
+        {
+            let text = "か\u{3099}き";
+            let (filterd_text, offsets, diffs) = filter.apply(text).unwrap();
+            assert_eq!("がき", filterd_text);
+            assert_eq!(vec![3], offsets);
+            assert_eq!(vec![3], diffs);
+        }
+
+        {
+            let text = "は゜ンと、ハ\u{309A}ン";
+            let (filterd_text, offsets, diffs) = filter.apply(text).unwrap();
+            assert_eq!("ぱンと、パン", filterd_text);
+            assert_eq!(vec![3, 15], offsets);
+            assert_eq!(vec![3, 6], diffs);
+            let tokens = [(0, 3), (3, 6), (6, 9), (9, 12), (12, 15), (15, 18)];
+            let surfaces = ["は゜", "ン", "と", "、", "ハ\u{309A}", "ン"];
+            for ((start, end), surface) in tokens.iter().zip(surfaces.iter()) {
+                let correct_start = correct_offset(*start, &offsets, &diffs, filterd_text.len());
+                let correct_end = correct_offset(*end, &offsets, &diffs, filterd_text.len());
+                assert_eq!(*surface, &text[correct_start..correct_end]);
+            }
+        }
+
+        {
+            // sound marks that cannot be composed are kept as is
+            let text = "あ゛が゛\u{3099}";
+            let (filterd_text, offsets, diffs) = filter.apply(text).unwrap();
+            assert_eq!(text, filterd_text);
+            assert!(offsets.is_empty());
+            assert!(diffs.is_empty());
+        }
+    }
+}
@@ -6,7 +6,7 @@ use serde_json::Value;
 use lindera_core::error::LinderaErrorKind;
 use lindera_core::LinderaResult;
 
-use crate::character_filter::CharacterFilter;
+use crate::character_filter::{add_offset_diff, CharacterFilter};
 
 pub const JAPANESE_ITERATION_MARK_CHARACTER_FILTER_NAME: &str = "japanese_iteration_mark";
 
@@ -18,44 +18,60 @@ const KATAKANA_DAKUON_ITERATION_MARK: char = 'ヾ';
 
 fn hiragana_add_dakuon(c: &char) -> char {
     let codepoint = *c as u32;
-    // Unsafe code is okay, because we know that all the characters within these ranges exist.
-    match codepoint {
-        0x304b..=0x3062 if codepoint % 2 == 1 => unsafe { char::from_u32_unchecked(codepoint + 1) },
-        0x3064..=0x3069 if codepoint % 2 == 0 => unsafe { char::from_u32_unchecked(codepoint + 1) },
-        0x306f..=0x307d if codepoint % 3 == 0 => unsafe { char::from_u32_unchecked(codepoint + 1) },
-        _ => *c,
+    let has_voiced = match codepoint {
+        0x304b..=0x3062 => !codepoint.is_multiple_of(2),
+        0x3064..=0x3069 => codepoint.is_multiple_of(2),
+        0x306f..=0x307d => codepoint.is_multiple_of(3),
+        _ => false,
+    };
+    if !has_voiced {
+        return *c;
     }
+    // Unsafe code is okay, because we know that all the characters within these ranges exist.
+    unsafe { char::from_u32_unchecked(codepoint + 1) }
 }
 
 fn hiragana_remove_dakuon(c: &char) -> char {
     let codepoint = *c as u32;
-    // Unsafe code is okay, because we know that all the characters within these ranges exist.
-    match codepoint {
-        0x304b..=0x3062 if codepoint % 2 == 0 => unsafe { char::from_u32_unchecked(codepoint - 1) },
-        0x3064..=0x3069 if codepoint % 2 == 1 => unsafe { char::from_u32_unchecked(codepoint - 1) },
-        0x306f..=0x307d if codepoint % 3 == 1 => unsafe { char::from_u32_unchecked(codepoint - 1) },
-        _ => *c,
+    let is_voiced = match codepoint {
+        0x304b..=0x3062 => codepoint.is_multiple_of(2),
+        0x3064..=0x3069 => !codepoint.is_multiple_of(2),
+        0x306f..=0x307d => codepoint % 3 == 1,
+        _ => false,
+    };
+    if !is_voiced {
+        return *c;
     }
+    // Unsafe code is okay, because we know that all the characters within these ranges exist.
+    unsafe { char::from_u32_unchecked(codepoint - 1) }
 }
 
 fn katakana_add_dakuon(c: &char) -> char {
     let codepoint = *c as u32;
-    match codepoint {
-        0x30ab..=0x30c2 if codepoint % 2 == 1 => unsafe { char::from_u32_unchecked(codepoint + 1) },
-        0x30c4..=0x30c9 if codepoint % 2 == 0 => unsafe { char::from_u32_unchecked(codepoint + 1) },
-        0x30cf..=0x30dd if codepoint % 3 == 0 => unsafe { char::from_u32_unchecked(codepoint + 1) },
-        _ => *c,
+    let has_voiced = match codepoint {
+        0x30ab..=0x30c2 => !codepoint.is_multiple_of(2),
+        0x30c4..=0x30c9 => codepoint.is_multiple_of(2),
+        0x30cf..=0x30dd => codepoint.is_multiple_of(3),
+        _ => false,
+    };
+    if !has_voiced {
+        return *c;
     }
+    unsafe { char::from_u32_unchecked(codepoint + 1) }
 }
 
 fn katakana_remove_dakuon(c: &char) -> char {
     let codepoint = *c as u32;
-    match codepoint {
-        0x30ab..=0x30c2 if codepoint % 2 == 0 => unsafe { char::from_u32_unchecked(codepoint - 1) },
-        0x30c4..=0x30c9 if codepoint % 2 == 1 => unsafe { char::from_u32_unchecked(codepoint - 1) },
-        0x30cf..=0x30dd if codepoint % 3 == 1 => unsafe { char::from_u32_unchecked(codepoint - 1) },
-        _ => *c,
+    let is_voiced = match codepoint {
+        0x30ab..=0x30c2 => codepoint.is_multiple_of(2),
+        0x30c4..=0x30c9 => !codepoint.is_multiple_of(2),
+        0x30cf..=0x30dd => codepoint % 3 == 1,
+        _ => false,
+    };
+    if !is_voiced {
+        return *c;
     }
+    unsafe { char::from_u32_unchecked(codepoint - 1) }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
@@ -155,7 +171,11 @@ impl CharacterFilter for JapaneseIterationMarkCharacterFilter {
     }
 
     fn apply(&self, text: &str) -> LinderaResult<(String, Vec<usize>, Vec<i64>)> {
+        let mut offsets: Vec<usize> = Vec::new();
+        let mut diffs: Vec<i64> = Vec::new();
+
         let mut filterd_text = String::with_capacity(text.len());
+        let mut input_start = 0;
 
         let text_chars = text.chars().collect::<Vec<char>>();
         let mut iter_marks = BTreeMap::new();
@@ -174,19 +194,78 @@ impl CharacterFilter for JapaneseIterationMarkCharacterFilter {
                 }
                 _ => {
                     if !iter_marks.is_empty() {
-                        filterd_text.push_str(&self.normalize(&iter_marks, &text_chars));
+                        let normalized = self.normalize(&iter_marks, &text_chars);
+                        push_normalized(
+                            &iter_marks,
+                            &normalized,
+                            &mut filterd_text,
+                            &mut input_start,
+                            &mut offsets,
+                            &mut diffs,
+                        );
                         iter_marks.clear();
                     }
                     filterd_text.push(*c);
+                    input_start += c.len_utf8();
                 }
             }
         }
 
         if !iter_marks.is_empty() {
-            filterd_text.push_str(&self.normalize(&iter_marks, &text_chars));
+            let normalized = self.normalize(&iter_marks, &text_chars);
+            push_normalized(
+                &iter_marks,
+                &normalized,
+                &mut filterd_text,
+                &mut input_start,
+                &mut offsets,
+                &mut diffs,
+            );
         }
 
-        Ok((filterd_text, Vec::new(), Vec::new()))
+        Ok((filterd_text, offsets, diffs))
+    }
+}
+
+/// Push the characters that replace the iteration marks, recording the offset corrections
+/// for the characters whose byte length differs from that of the iteration mark.
+fn push_normalized(
+    iter_marks: &BTreeMap<usize, &char>,
+    normalized: &str,
+    filterd_text: &mut String,
+    input_start: &mut usize,
+    offsets: &mut Vec<usize>,
+    diffs: &mut Vec<i64>,
+) {
+    for (iter_mark, replacement) in iter_marks.values().zip(normalized.chars()) {
+        let input_len = iter_mark.len_utf8();
+        let replacement_len = replacement.len_utf8();
+        let diff_len = input_len as i64 - replacement_len as i64;
+        let input_offset = *input_start + input_len;
+
+        if diff_len != 0 {
+            let prev_diff = *diffs.last().unwrap_or(&0);
+
+            if diff_len > 0 {
+                // Replacement is shorter than matched surface.
+                let offset = (input_offset as i64 - diff_len - prev_diff) as usize;
+                let diff = prev_diff + diff_len;
+                add_offset_diff(offsets, diffs, offset, diff);
+            } else {
+                // Replacement is longer than matched surface.
+                let output_start = (input_offset as i64 + -prev_diff) as usize;
+                for extra_idx in 0..diff_len.unsigned_abs() as usize {
+                    let offset = output_start + extra_idx;
+                    let diff = prev_diff - extra_idx as i64 - 1;
+                    add_offset_diff(offsets, diffs, offset, diff);
+                }
+            }
+        }
+
+        filterd_text.push(replacement);
+
+        // move start offset
+        *input_start += input_len;
     }
 }
 
@@ -200,26 +279,26 @@ mod tests {
         hiragana_add_dakuon, hiragana_remove_dakuon, katakana_add_dakuon, katakana_remove_dakuon,
         JapaneseIterationMarkCharacterFilter, JapaneseIterationMarkCharacterFilterConfig,
     };
-    use crate::character_filter::CharacterFilter;
+    use crate::character_filter::{correct_offset, CharacterFilter};
 
     fn hiragana_has_dakuon(c: &char) -> bool {
         let codepoint = *c as u32;
         // か…ぢ
-        (codepoint >= 0x304b && codepoint <= 0x3062 && codepoint % 2 == 0) ||
+        ((0x304b..=0x3062).contains(&codepoint) && codepoint.is_multiple_of(2)) ||
         // つ…ど
-        (codepoint >= 0x3064 && codepoint <= 0x3069 && codepoint % 2 == 1) ||
+        ((0x3064..=0x3069).contains(&codepoint) && !codepoint.is_multiple_of(2)) ||
         // は…ぽ
-        (codepoint >= 0x306f && codepoint <= 0x307d && codepoint % 3 == 1)
+        ((0x306f..=0x307d).contains(&codepoint) && codepoint % 3 == 1)
     }
 
     fn katakana_has_dakuon(c: &char) -> bool {
         let codepoint = *c as u32;
         // カ…ヂ
-        (codepoint >= 0x30ab && codepoint <= 0x30c2 && codepoint % 2 == 0) ||
+        ((0x30ab..=0x30c2).contains(&codepoint) && codepoint.is_multiple_of(2)) ||
         // ツ…ド
-        (codepoint >= 0x30c4 && codepoint <= 0x30c9 && codepoint % 2 == 1) ||
+        ((0x30c4..=0x30c9).contains(&codepoint) && !codepoint.is_multiple_of(2)) ||
         // ハ…ポ
-        (codepoint >= 0x30cf && codepoint <= 0x30dd && codepoint % 3 == 1)
+        ((0x30cf..=0x30dd).contains(&codepoint) && codepoint % 3 == 1)
     }
 
     static HIRAGANA_DAKUON_MAP: Lazy<HashMap<char, char>> = Lazy::new(|| {
@@ -461,6 +540,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_japanese_iteration_mark_character_filter_apply_offsets() {
+        let config_str = r#"
+        {
+            "normalize_kanji": true,
+            "normalize_kana": true
+        }
+        "#;
+        let filter =
+            JapaneseIterationMarkCharacterFilter::from_slice(config_str.as_bytes()).unwrap();
+
+        {
+            // The replacement is shorter than the iteration mark.
+            let text = "?ゝ";
+            let (filterd_text, offsets, diffs) = filter.apply(text).unwrap();
+            assert_eq!("??", filterd_text);
+            assert_eq!(vec![2], offsets);
+            assert_eq!(vec![2], diffs);
+            let start = 1;
+            let end = 2;
+            assert_eq!("?", &filterd_text[start..end]);
+            let correct_start = correct_offset(start, &offsets, &diffs, filterd_text.len());
+            let correct_end = correct_offset(end, &offsets, &diffs, filterd_text.len());
+            assert_eq!(1, correct_start);
+            assert_eq!(4, correct_end);
+            assert_eq!("ゝ", &text[correct_start..correct_end]);
+        }
+
+        {
+            // The replacement is longer than the iteration mark.
+            let text = "𠮟々る";
+            let (filterd_text, offsets, diffs) = filter.apply(text).unwrap();
+            assert_eq!("𠮟𠮟る", filterd_text);
+            assert_eq!(vec![7], offsets);
+            assert_eq!(vec![-1], diffs);
+            let start = 4;
+            let end = 8;
+            assert_eq!("𠮟", &filterd_text[start..end]);
+            let correct_start = correct_offset(start, &offsets, &diffs, filterd_text.len());
+            let correct_end = correct_offset(end, &offsets, &diffs, filterd_text.len());
+            assert_eq!(4, correct_start);
+            assert_eq!(7, correct_end);
+            assert_eq!("々", &text[correct_start..correct_end]);
+            let start = 8;
+            let end = 11;
+            assert_eq!("る", &filterd_text[start..end]);
+            let correct_start = correct_offset(start, &offsets, &diffs, filterd_text.len());
+            let correct_end = correct_offset(end, &offsets, &diffs, filterd_text.len());
+            assert_eq!(7, correct_start);
+            assert_eq!(10, correct_end);
+            assert_eq!("る", &text[correct_start..correct_end]);
+        }
+    }
+
     #[test]
     fn test_katakana_has_dakuon() {
         for (k, v) in KATAKANA_DAKUON_MAP.iter() {
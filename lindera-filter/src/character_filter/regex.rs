@@ -63,13 +63,28 @@ impl CharacterFilter for RegexCharacterFilter {
     fn apply(&self, text: &str) -> LinderaResult<(String, Vec<usize>, Vec<i64>)> {
         let mut offsets: Vec<usize> = Vec::new();
         let mut diffs: Vec<i64> = Vec::new();
-
-        self.regex.find_iter(text).for_each(|mat| {
+        let mut new_text = String::with_capacity(text.len());
+        let mut last_end = 0;
+
+        for caps in self.regex.captures_iter(text) {
+            let mat = match caps.get(0) {
+                Some(mat) => mat,
+                None => continue,
+            };
             let input_start = mat.start();
             let input_text = mat.as_str();
             let input_len = input_text.len();
-            let replacement_text = self.config.replacement.as_str();
+
+            // Expand capture group references such as `$1` so that the offsets are
+            // calculated from the text that is actually inserted.
+            let mut replacement_text = String::new();
+            caps.expand(&self.config.replacement, &mut replacement_text);
             let replacement_len = replacement_text.len();
+
+            new_text.push_str(&text[last_end..input_start]);
+            new_text.push_str(&replacement_text);
+            last_end = mat.end();
+
             let diff_len = input_len as i64 - replacement_len as i64;
             let input_offset = input_start + input_len;
 
@@ -91,12 +106,8 @@ impl CharacterFilter for RegexCharacterFilter {
                     }
                 }
             }
-        });
-
-        let new_text = self
-            .regex
-            .replace_all(text, &self.config.replacement)
-            .to_string();
+        }
+        new_text.push_str(&text[last_end..]);
 
         Ok((new_text, offsets, diffs))
     }
@@ -179,5 +190,59 @@ mod tests {
             assert_eq!(7, correct_end);
             assert_eq!("b", &text[correct_start..correct_end]);
         }
+
+        {
+            let config_str = r#"
+            {
+                "pattern": "Lindera",
+                "replacement": "リンデラ"
+            }
+            "#;
+            let filter = RegexCharacterFilter::from_slice(config_str.as_bytes()).unwrap();
+            let text = "Linderaは形態素解析器です。";
+            let (filterd_text, offsets, diffs) = filter.apply(text).unwrap();
+            assert_eq!("リンデラは形態素解析器です。", filterd_text);
+            assert_eq!(vec![7, 8, 9, 10, 11], offsets);
+            assert_eq!(vec![-1, -2, -3, -4, -5], diffs);
+            let start = 0;
+            let end = 12;
+            assert_eq!("リンデラ", &filterd_text[start..end]);
+            let correct_start = correct_offset(start, &offsets, &diffs, filterd_text.len());
+            let correct_end = correct_offset(end, &offsets, &diffs, filterd_text.len());
+            assert_eq!(0, correct_start);
+            assert_eq!(7, correct_end);
+            assert_eq!("Lindera", &text[correct_start..correct_end]);
+            let start = 12;
+            let end = 15;
+            assert_eq!("は", &filterd_text[start..end]);
+            let correct_start = correct_offset(start, &offsets, &diffs, filterd_text.len());
+            let correct_end = correct_offset(end, &offsets, &diffs, filterd_text.len());
+            assert_eq!(7, correct_start);
+            assert_eq!(10, correct_end);
+            assert_eq!("は", &text[correct_start..correct_end]);
+        }
+
+        {
+            let config_str = r#"
+            {
+                "pattern": "(\\d+)円",
+                "replacement": "${1} yen"
+            }
+            "#;
+            let filter = RegexCharacterFilter::from_slice(config_str.as_bytes()).unwrap();
+            let text = "価格は100円です";
+            let (filterd_text, offsets, diffs) = filter.apply(text).unwrap();
+            assert_eq!("価格は100 yenです", filterd_text);
+            assert_eq!(vec![15], offsets);
+            assert_eq!(vec![-1], diffs);
+            let start = 16;
+            let end = 22;
+            assert_eq!("です", &filterd_text[start..end]);
+            let correct_start = correct_offset(start, &offsets, &diffs, filterd_text.len());
+            let correct_end = correct_offset(end, &offsets, &diffs, filterd_text.len());
+            assert_eq!(15, correct_start);
+            assert_eq!(21, correct_end);
+            assert_eq!("です", &text[correct_start..correct_end]);
+        }
     }
 }
@@ -0,0 +1,161 @@
+use lindera_core::LinderaResult;
+
+use crate::character_filter::{add_offset_diff, CharacterFilter};
+use crate::check_args;
+
+pub const TRIM_WHITESPACE_CHARACTER_FILTER_NAME: &str = "trim_whitespace";
+
+/// Character filter that removes the whitespace at the start and end of the text and collapses
+/// each run of two or more whitespace characters into a single space.
+/// Whitespace is any character with the Unicode `White_Space` property, such as `\t`, `\n`
+/// or the ideographic space `　`. A single whitespace character is kept as is.
+///
+#[derive(Clone, Debug)]
+pub struct TrimWhitespaceCharacterFilter {}
+
+impl TrimWhitespaceCharacterFilter {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        check_args(data)?;
+
+        Ok(Self::new())
+    }
+}
+
+impl Default for TrimWhitespaceCharacterFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CharacterFilter for TrimWhitespaceCharacterFilter {
+    fn name(&self) -> &'static str {
+        TRIM_WHITESPACE_CHARACTER_FILTER_NAME
+    }
+
+    fn apply(&self, text: &str) -> LinderaResult<(String, Vec<usize>, Vec<i64>)> {
+        let mut offsets: Vec<usize> = Vec::new();
+        let mut diffs: Vec<i64> = Vec::new();
+        let mut new_text = String::with_capacity(text.len());
+        let mut last_end = 0;
+
+        let mut char_indices = text.char_indices().peekable();
+        while let Some((start, c)) = char_indices.next() {
+            if !c.is_whitespace() {
+                continue;
+            }
+
+            // Find the end of the run of whitespace.
+            let mut end = start + c.len_utf8();
+            let mut run_len = 1;
+            while let Some(&(next_start, next_c)) = char_indices.peek() {
+                if !next_c.is_whitespace() {
+                    break;
+                }
+                end = next_start + next_c.len_utf8();
+                run_len += 1;
+                char_indices.next();
+            }
+
+            let is_leading = start == 0;
+            let is_trailing = end == text.len();
+            let replacement = if is_leading || is_trailing {
+                ""
+            } else if run_len > 1 {
+                " "
+            } else {
+                // A single whitespace character is kept as is.
+                continue;
+            };
+
+            new_text.push_str(&text[last_end..start]);
+            new_text.push_str(replacement);
+            last_end = end;
+
+            // The end of the filtered text maps to the end of the last non-whitespace
+            // character, so the removal of the trailing whitespace needs no correction.
+            if is_trailing && !is_leading {
+                continue;
+            }
+
+            let diff_len = (end - start - replacement.len()) as i64;
+            let prev_diff = *diffs.last().unwrap_or(&0);
+            let offset = (end as i64 - diff_len - prev_diff) as usize;
+            add_offset_diff(&mut offsets, &mut diffs, offset, prev_diff + diff_len);
+        }
+        new_text.push_str(&text[last_end..]);
+
+        Ok((new_text, offsets, diffs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::character_filter::trim_whitespace::TrimWhitespaceCharacterFilter;
+    use crate::character_filter::{correct_offset, CharacterFilter};
+
+    #[test]
+    fn test_trim_whitespace_character_filter_from_slice() {
+        let config_str = r#"
+        {}
+        "#;
+        let result = TrimWhitespaceCharacterFilter::from_slice(config_str.as_bytes());
+        assert_eq!(true, result.is_ok());
+    }
+
+    #[test]
+    fn test_trim_whitespace_character_filter_apply() {
+        let filter = TrimWhitespaceCharacterFilter::new();
+
+        {
+            let text = "東京    都";
+            let (filterd_text, offsets, diffs) = filter.apply(text).unwrap();
+            assert_eq!("東京 都", filterd_text);
+            assert_eq!(vec![7], offsets);
+            assert_eq!(vec![3], diffs);
+            let start = 7;
+            let end = 10;
+            assert_eq!("都", &filterd_text[start..end]);
+            let correct_start = correct_offset(start, &offsets, &diffs, filterd_text.len());
+            let correct_end = correct_offset(end, &offsets, &diffs, filterd_text.len());
+            assert_eq!(10, correct_start);
+            assert_eq!(13, correct_end);
+            assert_eq!("都", &text[correct_start..correct_end]);
+
+            // the token before the collapsed run is unchanged
+            assert_eq!(0, correct_offset(0, &offsets, &diffs, filterd_text.len()));
+            assert_eq!(6, correct_offset(6, &offsets, &diffs, filterd_text.len()));
+        }
+
+        {
+            let text = " \t東京\u{3000}\u{3000}都 に 行く\n";
+            let (filterd_text, offsets, diffs) = filter.apply(text).unwrap();
+            assert_eq!("東京 都 に 行く", filterd_text);
+            let tokens = [(0, 6), (7, 10), (11, 14), (15, 21)];
+            let surfaces = ["東京", "都", "に", "行く"];
+            for ((start, end), surface) in tokens.iter().zip(surfaces.iter()) {
+                assert_eq!(*surface, &filterd_text[*start..*end]);
+                let correct_start = correct_offset(*start, &offsets, &diffs, filterd_text.len());
+                let correct_end = correct_offset(*end, &offsets, &diffs, filterd_text.len());
+                assert_eq!(*surface, &text[correct_start..correct_end]);
+            }
+        }
+
+        {
+            let text = "東京 都";
+            let (filterd_text, offsets, diffs) = filter.apply(text).unwrap();
+            assert_eq!("東京 都", filterd_text);
+            assert!(offsets.is_empty());
+            assert!(diffs.is_empty());
+        }
+
+        {
+            let text = "   ";
+            let (filterd_text, _, _) = filter.apply(text).unwrap();
+            assert_eq!("", filterd_text);
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use serde_json::Value;
+use std::borrow::Cow;
+
+use serde_json::{Map, Value};
 
 use lindera_core::error::LinderaErrorKind;
 use lindera_core::LinderaResult;
@@ -7,11 +9,84 @@ pub mod character_filter;
 pub mod token;
 pub mod token_filter;
 
+/// The description of a filter that a loader can build, for listing the available filters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FilterDescription {
+    /// The name of the filter, as given to the loader.
+    pub name: &'static str,
+
+    /// A one-line description of the filter.
+    pub description: &'static str,
+
+    /// Example arguments of the filter in JSON, or an empty string if it has no settings.
+    pub example_args: &'static str,
+}
+
+impl FilterDescription {
+    /// Return the example as a CLI flag, in the `<name>:<args>` form accepted by
+    /// `load_from_cli_flag`.
+    pub fn example_cli_flag(&self) -> String {
+        if self.example_args.is_empty() {
+            self.name.to_string()
+        } else {
+            format!("{}:{}", self.name, self.example_args)
+        }
+    }
+}
+
 fn parse_cli_flag(cli_flag: &str) -> LinderaResult<(&str, Value)> {
     let (kind, json) = cli_flag.split_once(':').unwrap_or((cli_flag, ""));
 
-    let args: Value = serde_json::from_str(json)
-        .map_err(|err| LinderaErrorKind::Content.with_error(anyhow::anyhow!(err)))?;
+    // Filters without settings can be specified by name only (e.g. `uppercase`).
+    let args: Value = if json.trim().is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_str(json)
+            .map_err(|err| LinderaErrorKind::Content.with_error(anyhow::anyhow!(err)))?
+    };
 
     Ok((kind, args))
 }
+
+/// Check that the arguments of a filter without settings are valid JSON, like those of the
+/// other filters, although they are ignored.
+pub(crate) fn check_args(data: &[u8]) -> LinderaResult<()> {
+    serde_json::from_slice::<Value>(data)
+        .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))?;
+
+    Ok(())
+}
+
+/// Return the arguments of a filter to be deserialized into its config. The arguments of a
+/// filter specified by name only (e.g. `lowercase`) are null, and are read as an empty object,
+/// so that the filter is built with its default settings.
+fn filter_args(args: &Value) -> Cow<'_, Value> {
+    match args {
+        Value::Null => Cow::Owned(Value::Object(Map::new())),
+        _ => Cow::Borrowed(args),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use crate::{filter_args, parse_cli_flag};
+
+    #[test]
+    fn test_parse_cli_flag() {
+        let (kind, args) = parse_cli_flag("uppercase").unwrap();
+        assert_eq!(kind, "uppercase");
+        assert_eq!(args, Value::Null);
+
+        let (kind, args) = parse_cli_flag(r#"length:{"min":2}"#).unwrap();
+        assert_eq!(kind, "length");
+        assert_eq!(args["min"], 2);
+    }
+
+    #[test]
+    fn test_filter_args() {
+        assert_eq!(filter_args(&Value::Null).as_ref(), &json!({}));
+        assert_eq!(filter_args(&json!({"min": 2})).as_ref(), &json!({"min": 2}));
+    }
+}
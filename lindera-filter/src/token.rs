@@ -1,8 +1,16 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use lindera_core::word_entry::WordId;
+use lindera_dictionary::DetailSchema;
+#[cfg(feature = "cc-cedict")]
+use lindera_dictionary::DictionaryKind;
 
-#[derive(Serialize, Clone)]
+/// A token produced by the analyzer.
+///
+/// The token is serialized with the same field names as the JSON output of the CLI, and the
+/// word ID is serialized as `[id, is_system]`. The field names are part of the wire format and
+/// must not be renamed. The costs are omitted unless the tokenizer has `debug_costs` enabled.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct Token {
     /// Text content of the token.
     pub text: String,
@@ -25,4 +33,359 @@ pub struct Token {
     /// Detailes about the token.
     /// It contains metadata for tokens, such as part-of-speech information.
     pub details: Vec<String>,
+
+    /// The word cost of the token, if the tokenizer has `debug_costs` enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub word_cost: Option<i32>,
+
+    /// The connection cost from the previous token, if the tokenizer has `debug_costs` enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection_cost: Option<i32>,
+}
+
+impl Token {
+    /// Get the surface form of the token.
+    pub fn surface(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the major part-of-speech of the token.
+    /// The layout of the details is given by a `DetailSchema` or a `DictionaryKind`.
+    ///
+    /// Returns `None` if the dictionary has no such field, the field is `*`, or the first detail
+    /// is `UNK`, which the tokenizer gives to the unknown words.
+    pub fn pos(&self, schema: impl Into<DetailSchema>) -> Option<&str> {
+        self.detail(schema.into().pos.first().copied())
+    }
+
+    /// Get the base form of the token.
+    /// The layout of the details is given by a `DetailSchema` or a `DictionaryKind`.
+    ///
+    /// Returns `None` if the dictionary has no such field, the field is `*`, or the first detail
+    /// is `UNK`, which the tokenizer gives to the unknown words.
+    pub fn base_form(&self, schema: impl Into<DetailSchema>) -> Option<&str> {
+        self.detail(schema.into().base_form)
+    }
+
+    /// Get the reading of the token (pinyin for CC-CEDICT).
+    /// The layout of the details is given by a `DetailSchema` or a `DictionaryKind`.
+    ///
+    /// Returns `None` if the dictionary has no such field, the field is `*`, or the first detail
+    /// is `UNK`, which the tokenizer gives to the unknown words.
+    pub fn reading(&self, schema: impl Into<DetailSchema>) -> Option<&str> {
+        self.detail(schema.into().reading)
+    }
+
+    /// Get the pronunciation of the token.
+    /// The layout of the details is given by a `DetailSchema` or a `DictionaryKind`.
+    ///
+    /// Returns `None` if the dictionary has no such field, the field is `*`, or the first detail
+    /// is `UNK`, which the tokenizer gives to the unknown words.
+    pub fn pronunciation(&self, schema: impl Into<DetailSchema>) -> Option<&str> {
+        self.detail(schema.into().pronunciation)
+    }
+
+    /// Get the pinyin of a token tokenized with CC-CEDICT.
+    ///
+    /// Returns `None` if the first detail is `UNK` or the details are too short.
+    #[cfg(feature = "cc-cedict")]
+    pub fn pinyin(&self) -> Option<&str> {
+        self.reading(&DictionaryKind::CcCedict)
+    }
+
+    /// Get the starting position of the token in characters of `text`, the original text
+    /// that was analyzed.
+    ///
+    /// Returns `None` if the byte offsets of the token are not valid for `text`.
+    pub fn char_start(&self, text: &str) -> Option<usize> {
+        text.get(..self.byte_start).map(|s| s.chars().count())
+    }
+
+    /// Get the ending position of the token in characters of `text`, the original text
+    /// that was analyzed.
+    ///
+    /// Returns `None` if the byte offsets of the token are not valid for `text`.
+    pub fn char_end(&self, text: &str) -> Option<usize> {
+        text.get(..self.byte_end).map(|s| s.chars().count())
+    }
+
+    fn detail(&self, index: Option<usize>) -> Option<&str> {
+        if self.details.first().is_some_and(|detail| detail == "UNK") {
+            return None;
+        }
+        match self.details.get(index?) {
+            Some(detail) if detail != "*" => Some(detail.as_str()),
+            _ => None,
+        }
+    }
+}
+
+/// Reconstruct the substring of `original`, the text that was analyzed, covered by a span of
+/// tokens, e.g. to display a phrase to the user.
+///
+/// The substring runs from the start of the first token to the end of the last token, so the
+/// text of the tokens dropped by the filters and the spacing between the tokens are taken from
+/// the original text. Tokens spanning several positions (`position_length` > 1) are covered
+/// entirely, even if they end after the last token of the span.
+///
+/// If the byte offsets of the tokens are not valid for `original`, the texts of the tokens are
+/// concatenated instead.
+pub fn reconstruct(tokens: &[Token], original: &str) -> String {
+    let start = tokens.iter().map(|token| token.byte_start).min();
+    let end = tokens.iter().map(|token| token.byte_end).max();
+
+    match (start, end) {
+        (Some(start), Some(end)) => match original.get(start..end) {
+            Some(text) => text.to_string(),
+            None => tokens.iter().map(|token| token.text.as_str()).collect(),
+        },
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use lindera_core::word_entry::WordId;
+    use lindera_dictionary::{DetailSchema, DictionaryKind};
+
+    use crate::token::{reconstruct, Token};
+    use crate::token_filter::stop_words::{StopWordsTokenFilter, StopWordsTokenFilterConfig};
+    use crate::token_filter::TokenFilter;
+
+    fn build_token(text: &str, details: Vec<&str>) -> Token {
+        Token {
+            text: text.to_string(),
+            byte_start: 0,
+            byte_end: text.len(),
+            position: 0,
+            position_length: 1,
+            word_id: WordId(0, true),
+            details: details.iter().map(|detail| detail.to_string()).collect(),
+            word_cost: None,
+            connection_cost: None,
+        }
+    }
+
+    #[test]
+    fn test_token_accessors_ipadic() {
+        let token = build_token(
+            "走っ",
+            vec![
+                "動詞",
+                "自立",
+                "*",
+                "*",
+                "五段・ラ行",
+                "連用タ接続",
+                "走る",
+                "ハシッ",
+                "ハシッ",
+            ],
+        );
+        let kind = DictionaryKind::IPADIC;
+
+        assert_eq!(token.surface(), "走っ");
+        assert_eq!(token.pos(&kind), Some("動詞"));
+        assert_eq!(token.base_form(&kind), Some("走る"));
+        assert_eq!(token.reading(&kind), Some("ハシッ"));
+    }
+
+    #[test]
+    fn test_token_accessors_ko_dic() {
+        let token = build_token(
+            "한국어",
+            vec![
+                "NNG",
+                "*",
+                "F",
+                "한국어",
+                "Compound",
+                "*",
+                "*",
+                "한국/NNG/*+어/NNG/*",
+            ],
+        );
+        let kind = DictionaryKind::KoDic;
+
+        assert_eq!(token.surface(), "한국어");
+        assert_eq!(token.pos(&kind), Some("NNG"));
+        assert_eq!(token.base_form(&kind), None);
+        assert_eq!(token.reading(&kind), Some("한국어"));
+    }
+
+    #[test]
+    fn test_token_accessors_custom_schema() {
+        // a custom dictionary with the base form and the reading at the beginning
+        let token = build_token("走っ", vec!["走る", "ハシッ", "動詞", "自立"]);
+        let schema = DetailSchema {
+            pos: vec![2, 3],
+            base_form: Some(0),
+            reading: Some(1),
+            pronunciation: None,
+        };
+
+        assert_eq!(token.pos(&schema), Some("動詞"));
+        assert_eq!(token.base_form(&schema), Some("走る"));
+        assert_eq!(token.reading(&schema), Some("ハシッ"));
+        assert_eq!(token.pronunciation(&schema), None);
+
+        // the built-in schema reads other columns
+        assert_eq!(token.base_form(&DictionaryKind::IPADIC), None);
+    }
+
+    #[test]
+    fn test_token_accessors_missing_fields() {
+        let kind = DictionaryKind::IPADIC;
+
+        // `*` means the field is empty
+        let token = build_token(
+            "トートバッグ",
+            vec!["名詞", "一般", "*", "*", "*", "*", "*", "*", "*"],
+        );
+        assert_eq!(token.base_form(&kind), None);
+        assert_eq!(token.reading(&kind), None);
+
+        // unknown words
+        let token = build_token("トートバッグ", vec!["UNK"]);
+        assert_eq!(token.pos(&kind), None);
+        assert_eq!(token.reading(&kind), None);
+
+        // the details are read whatever the word ID, e.g. for the tokens built by the filters
+        let mut token = build_token(
+            "トートバッグ",
+            vec!["名詞", "一般", "*", "*", "*", "*", "*", "*", "*"],
+        );
+        token.word_id = WordId::default();
+        assert_eq!(token.pos(&kind), Some("名詞"));
+    }
+
+    #[test]
+    #[cfg(feature = "cc-cedict")]
+    fn test_token_pinyin_cc_cedict() {
+        let token = build_token(
+            "中文",
+            vec![
+                "*",
+                "*",
+                "*",
+                "*",
+                "Zhong1 wen2",
+                "中文",
+                "中文",
+                "Chinese language/",
+            ],
+        );
+        assert_eq!(token.pinyin(), Some("Zhong1 wen2"));
+
+        // unknown words and details without the pinyin field
+        let token = build_token("。", vec!["UNK"]);
+        assert_eq!(token.pinyin(), None);
+        let token = build_token("中文", vec!["*", "*", "*"]);
+        assert_eq!(token.pinyin(), None);
+    }
+
+    #[test]
+    fn test_token_char_offsets() {
+        let text = "日本語の形態素解析";
+        let mut token = build_token("形態素", vec!["名詞", "一般"]);
+        token.byte_start = 12;
+        token.byte_end = 21;
+
+        // each character is 3 bytes long in UTF-8
+        assert_eq!(token.char_start(text), Some(4));
+        assert_eq!(token.char_end(text), Some(7));
+
+        // the offsets are not on a character boundary of the text
+        assert_eq!(token.char_start("abc"), None);
+        token.byte_start = 13;
+        assert_eq!(token.char_start(text), None);
+    }
+
+    #[test]
+    fn test_token_serde() {
+        let token = build_token("東京", vec!["名詞", "固有名詞"]);
+
+        let value = serde_json::to_value(&token).unwrap();
+        // The field names are part of the wire format.
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "text": "東京",
+                "byte_start": 0,
+                "byte_end": 6,
+                "position": 0,
+                "position_length": 1,
+                "word_id": [0, true],
+                "details": ["名詞", "固有名詞"],
+            })
+        );
+
+        let deserialized: Token = serde_json::from_value(value).unwrap();
+        assert_eq!(deserialized, token);
+
+        // The costs are serialized only if they are present.
+        let mut token = build_token("東京", vec!["名詞", "固有名詞"]);
+        token.word_id = WordId(u32::MAX, false);
+        token.word_cost = Some(3003);
+        token.connection_cost = Some(-283);
+
+        let json = serde_json::to_string(&token).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["word_id"], serde_json::json!([u32::MAX, false]));
+        assert_eq!(value["word_cost"], serde_json::json!(3003));
+        assert_eq!(value["connection_cost"], serde_json::json!(-283));
+
+        let deserialized: Token = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, token);
+    }
+
+    #[test]
+    fn test_reconstruct() {
+        let original = "東京の 最寄り駅";
+        let mut tokens = ["東京", "の", "最寄り", "駅"]
+            .iter()
+            .enumerate()
+            .map(|(position, text)| {
+                let mut token = build_token(text, vec!["名詞"]);
+                token.byte_start = original.find(text).unwrap();
+                token.byte_end = token.byte_start + text.len();
+                token.position = position;
+                token
+            })
+            .collect::<Vec<_>>();
+
+        // the stop words filter drops the particle
+        let filter = StopWordsTokenFilter::new(StopWordsTokenFilterConfig::new(
+            ["の".to_string()].into_iter().collect::<HashSet<_>>(),
+        ));
+        filter.apply(&mut tokens).unwrap();
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|token| token.surface())
+                .collect::<Vec<_>>(),
+            vec!["東京", "最寄り", "駅"]
+        );
+
+        // the dropped particle and the space are filled from the original text
+        assert_eq!(reconstruct(&tokens, original), "東京の 最寄り駅");
+        assert_eq!(reconstruct(&tokens[1..], original), "最寄り駅");
+        assert_eq!(reconstruct(&tokens[..1], original), "東京");
+        assert_eq!(reconstruct(&[], original), "");
+
+        // a compound token spanning several positions is covered entirely
+        let mut compound = build_token("最寄り駅", vec!["名詞"]);
+        compound.byte_start = tokens[1].byte_start;
+        compound.byte_end = tokens[2].byte_end;
+        compound.position_length = 2;
+        assert_eq!(
+            reconstruct(&[tokens[0].clone(), compound, tokens[1].clone()], original),
+            "東京の 最寄り駅"
+        );
+
+        // the offsets are not valid for the text
+        assert_eq!(reconstruct(&tokens, "東京"), "東京最寄り駅");
+    }
 }
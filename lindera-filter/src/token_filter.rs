@@ -1,3 +1,6 @@
+pub mod cost_threshold;
+pub mod dedupe;
+pub mod elongation;
 pub mod japanese_base_form;
 pub mod japanese_compound_word;
 pub mod japanese_kana;
@@ -6,6 +9,9 @@ pub mod japanese_keep_tags;
 pub mod japanese_number;
 pub mod japanese_reading_form;
 pub mod japanese_stop_tags;
+pub mod japanese_width;
+pub mod kana_convert;
+pub mod keep_longest;
 pub mod keep_words;
 pub mod korean_keep_tags;
 pub mod korean_reading_form;
@@ -13,7 +19,16 @@ pub mod korean_stop_tags;
 pub mod length;
 pub mod lowercase;
 pub mod mapping;
+pub mod ngram;
+pub mod number_counter;
+pub mod pos_map;
+pub mod punctuation;
+pub mod reading_at_same_position;
+pub mod regex_keep;
+pub mod script_tag;
+pub mod stem;
 pub mod stop_words;
+pub mod synonym;
 pub mod uppercase;
 
 use serde_json::Value;
@@ -21,9 +36,18 @@ use std::ops::Deref;
 
 use lindera_core::error::LinderaErrorKind;
 use lindera_core::LinderaResult;
+use lindera_dictionary::DictionaryKind;
 
-use crate::parse_cli_flag;
 use crate::token::Token;
+use crate::token_filter::cost_threshold::{
+    CostThresholdTokenFilter, CostThresholdTokenFilterConfig, COST_THRESHOLD_TOKEN_FILTER_NAME,
+};
+use crate::token_filter::dedupe::{
+    DedupeTokenFilter, DedupeTokenFilterConfig, DEDUPE_TOKEN_FILTER_NAME,
+};
+use crate::token_filter::elongation::{
+    ElongationTokenFilter, ElongationTokenFilterConfig, ELONGATION_TOKEN_FILTER_NAME,
+};
 use crate::token_filter::japanese_base_form::{
     JapaneseBaseFormTokenFilter, JapaneseBaseFormTokenFilterConfig,
     JAPANESE_BASE_FORM_TOKEN_FILTER_NAME,
@@ -54,6 +78,13 @@ use crate::token_filter::japanese_stop_tags::{
     JapaneseStopTagsTokenFilter, JapaneseStopTagsTokenFilterConfig,
     JAPANESE_STOP_TAGS_TOKEN_FILTER_NAME,
 };
+use crate::token_filter::japanese_width::{
+    JapaneseWidthTokenFilter, JapaneseWidthTokenFilterConfig, JAPANESE_WIDTH_TOKEN_FILTER_NAME,
+};
+use crate::token_filter::kana_convert::{
+    KanaConvertTokenFilter, KanaConvertTokenFilterConfig, KANA_CONVERT_TOKEN_FILTER_NAME,
+};
+use crate::token_filter::keep_longest::{KeepLongestTokenFilter, KEEP_LONGEST_TOKEN_FILTER_NAME};
 use crate::token_filter::keep_words::{
     KeepWordsTokenFilter, KeepWordsTokenFilterConfig, KEEP_WORDS_TOKEN_FILTER_NAME,
 };
@@ -69,14 +100,41 @@ use crate::token_filter::korean_stop_tags::{
 use crate::token_filter::length::{
     LengthTokenFilter, LengthTokenFilterConfig, LENGTH_TOKEN_FILTER_NAME,
 };
-use crate::token_filter::lowercase::{LowercaseTokenFilter, LOWERCASE_TOKEN_FILTER_NAME};
+use crate::token_filter::lowercase::{
+    LowercaseTokenFilter, LowercaseTokenFilterConfig, LOWERCASE_TOKEN_FILTER_NAME,
+};
 use crate::token_filter::mapping::{
     MappingTokenFilter, MappingTokenFilterConfig, MAPPING_TOKEN_FILTER_NAME,
 };
+use crate::token_filter::ngram::{
+    NgramTokenFilter, NgramTokenFilterConfig, NGRAM_TOKEN_FILTER_NAME,
+};
+use crate::token_filter::number_counter::{
+    NumberCounterTokenFilter, NumberCounterTokenFilterConfig, NUMBER_COUNTER_TOKEN_FILTER_NAME,
+};
+use crate::token_filter::pos_map::{
+    PosMapTokenFilter, PosMapTokenFilterConfig, POS_MAP_TOKEN_FILTER_NAME,
+};
+use crate::token_filter::punctuation::{
+    PunctuationTokenFilter, PunctuationTokenFilterConfig, PUNCTUATION_TOKEN_FILTER_NAME,
+};
+use crate::token_filter::reading_at_same_position::{
+    ReadingAtSamePositionTokenFilter, ReadingAtSamePositionTokenFilterConfig,
+    READING_AT_SAME_POSITION_TOKEN_FILTER_NAME,
+};
+use crate::token_filter::regex_keep::{
+    RegexKeepTokenFilter, RegexKeepTokenFilterConfig, REGEX_KEEP_TOKEN_FILTER_NAME,
+};
+use crate::token_filter::script_tag::{ScriptTagTokenFilter, SCRIPT_TAG_TOKEN_FILTER_NAME};
+use crate::token_filter::stem::{StemTokenFilter, StemTokenFilterConfig, STEM_TOKEN_FILTER_NAME};
 use crate::token_filter::stop_words::{
     StopWordsTokenFilter, StopWordsTokenFilterConfig, STOP_WORDS_TOKEN_FILTER_NAME,
 };
+use crate::token_filter::synonym::{
+    SynonymTokenFilter, SynonymTokenFilterConfig, SYNONYM_TOKEN_FILTER_NAME,
+};
 use crate::token_filter::uppercase::{UppercaseTokenFilter, UPPERCASE_TOKEN_FILTER_NAME};
+use crate::{filter_args, parse_cli_flag, FilterDescription};
 
 pub trait TokenFilter: 'static + Send + Sync + TokenFilterClone {
     fn name(&self) -> &str;
@@ -112,11 +170,190 @@ impl<T: TokenFilter + Clone + 'static> TokenFilterClone for T {
 pub struct TokenFilterLoader {}
 
 impl TokenFilterLoader {
+    /// The token filters that `load_from_value` can build, in alphabetical order.
+    pub const FILTERS: &'static [FilterDescription] = &[
+        FilterDescription {
+            name: COST_THRESHOLD_TOKEN_FILTER_NAME,
+            description: "Remove the tokens whose word cost is greater than max_word_cost (requires debug_costs)",
+            example_args: r#"{"max_word_cost":10000}"#,
+        },
+        FilterDescription {
+            name: DEDUPE_TOKEN_FILTER_NAME,
+            description: "Remove a token with the same text and position as the previous token",
+            example_args: r#"{"by_position_only":false}"#,
+        },
+        FilterDescription {
+            name: ELONGATION_TOKEN_FILTER_NAME,
+            description: "Collapse the characters repeated to lengthen a word, such as すごーーーい",
+            example_args: r#"{"max_repeat":1}"#,
+        },
+        FilterDescription {
+            name: JAPANESE_BASE_FORM_TOKEN_FILTER_NAME,
+            description: "Replace the token text with its base form in the dictionary",
+            example_args: r#"{"kind":"ipadic"}"#,
+        },
+        FilterDescription {
+            name: JAPANESE_COMPOUND_WORD_TOKEN_FILTER_NAME,
+            description: "Compound consecutive tokens with the specified part-of-speech tags into a single token",
+            example_args: r#"{"kind":"ipadic","tags":["名詞,数","名詞,接尾,助数詞"],"new_tag":"名詞,数"}"#,
+        },
+        FilterDescription {
+            name: JAPANESE_KANA_TOKEN_FILTER_NAME,
+            description: "Convert the token text to hiragana or katakana",
+            example_args: r#"{"kind":"hiragana"}"#,
+        },
+        FilterDescription {
+            name: JAPANESE_KATAKANA_STEM_TOKEN_FILTER_NAME,
+            description: "Remove the long sound mark at the end of katakana words longer than min",
+            example_args: r#"{"min":3}"#,
+        },
+        FilterDescription {
+            name: JAPANESE_KEEP_TAGS_TOKEN_FILTER_NAME,
+            description: "Keep only the tokens with the specified part-of-speech tags",
+            example_args: r#"{"tags":["名詞","動詞"],"prefix_match":true}"#,
+        },
+        FilterDescription {
+            name: JAPANESE_NUMBER_TOKEN_FILTER_NAME,
+            description: "Convert Japanese numerals, including kanji numerals, to Arabic numerals",
+            example_args: r#"{"tags":["名詞,数"]}"#,
+        },
+        FilterDescription {
+            name: JAPANESE_READING_FORM_TOKEN_FILTER_NAME,
+            description: "Replace the token text with its reading in the dictionary",
+            example_args: r#"{"kind":"ipadic","to":"katakana"}"#,
+        },
+        FilterDescription {
+            name: JAPANESE_STOP_TAGS_TOKEN_FILTER_NAME,
+            description: "Remove the tokens with the specified part-of-speech tags",
+            example_args: r#"{"tags":["助詞","助動詞","記号"]}"#,
+        },
+        FilterDescription {
+            name: JAPANESE_WIDTH_TOKEN_FILTER_NAME,
+            description: "Convert full-width ASCII to half-width and half-width katakana to full-width",
+            example_args: r#"{"to_half_width_ascii":true,"to_full_width_kana":true}"#,
+        },
+        FilterDescription {
+            name: KANA_CONVERT_TOKEN_FILTER_NAME,
+            description: "Convert between hiragana and katakana",
+            example_args: r#"{"direction":"kata2hira"}"#,
+        },
+        FilterDescription {
+            name: KEEP_LONGEST_TOKEN_FILTER_NAME,
+            description: "Among the tokens at the same position, keep only the longest one",
+            example_args: "",
+        },
+        FilterDescription {
+            name: KEEP_WORDS_TOKEN_FILTER_NAME,
+            description: "Keep only the tokens of the specified text",
+            example_args: r#"{"words":["東京","大阪"]}"#,
+        },
+        FilterDescription {
+            name: KOREAN_KEEP_TAGS_TOKEN_FILTER_NAME,
+            description: "Keep only the tokens with the specified part-of-speech tags",
+            example_args: r#"{"tags":["NNG","NNP"]}"#,
+        },
+        FilterDescription {
+            name: KOREAN_READING_FORM_TOKEN_FILTER_NAME,
+            description: "Replace the token text with its reading in the dictionary",
+            example_args: "",
+        },
+        FilterDescription {
+            name: KOREAN_STOP_TAGS_TOKEN_FILTER_NAME,
+            description: "Remove the tokens with the specified part-of-speech tags",
+            example_args: r#"{"tags":["JKS","JKO","JX"]}"#,
+        },
+        FilterDescription {
+            name: LENGTH_TOKEN_FILTER_NAME,
+            description: "Keep only the tokens whose text has between min and max characters",
+            example_args: r#"{"min":2,"max":10}"#,
+        },
+        FilterDescription {
+            name: LOWERCASE_TOKEN_FILTER_NAME,
+            description: "Convert the token text to lowercase",
+            example_args: r#"{"ascii_only":false}"#,
+        },
+        FilterDescription {
+            name: MAPPING_TOKEN_FILTER_NAME,
+            description: "Replace characters of the token text with the specified character mappings",
+            example_args: r#"{"mapping":{"ｱ":"ア","ｲ":"イ"}}"#,
+        },
+        FilterDescription {
+            name: NGRAM_TOKEN_FILTER_NAME,
+            description: "Replace each token with the character n-grams of its text",
+            example_args: r#"{"min":1,"max":2}"#,
+        },
+        FilterDescription {
+            name: NUMBER_COUNTER_TOKEN_FILTER_NAME,
+            description: "Merge a number token with the counter token following it, e.g. 三 and 個 into 三個",
+            example_args: r#"{"kind":"ipadic","exclude":["本"]}"#,
+        },
+        FilterDescription {
+            name: POS_MAP_TOKEN_FILTER_NAME,
+            description: "Map the part-of-speech tags to coarse tags, by default IPADIC to Universal Dependencies",
+            example_args: r#"{"mapping":{"名詞":"NOUN","動詞":"VERB"}}"#,
+        },
+        FilterDescription {
+            name: PUNCTUATION_TOKEN_FILTER_NAME,
+            description: "Remove the tokens that consist only of punctuation or symbols",
+            example_args: r#"{"keep":["！"]}"#,
+        },
+        FilterDescription {
+            name: READING_AT_SAME_POSITION_TOKEN_FILTER_NAME,
+            description: "Add a token of the reading at the same position as each token",
+            example_args: r#"{"kind":"ipadic"}"#,
+        },
+        FilterDescription {
+            name: REGEX_KEEP_TOKEN_FILTER_NAME,
+            description: "Keep only the tokens whose text matches the regular expression",
+            example_args: r#"{"pattern":"^#","invert":false}"#,
+        },
+        FilterDescription {
+            name: SCRIPT_TAG_TOKEN_FILTER_NAME,
+            description: "Append the dominant script of the token text to the token details",
+            example_args: "",
+        },
+        FilterDescription {
+            name: STEM_TOKEN_FILTER_NAME,
+            description: "Replace verbs and adjectives with their stem, optionally dropping the following auxiliary verbs",
+            example_args: r#"{"kind":"ipadic","drop_aux":true}"#,
+        },
+        FilterDescription {
+            name: STOP_WORDS_TOKEN_FILTER_NAME,
+            description: "Remove the tokens of the specified text",
+            example_args: r#"{"words":["の","は","を"]}"#,
+        },
+        FilterDescription {
+            name: SYNONYM_TOKEN_FILTER_NAME,
+            description: "Add the synonyms of the token text at the same position",
+            example_args: r#"{"synonyms":{"東京":["とうきょう","Tokyo"]}}"#,
+        },
+        FilterDescription {
+            name: UPPERCASE_TOKEN_FILTER_NAME,
+            description: "Convert the token text to uppercase",
+            example_args: "",
+        },
+    ];
+
     pub fn load_from_value(kind: &str, value: &Value) -> LinderaResult<BoxTokenFilter> {
+        let args = filter_args(value);
+        let value = args.as_ref();
+
         let token_filter = match kind {
+            COST_THRESHOLD_TOKEN_FILTER_NAME => {
+                let config = CostThresholdTokenFilterConfig::from_value(value)?;
+                BoxTokenFilter::from(CostThresholdTokenFilter::new(config))
+            }
+            DEDUPE_TOKEN_FILTER_NAME => {
+                let config = DedupeTokenFilterConfig::from_value(value)?;
+                BoxTokenFilter::from(DedupeTokenFilter::new(config))
+            }
+            ELONGATION_TOKEN_FILTER_NAME => {
+                let config = ElongationTokenFilterConfig::from_value(value)?;
+                BoxTokenFilter::from(ElongationTokenFilter::new(config)?)
+            }
             JAPANESE_BASE_FORM_TOKEN_FILTER_NAME => {
                 let config = JapaneseBaseFormTokenFilterConfig::from_value(value)?;
-                BoxTokenFilter::from(JapaneseBaseFormTokenFilter::new(config))
+                BoxTokenFilter::from(JapaneseBaseFormTokenFilter::new(config)?)
             }
             JAPANESE_COMPOUND_WORD_TOKEN_FILTER_NAME => {
                 let config = JapaneseCompoundWordTokenFilterConfig::from_value(value)?;
@@ -140,12 +377,21 @@ impl TokenFilterLoader {
             }
             JAPANESE_READING_FORM_TOKEN_FILTER_NAME => {
                 let config = JapaneseReadingFormTokenFilterConfig::from_value(value)?;
-                BoxTokenFilter::from(JapaneseReadingFormTokenFilter::new(config))
+                BoxTokenFilter::from(JapaneseReadingFormTokenFilter::new(config)?)
             }
             JAPANESE_STOP_TAGS_TOKEN_FILTER_NAME => {
                 let config = JapaneseStopTagsTokenFilterConfig::from_value(value)?;
                 BoxTokenFilter::from(JapaneseStopTagsTokenFilter::new(config))
             }
+            JAPANESE_WIDTH_TOKEN_FILTER_NAME => {
+                let config = JapaneseWidthTokenFilterConfig::from_value(value)?;
+                BoxTokenFilter::from(JapaneseWidthTokenFilter::new(config))
+            }
+            KANA_CONVERT_TOKEN_FILTER_NAME => {
+                let config = KanaConvertTokenFilterConfig::from_value(value)?;
+                BoxTokenFilter::from(KanaConvertTokenFilter::new(config))
+            }
+            KEEP_LONGEST_TOKEN_FILTER_NAME => BoxTokenFilter::from(KeepLongestTokenFilter::new()),
             KEEP_WORDS_TOKEN_FILTER_NAME => {
                 let config = KeepWordsTokenFilterConfig::from_value(value)?;
                 BoxTokenFilter::from(KeepWordsTokenFilter::new(config))
@@ -163,17 +409,53 @@ impl TokenFilterLoader {
             }
             LENGTH_TOKEN_FILTER_NAME => {
                 let config = LengthTokenFilterConfig::from_value(value)?;
-                BoxTokenFilter::from(LengthTokenFilter::new(config))
+                BoxTokenFilter::from(LengthTokenFilter::new(config)?)
+            }
+            LOWERCASE_TOKEN_FILTER_NAME => {
+                let config = LowercaseTokenFilterConfig::from_value(value)?;
+                BoxTokenFilter::from(LowercaseTokenFilter::from_config(config))
             }
-            LOWERCASE_TOKEN_FILTER_NAME => BoxTokenFilter::from(LowercaseTokenFilter::new()),
             MAPPING_TOKEN_FILTER_NAME => {
                 let config = MappingTokenFilterConfig::from_value(value)?;
                 BoxTokenFilter::from(MappingTokenFilter::new(config)?)
             }
+            NGRAM_TOKEN_FILTER_NAME => {
+                let config = NgramTokenFilterConfig::from_value(value)?;
+                BoxTokenFilter::from(NgramTokenFilter::new(config)?)
+            }
+            NUMBER_COUNTER_TOKEN_FILTER_NAME => {
+                let config = NumberCounterTokenFilterConfig::from_value(value)?;
+                BoxTokenFilter::from(NumberCounterTokenFilter::new(config)?)
+            }
+            POS_MAP_TOKEN_FILTER_NAME => {
+                let config = PosMapTokenFilterConfig::from_value(value)?;
+                BoxTokenFilter::from(PosMapTokenFilter::new(config))
+            }
+            PUNCTUATION_TOKEN_FILTER_NAME => {
+                let config = PunctuationTokenFilterConfig::from_value(value)?;
+                BoxTokenFilter::from(PunctuationTokenFilter::new(config))
+            }
+            READING_AT_SAME_POSITION_TOKEN_FILTER_NAME => {
+                let config = ReadingAtSamePositionTokenFilterConfig::from_value(value)?;
+                BoxTokenFilter::from(ReadingAtSamePositionTokenFilter::new(config)?)
+            }
+            REGEX_KEEP_TOKEN_FILTER_NAME => {
+                let config = RegexKeepTokenFilterConfig::from_value(value)?;
+                BoxTokenFilter::from(RegexKeepTokenFilter::new(config)?)
+            }
+            SCRIPT_TAG_TOKEN_FILTER_NAME => BoxTokenFilter::from(ScriptTagTokenFilter::new()),
+            STEM_TOKEN_FILTER_NAME => {
+                let config = StemTokenFilterConfig::from_value(value)?;
+                BoxTokenFilter::from(StemTokenFilter::new(config)?)
+            }
             STOP_WORDS_TOKEN_FILTER_NAME => {
                 let config = StopWordsTokenFilterConfig::from_value(value)?;
                 BoxTokenFilter::from(StopWordsTokenFilter::new(config))
             }
+            SYNONYM_TOKEN_FILTER_NAME => {
+                let config = SynonymTokenFilterConfig::from_value(value)?;
+                BoxTokenFilter::from(SynonymTokenFilter::new(config))
+            }
             UPPERCASE_TOKEN_FILTER_NAME => BoxTokenFilter::from(UppercaseTokenFilter::new()),
             _ => {
                 return Err(LinderaErrorKind::Deserialize
@@ -192,3 +474,125 @@ impl TokenFilterLoader {
         Ok(character_filter)
     }
 }
+
+/// Format a part-of-speech tag such as `名詞,固有名詞` into the four-field form
+/// used by the Japanese dictionaries, padding missing fields with `*`.
+pub(crate) fn format_japanese_tag(tag: &str) -> String {
+    let mut formatted_tag = ["*", "*", "*", "*"];
+    for (i, j) in tag.split(',').take(4).enumerate() {
+        formatted_tag[i] = j;
+    }
+
+    formatted_tag.join(",")
+}
+
+/// Get the four-field part-of-speech tag of a token tokenized with a Japanese dictionary.
+/// Unknown words only have a single field, which is padded with `*`.
+pub(crate) fn japanese_token_tag(token: &Token) -> String {
+    let mut formatted_tag = ["*", "*", "*", "*"];
+    let tags_len = if token.details.len() >= 4 { 4 } else { 1 };
+    for (i, j) in token.details[0..tags_len].iter().enumerate() {
+        formatted_tag[i] = j;
+    }
+
+    formatted_tag.join(",")
+}
+
+/// Check that the word details of the dictionary have the field at `index`, the index of the
+/// field named `field_name` in the detail schema of a filter, so that a filter configured for a
+/// dictionary without the field fails to be built instead of misbehaving.
+/// The number of fields is only known for the built-in schemas, so the index of a custom schema
+/// is not checked against it.
+pub(crate) fn check_detail_field(
+    filter_name: &str,
+    field_name: &str,
+    index: Option<usize>,
+    kind: &DictionaryKind,
+    custom_schema: bool,
+) -> LinderaResult<()> {
+    match index {
+        None => Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+            "{} requires the {} of the words, which is not in the details of {}",
+            filter_name,
+            field_name,
+            kind.as_str()
+        ))),
+        Some(index) if !custom_schema && index >= kind.detail_field_count() => {
+            Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                "{} reads the {} from the detail field {}, but {} has only {} detail fields",
+                filter_name,
+                field_name,
+                index,
+                kind.as_str(),
+                kind.detail_field_count()
+            )))
+        }
+        Some(_) => Ok(()),
+    }
+}
+
+/// Build a token of a single position with the given details for the tests of the token filters.
+#[cfg(all(test, any(feature = "ipadic", feature = "unidic",), feature = "filter"))]
+pub(crate) fn make_token(
+    text: &str,
+    byte_start: usize,
+    position: usize,
+    details: &[&str],
+) -> Token {
+    Token {
+        text: text.to_string(),
+        byte_start,
+        byte_end: byte_start + text.len(),
+        position,
+        position_length: 1,
+        word_id: lindera_core::word_entry::WordId(position as u32, true),
+        details: details.iter().map(|detail| detail.to_string()).collect(),
+        word_cost: None,
+        connection_cost: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lindera_dictionary::DictionaryKind;
+
+    use crate::token_filter::{check_detail_field, format_japanese_tag, TokenFilterLoader};
+
+    #[test]
+    fn test_token_filter_loader_filters() {
+        for filter in TokenFilterLoader::FILTERS {
+            let token_filter =
+                TokenFilterLoader::load_from_cli_flag(&filter.example_cli_flag()).unwrap();
+            assert_eq!(token_filter.name(), filter.name);
+        }
+
+        // Every token filter module is listed.
+        let modules = include_str!("token_filter.rs")
+            .lines()
+            .filter(|line| line.starts_with("pub mod "))
+            .count();
+        assert_eq!(TokenFilterLoader::FILTERS.len(), modules);
+    }
+
+    #[test]
+    fn test_format_japanese_tag() {
+        assert_eq!(format_japanese_tag("名詞"), "名詞,*,*,*");
+        assert_eq!(format_japanese_tag("名詞,固有名詞"), "名詞,固有名詞,*,*");
+        assert_eq!(
+            format_japanese_tag("名詞,固有名詞,地域,一般"),
+            "名詞,固有名詞,地域,一般"
+        );
+    }
+
+    #[test]
+    fn test_check_detail_field() {
+        let kind = DictionaryKind::IPADIC;
+        assert!(check_detail_field("filter", "reading", Some(7), &kind, false).is_ok());
+        assert!(check_detail_field("filter", "reading", Some(9), &kind, false).is_err());
+        assert!(check_detail_field("filter", "reading", None, &kind, false).is_err());
+
+        // the number of fields of a custom schema is unknown
+        assert!(check_detail_field("filter", "reading", Some(9), &kind, true).is_ok());
+        assert!(check_detail_field("filter", "reading", None, &kind, true).is_err());
+    }
+}
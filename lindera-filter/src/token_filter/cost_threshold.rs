@@ -0,0 +1,195 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use lindera_core::error::LinderaErrorKind;
+use lindera_core::LinderaResult;
+
+use crate::token::Token;
+use crate::token_filter::TokenFilter;
+
+pub const COST_THRESHOLD_TOKEN_FILTER_NAME: &str = "cost_threshold";
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct CostThresholdTokenFilterConfig {
+    max_word_cost: i32,
+}
+
+impl CostThresholdTokenFilterConfig {
+    pub fn new(max_word_cost: i32) -> Self {
+        Self { max_word_cost }
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        serde_json::from_slice::<CostThresholdTokenFilterConfig>(data)
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+
+    pub fn from_value(value: &Value) -> LinderaResult<Self> {
+        serde_json::from_value::<CostThresholdTokenFilterConfig>(value.clone())
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+}
+
+/// Remove the tokens whose word cost is greater than `max_word_cost`.
+/// This is useful to discard unlikely unknown words, such as OCR noise.
+/// The word cost is only available if the tokenizer has `debug_costs` enabled;
+/// tokens without a word cost are kept.
+///
+#[derive(Clone, Debug)]
+pub struct CostThresholdTokenFilter {
+    config: CostThresholdTokenFilterConfig,
+}
+
+impl CostThresholdTokenFilter {
+    pub fn new(config: CostThresholdTokenFilterConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        Ok(Self::new(CostThresholdTokenFilterConfig::from_slice(data)?))
+    }
+}
+
+impl TokenFilter for CostThresholdTokenFilter {
+    fn name(&self) -> &'static str {
+        COST_THRESHOLD_TOKEN_FILTER_NAME
+    }
+
+    fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
+        tokens.retain(|token| match token.word_cost {
+            Some(word_cost) => word_cost <= self.config.max_word_cost,
+            None => true,
+        });
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "ipadic")]
+    use lindera_core::word_entry::WordId;
+
+    use crate::token_filter::cost_threshold::{
+        CostThresholdTokenFilter, CostThresholdTokenFilterConfig,
+    };
+    #[cfg(feature = "ipadic")]
+    use crate::{token::Token, token_filter::TokenFilter};
+
+    #[test]
+    fn test_cost_threshold_token_filter_config_from_slice() {
+        let config_str = r#"
+            {
+                "max_word_cost": 5000
+            }
+            "#;
+        let config = CostThresholdTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
+
+        assert_eq!(config.max_word_cost, 5000);
+    }
+
+    #[test]
+    fn test_cost_threshold_token_filter_from_slice() {
+        let config_str = r#"
+            {
+                "max_word_cost": 5000
+            }
+            "#;
+        let result = CostThresholdTokenFilter::from_slice(config_str.as_bytes());
+
+        assert_eq!(true, result.is_ok());
+
+        let config_str = r#"
+            {}
+            "#;
+        let result = CostThresholdTokenFilter::from_slice(config_str.as_bytes());
+
+        assert_eq!(true, result.is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_cost_threshold_token_filter_apply_ipadic() {
+        let config_str = r#"
+            {
+                "max_word_cost": 5000
+            }
+            "#;
+        let filter = CostThresholdTokenFilter::from_slice(config_str.as_bytes()).unwrap();
+
+        let mut tokens: Vec<Token> = vec![
+            Token {
+                text: "東京".to_string(),
+                byte_start: 0,
+                byte_end: 6,
+                position: 0,
+                position_length: 1,
+                word_id: WordId(74410, true),
+                details: vec![
+                    "名詞".to_string(),
+                    "固有名詞".to_string(),
+                    "地域".to_string(),
+                    "一般".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "東京".to_string(),
+                    "トウキョウ".to_string(),
+                    "トーキョー".to_string(),
+                ],
+                word_cost: Some(3003),
+                connection_cost: Some(-283),
+            },
+            Token {
+                text: "ｌ１ｌ".to_string(),
+                byte_start: 6,
+                byte_end: 15,
+                position: 1,
+                position_length: 1,
+                word_id: WordId(4294967295, true),
+                details: vec!["UNK".to_string()],
+                word_cost: Some(12000),
+                connection_cost: Some(1500),
+            },
+            Token {
+                text: "へ".to_string(),
+                byte_start: 15,
+                byte_end: 18,
+                position: 2,
+                position_length: 1,
+                word_id: WordId(75930, true),
+                details: vec![
+                    "助詞".to_string(),
+                    "格助詞".to_string(),
+                    "一般".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "へ".to_string(),
+                    "ヘ".to_string(),
+                    "エ".to_string(),
+                ],
+                word_cost: Some(5000),
+                connection_cost: Some(-1024),
+            },
+            Token {
+                text: "行く".to_string(),
+                byte_start: 18,
+                byte_end: 24,
+                position: 3,
+                position_length: 1,
+                word_id: WordId(4294967295, true),
+                details: vec!["UNK".to_string()],
+                word_cost: None,
+                connection_cost: None,
+            },
+        ];
+
+        filter.apply(&mut tokens).unwrap();
+
+        // The cost of "へ" is equal to the threshold, and "行く" has no cost.
+        assert_eq!(
+            tokens.iter().map(|t| t.text.as_str()).collect::<Vec<_>>(),
+            vec!["東京", "へ", "行く"]
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use lindera_core::error::LinderaErrorKind;
+use lindera_core::LinderaResult;
+
+use crate::token::Token;
+use crate::token_filter::TokenFilter;
+
+pub const DEDUPE_TOKEN_FILTER_NAME: &str = "dedupe";
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct DedupeTokenFilterConfig {
+    /// If true, a token is removed when it has the same position as the previous token,
+    /// whatever its text.
+    #[serde(default)]
+    by_position_only: bool,
+}
+
+impl DedupeTokenFilterConfig {
+    pub fn new(by_position_only: bool) -> Self {
+        Self { by_position_only }
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        serde_json::from_slice::<DedupeTokenFilterConfig>(data)
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+
+    pub fn from_value(value: &Value) -> LinderaResult<Self> {
+        serde_json::from_value::<DedupeTokenFilterConfig>(value.clone())
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+}
+
+/// Remove a token when it has the same text and position as the previous token, such as the
+/// duplicates produced by the synonym filter when a synonym is the token text itself.
+/// With `by_position_only`, only the first of the adjacent tokens at the same position is kept.
+///
+#[derive(Clone, Debug)]
+pub struct DedupeTokenFilter {
+    config: DedupeTokenFilterConfig,
+}
+
+impl DedupeTokenFilter {
+    pub fn new(config: DedupeTokenFilterConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        Ok(Self::new(DedupeTokenFilterConfig::from_slice(data)?))
+    }
+}
+
+impl Default for DedupeTokenFilter {
+    fn default() -> Self {
+        Self::new(DedupeTokenFilterConfig::default())
+    }
+}
+
+impl TokenFilter for DedupeTokenFilter {
+    fn name(&self) -> &'static str {
+        DEDUPE_TOKEN_FILTER_NAME
+    }
+
+    fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
+        tokens.dedup_by(|token, previous| {
+            token.position == previous.position
+                && (self.config.by_position_only || token.text == previous.text)
+        });
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "ipadic")]
+    use lindera_core::word_entry::WordId;
+    use serde_json::json;
+
+    use crate::token_filter::dedupe::{DedupeTokenFilter, DedupeTokenFilterConfig};
+    #[cfg(feature = "ipadic")]
+    use crate::{token::Token, token_filter::TokenFilter};
+
+    #[test]
+    fn test_dedupe_token_filter_config_from_slice() {
+        let config_str = r#"
+            {
+                "by_position_only": true
+            }
+            "#;
+        let config = DedupeTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
+
+        assert!(config.by_position_only);
+
+        let config = DedupeTokenFilterConfig::from_value(&json!({})).unwrap();
+
+        assert!(!config.by_position_only);
+    }
+
+    #[test]
+    fn test_dedupe_token_filter_from_slice() {
+        let config_str = r#"
+            {}
+            "#;
+        let result = DedupeTokenFilter::from_slice(config_str.as_bytes());
+
+        assert_eq!(true, result.is_ok());
+    }
+
+    #[cfg(feature = "ipadic")]
+    fn token(text: &str, byte_start: usize, position: usize) -> Token {
+        Token {
+            text: text.to_string(),
+            byte_start,
+            byte_end: byte_start + 6,
+            position,
+            position_length: 1,
+            word_id: WordId(4294967295, true),
+            details: vec!["UNK".to_string()],
+            word_cost: None,
+            connection_cost: None,
+        }
+    }
+
+    #[cfg(feature = "ipadic")]
+    fn ipadic_tokens() -> Vec<Token> {
+        // the tokens of "東京へ" after a synonym filter with `東京` => [`東京`, `Tokyo`]
+        vec![
+            token("東京", 0, 0),
+            token("東京", 0, 0),
+            token("Tokyo", 0, 0),
+            token("東京", 0, 0),
+            token("へ", 6, 1),
+            // the same text at another position is kept
+            token("へ", 9, 2),
+        ]
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_dedupe_token_filter_apply_ipadic() {
+        let filter = DedupeTokenFilter::default();
+
+        let mut tokens = ipadic_tokens();
+        filter.apply(&mut tokens).unwrap();
+
+        // only the adjacent duplicates are removed
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|t| (t.text.as_str(), t.position))
+                .collect::<Vec<_>>(),
+            vec![("東京", 0), ("Tokyo", 0), ("東京", 0), ("へ", 1), ("へ", 2)]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_dedupe_token_filter_apply_by_position_only_ipadic() {
+        let filter = DedupeTokenFilter::new(DedupeTokenFilterConfig::new(true));
+
+        let mut tokens = ipadic_tokens();
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|t| (t.text.as_str(), t.position))
+                .collect::<Vec<_>>(),
+            vec![("東京", 0), ("へ", 1), ("へ", 2)]
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use lindera_core::error::LinderaErrorKind;
+use lindera_core::LinderaResult;
+
+use crate::token::Token;
+use crate::token_filter::TokenFilter;
+
+pub const ELONGATION_TOKEN_FILTER_NAME: &str = "elongation";
+
+/// The characters used to lengthen a vowel, such as in `すごーーーい` or `やば〜〜〜`.
+const LONG_VOWEL_MARKS: [char; 4] = ['ー', 'ｰ', '〜', '～'];
+
+/// The minimum length of a run of characters to be collapsed.
+const MIN_ELONGATION_LENGTH: usize = 3;
+
+fn default_max_repeat() -> usize {
+    1
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ElongationTokenFilterConfig {
+    /// The number of characters a run is collapsed to.
+    #[serde(default = "default_max_repeat")]
+    max_repeat: usize,
+}
+
+impl ElongationTokenFilterConfig {
+    pub fn new(max_repeat: usize) -> Self {
+        Self { max_repeat }
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        serde_json::from_slice::<ElongationTokenFilterConfig>(data)
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+
+    pub fn from_value(value: &Value) -> LinderaResult<Self> {
+        serde_json::from_value::<ElongationTokenFilterConfig>(value.clone())
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+}
+
+impl Default for ElongationTokenFilterConfig {
+    fn default() -> Self {
+        Self::new(default_max_repeat())
+    }
+}
+
+/// Collapse the characters repeated to lengthen a word, as in `うれしいいいい` or `やばーーーー`,
+/// down to `max_repeat` characters.
+/// A run of 3 or more long-vowel marks (`ー`, `ｰ`, `〜`, `～`) is collapsed wherever it is,
+/// while a run of 3 or more of another character is collapsed only at the end of the token.
+/// Digits are never collapsed, so numbers such as `1000` are left untouched.
+///
+#[derive(Clone, Debug)]
+pub struct ElongationTokenFilter {
+    config: ElongationTokenFilterConfig,
+}
+
+impl ElongationTokenFilter {
+    pub fn new(config: ElongationTokenFilterConfig) -> LinderaResult<Self> {
+        if config.max_repeat == 0 {
+            return Err(LinderaErrorKind::Args
+                .with_error(anyhow::anyhow!("max_repeat must be greater than 0")));
+        }
+
+        Ok(Self { config })
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        Self::new(ElongationTokenFilterConfig::from_slice(data)?)
+    }
+}
+
+impl TokenFilter for ElongationTokenFilter {
+    fn name(&self) -> &'static str {
+        ELONGATION_TOKEN_FILTER_NAME
+    }
+
+    fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
+        for token in tokens.iter_mut() {
+            if let Some(text) = collapse_elongation(&token.text, self.config.max_repeat) {
+                token.text = text;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Return the collapsed text, or `None` if the text has no elongation.
+fn collapse_elongation(text: &str, max_repeat: usize) -> Option<String> {
+    let chars: Vec<char> = text.chars().collect();
+
+    let mut collapsed = String::with_capacity(text.len());
+    let mut changed = false;
+
+    let mut start = 0;
+    while start < chars.len() {
+        let c = chars[start];
+        let mut end = start + 1;
+        while end < chars.len() && chars[end] == c {
+            end += 1;
+        }
+
+        let len = end - start;
+        let is_elongation = len >= MIN_ELONGATION_LENGTH
+            && len > max_repeat
+            && !c.is_numeric()
+            && (LONG_VOWEL_MARKS.contains(&c) || end == chars.len());
+
+        let repeat = if is_elongation {
+            changed = true;
+            max_repeat
+        } else {
+            len
+        };
+        collapsed.extend(&chars[start..start + repeat]);
+
+        start = end;
+    }
+
+    changed.then_some(collapsed)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "ipadic")]
+    use lindera_core::word_entry::WordId;
+    use serde_json::json;
+
+    use crate::token_filter::elongation::{
+        collapse_elongation, ElongationTokenFilter, ElongationTokenFilterConfig,
+    };
+    #[cfg(feature = "ipadic")]
+    use crate::{token::Token, token_filter::TokenFilter};
+
+    #[test]
+    fn test_elongation_token_filter_config_from_slice() {
+        let config_str = r#"
+            {
+                "max_repeat": 2
+            }
+            "#;
+        let config = ElongationTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
+
+        assert_eq!(config.max_repeat, 2);
+
+        let config = ElongationTokenFilterConfig::from_value(&json!({})).unwrap();
+
+        assert_eq!(config.max_repeat, 1);
+    }
+
+    #[test]
+    fn test_elongation_token_filter_from_slice() {
+        let config_str = r#"
+            {
+                "max_repeat": 1
+            }
+            "#;
+        let result = ElongationTokenFilter::from_slice(config_str.as_bytes());
+
+        assert_eq!(true, result.is_ok());
+
+        let config_str = r#"
+            {
+                "max_repeat": 0
+            }
+            "#;
+        let result = ElongationTokenFilter::from_slice(config_str.as_bytes());
+
+        assert_eq!(true, result.is_err());
+    }
+
+    #[test]
+    fn test_collapse_elongation() {
+        assert_eq!(
+            collapse_elongation("すごーーーい", 1),
+            Some("すごーい".to_string())
+        );
+        assert_eq!(
+            collapse_elongation("うれしいいいい", 1),
+            Some("うれしい".to_string())
+        );
+        assert_eq!(
+            collapse_elongation("やばーーーー", 1),
+            Some("やばー".to_string())
+        );
+        assert_eq!(
+            collapse_elongation("やば〜〜〜", 2),
+            Some("やば〜〜".to_string())
+        );
+        assert_eq!(collapse_elongation("soooo", 1), Some("so".to_string()));
+
+        // normal words are untouched
+        assert_eq!(collapse_elongation("すごい", 1), None);
+        assert_eq!(collapse_elongation("コーヒー", 1), None);
+        assert_eq!(collapse_elongation("ああ", 1), None);
+        assert_eq!(collapse_elongation("1000", 1), None);
+        assert_eq!(collapse_elongation("すごーーい", 1), None);
+        assert_eq!(collapse_elongation("いいいね", 1), None);
+        assert_eq!(collapse_elongation("やばーーー", 3), None);
+        assert_eq!(collapse_elongation("", 1), None);
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_elongation_token_filter_apply_ipadic() {
+        let filter = ElongationTokenFilter::new(ElongationTokenFilterConfig::default()).unwrap();
+
+        let mut tokens: Vec<Token> = vec![
+            Token {
+                text: "すごーーーい".to_string(),
+                byte_start: 0,
+                byte_end: 18,
+                position: 0,
+                position_length: 1,
+                word_id: WordId(4294967295, true),
+                details: vec!["UNK".to_string()],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "コーヒー".to_string(),
+                byte_start: 18,
+                byte_end: 30,
+                position: 1,
+                position_length: 1,
+                word_id: WordId(20398, true),
+                details: vec![
+                    "名詞".to_string(),
+                    "一般".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "コーヒー".to_string(),
+                    "コーヒー".to_string(),
+                    "コーヒー".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+        ];
+
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(&tokens[0].text, "すごーい");
+        assert_eq!(tokens[0].byte_start, 0);
+        assert_eq!(tokens[0].byte_end, 18);
+        assert_eq!(&tokens[1].text, "コーヒー");
+    }
+}
@@ -3,21 +3,33 @@ use serde_json::Value;
 
 use lindera_core::error::LinderaErrorKind;
 use lindera_core::LinderaResult;
-use lindera_dictionary::DictionaryKind;
+use lindera_dictionary::{DetailSchema, DictionaryKind};
 
 use crate::token::Token;
-use crate::token_filter::TokenFilter;
+use crate::token_filter::{check_detail_field, TokenFilter};
 
 pub const JAPANESE_BASE_FORM_TOKEN_FILTER_NAME: &str = "japanese_base_form";
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub struct JapaneseBaseFormTokenFilterConfig {
     kind: DictionaryKind,
+    /// The layout of the details, if it differs from the one of `kind`.
+    #[serde(default)]
+    detail_schema: Option<DetailSchema>,
 }
 
 impl JapaneseBaseFormTokenFilterConfig {
     pub fn new(kind: DictionaryKind) -> Self {
-        Self { kind }
+        Self {
+            kind,
+            detail_schema: None,
+        }
+    }
+
+    /// Set the layout of the details, if it differs from the one of `kind`.
+    pub fn with_detail_schema(mut self, detail_schema: DetailSchema) -> Self {
+        self.detail_schema = Some(detail_schema);
+        self
     }
 
     pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
@@ -36,18 +48,28 @@ impl JapaneseBaseFormTokenFilterConfig {
 ///
 #[derive(Clone, Debug)]
 pub struct JapaneseBaseFormTokenFilter {
-    config: JapaneseBaseFormTokenFilterConfig,
+    detail_schema: DetailSchema,
 }
 
 impl JapaneseBaseFormTokenFilter {
-    pub fn new(config: JapaneseBaseFormTokenFilterConfig) -> Self {
-        Self { config }
+    pub fn new(config: JapaneseBaseFormTokenFilterConfig) -> LinderaResult<Self> {
+        let custom_schema = config.detail_schema.is_some();
+        let detail_schema = config
+            .detail_schema
+            .unwrap_or_else(|| config.kind.detail_schema());
+        check_detail_field(
+            JAPANESE_BASE_FORM_TOKEN_FILTER_NAME,
+            "base form",
+            detail_schema.base_form,
+            &config.kind,
+            custom_schema,
+        )?;
+
+        Ok(Self { detail_schema })
     }
 
     pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
-        Ok(Self::new(JapaneseBaseFormTokenFilterConfig::from_slice(
-            data,
-        )?))
+        Self::new(JapaneseBaseFormTokenFilterConfig::from_slice(data)?)
     }
 }
 
@@ -58,26 +80,9 @@ impl TokenFilter for JapaneseBaseFormTokenFilter {
 
     fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
         for token in tokens.iter_mut() {
-            if token.details[0] == "UNK" {
-                // NOOP
-                continue;
-            }
-            match self.config.kind {
-                #[cfg(feature = "ipadic")]
-                DictionaryKind::IPADIC => {
-                    token.text = token.details[6].to_string();
-                }
-                #[cfg(feature = "ipadic-neologd")]
-                DictionaryKind::IPADICNEologd => {
-                    token.text = token.details[6].to_string();
-                }
-                #[cfg(feature = "unidic")]
-                DictionaryKind::UniDic => {
-                    token.text = token.details[10].to_string();
-                }
-                _ => {
-                    // NOOP
-                }
+            // Unknown words and words without a base form (`*`) are left unchanged.
+            if let Some(base_form) = token.base_form(&self.detail_schema) {
+                token.text = base_form.to_string();
             }
         }
 
@@ -89,16 +94,16 @@ impl TokenFilter for JapaneseBaseFormTokenFilter {
 mod tests {
     #[cfg(all(any(feature = "ipadic", feature = "unidic",), feature = "filter"))]
     use lindera_core::word_entry::WordId;
+    #[cfg(all(feature = "ipadic", feature = "filter"))]
+    use lindera_dictionary::DetailSchema;
     #[cfg(all(any(feature = "ipadic", feature = "unidic",), feature = "filter"))]
     use lindera_dictionary::DictionaryKind;
 
+    use crate::token_filter::japanese_base_form::JapaneseBaseFormTokenFilter;
     #[cfg(all(any(feature = "ipadic", feature = "unidic",), feature = "filter"))]
     use crate::{
         token::Token,
-        token_filter::{
-            japanese_base_form::{JapaneseBaseFormTokenFilter, JapaneseBaseFormTokenFilterConfig},
-            TokenFilter,
-        },
+        token_filter::{japanese_base_form::JapaneseBaseFormTokenFilterConfig, TokenFilter},
     };
 
     #[cfg(all(feature = "ipadic", feature = "filter"))]
@@ -112,6 +117,30 @@ mod tests {
         let config = JapaneseBaseFormTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
 
         assert_eq!(config.kind, DictionaryKind::IPADIC);
+        assert_eq!(config.detail_schema, None);
+
+        let config_str = r#"
+        {
+            "kind": "ipadic",
+            "detail_schema": {
+                "pos": [0],
+                "base_form": 1
+            }
+        }
+        "#;
+        let config = JapaneseBaseFormTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
+
+        assert_eq!(
+            config,
+            JapaneseBaseFormTokenFilterConfig::new(DictionaryKind::IPADIC).with_detail_schema(
+                DetailSchema {
+                    pos: vec![0],
+                    base_form: Some(1),
+                    reading: None,
+                    pronunciation: None,
+                }
+            )
+        );
     }
 
     #[cfg(all(feature = "unidic", feature = "filter",))]
@@ -182,6 +211,8 @@ mod tests {
                     "ハネダクウコウ".to_string(),
                     "ハネダクーコー".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "に".to_string(),
@@ -201,6 +232,8 @@ mod tests {
                     "ニ".to_string(),
                     "ニ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "あり".to_string(),
@@ -220,6 +253,8 @@ mod tests {
                     "アリ".to_string(),
                     "アリ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "ます".to_string(),
@@ -239,6 +274,8 @@ mod tests {
                     "マス".to_string(),
                     "マス".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
         ];
 
@@ -288,6 +325,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "空港".to_string(),
@@ -315,6 +354,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "に".to_string(),
@@ -342,6 +383,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "あり".to_string(),
@@ -369,6 +412,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "ます".to_string(),
@@ -396,6 +441,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
         ];
 
@@ -408,4 +455,74 @@ mod tests {
         assert_eq!(&tokens[3].text, "ある");
         assert_eq!(&tokens[4].text, "ます");
     }
+
+    #[cfg(all(feature = "ipadic", feature = "filter",))]
+    #[test]
+    fn test_japanese_base_form_token_filter_apply_custom_schema() {
+        // a custom dictionary with the base form in the second column
+        let config_str = r#"
+            {
+                "kind": "ipadic",
+                "detail_schema": {
+                    "pos": [0],
+                    "base_form": 1
+                }
+            }
+            "#;
+        let filter = JapaneseBaseFormTokenFilter::from_slice(config_str.as_bytes()).unwrap();
+
+        let mut tokens: Vec<Token> = vec![Token {
+            text: "あり".to_string(),
+            byte_start: 0,
+            byte_end: 6,
+            position: 0,
+            position_length: 1,
+            word_id: WordId(0, true),
+            details: vec!["動詞".to_string(), "ある".to_string(), "アリ".to_string()],
+            word_cost: None,
+            connection_cost: None,
+        }];
+
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(&tokens[0].text, "ある");
+    }
+
+    #[test]
+    fn test_japanese_base_form_token_filter_from_slice_cc_cedict() {
+        // CC-CEDICT has no base form in the details.
+        let config_str = r#"
+            {
+                "kind": "cc-cedict"
+            }
+            "#;
+        let result = JapaneseBaseFormTokenFilter::from_slice(config_str.as_bytes());
+
+        assert_eq!(true, result.is_err());
+        assert!(result.unwrap_err().to_string().contains("base form"));
+    }
+
+    #[test]
+    fn test_japanese_base_form_token_filter_from_slice_custom_schema() {
+        // ko-dic has no base form either.
+        let config_str = r#"
+            {
+                "kind": "ko-dic"
+            }
+            "#;
+        let result = JapaneseBaseFormTokenFilter::from_slice(config_str.as_bytes());
+        assert_eq!(true, result.is_err());
+
+        // A custom schema may name a field beyond those of the built-in layout.
+        let config_str = r#"
+            {
+                "kind": "ko-dic",
+                "detail_schema": {
+                    "base_form": 10
+                }
+            }
+            "#;
+        let result = JapaneseBaseFormTokenFilter::from_slice(config_str.as_bytes());
+        assert_eq!(true, result.is_ok());
+    }
 }
@@ -8,7 +8,7 @@ use lindera_core::LinderaResult;
 use lindera_dictionary::DictionaryKind;
 
 use crate::token::Token;
-use crate::token_filter::TokenFilter;
+use crate::token_filter::{format_japanese_tag, japanese_token_tag, TokenFilter};
 
 pub const JAPANESE_COMPOUND_WORD_TOKEN_FILTER_NAME: &str = "japanese_compound_word";
 
@@ -25,30 +25,10 @@ impl JapaneseCompoundWordTokenFilterConfig {
         tags: HashSet<String>,
         new_tag: Option<String>,
     ) -> LinderaResult<Self> {
-        let mut formatted_tags: HashSet<String> = HashSet::new();
-        for tag in tags.iter() {
-            let mut formatted_tag = ["*", "*", "*", "*"];
+        let formatted_tags: HashSet<String> =
+            tags.iter().map(|tag| format_japanese_tag(tag)).collect();
 
-            let tag_array: Vec<&str> = tag.split(',').collect();
-            for (i, j) in tag_array.iter().enumerate() {
-                formatted_tag[i] = j;
-            }
-
-            formatted_tags.insert(formatted_tag.join(","));
-        }
-
-        let formatted_new_tag = if let Some(new_tag_str) = new_tag {
-            let mut formatted_tag = ["*", "*", "*", "*"];
-
-            let tag_array: Vec<&str> = new_tag_str.split(',').collect();
-            for (i, j) in tag_array.iter().enumerate() {
-                formatted_tag[i] = j;
-            }
-
-            Some(formatted_tag.join(","))
-        } else {
-            None
-        };
+        let formatted_new_tag = new_tag.map(|new_tag_str| format_japanese_tag(&new_tag_str));
 
         Ok(Self {
             kind,
@@ -96,22 +76,9 @@ impl JapaneseCompoundWordTokenFilter {
         token1.byte_end = token2.byte_end;
         token1.position_length += token2.position_length;
 
-        let mut formatted_details = match self.config.kind {
-            #[cfg(any(feature = "ipadic", feature = "ipadic-neologd"))]
-            DictionaryKind::IPADIC => "複合語,*,*,*,*,*,*,*,*"
-                .split(',')
-                .map(|s| s.to_string())
-                .collect::<Vec<String>>(),
-            #[cfg(feature = "unidic")]
-            DictionaryKind::UniDic => "複合語,*,*,*,*,*,*,*,*,*,*,*,*,*,*,*,*"
-                .split(',')
-                .map(|s| s.to_string())
-                .collect::<Vec<String>>(),
-            _ => "複合語,*,*,*,*,*,*,*,*"
-                .split(',')
-                .map(|s| s.to_string())
-                .collect::<Vec<String>>(),
-        };
+        // The compound token has as many detail fields as the words of the dictionary.
+        let mut formatted_details = vec!["*".to_string(); self.config.kind.detail_field_count()];
+        formatted_details[0] = "複合語".to_string();
         if let Some(new_tag_str) = &self.config.new_tag {
             let new_tag_array: Vec<&str> = new_tag_str.split(',').collect();
             for (i, j) in new_tag_array.iter().enumerate() {
@@ -132,13 +99,7 @@ impl TokenFilter for JapaneseCompoundWordTokenFilter {
         let mut new_tokens = Vec::new();
         let mut compound_token_opt = None;
         for token in tokens.iter_mut() {
-            let mut formatted_tags = ["*", "*", "*", "*"];
-            let tags_len = if token.details.len() >= 4 { 4 } else { 1 };
-            for (i, j) in token.details[0..tags_len].iter().enumerate() {
-                formatted_tags[i] = j;
-            }
-
-            let pos = formatted_tags.join(",");
+            let pos = japanese_token_tag(token);
 
             if self.config.tags.contains(&pos) {
                 if compound_token_opt.is_none() {
@@ -269,6 +230,8 @@ mod tests {
                         "イチ".to_string(),
                         "イチ".to_string(),
                     ],
+                    word_cost: None,
+                    connection_cost: None,
                 },
                 Token {
                     text: "０".to_string(),
@@ -288,6 +251,8 @@ mod tests {
                         "ゼロ".to_string(),
                         "ゼロ".to_string(),
                     ],
+                    word_cost: None,
+                    connection_cost: None,
                 },
                 Token {
                     text: "０".to_string(),
@@ -307,6 +272,8 @@ mod tests {
                         "ゼロ".to_string(),
                         "ゼロ".to_string(),
                     ],
+                    word_cost: None,
+                    connection_cost: None,
                 },
                 Token {
                     text: "円".to_string(),
@@ -326,6 +293,8 @@ mod tests {
                         "エン".to_string(),
                         "エン".to_string(),
                     ],
+                    word_cost: None,
+                    connection_cost: None,
                 },
                 Token {
                     text: "玉".to_string(),
@@ -345,6 +314,8 @@ mod tests {
                         "ダマ".to_string(),
                         "ダマ".to_string(),
                     ],
+                    word_cost: None,
+                    connection_cost: None,
                 },
                 Token {
                     text: "を".to_string(),
@@ -364,6 +335,8 @@ mod tests {
                         "ダマ".to_string(),
                         "ダマ".to_string(),
                     ],
+                    word_cost: None,
+                    connection_cost: None,
                 },
                 Token {
                     text: "拾う".to_string(),
@@ -383,6 +356,8 @@ mod tests {
                         "ヒロウ".to_string(),
                         "ヒロウ".to_string(),
                     ],
+                    word_cost: None,
+                    connection_cost: None,
                 },
             ];
 
@@ -416,4 +391,179 @@ mod tests {
             );
         }
     }
+
+    #[cfg(all(feature = "ipadic", feature = "filter",))]
+    #[test]
+    fn test_japanese_compound_word_token_filter_apply_new_tag_ipadic() {
+        let config_str = r#"
+            {
+                "kind": "ipadic",
+                "tags": [
+                    "名詞,固有名詞,地域,一般",
+                    "名詞,サ変接続",
+                    "名詞,接尾,一般"
+                ],
+                "new_tag": "名詞,固有名詞"
+            }
+            "#;
+        let filter = JapaneseCompoundWordTokenFilter::from_slice(config_str.as_bytes()).unwrap();
+
+        {
+            let mut tokens: Vec<Token> = vec![
+                Token {
+                    text: "東京".to_string(),
+                    byte_start: 0,
+                    byte_end: 6,
+                    position: 0,
+                    position_length: 1,
+                    word_id: WordId(294912, true),
+                    details: vec![
+                        "名詞".to_string(),
+                        "固有名詞".to_string(),
+                        "地域".to_string(),
+                        "一般".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "東京".to_string(),
+                        "トウキョウ".to_string(),
+                        "トーキョー".to_string(),
+                    ],
+                    word_cost: None,
+                    connection_cost: None,
+                },
+                Token {
+                    text: "特許".to_string(),
+                    byte_start: 6,
+                    byte_end: 12,
+                    position: 1,
+                    position_length: 1,
+                    word_id: WordId(301226, true),
+                    details: vec![
+                        "名詞".to_string(),
+                        "サ変接続".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "特許".to_string(),
+                        "トッキョ".to_string(),
+                        "トッキョ".to_string(),
+                    ],
+                    word_cost: None,
+                    connection_cost: None,
+                },
+                Token {
+                    text: "許可".to_string(),
+                    byte_start: 12,
+                    byte_end: 18,
+                    position: 2,
+                    position_length: 1,
+                    word_id: WordId(125567, true),
+                    details: vec![
+                        "名詞".to_string(),
+                        "サ変接続".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "許可".to_string(),
+                        "キョカ".to_string(),
+                        "キョカ".to_string(),
+                    ],
+                    word_cost: None,
+                    connection_cost: None,
+                },
+                Token {
+                    text: "局".to_string(),
+                    byte_start: 18,
+                    byte_end: 21,
+                    position: 3,
+                    position_length: 1,
+                    word_id: WordId(125587, true),
+                    details: vec![
+                        "名詞".to_string(),
+                        "接尾".to_string(),
+                        "一般".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "局".to_string(),
+                        "キョク".to_string(),
+                        "キョク".to_string(),
+                    ],
+                    word_cost: None,
+                    connection_cost: None,
+                },
+                Token {
+                    text: "に".to_string(),
+                    byte_start: 21,
+                    byte_end: 24,
+                    position: 4,
+                    position_length: 1,
+                    word_id: WordId(57120, true),
+                    details: vec![
+                        "助詞".to_string(),
+                        "格助詞".to_string(),
+                        "一般".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "に".to_string(),
+                        "ニ".to_string(),
+                        "ニ".to_string(),
+                    ],
+                    word_cost: None,
+                    connection_cost: None,
+                },
+                Token {
+                    text: "行く".to_string(),
+                    byte_start: 24,
+                    byte_end: 30,
+                    position: 5,
+                    position_length: 1,
+                    word_id: WordId(52263, true),
+                    details: vec![
+                        "動詞".to_string(),
+                        "自立".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "五段・カ行促音便".to_string(),
+                        "基本形".to_string(),
+                        "行く".to_string(),
+                        "イク".to_string(),
+                        "イク".to_string(),
+                    ],
+                    word_cost: None,
+                    connection_cost: None,
+                },
+            ];
+
+            filter.apply(&mut tokens).unwrap();
+
+            assert_eq!(tokens.len(), 3);
+            assert_eq!(tokens[0].text, "東京特許許可局".to_string());
+            assert_eq!(tokens[0].byte_start, 0);
+            assert_eq!(tokens[0].byte_end, 21);
+            assert_eq!(tokens[0].position, 0);
+            assert_eq!(tokens[0].position_length, 4);
+            assert_eq!(
+                tokens[0].details,
+                vec!["名詞", "固有名詞", "*", "*", "*", "*", "*", "*", "*",]
+            );
+
+            // The merge stops at the first token that does not match the tags.
+            assert_eq!(tokens[1].text, "に".to_string());
+            assert_eq!(tokens[1].byte_start, 21);
+            assert_eq!(tokens[1].byte_end, 24);
+            assert_eq!(tokens[1].position, 4);
+            assert_eq!(
+                tokens[1].details,
+                vec!["助詞", "格助詞", "一般", "*", "*", "*", "に", "ニ", "ニ"]
+            );
+            assert_eq!(tokens[2].text, "行く".to_string());
+            assert_eq!(tokens[2].byte_start, 24);
+            assert_eq!(tokens[2].byte_end, 30);
+            assert_eq!(tokens[2].position, 5);
+        }
+    }
 }
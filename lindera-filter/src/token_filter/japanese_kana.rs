@@ -168,6 +168,8 @@ mod tests {
                     "ハネダクウコウ".to_string(),
                     "ハネダクーコー".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "限定".to_string(),
@@ -187,6 +189,8 @@ mod tests {
                     "ゲンテイ".to_string(),
                     "ゲンテイ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "トートバッグ".to_string(),
@@ -196,6 +200,8 @@ mod tests {
                 position_length: 1,
                 word_id: WordId(4294967295, true),
                 details: vec!["UNK".to_string()],
+                word_cost: None,
+                connection_cost: None,
             },
         ];
 
@@ -236,6 +242,8 @@ mod tests {
                     "サイタマ".to_string(),
                     "サイタマ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "県".to_string(),
@@ -255,6 +263,8 @@ mod tests {
                     "ケン".to_string(),
                     "ケン".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "さいたま".to_string(),
@@ -274,6 +284,8 @@ mod tests {
                     "サイタマ".to_string(),
                     "サイタマ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "市".to_string(),
@@ -293,6 +305,8 @@ mod tests {
                     "シ".to_string(),
                     "シ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
         ];
 
@@ -334,6 +348,8 @@ mod tests {
                     "ハネダクウコウ".to_string(),
                     "ハネダクーコー".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "限定".to_string(),
@@ -353,6 +369,8 @@ mod tests {
                     "ゲンテイ".to_string(),
                     "ゲンテイ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "トートバッグ".to_string(),
@@ -362,6 +380,8 @@ mod tests {
                 position_length: 1,
                 word_id: WordId(4294967295, true),
                 details: vec!["UNK".to_string()],
+                word_cost: None,
+                connection_cost: None,
             },
         ];
 
@@ -402,6 +422,8 @@ mod tests {
                     "サイタマ".to_string(),
                     "サイタマ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "県".to_string(),
@@ -421,6 +443,8 @@ mod tests {
                     "ケン".to_string(),
                     "ケン".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "さいたま".to_string(),
@@ -440,6 +464,8 @@ mod tests {
                     "サイタマ".to_string(),
                     "サイタマ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "市".to_string(),
@@ -459,6 +485,8 @@ mod tests {
                     "シ".to_string(),
                     "シ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
         ];
 
@@ -500,6 +528,8 @@ mod tests {
                     "トウキョウ".to_string(),
                     "トーキョー".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "都".to_string(),
@@ -519,6 +549,8 @@ mod tests {
                     "ト".to_string(),
                     "ト".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "あきる野".to_string(),
@@ -538,6 +570,8 @@ mod tests {
                     "アキルノ".to_string(),
                     "アキルノ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "市".to_string(),
@@ -557,6 +591,8 @@ mod tests {
                     "シ".to_string(),
                     "シ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
         ];
 
@@ -598,6 +634,8 @@ mod tests {
                     "ナンボクセン".to_string(),
                     "ナンボクセン".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "四ツ谷".to_string(),
@@ -617,6 +655,8 @@ mod tests {
                     "ヨツヤ".to_string(),
                     "ヨツヤ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "駅".to_string(),
@@ -636,6 +676,8 @@ mod tests {
                     "エキ".to_string(),
                     "エキ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
         ];
 
@@ -91,9 +91,10 @@ impl TokenFilter for JapaneseKatakanaStemTokenFilter {
 
 fn is_katakana(text: &str) -> bool {
     for ch in text.chars() {
-        let block = unicode_blocks::find_unicode_block(ch).unwrap();
-        if block != unicode_blocks::KATAKANA {
-            return false;
+        // Characters outside of any Unicode block are not katakana either.
+        match unicode_blocks::find_unicode_block(ch) {
+            Some(block) if block == unicode_blocks::KATAKANA => {}
+            _ => return false,
         }
     }
 
@@ -198,6 +199,8 @@ mod tests {
                     "バター".to_string(),
                     "バター".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "メーカー".to_string(),
@@ -217,6 +220,8 @@ mod tests {
                     "バター".to_string(),
                     "バター".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
         ];
 
@@ -226,4 +231,46 @@ mod tests {
         assert_eq!(&tokens[0].text, "バター");
         assert_eq!(&tokens[1].text, "メーカ");
     }
+
+    #[test]
+    #[cfg(all(feature = "ipadic", feature = "filter",))]
+    fn test_japanese_katakana_stem_token_filter_apply_non_katakana_ipadic() {
+        let config_str = r#"
+            {
+                "min": 1
+            }
+            "#;
+        let filter = JapaneseKatakanaStemTokenFilter::from_slice(config_str.as_bytes()).unwrap();
+
+        let mut tokens: Vec<Token> = vec![
+            Token {
+                text: "すもー".to_string(),
+                byte_start: 0,
+                byte_end: 9,
+                position: 0,
+                position_length: 1,
+                word_id: WordId(u32::MAX, true),
+                details: vec!["UNK".to_string()],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "\u{2FE0}ー".to_string(),
+                byte_start: 9,
+                byte_end: 15,
+                position: 1,
+                position_length: 1,
+                word_id: WordId(u32::MAX, true),
+                details: vec!["UNK".to_string()],
+                word_cost: None,
+                connection_cost: None,
+            },
+        ];
+
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(&tokens[0].text, "すもー");
+        assert_eq!(&tokens[1].text, "\u{2FE0}ー");
+    }
 }
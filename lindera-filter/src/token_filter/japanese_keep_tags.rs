@@ -7,34 +7,36 @@ use lindera_core::error::LinderaErrorKind;
 use lindera_core::LinderaResult;
 
 use crate::token::Token;
-use crate::token_filter::TokenFilter;
+use crate::token_filter::{format_japanese_tag, japanese_token_tag, TokenFilter};
 
 pub const JAPANESE_KEEP_TAGS_TOKEN_FILTER_NAME: &str = "japanese_keep_tags";
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub struct JapaneseKeepTagsTokenFilterConfig {
     tags: HashSet<String>,
+    /// If true, a token is kept when one of the tags is a prefix of its tag, field by field,
+    /// so that `名詞` keeps all the nouns whatever their subcategories.
+    #[serde(default)]
+    prefix_match: bool,
 }
 
 impl JapaneseKeepTagsTokenFilterConfig {
     pub fn new(tags: HashSet<String>) -> Self {
-        let mut formatted_tags: HashSet<String> = HashSet::new();
-        for tag in tags.iter() {
-            let mut formatted_tag = ["*", "*", "*", "*"];
-
-            let tag_array: Vec<&str> = tag.split(',').collect();
-            for (i, j) in tag_array.iter().enumerate() {
-                formatted_tag[i] = j;
-            }
-
-            formatted_tags.insert(formatted_tag.join(","));
-        }
+        let formatted_tags: HashSet<String> =
+            tags.iter().map(|tag| format_japanese_tag(tag)).collect();
 
         Self {
             tags: formatted_tags,
+            prefix_match: false,
         }
     }
 
+    /// Set whether a tag also keeps the tokens of all its subcategories.
+    pub fn with_prefix_match(mut self, prefix_match: bool) -> Self {
+        self.prefix_match = prefix_match;
+        self
+    }
+
     pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
         let args = serde_json::from_slice::<Value>(data)
             .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))?;
@@ -57,20 +59,43 @@ impl JapaneseKeepTagsTokenFilterConfig {
                     .map(|s| s.to_string())
             })
             .collect::<LinderaResult<HashSet<String>>>()?;
-        Ok(Self::new(tags))
+        let prefix_match = match &value["prefix_match"] {
+            Value::Null => false,
+            prefix_match => prefix_match.as_bool().ok_or_else(|| {
+                LinderaErrorKind::Deserialize
+                    .with_error(anyhow::anyhow!("prefix_match must be boolean"))
+            })?,
+        };
+        Ok(Self::new(tags).with_prefix_match(prefix_match))
     }
 }
 
 /// Keep only tokens with the specified part-of-speech tag.
+/// With `prefix_match`, a tag also keeps the tokens of all its subcategories.
 ///
 #[derive(Clone, Debug)]
 pub struct JapaneseKeepTagsTokenFilter {
     config: JapaneseKeepTagsTokenFilterConfig,
+    /// The tags without their trailing `*` fields, each followed by a comma, so that a tag
+    /// only matches whole fields.
+    prefixes: Vec<String>,
 }
 
 impl JapaneseKeepTagsTokenFilter {
     pub fn new(config: JapaneseKeepTagsTokenFilterConfig) -> Self {
-        Self { config }
+        let prefixes = config
+            .tags
+            .iter()
+            .map(|tag| {
+                let mut prefix = tag.as_str();
+                while let Some(stripped) = prefix.strip_suffix(",*") {
+                    prefix = stripped;
+                }
+                format!("{},", prefix)
+            })
+            .collect();
+
+        Self { config, prefixes }
     }
 
     pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
@@ -86,13 +111,14 @@ impl TokenFilter for JapaneseKeepTagsTokenFilter {
     }
 
     fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
+        if !self.config.prefix_match {
+            tokens.retain(|token| self.config.tags.contains(&japanese_token_tag(token)));
+            return Ok(());
+        }
+
         tokens.retain(|token| {
-            let mut formatted_tags = ["*", "*", "*", "*"];
-            let tags_len = if token.details.len() >= 4 { 4 } else { 1 };
-            for (i, j) in token.details[0..tags_len].iter().enumerate() {
-                formatted_tags[i] = j;
-            }
-            self.config.tags.contains(&formatted_tags.join(","))
+            let tag = format!("{},", japanese_token_tag(token));
+            self.prefixes.iter().any(|prefix| tag.starts_with(prefix))
         });
 
         Ok(())
@@ -101,6 +127,9 @@ impl TokenFilter for JapaneseKeepTagsTokenFilter {
 
 #[cfg(test)]
 mod tests {
+    #[cfg(all(feature = "ipadic", feature = "filter",))]
+    use std::collections::HashSet;
+
     #[cfg(all(feature = "ipadic", feature = "filter",))]
     use lindera_core::word_entry::WordId;
 
@@ -288,6 +317,8 @@ mod tests {
                     "スモモ".to_string(),
                     "スモモ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "も".to_string(),
@@ -307,6 +338,8 @@ mod tests {
                     "モ".to_string(),
                     "モ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "もも".to_string(),
@@ -326,6 +359,8 @@ mod tests {
                     "モモ".to_string(),
                     "モモ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "も".to_string(),
@@ -345,6 +380,8 @@ mod tests {
                     "モ".to_string(),
                     "モ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "もも".to_string(),
@@ -364,6 +401,8 @@ mod tests {
                     "モモ".to_string(),
                     "モモ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "の".to_string(),
@@ -383,6 +422,8 @@ mod tests {
                     "ノ".to_string(),
                     "ノ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "うち".to_string(),
@@ -402,6 +443,8 @@ mod tests {
                     "ウチ".to_string(),
                     "ウチ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
         ];
 
@@ -413,4 +456,116 @@ mod tests {
         assert_eq!(&tokens[2].text, "もも");
         assert_eq!(&tokens[3].text, "うち");
     }
+
+    #[test]
+    #[cfg(all(feature = "ipadic", feature = "filter"))]
+    fn test_japanese_keep_tags_token_filter_config_from_slice_prefix_match_ipadic() {
+        let config_str = r#"
+            {
+                "tags": [
+                    "名詞"
+                ],
+                "prefix_match": true
+            }
+            "#;
+        let config = JapaneseKeepTagsTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
+
+        assert!(config.prefix_match);
+        assert_eq!(
+            config,
+            JapaneseKeepTagsTokenFilterConfig::new(HashSet::from(["名詞".to_string()]))
+                .with_prefix_match(true)
+        );
+
+        let config_str = r#"
+            {
+                "tags": [
+                    "名詞"
+                ],
+                "prefix_match": "yes"
+            }
+            "#;
+        let result = JapaneseKeepTagsTokenFilterConfig::from_slice(config_str.as_bytes());
+
+        assert!(result.is_err());
+    }
+
+    #[cfg(all(feature = "ipadic", feature = "filter"))]
+    fn token(text: &str, byte_start: usize, position: usize, tag: &[&str]) -> Token {
+        let mut details: Vec<String> = tag.iter().map(|field| field.to_string()).collect();
+        details.resize(6, "*".to_string());
+        details.extend([text.to_string(), "*".to_string(), "*".to_string()]);
+
+        Token {
+            text: text.to_string(),
+            byte_start,
+            byte_end: byte_start + text.len(),
+            position,
+            position_length: 1,
+            word_id: WordId(0, true),
+            details,
+            word_cost: None,
+            connection_cost: None,
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "ipadic", feature = "filter"))]
+    fn test_japanese_keep_tags_token_filter_apply_prefix_match_ipadic() {
+        let mut tokens: Vec<Token> = vec![
+            token("田中", 0, 0, &["名詞", "固有名詞", "人名", "姓"]),
+            token("さん", 6, 1, &["名詞", "接尾", "人名"]),
+            token("は", 12, 2, &["助詞", "係助詞"]),
+            token("東京", 15, 3, &["名詞", "固有名詞", "地域", "一般"]),
+            token("に", 21, 4, &["助詞", "格助詞", "一般"]),
+            token("住む", 24, 5, &["動詞", "自立"]),
+        ];
+
+        let config_str = r#"
+            {
+                "tags": [
+                    "名詞"
+                ],
+                "prefix_match": true
+            }
+            "#;
+        let filter = JapaneseKeepTagsTokenFilter::from_slice(config_str.as_bytes()).unwrap();
+        filter.apply(&mut tokens).unwrap();
+
+        // "名詞" keeps the nouns of any depth
+        assert_eq!(
+            tokens.iter().map(|t| t.text.as_str()).collect::<Vec<_>>(),
+            vec!["田中", "さん", "東京"]
+        );
+
+        let config_str = r#"
+            {
+                "tags": [
+                    "名詞,固有名詞"
+                ],
+                "prefix_match": true
+            }
+            "#;
+        let filter = JapaneseKeepTagsTokenFilter::from_slice(config_str.as_bytes()).unwrap();
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(
+            tokens.iter().map(|t| t.text.as_str()).collect::<Vec<_>>(),
+            vec!["田中", "東京"]
+        );
+
+        // a tag only matches whole fields
+        let config_str = r#"
+            {
+                "tags": [
+                    "名詞,固有"
+                ],
+                "prefix_match": true
+            }
+            "#;
+        let filter = JapaneseKeepTagsTokenFilter::from_slice(config_str.as_bytes()).unwrap();
+        filter.apply(&mut tokens).unwrap();
+
+        assert!(tokens.is_empty());
+    }
 }
@@ -1,4 +1,5 @@
 use std::collections::HashSet;
+use std::mem;
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -7,10 +8,13 @@ use lindera_core::error::LinderaErrorKind;
 use lindera_core::LinderaResult;
 
 use crate::token::Token;
-use crate::token_filter::TokenFilter;
+use crate::token_filter::{format_japanese_tag, japanese_token_tag, TokenFilter};
 
 pub const JAPANESE_NUMBER_TOKEN_FILTER_NAME: &str = "japanese_number";
 
+/// The part-of-speech tag of the number tokens to be concatenated if no tags are specified.
+const NUMBER_TAG: &str = "名詞,数,*,*";
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub struct JapaneseNumberTokenFilterConfig {
     tags: Option<HashSet<String>>,
@@ -20,17 +24,8 @@ impl JapaneseNumberTokenFilterConfig {
     pub fn new(tags: Option<HashSet<String>>) -> Self {
         match tags {
             Some(tags) => {
-                let mut formatted_tags: HashSet<String> = HashSet::new();
-                for tag in tags.iter() {
-                    let mut formatted_tag = ["*", "*", "*", "*"];
-
-                    let tag_array: Vec<&str> = tag.split(',').collect();
-                    for (i, j) in tag_array.iter().enumerate() {
-                        formatted_tag[i] = j;
-                    }
-
-                    formatted_tags.insert(formatted_tag.join(","));
-                }
+                let formatted_tags: HashSet<String> =
+                    tags.iter().map(|tag| format_japanese_tag(tag)).collect();
 
                 Self {
                     tags: Some(formatted_tags),
@@ -78,6 +73,7 @@ impl JapaneseNumberTokenFilterConfig {
 }
 
 /// Convert tokens representing Japanese numerals, including Kanji numerals, to Arabic numerals.
+/// Runs of adjacent number tokens (e.g. "五十" and "四") are concatenated into a single token ("54").
 ///
 #[derive(Clone, Debug)]
 pub struct JapaneseNumberTokenFilter {
@@ -102,42 +98,94 @@ impl TokenFilter for JapaneseNumberTokenFilter {
     }
 
     fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
-        for token in tokens.iter_mut() {
-            let mut tag_vec = ["*", "*", "*", "*"];
-            let tags_len = if token.details.len() >= 4 { 4 } else { 1 };
-            for (i, j) in token.details[0..tags_len].iter().enumerate() {
-                tag_vec[i] = j;
+        let mut new_tokens: Vec<Token> = Vec::with_capacity(tokens.len());
+        let mut number_token_opt: Option<Token> = None;
+
+        for token in tokens.drain(..) {
+            let tag = japanese_token_tag(&token);
+
+            let is_number = match self.config.tags {
+                Some(ref tags) => tags.contains(&tag),
+                None => tag == NUMBER_TAG,
+            };
+
+            if is_number {
+                match number_token_opt.take() {
+                    // Concatenate the adjacent number token.
+                    Some(mut number_token) if number_token.byte_end == token.byte_start => {
+                        concat_token(&mut number_token, &token);
+                        number_token_opt = Some(number_token);
+                    }
+                    Some(number_token) => {
+                        new_tokens.push(convert_token(number_token));
+                        number_token_opt = Some(token);
+                    }
+                    None => {
+                        number_token_opt = Some(token);
+                    }
+                }
+                continue;
+            }
+
+            if let Some(number_token) = number_token_opt.take() {
+                new_tokens.push(convert_token(number_token));
             }
-            let tag = tag_vec.join(",");
 
             match self.config.tags {
-                Some(ref tags) => {
-                    if tags.contains(&tag) {
-                        token.text = to_arabic_numerals(&token.text);
-                    }
+                Some(_) => {
+                    new_tokens.push(token);
                 }
                 None => {
                     // If a tag is omitted, all tokans are covered.
-                    token.text = to_arabic_numerals(&token.text);
+                    new_tokens.push(convert_token(token));
                 }
             }
         }
 
+        if let Some(number_token) = number_token_opt.take() {
+            new_tokens.push(convert_token(number_token));
+        }
+
+        mem::swap(tokens, &mut new_tokens);
+
         Ok(())
     }
 }
 
-fn adjust_digits(num: &str, base: &str, digit: &str) -> String {
+fn concat_token(token1: &mut Token, token2: &Token) {
+    token1.text = format!("{}{}", token1.text, token2.text);
+    token1.byte_end = token2.byte_end;
+    token1.position_length += token2.position_length;
+
+    // Only the part-of-speech tag is valid for the concatenated token.
+    for detail in token1.details.iter_mut().skip(4) {
+        *detail = "*".to_string();
+    }
+}
+
+fn convert_token(mut token: Token) -> Token {
+    token.text = to_arabic_numerals(&token.text);
+    token
+}
+
+fn adjust_digits(num: &str, base: &str, digit: &str) -> Option<String> {
     let zero_str = format!("{}{}", base, digit);
-    let zero_len = zero_str.len() - num.len();
+    // The number does not fit below the unit (e.g. "五六" in "三十五六"), so the numeral is malformed.
+    let zero_len = zero_str.len().checked_sub(num.len())?;
     let zeros = &zero_str[0..zero_len];
 
     let mut num_str = num.to_owned();
     num_str.insert_str(0, zeros);
-    num_str
+    Some(num_str)
 }
 
+/// Convert the numeral to Arabic numerals.
+/// A malformed numeral (e.g. "三十五六" or "十12") is returned as it is.
 fn to_arabic_numerals(from_str: &str) -> String {
+    parse_numerals(from_str).unwrap_or_else(|| from_str.to_string())
+}
+
+fn parse_numerals(from_str: &str) -> Option<String> {
     let mut num_buf = String::new();
     let mut digit = String::new();
 
@@ -157,7 +205,7 @@ fn to_arabic_numerals(from_str: &str) -> String {
             '8' | '８' | '八' => num_buf.insert(0, '8'),
             '9' | '９' | '九' => num_buf.insert(0, '9'),
             '十' | '拾' => {
-                num_buf = adjust_digits(&num_buf, "0", &digit);
+                num_buf = adjust_digits(&num_buf, "0", &digit)?;
 
                 match i.peek() {
                     Some('百') | Some('千') | Some('万') | Some('億') | Some('兆') | Some('京')
@@ -172,7 +220,7 @@ fn to_arabic_numerals(from_str: &str) -> String {
                 }
             }
             '百' => {
-                num_buf = adjust_digits(&num_buf, "00", &digit);
+                num_buf = adjust_digits(&num_buf, "00", &digit)?;
 
                 match i.peek() {
                     Some('千') | Some('万') | Some('億') | Some('兆') | Some('京') | Some('垓')
@@ -187,7 +235,7 @@ fn to_arabic_numerals(from_str: &str) -> String {
                 }
             }
             '千' => {
-                num_buf = adjust_digits(&num_buf, "000", &digit);
+                num_buf = adjust_digits(&num_buf, "000", &digit)?;
 
                 match i.peek() {
                     Some('万') | Some('億') | Some('兆') | Some('京') | Some('垓') | None => {
@@ -203,7 +251,7 @@ fn to_arabic_numerals(from_str: &str) -> String {
             '万' => {
                 digit = "0000".to_string();
 
-                num_buf = adjust_digits(&num_buf, "", &digit);
+                num_buf = adjust_digits(&num_buf, "", &digit)?;
 
                 match i.peek() {
                     Some('億') | Some('兆') | Some('京') | Some('垓') | None => {
@@ -219,7 +267,7 @@ fn to_arabic_numerals(from_str: &str) -> String {
             '億' => {
                 digit = "00000000".to_string();
 
-                num_buf = adjust_digits(&num_buf, "", &digit);
+                num_buf = adjust_digits(&num_buf, "", &digit)?;
 
                 match i.peek() {
                     Some('兆') | Some('京') | Some('垓') | None => {
@@ -235,7 +283,7 @@ fn to_arabic_numerals(from_str: &str) -> String {
             '兆' => {
                 digit = "000000000000".to_string();
 
-                num_buf = adjust_digits(&num_buf, "", &digit);
+                num_buf = adjust_digits(&num_buf, "", &digit)?;
 
                 match i.peek() {
                     Some('京') | Some('垓') | None => {
@@ -251,7 +299,7 @@ fn to_arabic_numerals(from_str: &str) -> String {
             '京' => {
                 digit = "0000000000000000".to_string();
 
-                num_buf = adjust_digits(&num_buf, "", &digit);
+                num_buf = adjust_digits(&num_buf, "", &digit)?;
 
                 match i.peek() {
                     Some('垓') | None => {
@@ -267,7 +315,7 @@ fn to_arabic_numerals(from_str: &str) -> String {
             '垓' => {
                 digit = "00000000000000000000".to_string();
 
-                num_buf = adjust_digits(&num_buf, "", &digit);
+                num_buf = adjust_digits(&num_buf, "", &digit)?;
 
                 if i.peek().is_none() {
                     // If the first character is a '0', the '1' has been omitted.
@@ -282,7 +330,7 @@ fn to_arabic_numerals(from_str: &str) -> String {
         }
     }
 
-    num_buf
+    Some(num_buf)
 }
 
 #[cfg(test)]
@@ -843,6 +891,31 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(all(feature = "ipadic", feature = "filter",))]
+    fn test_to_number_str_malformed_ipadic() {
+        // Concatenated runs of number tokens are not always well-formed numerals.
+        {
+            let s = "三十五六";
+            assert_eq!(to_arabic_numerals(s), String::from_str("三十五六").unwrap());
+        }
+
+        {
+            let s = "十12";
+            assert_eq!(to_arabic_numerals(s), String::from_str("十12").unwrap());
+        }
+
+        {
+            let s = "百２３４";
+            assert_eq!(to_arabic_numerals(s), String::from_str("百２３４").unwrap());
+        }
+
+        {
+            let s = "万12345";
+            assert_eq!(to_arabic_numerals(s), String::from_str("万12345").unwrap());
+        }
+    }
+
     #[test]
     #[cfg(all(feature = "ipadic", feature = "filter",))]
     fn test_japanese_number_token_filter_config_from_slice_ipadic() {
@@ -945,6 +1018,8 @@ mod tests {
                     "イチ".to_string(),
                     "イチ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             }];
 
             filter.apply(&mut tokens).unwrap();
@@ -972,6 +1047,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             }];
 
             filter.apply(&mut tokens).unwrap();
@@ -1001,6 +1078,8 @@ mod tests {
                             "*".to_string(),
                             "*".to_string(),
                         ],
+                        word_cost: None,
+                        connection_cost: None,
                     },
                 ];
 
@@ -1030,6 +1109,8 @@ mod tests {
                         "スズキ".to_string(),
                         "スズキ".to_string(),
                     ],
+                    word_cost: None,
+                    connection_cost: None,
                 },
                 Token {
                     text: "一郎".to_string(),
@@ -1049,6 +1130,8 @@ mod tests {
                         "イチロウ".to_string(),
                         "イチロー".to_string(),
                     ],
+                    word_cost: None,
+                    connection_cost: None,
                 },
             ];
 
@@ -1060,6 +1143,279 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(all(feature = "ipadic", feature = "filter",))]
+    fn test_japanese_number_token_filter_apply_concat_ipadic() {
+        let config_str = r#"
+            {
+                "tags": [
+                    "名詞,数"
+                ]
+            }
+            "#;
+        let filter = JapaneseNumberTokenFilter::from_slice(config_str.as_bytes()).unwrap();
+
+        {
+            let mut tokens: Vec<Token> = vec![
+                Token {
+                    text: "一".to_string(),
+                    byte_start: 0,
+                    byte_end: 3,
+                    position: 0,
+                    position_length: 1,
+                    word_id: WordId(102657, true),
+                    details: vec![
+                        "名詞".to_string(),
+                        "数".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "一".to_string(),
+                        "イチ".to_string(),
+                        "イチ".to_string(),
+                    ],
+                    word_cost: None,
+                    connection_cost: None,
+                },
+                Token {
+                    text: "億".to_string(),
+                    byte_start: 3,
+                    byte_end: 6,
+                    position: 1,
+                    position_length: 1,
+                    word_id: WordId(102931, true),
+                    details: vec![
+                        "名詞".to_string(),
+                        "数".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "億".to_string(),
+                        "オク".to_string(),
+                        "オク".to_string(),
+                    ],
+                    word_cost: None,
+                    connection_cost: None,
+                },
+                Token {
+                    text: "二".to_string(),
+                    byte_start: 6,
+                    byte_end: 9,
+                    position: 2,
+                    position_length: 1,
+                    word_id: WordId(102848, true),
+                    details: vec![
+                        "名詞".to_string(),
+                        "数".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "二".to_string(),
+                        "ニ".to_string(),
+                        "ニ".to_string(),
+                    ],
+                    word_cost: None,
+                    connection_cost: None,
+                },
+                Token {
+                    text: "千".to_string(),
+                    byte_start: 9,
+                    byte_end: 12,
+                    position: 3,
+                    position_length: 1,
+                    word_id: WordId(117232, true),
+                    details: vec![
+                        "名詞".to_string(),
+                        "数".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "千".to_string(),
+                        "セン".to_string(),
+                        "セン".to_string(),
+                    ],
+                    word_cost: None,
+                    connection_cost: None,
+                },
+                Token {
+                    text: "三".to_string(),
+                    byte_start: 12,
+                    byte_end: 15,
+                    position: 4,
+                    position_length: 1,
+                    word_id: WordId(102661, true),
+                    details: vec![
+                        "名詞".to_string(),
+                        "数".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "三".to_string(),
+                        "サン".to_string(),
+                        "サン".to_string(),
+                    ],
+                    word_cost: None,
+                    connection_cost: None,
+                },
+                Token {
+                    text: "百".to_string(),
+                    byte_start: 15,
+                    byte_end: 18,
+                    position: 5,
+                    position_length: 1,
+                    word_id: WordId(161542, true),
+                    details: vec![
+                        "名詞".to_string(),
+                        "数".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "百".to_string(),
+                        "ヒャク".to_string(),
+                        "ヒャク".to_string(),
+                    ],
+                    word_cost: None,
+                    connection_cost: None,
+                },
+                Token {
+                    text: "万".to_string(),
+                    byte_start: 18,
+                    byte_end: 21,
+                    position: 6,
+                    position_length: 1,
+                    word_id: WordId(102729, true),
+                    details: vec![
+                        "名詞".to_string(),
+                        "数".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "万".to_string(),
+                        "マン".to_string(),
+                        "マン".to_string(),
+                    ],
+                    word_cost: None,
+                    connection_cost: None,
+                },
+            ];
+
+            filter.apply(&mut tokens).unwrap();
+
+            assert_eq!(tokens.len(), 1);
+            assert_eq!(&tokens[0].text, "123000000");
+            assert_eq!(tokens[0].byte_start, 0);
+            assert_eq!(tokens[0].byte_end, 21);
+            assert_eq!(tokens[0].position, 0);
+            assert_eq!(tokens[0].position_length, 7);
+            assert_eq!(&tokens[0].details[0], "名詞");
+            assert_eq!(&tokens[0].details[1], "数");
+        }
+
+        {
+            let mut tokens: Vec<Token> = vec![
+                Token {
+                    text: "２".to_string(),
+                    byte_start: 0,
+                    byte_end: 3,
+                    position: 0,
+                    position_length: 1,
+                    word_id: WordId(393070, true),
+                    details: vec![
+                        "名詞".to_string(),
+                        "数".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "２".to_string(),
+                        "ニ".to_string(),
+                        "ニ".to_string(),
+                    ],
+                    word_cost: None,
+                    connection_cost: None,
+                },
+                Token {
+                    text: "千".to_string(),
+                    byte_start: 3,
+                    byte_end: 6,
+                    position: 1,
+                    position_length: 1,
+                    word_id: WordId(117232, true),
+                    details: vec![
+                        "名詞".to_string(),
+                        "数".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "千".to_string(),
+                        "セン".to_string(),
+                        "セン".to_string(),
+                    ],
+                    word_cost: None,
+                    connection_cost: None,
+                },
+                Token {
+                    text: "５".to_string(),
+                    byte_start: 6,
+                    byte_end: 9,
+                    position: 2,
+                    position_length: 1,
+                    word_id: WordId(393073, true),
+                    details: vec![
+                        "名詞".to_string(),
+                        "数".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "５".to_string(),
+                        "ゴ".to_string(),
+                        "ゴ".to_string(),
+                    ],
+                    word_cost: None,
+                    connection_cost: None,
+                },
+                Token {
+                    text: "百".to_string(),
+                    byte_start: 9,
+                    byte_end: 12,
+                    position: 3,
+                    position_length: 1,
+                    word_id: WordId(161542, true),
+                    details: vec![
+                        "名詞".to_string(),
+                        "数".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "百".to_string(),
+                        "ヒャク".to_string(),
+                        "ヒャク".to_string(),
+                    ],
+                    word_cost: None,
+                    connection_cost: None,
+                },
+            ];
+
+            filter.apply(&mut tokens).unwrap();
+
+            assert_eq!(tokens.len(), 1);
+            assert_eq!(&tokens[0].text, "2500");
+            assert_eq!(tokens[0].byte_start, 0);
+            assert_eq!(tokens[0].byte_end, 12);
+            assert_eq!(tokens[0].position_length, 4);
+        }
+    }
+
     #[test]
     #[cfg(all(feature = "ipadic", feature = "filter",))]
     fn test_japanese_number_token_filter_apply_empty_ipadic() {
@@ -1088,6 +1444,8 @@ mod tests {
                     "イチ".to_string(),
                     "イチ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             }];
 
             filter.apply(&mut tokens).unwrap();
@@ -1115,6 +1473,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             }];
 
             filter.apply(&mut tokens).unwrap();
@@ -1144,6 +1504,8 @@ mod tests {
                             "*".to_string(),
                             "*".to_string(),
                         ],
+                        word_cost: None,
+                        connection_cost: None,
                     },
                 ];
 
@@ -1173,6 +1535,8 @@ mod tests {
                         "スズキ".to_string(),
                         "スズキ".to_string(),
                     ],
+                    word_cost: None,
+                    connection_cost: None,
                 },
                 Token {
                     text: "一郎".to_string(),
@@ -1192,6 +1556,8 @@ mod tests {
                         "イチロウ".to_string(),
                         "イチロー".to_string(),
                     ],
+                    word_cost: None,
+                    connection_cost: None,
                 },
             ];
 
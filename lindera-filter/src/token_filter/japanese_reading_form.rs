@@ -1,22 +1,49 @@
+use kanaria::string::UCSStr;
 use serde::{Deserialize, Serialize};
 
 use lindera_core::error::LinderaErrorKind;
 use lindera_core::LinderaResult;
-use lindera_dictionary::DictionaryKind;
+use lindera_dictionary::{DetailSchema, DictionaryKind};
 
 use crate::token::Token;
-use crate::token_filter::TokenFilter;
+use crate::token_filter::japanese_kana::KanaKind;
+use crate::token_filter::{check_detail_field, TokenFilter};
 
 pub const JAPANESE_READING_FORM_TOKEN_FILTER_NAME: &str = "japanese_reading_form";
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub struct JapaneseReadingFormTokenFilterConfig {
     kind: DictionaryKind,
+    #[serde(default = "default_to")]
+    to: KanaKind,
+    /// The layout of the details, if it differs from the one of `kind`.
+    #[serde(default)]
+    detail_schema: Option<DetailSchema>,
+}
+
+fn default_to() -> KanaKind {
+    KanaKind::Katakana
 }
 
 impl JapaneseReadingFormTokenFilterConfig {
     pub fn new(kind: DictionaryKind) -> Self {
-        Self { kind }
+        Self {
+            kind,
+            to: default_to(),
+            detail_schema: None,
+        }
+    }
+
+    /// Set the kana the reading is written in, katakana by default.
+    pub fn with_to(mut self, to: KanaKind) -> Self {
+        self.to = to;
+        self
+    }
+
+    /// Set the layout of the details, if it differs from the one of `kind`.
+    pub fn with_detail_schema(mut self, detail_schema: DetailSchema) -> Self {
+        self.detail_schema = Some(detail_schema);
+        self
     }
 
     pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
@@ -31,22 +58,37 @@ impl JapaneseReadingFormTokenFilterConfig {
 }
 
 /// Replace the text of a token with the reading of the text as registered in the morphological dictionary.
-/// The reading is in katakana.
+/// The reading is in katakana by default, or in hiragana if `to` is set to `hiragana`.
+/// Tokens without a registered reading (`*`) are left unchanged.
 ///
 #[derive(Clone, Debug)]
 pub struct JapaneseReadingFormTokenFilter {
-    config: JapaneseReadingFormTokenFilterConfig,
+    to: KanaKind,
+    detail_schema: DetailSchema,
 }
 
 impl JapaneseReadingFormTokenFilter {
-    pub fn new(config: JapaneseReadingFormTokenFilterConfig) -> Self {
-        Self { config }
+    pub fn new(config: JapaneseReadingFormTokenFilterConfig) -> LinderaResult<Self> {
+        let custom_schema = config.detail_schema.is_some();
+        let detail_schema = config
+            .detail_schema
+            .unwrap_or_else(|| config.kind.detail_schema());
+        check_detail_field(
+            JAPANESE_READING_FORM_TOKEN_FILTER_NAME,
+            "reading",
+            detail_schema.reading,
+            &config.kind,
+            custom_schema,
+        )?;
+
+        Ok(Self {
+            to: config.to,
+            detail_schema,
+        })
     }
 
     pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
-        Ok(Self::new(JapaneseReadingFormTokenFilterConfig::from_slice(
-            data,
-        )?))
+        Self::new(JapaneseReadingFormTokenFilterConfig::from_slice(data)?)
     }
 }
 
@@ -57,27 +99,17 @@ impl TokenFilter for JapaneseReadingFormTokenFilter {
 
     fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
         for token in tokens.iter_mut() {
-            if token.details[0] == "UNK" {
-                // NOOP
-                continue;
-            }
-            match self.config.kind {
-                #[cfg(feature = "ipadic")]
-                DictionaryKind::IPADIC => {
-                    token.text = token.details[7].to_string();
-                }
-                #[cfg(feature = "ipadic-neologd")]
-                DictionaryKind::IPADICNEologd => {
-                    token.text = token.details[7].to_string();
-                }
-                #[cfg(feature = "unidic")]
-                DictionaryKind::UniDic => {
-                    token.text = token.details[6].to_string();
-                }
-                _ => {
+            let reading = match token.reading(&self.detail_schema) {
+                Some(reading) => reading,
+                None => {
                     // NOOP
+                    continue;
                 }
-            }
+            };
+            token.text = match self.to {
+                KanaKind::Hiragana => UCSStr::from_str(reading).hiragana().to_string(),
+                KanaKind::Katakana => reading.to_string(),
+            };
         }
 
         Ok(())
@@ -102,6 +134,8 @@ mod tests {
     ))]
     use lindera_dictionary::DictionaryKind;
 
+    #[cfg(all(feature = "ipadic", feature = "filter"))]
+    use crate::token_filter::japanese_kana::KanaKind;
     #[cfg(any(
         all(feature = "ipadic", feature = "filter",),
         all(feature = "unidic", feature = "filter",)
@@ -125,6 +159,24 @@ mod tests {
             JapaneseReadingFormTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
 
         assert_eq!(config.kind, DictionaryKind::IPADIC);
+        assert_eq!(config.to, KanaKind::Katakana);
+
+        let config_str = r#"
+            {
+                "kind": "ipadic",
+                "to": "hiragana"
+            }
+            "#;
+        let config =
+            JapaneseReadingFormTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
+
+        assert_eq!(config.kind, DictionaryKind::IPADIC);
+        assert_eq!(config.to, KanaKind::Hiragana);
+        assert_eq!(
+            config,
+            JapaneseReadingFormTokenFilterConfig::new(DictionaryKind::IPADIC)
+                .with_to(KanaKind::Hiragana)
+        );
     }
 
     #[cfg(all(feature = "unidic", feature = "filter"))]
@@ -196,6 +248,8 @@ mod tests {
                     "ハネダクウコウ".to_string(),
                     "ハネダクーコー".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "限定".to_string(),
@@ -215,6 +269,8 @@ mod tests {
                     "ゲンテイ".to_string(),
                     "ゲンテイ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "トートバッグ".to_string(),
@@ -224,6 +280,8 @@ mod tests {
                 position_length: 1,
                 word_id: WordId(4294967295, true),
                 details: vec!["UNK".to_string()],
+                word_cost: None,
+                connection_cost: None,
             },
         ];
 
@@ -235,6 +293,103 @@ mod tests {
         assert_eq!(&tokens[2].text, "トートバッグ");
     }
 
+    #[cfg(all(feature = "ipadic", feature = "filter"))]
+    #[test]
+    fn test_japanese_reading_form_token_filter_apply_hiragana_ipadic() {
+        let config_str = r#"
+            {
+                "kind": "ipadic",
+                "to": "hiragana"
+            }
+            "#;
+        let filter = JapaneseReadingFormTokenFilter::from_slice(config_str.as_bytes()).unwrap();
+
+        let mut tokens: Vec<Token> = vec![
+            Token {
+                text: "東京".to_string(),
+                byte_start: 0,
+                byte_end: 6,
+                position: 0,
+                position_length: 1,
+                word_id: WordId(294912, true),
+                details: vec![
+                    "名詞".to_string(),
+                    "固有名詞".to_string(),
+                    "地域".to_string(),
+                    "一般".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "東京".to_string(),
+                    "トウキョウ".to_string(),
+                    "トーキョー".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "で".to_string(),
+                byte_start: 6,
+                byte_end: 9,
+                position: 1,
+                position_length: 1,
+                word_id: WordId(59873, true),
+                details: vec![
+                    "助詞".to_string(),
+                    "格助詞".to_string(),
+                    "一般".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "で".to_string(),
+                    "デ".to_string(),
+                    "デ".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "ＵＳＢ".to_string(),
+                byte_start: 9,
+                byte_end: 18,
+                position: 2,
+                position_length: 1,
+                word_id: WordId(1, true),
+                details: vec![
+                    "名詞".to_string(),
+                    "固有名詞".to_string(),
+                    "組織".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "ＵＳＢ".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "トートバッグ".to_string(),
+                byte_start: 18,
+                byte_end: 36,
+                position: 3,
+                position_length: 1,
+                word_id: WordId(4294967295, true),
+                details: vec!["UNK".to_string()],
+                word_cost: None,
+                connection_cost: None,
+            },
+        ];
+
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(tokens.len(), 4);
+        assert_eq!(&tokens[0].text, "とうきょう");
+        assert_eq!(&tokens[1].text, "で");
+        assert_eq!(&tokens[2].text, "ＵＳＢ");
+        assert_eq!(&tokens[3].text, "トートバッグ");
+    }
+
     #[cfg(all(feature = "unidic", feature = "filter",))]
     #[test]
     fn test_japanese_reading_form_token_filter_apply_unidic() {
@@ -272,6 +427,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "空港".to_string(),
@@ -299,6 +456,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "限定".to_string(),
@@ -326,6 +485,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "トート".to_string(),
@@ -353,6 +514,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "バッグ".to_string(),
@@ -380,6 +543,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
         ];
 
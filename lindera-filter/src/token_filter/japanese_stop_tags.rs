@@ -7,7 +7,7 @@ use lindera_core::error::LinderaErrorKind;
 use lindera_core::LinderaResult;
 
 use crate::token::Token;
-use crate::token_filter::TokenFilter;
+use crate::token_filter::{format_japanese_tag, japanese_token_tag, TokenFilter};
 
 pub const JAPANESE_STOP_TAGS_TOKEN_FILTER_NAME: &str = "japanese_stop_tags";
 
@@ -18,17 +18,8 @@ pub struct JapaneseStopTagsTokenFilterConfig {
 
 impl JapaneseStopTagsTokenFilterConfig {
     pub fn new(tags: HashSet<String>) -> Self {
-        let mut formatted_tags: HashSet<String> = HashSet::new();
-        for tag in tags.iter() {
-            let mut formatted_tag = ["*", "*", "*", "*"];
-
-            let tag_array: Vec<&str> = tag.split(',').collect();
-            for (i, j) in tag_array.iter().enumerate() {
-                formatted_tag[i] = j;
-            }
-
-            formatted_tags.insert(formatted_tag.join(","));
-        }
+        let formatted_tags: HashSet<String> =
+            tags.iter().map(|tag| format_japanese_tag(tag)).collect();
 
         Self {
             tags: formatted_tags,
@@ -86,14 +77,7 @@ impl TokenFilter for JapaneseStopTagsTokenFilter {
     }
 
     fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
-        tokens.retain(|token| {
-            let mut formatted_tags = ["*", "*", "*", "*"];
-            let tags_len = if token.details.len() >= 4 { 4 } else { 1 };
-            for (i, j) in token.details[0..tags_len].iter().enumerate() {
-                formatted_tags[i] = j;
-            }
-            !self.config.tags.contains(&formatted_tags.join(","))
-        });
+        tokens.retain(|token| !self.config.tags.contains(&japanese_token_tag(token)));
 
         Ok(())
     }
@@ -246,6 +230,8 @@ mod tests {
                     "スモモ".to_string(),
                     "スモモ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "も".to_string(),
@@ -265,6 +251,8 @@ mod tests {
                     "モ".to_string(),
                     "モ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "もも".to_string(),
@@ -284,6 +272,8 @@ mod tests {
                     "モモ".to_string(),
                     "モモ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "も".to_string(),
@@ -303,6 +293,8 @@ mod tests {
                     "モ".to_string(),
                     "モ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "もも".to_string(),
@@ -322,6 +314,8 @@ mod tests {
                     "モモ".to_string(),
                     "モモ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "の".to_string(),
@@ -341,6 +335,8 @@ mod tests {
                     "ノ".to_string(),
                     "ノ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "うち".to_string(),
@@ -360,6 +356,8 @@ mod tests {
                     "ウチ".to_string(),
                     "ウチ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
         ];
 
@@ -371,4 +369,113 @@ mod tests {
         assert_eq!(&tokens[2].text, "もも");
         assert_eq!(&tokens[3].text, "うち");
     }
+
+    #[test]
+    #[cfg(all(feature = "ipadic", feature = "filter"))]
+    fn test_japanese_stop_tags_token_filter_apply_particles_and_auxiliary_verbs_ipadic() {
+        let config_str = r#"
+            {
+                "tags": [
+                    "助詞",
+                    "助詞,格助詞",
+                    "助詞,格助詞,一般",
+                    "助動詞"
+                ]
+            }
+            "#;
+        let filter = JapaneseStopTagsTokenFilter::from_slice(config_str.as_bytes()).unwrap();
+
+        let mut tokens: Vec<Token> = vec![
+            Token {
+                text: "本".to_string(),
+                byte_start: 0,
+                byte_end: 3,
+                position: 0,
+                position_length: 1,
+                word_id: WordId(390225, true),
+                details: vec![
+                    "名詞".to_string(),
+                    "一般".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "本".to_string(),
+                    "ホン".to_string(),
+                    "ホン".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "を".to_string(),
+                byte_start: 3,
+                byte_end: 6,
+                position: 1,
+                position_length: 1,
+                word_id: WordId(80998, true),
+                details: vec![
+                    "助詞".to_string(),
+                    "格助詞".to_string(),
+                    "一般".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "を".to_string(),
+                    "ヲ".to_string(),
+                    "ヲ".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "読ん".to_string(),
+                byte_start: 6,
+                byte_end: 12,
+                position: 2,
+                position_length: 1,
+                word_id: WordId(380024, true),
+                details: vec![
+                    "動詞".to_string(),
+                    "自立".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "五段・マ行".to_string(),
+                    "連用タ接続".to_string(),
+                    "読む".to_string(),
+                    "ヨン".to_string(),
+                    "ヨン".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "だ".to_string(),
+                byte_start: 12,
+                byte_end: 15,
+                position: 3,
+                position_length: 1,
+                word_id: WordId(52926, true),
+                details: vec![
+                    "助動詞".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "特殊・タ".to_string(),
+                    "基本形".to_string(),
+                    "だ".to_string(),
+                    "ダ".to_string(),
+                    "ダ".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+        ];
+
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(&tokens[0].text, "本");
+        assert_eq!(&tokens[1].text, "読ん");
+    }
 }
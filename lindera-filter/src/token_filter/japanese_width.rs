@@ -0,0 +1,293 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use lindera_core::error::LinderaErrorKind;
+use lindera_core::LinderaResult;
+
+use crate::token::Token;
+use crate::token_filter::TokenFilter;
+
+pub const JAPANESE_WIDTH_TOKEN_FILTER_NAME: &str = "japanese_width";
+
+/// The first character of the half-width katakana block (`｡`, U+FF61).
+const HALF_WIDTH_KATAKANA_START: u32 = 0xFF61;
+
+/// The full-width characters of the half-width katakana block, from `｡` (U+FF61) to `ﾟ` (U+FF9F).
+const FULL_WIDTH_KATAKANA: [char; 63] = [
+    '。', '「', '」', '、', '・', 'ヲ', 'ァ', 'ィ', 'ゥ', 'ェ', 'ォ', 'ャ', 'ュ', 'ョ', 'ッ', 'ー',
+    'ア', 'イ', 'ウ', 'エ', 'オ', 'カ', 'キ', 'ク', 'ケ', 'コ', 'サ', 'シ', 'ス', 'セ', 'ソ', 'タ',
+    'チ', 'ツ', 'テ', 'ト', 'ナ', 'ニ', 'ヌ', 'ネ', 'ノ', 'ハ', 'ヒ', 'フ', 'ヘ', 'ホ', 'マ', 'ミ',
+    'ム', 'メ', 'モ', 'ヤ', 'ユ', 'ヨ', 'ラ', 'リ', 'ル', 'レ', 'ロ', 'ワ', 'ン', '゛', '゜',
+];
+
+/// The half-width voiced sound mark `ﾞ`.
+const HALF_WIDTH_VOICED_MARK: char = 'ﾞ';
+
+/// The half-width semi-voiced sound mark `ﾟ`.
+const HALF_WIDTH_SEMI_VOICED_MARK: char = 'ﾟ';
+
+/// The offset between a full-width ASCII character and the ASCII character.
+const FULL_WIDTH_ASCII_OFFSET: u32 = 0xFEE0;
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct JapaneseWidthTokenFilterConfig {
+    /// Convert the full-width digits and Latin letters (`０`-`９`, `Ａ`-`Ｚ`, `ａ`-`ｚ`) to ASCII.
+    #[serde(default = "default_true")]
+    to_half_width_ascii: bool,
+
+    /// Convert the half-width katakana (`ｱ`, `ｶﾞ`, ...) to full-width katakana.
+    #[serde(default = "default_true")]
+    to_full_width_kana: bool,
+}
+
+impl JapaneseWidthTokenFilterConfig {
+    pub fn new(to_half_width_ascii: bool, to_full_width_kana: bool) -> Self {
+        Self {
+            to_half_width_ascii,
+            to_full_width_kana,
+        }
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        serde_json::from_slice::<JapaneseWidthTokenFilterConfig>(data)
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+
+    pub fn from_value(value: &Value) -> LinderaResult<Self> {
+        serde_json::from_value::<JapaneseWidthTokenFilterConfig>(value.clone())
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+}
+
+impl Default for JapaneseWidthTokenFilterConfig {
+    fn default() -> Self {
+        Self::new(true, true)
+    }
+}
+
+/// Normalize the width of the characters of the token text: the full-width digits and Latin
+/// letters are converted to ASCII, and the half-width katakana are converted to full-width
+/// katakana, combining a voiced sound mark with the preceding kana (`ｶﾞ` to `ガ`).
+/// This is a curated subset of NFKC, so the other compatibility characters, such as the
+/// full-width symbols or `①`, are left untouched.
+///
+#[derive(Clone, Debug)]
+pub struct JapaneseWidthTokenFilter {
+    config: JapaneseWidthTokenFilterConfig,
+}
+
+impl JapaneseWidthTokenFilter {
+    pub fn new(config: JapaneseWidthTokenFilterConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        Ok(Self::new(JapaneseWidthTokenFilterConfig::from_slice(data)?))
+    }
+}
+
+impl TokenFilter for JapaneseWidthTokenFilter {
+    fn name(&self) -> &'static str {
+        JAPANESE_WIDTH_TOKEN_FILTER_NAME
+    }
+
+    fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
+        for token in tokens.iter_mut() {
+            if self.config.to_half_width_ascii {
+                token.text = to_half_width_ascii(&token.text);
+            }
+            if self.config.to_full_width_kana {
+                token.text = to_full_width_kana(&token.text);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Convert the full-width digits and Latin letters to ASCII.
+fn to_half_width_ascii(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '０'..='９' | 'Ａ'..='Ｚ' | 'ａ'..='ｚ' => {
+                char::from_u32(c as u32 - FULL_WIDTH_ASCII_OFFSET).unwrap_or(c)
+            }
+            _ => c,
+        })
+        .collect()
+}
+
+/// Convert the half-width katakana to full-width katakana.
+fn to_full_width_kana(text: &str) -> String {
+    let mut converted = String::with_capacity(text.len());
+
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let kana = match (c as u32).checked_sub(HALF_WIDTH_KATAKANA_START) {
+            Some(index) if (index as usize) < FULL_WIDTH_KATAKANA.len() => {
+                FULL_WIDTH_KATAKANA[index as usize]
+            }
+            _ => {
+                converted.push(c);
+                continue;
+            }
+        };
+
+        // A sound mark following the kana is combined with it if possible.
+        let combined = match chars.peek() {
+            Some(&HALF_WIDTH_VOICED_MARK) => voiced(kana),
+            Some(&HALF_WIDTH_SEMI_VOICED_MARK) => semi_voiced(kana),
+            _ => None,
+        };
+        match combined {
+            Some(combined) => {
+                converted.push(combined);
+                chars.next();
+            }
+            None => converted.push(kana),
+        }
+    }
+
+    converted
+}
+
+/// Return the voiced form of the katakana, such as `ガ` for `カ`.
+fn voiced(kana: char) -> Option<char> {
+    match kana {
+        'カ' | 'キ' | 'ク' | 'ケ' | 'コ' | 'サ' | 'シ' | 'ス' | 'セ' | 'ソ' | 'タ' | 'チ'
+        | 'ツ' | 'テ' | 'ト' | 'ハ' | 'ヒ' | 'フ' | 'ヘ' | 'ホ' => {
+            char::from_u32(kana as u32 + 1)
+        }
+        'ウ' => Some('ヴ'),
+        _ => None,
+    }
+}
+
+/// Return the semi-voiced form of the katakana, such as `パ` for `ハ`.
+fn semi_voiced(kana: char) -> Option<char> {
+    match kana {
+        'ハ' | 'ヒ' | 'フ' | 'ヘ' | 'ホ' => char::from_u32(kana as u32 + 2),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "ipadic")]
+    use lindera_core::word_entry::WordId;
+    use serde_json::json;
+
+    use crate::token_filter::japanese_width::{
+        to_full_width_kana, to_half_width_ascii, JapaneseWidthTokenFilter,
+        JapaneseWidthTokenFilterConfig,
+    };
+    #[cfg(feature = "ipadic")]
+    use crate::{token::Token, token_filter::TokenFilter};
+
+    #[test]
+    fn test_japanese_width_token_filter_config_from_slice() {
+        let config_str = r#"
+            {
+                "to_half_width_ascii": true,
+                "to_full_width_kana": false
+            }
+            "#;
+        let config = JapaneseWidthTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
+
+        assert!(config.to_half_width_ascii);
+        assert!(!config.to_full_width_kana);
+
+        let config = JapaneseWidthTokenFilterConfig::from_value(&json!({})).unwrap();
+
+        assert!(config.to_half_width_ascii);
+        assert!(config.to_full_width_kana);
+    }
+
+    #[test]
+    fn test_japanese_width_token_filter_from_slice() {
+        let config_str = r#"
+            {}
+            "#;
+        let result = JapaneseWidthTokenFilter::from_slice(config_str.as_bytes());
+
+        assert_eq!(true, result.is_ok());
+    }
+
+    #[test]
+    fn test_to_half_width_ascii() {
+        assert_eq!(to_half_width_ascii("２０２４年"), "2024年");
+        assert_eq!(to_half_width_ascii("ＡＢＣｘｙｚ"), "ABCxyz");
+        assert_eq!(to_half_width_ascii("Lindera１"), "Lindera1");
+
+        // the other full-width characters and the kana are untouched
+        assert_eq!(to_half_width_ascii("！＠（）"), "！＠（）");
+        assert_eq!(to_half_width_ascii("ｶﾀｶﾅ"), "ｶﾀｶﾅ");
+        assert_eq!(to_half_width_ascii("①"), "①");
+    }
+
+    #[test]
+    fn test_to_full_width_kana() {
+        assert_eq!(to_full_width_kana("ｶﾀｶﾅ"), "カタカナ");
+        assert_eq!(to_full_width_kana("ｶﾞｷﾞｸﾞ"), "ガギグ");
+        assert_eq!(to_full_width_kana("ﾊﾟﾋﾟﾌﾟﾍﾟﾎﾟ"), "パピプペポ");
+        assert_eq!(to_full_width_kana("ｳﾞｧｲｵﾘﾝ"), "ヴァイオリン");
+        assert_eq!(to_full_width_kana("ｺｰﾋｰ｡"), "コーヒー。");
+
+        // a sound mark that cannot be combined is converted alone
+        assert_eq!(to_full_width_kana("ｱﾞ"), "ア゛");
+        assert_eq!(to_full_width_kana("ﾟ"), "゜");
+
+        // the ASCII characters are untouched
+        assert_eq!(to_full_width_kana("ABC123"), "ABC123");
+        assert_eq!(to_full_width_kana("２０２４"), "２０２４");
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_japanese_width_token_filter_apply_ipadic() {
+        let mut tokens: Vec<Token> = vec![
+            Token {
+                text: "ＩＰＡ".to_string(),
+                byte_start: 0,
+                byte_end: 9,
+                position: 0,
+                position_length: 1,
+                word_id: WordId(4294967295, true),
+                details: vec!["UNK".to_string()],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "ﾃﾞｰﾀ".to_string(),
+                byte_start: 9,
+                byte_end: 21,
+                position: 1,
+                position_length: 1,
+                word_id: WordId(4294967295, true),
+                details: vec!["UNK".to_string()],
+                word_cost: None,
+                connection_cost: None,
+            },
+        ];
+
+        let filter =
+            JapaneseWidthTokenFilter::new(JapaneseWidthTokenFilterConfig::new(true, false));
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(&tokens[0].text, "IPA");
+        assert_eq!(&tokens[1].text, "ﾃﾞｰﾀ");
+
+        let filter =
+            JapaneseWidthTokenFilter::new(JapaneseWidthTokenFilterConfig::new(false, true));
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(&tokens[0].text, "IPA");
+        assert_eq!(&tokens[1].text, "データ");
+        assert_eq!(tokens[1].byte_start, 9);
+        assert_eq!(tokens[1].byte_end, 21);
+    }
+}
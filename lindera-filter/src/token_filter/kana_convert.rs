@@ -0,0 +1,217 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use lindera_core::error::LinderaErrorKind;
+use lindera_core::LinderaResult;
+
+use crate::token::Token;
+use crate::token_filter::TokenFilter;
+
+pub const KANA_CONVERT_TOKEN_FILTER_NAME: &str = "kana_convert";
+
+/// The hiragana that have a katakana counterpart, from `ぁ` (U+3041) to `ゖ` (U+3096).
+const HIRAGANA_START: char = '\u{3041}';
+const HIRAGANA_END: char = '\u{3096}';
+
+/// The katakana that have a hiragana counterpart, from `ァ` (U+30A1) to `ヶ` (U+30F6).
+const KATAKANA_START: char = '\u{30A1}';
+const KATAKANA_END: char = '\u{30F6}';
+
+/// The offset between a katakana and the corresponding hiragana.
+const KANA_OFFSET: u32 = KATAKANA_START as u32 - HIRAGANA_START as u32;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum KanaConvertDirection {
+    /// Hiragana to katakana.
+    #[serde(rename = "hira2kata")]
+    Hira2Kata,
+    /// Katakana to hiragana.
+    #[serde(rename = "kata2hira")]
+    Kata2Hira,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct KanaConvertTokenFilterConfig {
+    direction: KanaConvertDirection,
+}
+
+impl KanaConvertTokenFilterConfig {
+    pub fn new(direction: KanaConvertDirection) -> Self {
+        Self { direction }
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        serde_json::from_slice::<KanaConvertTokenFilterConfig>(data)
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+
+    pub fn from_value(value: &Value) -> LinderaResult<Self> {
+        serde_json::from_value::<KanaConvertTokenFilterConfig>(value.clone())
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+}
+
+/// Convert the hiragana of the token text to katakana (`hira2kata`), or the katakana to
+/// hiragana (`kata2hira`), by shifting the codepoints between `ぁ`-`ゖ` and `ァ`-`ヶ`.
+/// The other characters are left untouched, including the prolonged sound mark `ー`, which is
+/// in the katakana block but is also used in hiragana text (`らーめん`), and the katakana that
+/// have no hiragana counterpart, such as `ヷ`.
+///
+#[derive(Clone, Debug)]
+pub struct KanaConvertTokenFilter {
+    config: KanaConvertTokenFilterConfig,
+}
+
+impl KanaConvertTokenFilter {
+    pub fn new(config: KanaConvertTokenFilterConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        Ok(Self::new(KanaConvertTokenFilterConfig::from_slice(data)?))
+    }
+}
+
+impl TokenFilter for KanaConvertTokenFilter {
+    fn name(&self) -> &'static str {
+        KANA_CONVERT_TOKEN_FILTER_NAME
+    }
+
+    fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
+        for token in tokens.iter_mut() {
+            token.text = match self.config.direction {
+                KanaConvertDirection::Hira2Kata => hiragana_to_katakana(&token.text),
+                KanaConvertDirection::Kata2Hira => katakana_to_hiragana(&token.text),
+            };
+        }
+
+        Ok(())
+    }
+}
+
+fn hiragana_to_katakana(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            HIRAGANA_START..=HIRAGANA_END => char::from_u32(c as u32 + KANA_OFFSET).unwrap_or(c),
+            _ => c,
+        })
+        .collect()
+}
+
+fn katakana_to_hiragana(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            KATAKANA_START..=KATAKANA_END => char::from_u32(c as u32 - KANA_OFFSET).unwrap_or(c),
+            _ => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "ipadic")]
+    use lindera_core::word_entry::WordId;
+
+    use crate::token_filter::kana_convert::{
+        hiragana_to_katakana, katakana_to_hiragana, KanaConvertDirection, KanaConvertTokenFilter,
+        KanaConvertTokenFilterConfig,
+    };
+    #[cfg(feature = "ipadic")]
+    use crate::{token::Token, token_filter::TokenFilter};
+
+    #[test]
+    fn test_kana_convert_token_filter_config_from_slice() {
+        let config_str = r#"
+            {
+                "direction": "hira2kata"
+            }
+            "#;
+        let config = KanaConvertTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
+
+        assert_eq!(config.direction, KanaConvertDirection::Hira2Kata);
+
+        let config_str = r#"
+            {
+                "direction": "kata2hira"
+            }
+            "#;
+        let config = KanaConvertTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
+
+        assert_eq!(config.direction, KanaConvertDirection::Kata2Hira);
+
+        let config_str = r#"
+            {
+                "direction": "romaji"
+            }
+            "#;
+        let result = KanaConvertTokenFilterConfig::from_slice(config_str.as_bytes());
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_kana_convert_token_filter_from_slice() {
+        let config_str = r#"
+            {
+                "direction": "kata2hira"
+            }
+            "#;
+        let result = KanaConvertTokenFilter::from_slice(config_str.as_bytes());
+
+        assert_eq!(true, result.is_ok());
+    }
+
+    #[test]
+    fn test_hiragana_to_katakana() {
+        assert_eq!(hiragana_to_katakana("かたかな"), "カタカナ");
+        assert_eq!(hiragana_to_katakana("ぁゔゖ"), "ァヴヶ");
+
+        // the prolonged sound mark and the other characters are untouched
+        assert_eq!(hiragana_to_katakana("らーめん"), "ラーメン");
+        assert_eq!(hiragana_to_katakana("東京ABCカナ"), "東京ABCカナ");
+    }
+
+    #[test]
+    fn test_katakana_to_hiragana() {
+        assert_eq!(katakana_to_hiragana("カタカナ"), "かたかな");
+        assert_eq!(katakana_to_hiragana("ァヴヶ"), "ぁゔゖ");
+
+        // the prolonged sound mark, the katakana without hiragana counterpart and the other
+        // characters are untouched
+        assert_eq!(katakana_to_hiragana("ラーメン"), "らーめん");
+        assert_eq!(katakana_to_hiragana("ヷ・ｶﾅ"), "ヷ・ｶﾅ");
+        assert_eq!(katakana_to_hiragana("東京abcかな"), "東京abcかな");
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_kana_convert_token_filter_apply_ipadic() {
+        let mut tokens: Vec<Token> = vec![Token {
+            text: "かたかな".to_string(),
+            byte_start: 0,
+            byte_end: 12,
+            position: 0,
+            position_length: 1,
+            word_id: WordId(4294967295, true),
+            details: vec!["UNK".to_string()],
+            word_cost: None,
+            connection_cost: None,
+        }];
+
+        let filter = KanaConvertTokenFilter::new(KanaConvertTokenFilterConfig::new(
+            KanaConvertDirection::Hira2Kata,
+        ));
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(&tokens[0].text, "カタカナ");
+
+        let filter = KanaConvertTokenFilter::new(KanaConvertTokenFilterConfig::new(
+            KanaConvertDirection::Kata2Hira,
+        ));
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(&tokens[0].text, "かたかな");
+        assert_eq!(tokens[0].byte_start, 0);
+        assert_eq!(tokens[0].byte_end, 12);
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+use lindera_core::LinderaResult;
+
+use crate::check_args;
+use crate::token::Token;
+use crate::token_filter::TokenFilter;
+
+pub const KEEP_LONGEST_TOKEN_FILTER_NAME: &str = "keep_longest";
+
+/// Among the tokens at the same position, keep only the one with the largest byte span.
+/// If several tokens have the same span, the first one is kept.
+/// This removes the overlapping tokens added by filters such as the synonym filter.
+///
+#[derive(Clone, Debug)]
+pub struct KeepLongestTokenFilter {}
+
+impl KeepLongestTokenFilter {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        check_args(data)?;
+
+        Ok(Self::new())
+    }
+}
+
+impl Default for KeepLongestTokenFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TokenFilter for KeepLongestTokenFilter {
+    fn name(&self) -> &'static str {
+        KEEP_LONGEST_TOKEN_FILTER_NAME
+    }
+
+    fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
+        // The index of the longest token at each position.
+        let mut longest: HashMap<usize, usize> = HashMap::new();
+        for (index, token) in tokens.iter().enumerate() {
+            let span = token.byte_end - token.byte_start;
+            longest
+                .entry(token.position)
+                .and_modify(|longest_index| {
+                    let longest_token = &tokens[*longest_index];
+                    if span > longest_token.byte_end - longest_token.byte_start {
+                        *longest_index = index;
+                    }
+                })
+                .or_insert(index);
+        }
+
+        let mut index = 0;
+        tokens.retain(|token| {
+            let keep = longest.get(&token.position) == Some(&index);
+            index += 1;
+            keep
+        });
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "ipadic")]
+    use lindera_core::word_entry::WordId;
+
+    use crate::token_filter::keep_longest::KeepLongestTokenFilter;
+    #[cfg(feature = "ipadic")]
+    use crate::{token::Token, token_filter::TokenFilter};
+
+    #[test]
+    fn test_keep_longest_token_filter_from_slice() {
+        let config_str = r#"
+            {}
+            "#;
+        let result = KeepLongestTokenFilter::from_slice(config_str.as_bytes());
+
+        assert_eq!(true, result.is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_keep_longest_token_filter_apply_ipadic() {
+        let filter = KeepLongestTokenFilter::default();
+
+        let mut tokens: Vec<Token> = vec![
+            Token {
+                text: "東京".to_string(),
+                byte_start: 0,
+                byte_end: 6,
+                position: 0,
+                position_length: 1,
+                word_id: WordId(74410, true),
+                details: vec![
+                    "名詞".to_string(),
+                    "固有名詞".to_string(),
+                    "地域".to_string(),
+                    "一般".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "東京".to_string(),
+                    "トウキョウ".to_string(),
+                    "トーキョー".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "東京都".to_string(),
+                byte_start: 0,
+                byte_end: 9,
+                position: 0,
+                position_length: 2,
+                word_id: WordId(4294967295, true),
+                details: vec![
+                    "名詞".to_string(),
+                    "固有名詞".to_string(),
+                    "地域".to_string(),
+                    "一般".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "へ".to_string(),
+                byte_start: 9,
+                byte_end: 12,
+                position: 2,
+                position_length: 1,
+                word_id: WordId(75930, true),
+                details: vec![
+                    "助詞".to_string(),
+                    "格助詞".to_string(),
+                    "一般".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "へ".to_string(),
+                    "ヘ".to_string(),
+                    "エ".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+        ];
+
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(&tokens[0].text, "東京都");
+        assert_eq!(tokens[0].byte_start, 0);
+        assert_eq!(tokens[0].byte_end, 9);
+        assert_eq!(&tokens[1].text, "へ");
+        assert_eq!(tokens[1].position, 2);
+    }
+}
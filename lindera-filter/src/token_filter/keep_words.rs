@@ -131,6 +131,8 @@ mod tests {
                     "スモモ".to_string(),
                     "スモモ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "も".to_string(),
@@ -150,6 +152,8 @@ mod tests {
                     "モ".to_string(),
                     "モ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "もも".to_string(),
@@ -169,6 +173,8 @@ mod tests {
                     "モモ".to_string(),
                     "モモ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "も".to_string(),
@@ -188,6 +194,8 @@ mod tests {
                     "モ".to_string(),
                     "モ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "もも".to_string(),
@@ -207,6 +215,8 @@ mod tests {
                     "モモ".to_string(),
                     "モモ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "の".to_string(),
@@ -226,6 +236,8 @@ mod tests {
                     "ノ".to_string(),
                     "ノ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "うち".to_string(),
@@ -245,6 +257,8 @@ mod tests {
                     "ウチ".to_string(),
                     "ウチ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
         ];
 
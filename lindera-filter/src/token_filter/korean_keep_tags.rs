@@ -136,6 +136,8 @@ mod tests {
                     "*".to_string(),
                     "한국/NNG/*+어/NNG/*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "의".to_string(),
@@ -154,6 +156,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "형태소".to_string(),
@@ -172,6 +176,8 @@ mod tests {
                     "*".to_string(),
                     "형태/NNG/*+소/NNG/*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "분석".to_string(),
@@ -190,6 +196,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "을".to_string(),
@@ -208,6 +216,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "할".to_string(),
@@ -226,6 +236,8 @@ mod tests {
                     "ETM".to_string(),
                     "하/VV/*+ᆯ/ETM/*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "수".to_string(),
@@ -244,6 +256,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "있".to_string(),
@@ -262,6 +276,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "습니다".to_string(),
@@ -280,6 +296,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
         ];
 
@@ -291,4 +309,127 @@ mod tests {
         assert_eq!(&tokens[2].text, "분석");
         assert_eq!(&tokens[3].text, "수");
     }
+
+    #[test]
+    #[cfg(all(feature = "ko-dic", feature = "filter",))]
+    fn test_korean_keep_tags_token_filter_apply_nouns_and_verbs() {
+        let config_str = r#"
+            {
+                "tags": [
+                    "NNG",
+                    "VV"
+                ]
+            }
+            "#;
+        let filter = KoreanKeepTagsTokenFilter::from_slice(config_str.as_bytes()).unwrap();
+
+        let mut tokens: Vec<Token> = vec![
+            Token {
+                text: "밥".to_string(),
+                byte_start: 0,
+                byte_end: 3,
+                position: 0,
+                position_length: 1,
+                word_id: WordId(391950, true),
+                details: vec![
+                    "NNG".to_string(),
+                    "*".to_string(),
+                    "T".to_string(),
+                    "밥".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "을".to_string(),
+                byte_start: 3,
+                byte_end: 6,
+                position: 1,
+                position_length: 1,
+                word_id: WordId(574939, true),
+                details: vec![
+                    "JKO".to_string(),
+                    "*".to_string(),
+                    "T".to_string(),
+                    "을".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "먹".to_string(),
+                byte_start: 6,
+                byte_end: 9,
+                position: 2,
+                position_length: 1,
+                word_id: WordId(359417, true),
+                details: vec![
+                    "VV".to_string(),
+                    "*".to_string(),
+                    "T".to_string(),
+                    "먹".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "었".to_string(),
+                byte_start: 9,
+                byte_end: 12,
+                position: 3,
+                position_length: 1,
+                word_id: WordId(594081, true),
+                details: vec![
+                    "EP".to_string(),
+                    "*".to_string(),
+                    "T".to_string(),
+                    "었".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "다".to_string(),
+                byte_start: 12,
+                byte_end: 15,
+                position: 4,
+                position_length: 1,
+                word_id: WordId(290493, true),
+                details: vec![
+                    "EF".to_string(),
+                    "*".to_string(),
+                    "F".to_string(),
+                    "다".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+        ];
+
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(&tokens[0].text, "밥");
+        assert_eq!(&tokens[1].text, "먹");
+    }
 }
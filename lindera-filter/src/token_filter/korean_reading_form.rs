@@ -1,4 +1,5 @@
 use lindera_core::LinderaResult;
+use lindera_dictionary::DictionaryKind;
 
 use crate::token::Token;
 use crate::token_filter::TokenFilter;
@@ -29,8 +30,8 @@ impl TokenFilter for KoreanReadingFormTokenFilter {
 
     fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
         for token in tokens.iter_mut() {
-            if token.details[0] != "UNK" {
-                token.text = token.details[3].to_string();
+            if let Some(reading) = token.reading(&DictionaryKind::KoDic) {
+                token.text = reading.to_string();
             }
         }
 
@@ -72,6 +73,8 @@ mod tests {
                     "*".to_string(),
                     "한국/NNG/*+어/NNG/*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "의".to_string(),
@@ -90,6 +93,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "형태소".to_string(),
@@ -108,6 +113,8 @@ mod tests {
                     "*".to_string(),
                     "형태/NNG/*+소/NNG/*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "분석".to_string(),
@@ -126,6 +133,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "을".to_string(),
@@ -144,6 +153,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "할".to_string(),
@@ -162,6 +173,8 @@ mod tests {
                     "ETM".to_string(),
                     "하/VV/*+ᆯ/ETM/*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "수".to_string(),
@@ -180,6 +193,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "있".to_string(),
@@ -198,6 +213,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "습니다".to_string(),
@@ -216,6 +233,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
         ];
 
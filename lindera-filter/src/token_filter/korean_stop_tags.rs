@@ -1,6 +1,7 @@
 use std::collections::HashSet;
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use lindera_core::error::LinderaErrorKind;
 use lindera_core::LinderaResult;
@@ -10,6 +11,19 @@ use crate::token_filter::TokenFilter;
 
 pub const KOREAN_STOP_TAGS_TOKEN_FILTER_NAME: &str = "korean_stop_tags";
 
+/// The ko-dic part-of-speech tags of the josa (particles).
+pub const KOREAN_JOSA_TAGS: &[&str] = &[
+    "JKS", // subjective case marker
+    "JKC", // complement case marker
+    "JKG", // adnominal case marker
+    "JKO", // objective case marker
+    "JKB", // adverbial case marker
+    "JKV", // vocative case marker
+    "JKQ", // quotative case marker
+    "JX",  // auxiliary particle
+    "JC",  // conjunctive particle
+];
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub struct KoreanStopTagsTokenFilterConfig {
     tags: HashSet<String>,
@@ -20,14 +34,29 @@ impl KoreanStopTagsTokenFilterConfig {
         Self { tags }
     }
 
+    /// Create a config that removes the josa (particles) listed in [`KOREAN_JOSA_TAGS`].
+    /// It is also given by `{"preset": "josa"}` in the JSON config.
+    pub fn josa() -> Self {
+        Self::new(KOREAN_JOSA_TAGS.iter().map(|tag| tag.to_string()).collect())
+    }
+
     pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
-        serde_json::from_slice::<KoreanStopTagsTokenFilterConfig>(data)
-            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+        let value = serde_json::from_slice::<Value>(data)
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))?;
+        Self::from_value(&value)
     }
 
-    pub fn from_value(value: &serde_json::Value) -> LinderaResult<Self> {
-        serde_json::from_value::<KoreanStopTagsTokenFilterConfig>(value.clone())
-            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    /// The tags are given by `tags`, or by `preset`, which is `josa` for [`Self::josa`].
+    pub fn from_value(value: &Value) -> LinderaResult<Self> {
+        match &value["preset"] {
+            Value::Null => serde_json::from_value::<KoreanStopTagsTokenFilterConfig>(value.clone())
+                .map_err(|err| LinderaErrorKind::Deserialize.with_error(err)),
+            preset => match preset.as_str() {
+                Some("josa") => Ok(Self::josa()),
+                _ => Err(LinderaErrorKind::Deserialize
+                    .with_error(anyhow::anyhow!("unknown preset: {}", preset))),
+            },
+        }
     }
 }
 
@@ -66,12 +95,16 @@ impl TokenFilter for KoreanStopTagsTokenFilter {
 mod tests {
     #[cfg(all(feature = "ko-dic", feature = "filter",))]
     use lindera_core::word_entry::WordId;
+    #[cfg(all(feature = "ko-dic", feature = "filter",))]
+    use serde_json::json;
 
     #[cfg(all(feature = "ko-dic", feature = "filter",))]
     use crate::{
         token::Token,
         token_filter::{
-            korean_stop_tags::{KoreanStopTagsTokenFilter, KoreanStopTagsTokenFilterConfig},
+            korean_stop_tags::{
+                KoreanStopTagsTokenFilter, KoreanStopTagsTokenFilterConfig, KOREAN_JOSA_TAGS,
+            },
             TokenFilter,
         },
     };
@@ -120,6 +153,33 @@ mod tests {
         assert_eq!(config.tags.len(), 30);
     }
 
+    #[test]
+    #[cfg(all(feature = "ko-dic", feature = "filter",))]
+    fn test_korean_stop_tags_token_filter_config_josa() {
+        let config = KoreanStopTagsTokenFilterConfig::josa();
+
+        assert_eq!(config.tags.len(), KOREAN_JOSA_TAGS.len());
+        assert!(config.tags.contains("JKS"));
+        assert!(config.tags.contains("JKO"));
+        assert!(config.tags.contains("JX"));
+        assert!(!config.tags.contains("NNG"));
+
+        let config =
+            KoreanStopTagsTokenFilterConfig::from_value(&json!({"preset": "josa"})).unwrap();
+        assert_eq!(config, KoreanStopTagsTokenFilterConfig::josa());
+
+        let config_str = r#"
+            {
+                "preset": "josa"
+            }
+            "#;
+        let config = KoreanStopTagsTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
+        assert_eq!(config, KoreanStopTagsTokenFilterConfig::josa());
+
+        let result = KoreanStopTagsTokenFilterConfig::from_value(&json!({"preset": "eomi"}));
+        assert!(result.is_err());
+    }
+
     #[test]
     #[cfg(all(feature = "ko-dic", feature = "filter",))]
     fn test_korean_stop_tagss_token_filter_from_slice() {
@@ -223,6 +283,8 @@ mod tests {
                     "*".to_string(),
                     "한국/NNG/*+어/NNG/*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "의".to_string(),
@@ -241,6 +303,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "형태소".to_string(),
@@ -259,6 +323,8 @@ mod tests {
                     "*".to_string(),
                     "형태/NNG/*+소/NNG/*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "분석".to_string(),
@@ -277,6 +343,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "을".to_string(),
@@ -295,6 +363,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "할".to_string(),
@@ -313,6 +383,8 @@ mod tests {
                     "ETM".to_string(),
                     "하/VV/*+ᆯ/ETM/*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "수".to_string(),
@@ -331,6 +403,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "있".to_string(),
@@ -349,6 +423,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "습니다".to_string(),
@@ -367,6 +443,8 @@ mod tests {
                     "*".to_string(),
                     "*".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
         ];
 
@@ -380,4 +458,201 @@ mod tests {
         assert_eq!(&tokens[4].text, "수");
         assert_eq!(&tokens[5].text, "있");
     }
+
+    #[test]
+    #[cfg(all(feature = "ko-dic", feature = "filter",))]
+    fn test_korean_stop_tags_token_filter_apply_josa() {
+        let filter = KoreanStopTagsTokenFilter::new(KoreanStopTagsTokenFilterConfig::josa());
+
+        let mut tokens: Vec<Token> = vec![
+            Token {
+                text: "한국어".to_string(),
+                byte_start: 0,
+                byte_end: 9,
+                position: 0,
+                position_length: 1,
+                word_id: WordId(770060, true),
+                details: vec![
+                    "NNG".to_string(),
+                    "*".to_string(),
+                    "F".to_string(),
+                    "한국어".to_string(),
+                    "Compound".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "한국/NNG/*+어/NNG/*".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "의".to_string(),
+                byte_start: 9,
+                byte_end: 12,
+                position: 1,
+                position_length: 1,
+                word_id: WordId(576336, true),
+                details: vec![
+                    "JKG".to_string(),
+                    "*".to_string(),
+                    "F".to_string(),
+                    "의".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "형태소".to_string(),
+                byte_start: 12,
+                byte_end: 21,
+                position: 2,
+                position_length: 1,
+                word_id: WordId(787807, true),
+                details: vec![
+                    "NNG".to_string(),
+                    "*".to_string(),
+                    "F".to_string(),
+                    "형태소".to_string(),
+                    "Compound".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "형태/NNG/*+소/NNG/*".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "분석".to_string(),
+                byte_start: 21,
+                byte_end: 27,
+                position: 3,
+                position_length: 1,
+                word_id: WordId(383955, true),
+                details: vec![
+                    "NNG".to_string(),
+                    "행위".to_string(),
+                    "T".to_string(),
+                    "분석".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "을".to_string(),
+                byte_start: 27,
+                byte_end: 30,
+                position: 4,
+                position_length: 1,
+                word_id: WordId(574939, true),
+                details: vec![
+                    "JKO".to_string(),
+                    "*".to_string(),
+                    "T".to_string(),
+                    "을".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "할".to_string(),
+                byte_start: 30,
+                byte_end: 33,
+                position: 5,
+                position_length: 1,
+                word_id: WordId(774117, true),
+                details: vec![
+                    "VV+ETM".to_string(),
+                    "*".to_string(),
+                    "T".to_string(),
+                    "할".to_string(),
+                    "Inflect".to_string(),
+                    "VV".to_string(),
+                    "ETM".to_string(),
+                    "하/VV/*+ᆯ/ETM/*".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "수".to_string(),
+                byte_start: 33,
+                byte_end: 36,
+                position: 6,
+                position_length: 1,
+                word_id: WordId(444151, true),
+                details: vec![
+                    "NNG".to_string(),
+                    "*".to_string(),
+                    "F".to_string(),
+                    "수".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "있".to_string(),
+                byte_start: 36,
+                byte_end: 39,
+                position: 7,
+                position_length: 1,
+                word_id: WordId(602850, true),
+                details: vec![
+                    "VX".to_string(),
+                    "*".to_string(),
+                    "T".to_string(),
+                    "있".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "습니다".to_string(),
+                byte_start: 39,
+                byte_end: 48,
+                position: 8,
+                position_length: 1,
+                word_id: WordId(458024, true),
+                details: vec![
+                    "EF".to_string(),
+                    "*".to_string(),
+                    "F".to_string(),
+                    "습니다".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+        ];
+
+        filter.apply(&mut tokens).unwrap();
+
+        // "의" (JKG) and "을" (JKO) are removed.
+        assert_eq!(
+            tokens.iter().map(|t| t.text.as_str()).collect::<Vec<_>>(),
+            vec!["한국어", "형태소", "분석", "할", "수", "있", "습니다"]
+        );
+    }
 }
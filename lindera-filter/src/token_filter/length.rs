@@ -38,12 +38,22 @@ pub struct LengthTokenFilter {
 }
 
 impl LengthTokenFilter {
-    pub fn new(config: LengthTokenFilterConfig) -> Self {
-        Self { config }
+    pub fn new(config: LengthTokenFilterConfig) -> LinderaResult<Self> {
+        if let (Some(min), Some(max)) = (config.min, config.max) {
+            if min > max {
+                return Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                    "min ({}) must be less than or equal to max ({})",
+                    min,
+                    max
+                )));
+            }
+        }
+
+        Ok(Self { config })
     }
 
     pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
-        Ok(Self::new(LengthTokenFilterConfig::from_slice(data)?))
+        Self::new(LengthTokenFilterConfig::from_slice(data)?)
     }
 }
 
@@ -144,6 +154,16 @@ mod tests {
         let result = LengthTokenFilter::from_slice(config_str.as_bytes());
 
         assert_eq!(result.is_ok(), true);
+
+        let config_str = r#"
+            {
+                "min": 3,
+                "max": 2
+            }
+            "#;
+        let result = LengthTokenFilter::from_slice(config_str.as_bytes());
+
+        assert_eq!(result.is_err(), true);
     }
 
     #[test]
@@ -176,6 +196,8 @@ mod tests {
                     "スモモ".to_string(),
                     "スモモ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "も".to_string(),
@@ -195,6 +217,8 @@ mod tests {
                     "モ".to_string(),
                     "モ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "もも".to_string(),
@@ -214,6 +238,8 @@ mod tests {
                     "モモ".to_string(),
                     "モモ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "も".to_string(),
@@ -233,6 +259,8 @@ mod tests {
                     "モ".to_string(),
                     "モ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "もも".to_string(),
@@ -252,6 +280,8 @@ mod tests {
                     "モモ".to_string(),
                     "モモ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "の".to_string(),
@@ -271,6 +301,8 @@ mod tests {
                     "ノ".to_string(),
                     "ノ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "うち".to_string(),
@@ -290,6 +322,8 @@ mod tests {
                     "ウチ".to_string(),
                     "ウチ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
         ];
 
@@ -1,3 +1,7 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use lindera_core::error::LinderaErrorKind;
 use lindera_core::LinderaResult;
 
 use crate::token::Token;
@@ -5,14 +9,51 @@ use crate::token_filter::TokenFilter;
 
 pub const LOWERCASE_TOKEN_FILTER_NAME: &str = "lowercase";
 
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct LowercaseTokenFilterConfig {
+    /// If true, only the ASCII letters `A`-`Z` are lowercased, and other letters such as
+    /// full-width Latin letters are left untouched.
+    #[serde(default)]
+    ascii_only: bool,
+}
+
+impl LowercaseTokenFilterConfig {
+    pub fn new(ascii_only: bool) -> Self {
+        Self { ascii_only }
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        serde_json::from_slice::<LowercaseTokenFilterConfig>(data)
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+
+    pub fn from_value(value: &Value) -> LinderaResult<Self> {
+        serde_json::from_value::<LowercaseTokenFilterConfig>(value.clone())
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+}
+
 /// Normalizes token text to lowercase.
+/// With `ascii_only`, only the ASCII letters are lowercased.
 ///
 #[derive(Clone, Debug)]
-pub struct LowercaseTokenFilter {}
+pub struct LowercaseTokenFilter {
+    config: LowercaseTokenFilterConfig,
+}
 
 impl LowercaseTokenFilter {
     pub fn new() -> Self {
-        Self {}
+        Self::from_config(LowercaseTokenFilterConfig::default())
+    }
+
+    pub fn from_config(config: LowercaseTokenFilterConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        Ok(Self::from_config(LowercaseTokenFilterConfig::from_slice(
+            data,
+        )?))
     }
 }
 
@@ -29,7 +70,11 @@ impl TokenFilter for LowercaseTokenFilter {
 
     fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
         for token in tokens.iter_mut() {
-            token.text = token.text.to_lowercase();
+            if self.config.ascii_only {
+                token.text.make_ascii_lowercase();
+            } else {
+                token.text = token.text.to_lowercase();
+            }
         }
 
         Ok(())
@@ -40,31 +85,139 @@ impl TokenFilter for LowercaseTokenFilter {
 mod tests {
     #[cfg(feature = "ipadic")]
     use lindera_core::word_entry::WordId;
+    use serde_json::json;
 
+    use crate::token_filter::lowercase::{LowercaseTokenFilter, LowercaseTokenFilterConfig};
     #[cfg(feature = "ipadic")]
-    use crate::{
-        token::Token,
-        token_filter::{lowercase::LowercaseTokenFilter, TokenFilter},
-    };
+    use crate::{token::Token, token_filter::TokenFilter};
+
+    #[test]
+    fn test_lowercase_token_filter_config_from_slice() {
+        let config_str = r#"
+            {
+                "ascii_only": true
+            }
+            "#;
+        let config = LowercaseTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
+
+        assert_eq!(config.ascii_only, true);
+
+        let config_str = r#"
+            {}
+            "#;
+        let config = LowercaseTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
+
+        assert_eq!(config.ascii_only, false);
+
+        let config = LowercaseTokenFilterConfig::from_value(&json!({})).unwrap();
+
+        assert_eq!(config.ascii_only, false);
+    }
+
+    #[test]
+    fn test_lowercase_token_filter_from_slice() {
+        let config_str = r#"
+            {}
+            "#;
+        let result = LowercaseTokenFilter::from_slice(config_str.as_bytes());
+
+        assert_eq!(true, result.is_ok());
+    }
 
     #[test]
     #[cfg(feature = "ipadic")]
     fn test_lowercase_token_filter_apply_ipadic() {
         let filter = LowercaseTokenFilter::default();
 
-        let mut tokens: Vec<Token> = vec![Token {
-            text: "Rust".to_string(),
-            byte_start: 0,
-            byte_end: 4,
-            position: 0,
-            position_length: 1,
-            word_id: WordId(4294967295, true),
-            details: vec!["UNK".to_string()],
-        }];
+        let mut tokens: Vec<Token> = vec![
+            Token {
+                text: "Rust".to_string(),
+                byte_start: 0,
+                byte_end: 4,
+                position: 0,
+                position_length: 1,
+                word_id: WordId(4294967295, true),
+                details: vec!["UNK".to_string()],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "ＲＵＳＴ".to_string(),
+                byte_start: 4,
+                byte_end: 16,
+                position: 1,
+                position_length: 1,
+                word_id: WordId(4294967295, true),
+                details: vec!["UNK".to_string()],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "すもも".to_string(),
+                byte_start: 16,
+                byte_end: 25,
+                position: 2,
+                position_length: 1,
+                word_id: WordId(36165, true),
+                details: vec![
+                    "名詞".to_string(),
+                    "一般".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "すもも".to_string(),
+                    "スモモ".to_string(),
+                    "スモモ".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+        ];
 
         filter.apply(&mut tokens).unwrap();
 
-        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens.len(), 3);
         assert_eq!(&tokens[0].text, "rust");
+        assert_eq!(&tokens[1].text, "ｒｕｓｔ");
+        assert_eq!(&tokens[2].text, "すもも");
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_lowercase_token_filter_apply_ascii_only_ipadic() {
+        let filter = LowercaseTokenFilter::from_config(LowercaseTokenFilterConfig::new(true));
+
+        let mut tokens: Vec<Token> = vec![
+            Token {
+                text: "ABC".to_string(),
+                byte_start: 0,
+                byte_end: 3,
+                position: 0,
+                position_length: 1,
+                word_id: WordId(4294967295, true),
+                details: vec!["UNK".to_string()],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "ＡＢＣ".to_string(),
+                byte_start: 3,
+                byte_end: 12,
+                position: 1,
+                position_length: 1,
+                word_id: WordId(4294967295, true),
+                details: vec!["UNK".to_string()],
+                word_cost: None,
+                connection_cost: None,
+            },
+        ];
+
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(&tokens[0].text, "abc");
+        // full-width letters are not ASCII and are left untouched
+        assert_eq!(&tokens[1].text, "ＡＢＣ");
     }
 }
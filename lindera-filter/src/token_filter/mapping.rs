@@ -187,6 +187,8 @@ mod tests {
                     "カゴハラ".to_string(),
                     "カゴハラ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "駅".to_string(),
@@ -206,6 +208,8 @@ mod tests {
                     "エキ".to_string(),
                     "エキ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
         ];
 
@@ -0,0 +1,237 @@
+use serde::{Deserialize, Serialize};
+
+use lindera_core::error::LinderaErrorKind;
+use lindera_core::LinderaResult;
+
+use crate::token::Token;
+use crate::token_filter::TokenFilter;
+
+pub const NGRAM_TOKEN_FILTER_NAME: &str = "ngram";
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct NgramTokenFilterConfig {
+    min: usize,
+    max: usize,
+}
+
+impl NgramTokenFilterConfig {
+    pub fn new(min: usize, max: usize) -> Self {
+        Self { min, max }
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        serde_json::from_slice::<NgramTokenFilterConfig>(data)
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+
+    pub fn from_value(value: &serde_json::Value) -> LinderaResult<Self> {
+        serde_json::from_value::<NgramTokenFilterConfig>(value.clone())
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+}
+
+/// Replace each token with the character n-grams of its text.
+/// The n-grams have the same offsets as the original token, and successive positions.
+/// Tokens shorter than `min` characters are removed.
+///
+#[derive(Clone, Debug)]
+pub struct NgramTokenFilter {
+    config: NgramTokenFilterConfig,
+}
+
+impl NgramTokenFilter {
+    pub fn new(config: NgramTokenFilterConfig) -> LinderaResult<Self> {
+        if config.min == 0 {
+            return Err(
+                LinderaErrorKind::Args.with_error(anyhow::anyhow!("min must be greater than 0"))
+            );
+        }
+        if config.min > config.max {
+            return Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                "min ({}) must be less than or equal to max ({})",
+                config.min,
+                config.max
+            )));
+        }
+
+        Ok(Self { config })
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        Self::new(NgramTokenFilterConfig::from_slice(data)?)
+    }
+}
+
+impl TokenFilter for NgramTokenFilter {
+    fn name(&self) -> &'static str {
+        NGRAM_TOKEN_FILTER_NAME
+    }
+
+    fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
+        let mut new_tokens = Vec::with_capacity(tokens.len());
+        let mut position = 0;
+
+        for token in tokens.drain(..) {
+            // Byte offsets of the characters, including the end of the text.
+            let mut char_indices = token
+                .text
+                .char_indices()
+                .map(|(index, _)| index)
+                .collect::<Vec<_>>();
+            char_indices.push(token.text.len());
+            let num_chars = char_indices.len() - 1;
+
+            for start in 0..num_chars {
+                for len in self.config.min..=self.config.max {
+                    let end = start + len;
+                    if end > num_chars {
+                        break;
+                    }
+
+                    new_tokens.push(Token {
+                        text: token.text[char_indices[start]..char_indices[end]].to_string(),
+                        position,
+                        position_length: 1,
+                        ..token.clone()
+                    });
+                    position += 1;
+                }
+            }
+        }
+
+        *tokens = new_tokens;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "ipadic")]
+    use lindera_core::word_entry::WordId;
+
+    use crate::token_filter::ngram::{NgramTokenFilter, NgramTokenFilterConfig};
+    #[cfg(feature = "ipadic")]
+    use crate::{token::Token, token_filter::TokenFilter};
+
+    #[test]
+    fn test_ngram_token_filter_config_from_slice() {
+        let config_str = r#"
+            {
+                "min": 2,
+                "max": 3
+            }
+            "#;
+        let config = NgramTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
+
+        assert_eq!(config.min, 2);
+        assert_eq!(config.max, 3);
+    }
+
+    #[test]
+    fn test_ngram_token_filter_from_slice() {
+        let config_str = r#"
+            {
+                "min": 2,
+                "max": 3
+            }
+            "#;
+        let result = NgramTokenFilter::from_slice(config_str.as_bytes());
+
+        assert_eq!(true, result.is_ok());
+
+        let config_str = r#"
+            {
+                "min": 3,
+                "max": 2
+            }
+            "#;
+        let result = NgramTokenFilter::from_slice(config_str.as_bytes());
+
+        assert_eq!(true, result.is_err());
+
+        let config_str = r#"
+            {
+                "min": 0,
+                "max": 2
+            }
+            "#;
+        let result = NgramTokenFilter::from_slice(config_str.as_bytes());
+
+        assert_eq!(true, result.is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_ngram_token_filter_apply_ipadic() {
+        let config_str = r#"
+            {
+                "min": 2,
+                "max": 3
+            }
+            "#;
+        let filter = NgramTokenFilter::from_slice(config_str.as_bytes()).unwrap();
+
+        let mut tokens: Vec<Token> = vec![
+            Token {
+                text: "ラーメン".to_string(),
+                byte_start: 0,
+                byte_end: 12,
+                position: 0,
+                position_length: 1,
+                word_id: WordId(321702, true),
+                details: vec![
+                    "名詞".to_string(),
+                    "一般".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "ラーメン".to_string(),
+                    "ラーメン".to_string(),
+                    "ラーメン".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "を".to_string(),
+                byte_start: 12,
+                byte_end: 15,
+                position: 1,
+                position_length: 1,
+                word_id: WordId(85851, true),
+                details: vec![
+                    "助詞".to_string(),
+                    "格助詞".to_string(),
+                    "一般".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "を".to_string(),
+                    "ヲ".to_string(),
+                    "ヲ".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+        ];
+
+        filter.apply(&mut tokens).unwrap();
+
+        // "を" is shorter than min and is removed
+        assert_eq!(
+            tokens.iter().map(|t| t.text.as_str()).collect::<Vec<_>>(),
+            vec!["ラー", "ラーメ", "ーメ", "ーメン", "メン"]
+        );
+        assert_eq!(
+            tokens.iter().map(|t| t.position).collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+        for token in tokens.iter() {
+            assert_eq!(token.byte_start, 0);
+            assert_eq!(token.byte_end, 12);
+            assert_eq!(token.position_length, 1);
+        }
+    }
+}
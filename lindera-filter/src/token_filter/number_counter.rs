@@ -0,0 +1,484 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use lindera_core::error::LinderaErrorKind;
+use lindera_core::LinderaResult;
+use lindera_dictionary::{DetailSchema, DictionaryKind};
+
+use crate::token::Token;
+use crate::token_filter::{check_detail_field, TokenFilter};
+
+pub const NUMBER_COUNTER_TOKEN_FILTER_NAME: &str = "number_counter";
+
+/// The major part-of-speech of the numbers.
+const NUMBER_MAJOR_POS: &str = "名詞";
+
+/// The part-of-speech subcategories of the numbers, `数` in IPADIC and `数詞` in UniDic.
+const NUMBER_POS: [&str; 2] = ["数", "数詞"];
+
+/// The part-of-speech subcategory of the counters, such as `個` or `本`, at the third level in
+/// both IPADIC (`名詞,接尾,助数詞`) and UniDic (`接尾辞,名詞的,助数詞`).
+const COUNTER_POS: &str = "助数詞";
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct NumberCounterTokenFilterConfig {
+    kind: DictionaryKind,
+    /// The layout of the details, if it differs from the one of `kind`.
+    #[serde(default)]
+    detail_schema: Option<DetailSchema>,
+    /// The texts of the counters to merge. If omitted, all counters are merged.
+    #[serde(default)]
+    include: Option<HashSet<String>>,
+    /// The texts of the counters not to merge.
+    #[serde(default)]
+    exclude: HashSet<String>,
+}
+
+impl NumberCounterTokenFilterConfig {
+    pub fn new(
+        kind: DictionaryKind,
+        detail_schema: Option<DetailSchema>,
+        include: Option<HashSet<String>>,
+        exclude: HashSet<String>,
+    ) -> Self {
+        Self {
+            kind,
+            detail_schema,
+            include,
+            exclude,
+        }
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        serde_json::from_slice::<NumberCounterTokenFilterConfig>(data)
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+
+    pub fn from_value(value: &Value) -> LinderaResult<Self> {
+        serde_json::from_value::<NumberCounterTokenFilterConfig>(value.clone())
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+}
+
+/// Merge a number token (`名詞,数`) with the counter token (`名詞,接尾,助数詞`) immediately
+/// following it into a single token spanning both, e.g. `三` and `個` into `三個`.
+/// The part-of-speech tags of UniDic (`名詞,数詞` and `接尾辞,名詞的,助数詞`) are handled as well.
+///
+/// The merged token keeps the details of the number token, so the value of the number (e.g. the
+/// base form `三`) remains available. A number takes at most one counter. The text of the number
+/// is kept as is, so this filter can follow `japanese_number` to get `3個` from `三個`.
+/// Only the counters in `include`, if specified, and not in `exclude` are merged.
+///
+#[derive(Clone, Debug)]
+pub struct NumberCounterTokenFilter {
+    detail_schema: DetailSchema,
+    include: Option<HashSet<String>>,
+    exclude: HashSet<String>,
+}
+
+impl NumberCounterTokenFilter {
+    pub fn new(config: NumberCounterTokenFilterConfig) -> LinderaResult<Self> {
+        let custom_schema = config.detail_schema.is_some();
+        let detail_schema = config
+            .detail_schema
+            .unwrap_or_else(|| config.kind.detail_schema());
+        check_detail_field(
+            NUMBER_COUNTER_TOKEN_FILTER_NAME,
+            "part-of-speech subcategory",
+            detail_schema.pos.get(1).copied(),
+            &config.kind,
+            custom_schema,
+        )?;
+        check_detail_field(
+            NUMBER_COUNTER_TOKEN_FILTER_NAME,
+            "second part-of-speech subcategory",
+            detail_schema.pos.get(2).copied(),
+            &config.kind,
+            custom_schema,
+        )?;
+
+        Ok(Self {
+            detail_schema,
+            include: config.include,
+            exclude: config.exclude,
+        })
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        Self::new(NumberCounterTokenFilterConfig::from_slice(data)?)
+    }
+
+    /// Get the part-of-speech of the token at the given level, `0` being the major one.
+    fn pos_level<'a>(&self, token: &'a Token, level: usize) -> Option<&'a str> {
+        self.detail_schema
+            .pos
+            .get(level)
+            .and_then(|index| token.details.get(*index))
+            .map(|pos| pos.as_str())
+    }
+
+    fn is_number(&self, token: &Token) -> bool {
+        token.pos(&self.detail_schema) == Some(NUMBER_MAJOR_POS)
+            && self
+                .pos_level(token, 1)
+                .is_some_and(|pos| NUMBER_POS.contains(&pos))
+    }
+
+    fn is_merged_counter(&self, token: &Token) -> bool {
+        self.pos_level(token, 2) == Some(COUNTER_POS)
+            && self
+                .include
+                .as_ref()
+                .is_none_or(|include| include.contains(&token.text))
+            && !self.exclude.contains(&token.text)
+    }
+}
+
+impl TokenFilter for NumberCounterTokenFilter {
+    fn name(&self) -> &'static str {
+        NUMBER_COUNTER_TOKEN_FILTER_NAME
+    }
+
+    fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
+        let mut new_tokens: Vec<Token> = Vec::with_capacity(tokens.len());
+        // Whether the last token has already taken a counter.
+        let mut merged = false;
+
+        for token in tokens.drain(..) {
+            if let Some(number_token) = new_tokens.last_mut() {
+                if !merged && self.is_number(number_token) && self.is_merged_counter(&token) {
+                    number_token.text = format!("{}{}", number_token.text, token.text);
+                    number_token.byte_end = token.byte_end;
+                    number_token.position_length += token.position_length;
+                    merged = true;
+                    continue;
+                }
+            }
+
+            new_tokens.push(token);
+            merged = false;
+        }
+
+        *tokens = new_tokens;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use lindera_dictionary::DictionaryKind;
+    use serde_json::json;
+
+    #[cfg(all(feature = "ipadic", feature = "filter"))]
+    use crate::token::Token;
+    #[cfg(all(any(feature = "ipadic", feature = "unidic",), feature = "filter"))]
+    use crate::token_filter::make_token;
+    use crate::token_filter::number_counter::{
+        NumberCounterTokenFilter, NumberCounterTokenFilterConfig,
+    };
+    #[cfg(all(any(feature = "ipadic", feature = "unidic",), feature = "filter"))]
+    use crate::token_filter::TokenFilter;
+
+    /// The tokens of `りんごを三個と鉛筆5本` with IPADIC.
+    #[cfg(all(feature = "ipadic", feature = "filter"))]
+    fn ipadic_tokens() -> Vec<Token> {
+        vec![
+            make_token(
+                "りんご",
+                0,
+                0,
+                &[
+                    "名詞",
+                    "一般",
+                    "*",
+                    "*",
+                    "*",
+                    "*",
+                    "りんご",
+                    "リンゴ",
+                    "リンゴ",
+                ],
+            ),
+            make_token(
+                "を",
+                9,
+                1,
+                &["助詞", "格助詞", "一般", "*", "*", "*", "を", "ヲ", "ヲ"],
+            ),
+            make_token(
+                "三",
+                12,
+                2,
+                &["名詞", "数", "*", "*", "*", "*", "三", "サン", "サン"],
+            ),
+            make_token(
+                "個",
+                15,
+                3,
+                &["名詞", "接尾", "助数詞", "*", "*", "*", "個", "コ", "コ"],
+            ),
+            make_token(
+                "と",
+                18,
+                4,
+                &["助詞", "並立助詞", "*", "*", "*", "*", "と", "ト", "ト"],
+            ),
+            make_token(
+                "鉛筆",
+                21,
+                5,
+                &[
+                    "名詞",
+                    "一般",
+                    "*",
+                    "*",
+                    "*",
+                    "*",
+                    "鉛筆",
+                    "エンピツ",
+                    "エンピツ",
+                ],
+            ),
+            // `5` is an unknown word.
+            make_token("5", 27, 6, &["名詞", "数", "*", "*", "*", "*", "*"]),
+            make_token(
+                "本",
+                28,
+                7,
+                &[
+                    "名詞",
+                    "接尾",
+                    "助数詞",
+                    "*",
+                    "*",
+                    "*",
+                    "本",
+                    "ホン",
+                    "ホン",
+                ],
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_number_counter_token_filter_config_from_slice() {
+        let config_str = r#"
+            {
+                "kind": "ipadic",
+                "exclude": ["本"]
+            }
+            "#;
+        let config = NumberCounterTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
+        assert_eq!(config.kind, DictionaryKind::IPADIC);
+        assert_eq!(config.include, None);
+        assert!(config.exclude.contains("本"));
+
+        let config =
+            NumberCounterTokenFilterConfig::from_value(&json!({"kind": "unidic"})).unwrap();
+        assert_eq!(
+            config,
+            NumberCounterTokenFilterConfig::new(DictionaryKind::UniDic, None, None, HashSet::new())
+        );
+
+        // The kind of the dictionary is required.
+        assert!(NumberCounterTokenFilterConfig::from_value(&json!({})).is_err());
+    }
+
+    #[test]
+    fn test_number_counter_token_filter_from_slice() {
+        let config_str = r#"
+            {
+                "kind": "ipadic",
+                "include": ["個", "本"]
+            }
+            "#;
+        let result = NumberCounterTokenFilter::from_slice(config_str.as_bytes());
+        assert!(result.is_ok());
+
+        // ko-dic has a single level of part-of-speech.
+        let config_str = r#"
+            {
+                "kind": "ko-dic"
+            }
+            "#;
+        let result = NumberCounterTokenFilter::from_slice(config_str.as_bytes());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "ipadic", feature = "filter"))]
+    fn test_number_counter_token_filter_apply_ipadic() {
+        let config_str = r#"
+            {
+                "kind": "ipadic"
+            }
+            "#;
+        let filter = NumberCounterTokenFilter::from_slice(config_str.as_bytes()).unwrap();
+
+        let mut tokens = ipadic_tokens();
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|token| token.text.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["りんご", "を", "三個", "と", "鉛筆", "5本"]
+        );
+
+        // The merged tokens span both the number and the counter.
+        assert_eq!(tokens[2].byte_start, 12);
+        assert_eq!(tokens[2].byte_end, 18);
+        assert_eq!(tokens[2].position, 2);
+        assert_eq!(tokens[2].position_length, 2);
+        assert_eq!(tokens[5].byte_start, 27);
+        assert_eq!(tokens[5].byte_end, 31);
+        assert_eq!(tokens[5].position, 6);
+        assert_eq!(tokens[5].position_length, 2);
+
+        // The details of the number are kept.
+        assert_eq!(tokens[2].details[1], "数");
+        assert_eq!(tokens[2].details[2], "*");
+        assert_eq!(tokens[2].details[6], "三");
+        assert_eq!(tokens[2].details[7], "サン");
+    }
+
+    #[test]
+    #[cfg(all(feature = "ipadic", feature = "filter"))]
+    fn test_number_counter_token_filter_apply_merged_once_ipadic() {
+        let config_str = r#"
+            {
+                "kind": "ipadic"
+            }
+            "#;
+        let filter = NumberCounterTokenFilter::from_slice(config_str.as_bytes()).unwrap();
+
+        let counter = ["名詞", "接尾", "助数詞", "*", "*", "*", "個", "コ", "コ"];
+        let mut tokens = vec![
+            make_token(
+                "三",
+                0,
+                0,
+                &["名詞", "数", "*", "*", "*", "*", "三", "サン", "サン"],
+            ),
+            make_token("個", 3, 1, &counter),
+            make_token("個", 6, 2, &counter),
+        ];
+        filter.apply(&mut tokens).unwrap();
+
+        // The merged token `三個` does not take the second counter.
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|token| token.text.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["三個", "個"]
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "ipadic", feature = "filter"))]
+    fn test_number_counter_token_filter_apply_include_exclude_ipadic() {
+        let config_str = r#"
+            {
+                "kind": "ipadic",
+                "include": ["個", "本"],
+                "exclude": ["本"]
+            }
+            "#;
+        let filter = NumberCounterTokenFilter::from_slice(config_str.as_bytes()).unwrap();
+
+        let mut tokens = ipadic_tokens();
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|token| token.text.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["りんご", "を", "三個", "と", "鉛筆", "5", "本"]
+        );
+
+        let config_str = r#"
+            {
+                "kind": "ipadic",
+                "include": ["本"]
+            }
+            "#;
+        let filter = NumberCounterTokenFilter::from_slice(config_str.as_bytes()).unwrap();
+
+        let mut tokens = ipadic_tokens();
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|token| token.text.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["りんご", "を", "三", "個", "と", "鉛筆", "5本"]
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "unidic", feature = "filter"))]
+    fn test_number_counter_token_filter_apply_unidic() {
+        let config_str = r#"
+            {
+                "kind": "unidic"
+            }
+            "#;
+        let filter = NumberCounterTokenFilter::from_slice(config_str.as_bytes()).unwrap();
+
+        let mut tokens = vec![
+            make_token(
+                "三",
+                0,
+                0,
+                &[
+                    "名詞", "数詞", "*", "*", "*", "*", "サン", "三", "三", "サン", "三", "サン",
+                    "漢", "*", "*", "*", "*",
+                ],
+            ),
+            make_token(
+                "個",
+                3,
+                1,
+                &[
+                    "接尾辞",
+                    "名詞的",
+                    "助数詞",
+                    "*",
+                    "*",
+                    "*",
+                    "コ",
+                    "個",
+                    "個",
+                    "コ",
+                    "個",
+                    "コ",
+                    "漢",
+                    "*",
+                    "*",
+                    "*",
+                    "*",
+                ],
+            ),
+        ];
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|token| token.text.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["三個"]
+        );
+        assert_eq!(tokens[0].byte_end, 6);
+        assert_eq!(tokens[0].position_length, 2);
+    }
+}
@@ -0,0 +1,325 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use lindera_core::error::LinderaErrorKind;
+use lindera_core::LinderaResult;
+
+use crate::token::Token;
+use crate::token_filter::TokenFilter;
+
+pub const POS_MAP_TOKEN_FILTER_NAME: &str = "pos_map";
+
+/// The mapping from the IPADIC part-of-speech tags (the first detail) to the Universal
+/// Dependencies part-of-speech tags.
+pub const IPADIC_UD_POS_MAPPING: &[(&str, &str)] = &[
+    ("名詞", "NOUN"),
+    ("動詞", "VERB"),
+    ("形容詞", "ADJ"),
+    ("副詞", "ADV"),
+    ("連体詞", "DET"),
+    ("接続詞", "CCONJ"),
+    ("助詞", "ADP"),
+    ("助動詞", "AUX"),
+    ("感動詞", "INTJ"),
+    ("接頭詞", "NOUN"),
+    ("記号", "PUNCT"),
+    ("フィラー", "INTJ"),
+    ("その他", "X"),
+    ("UNK", "X"),
+];
+
+fn default_mapping() -> HashMap<String, String> {
+    IPADIC_UD_POS_MAPPING
+        .iter()
+        .map(|(tag, coarse_tag)| (tag.to_string(), coarse_tag.to_string()))
+        .collect()
+}
+
+/// Where the coarse tag is written.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PosMapTarget {
+    /// Write the coarse tag into the detail at this index, e.g. `{"detail": 0}` to replace the
+    /// part-of-speech tag. If the token has fewer details, they are padded with `*`.
+    Detail(usize),
+    /// Prepend the coarse tag and a `/` to the token text, e.g. `NOUN/東京`.
+    TextPrefix,
+}
+
+impl Default for PosMapTarget {
+    fn default() -> Self {
+        PosMapTarget::Detail(0)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PosMapTokenFilterConfig {
+    /// The coarse tag of each part-of-speech tag. Defaults to `IPADIC_UD_POS_MAPPING`.
+    #[serde(default = "default_mapping")]
+    mapping: HashMap<String, String>,
+
+    /// Where the coarse tag is written. Defaults to the first detail.
+    #[serde(default)]
+    target: PosMapTarget,
+}
+
+impl PosMapTokenFilterConfig {
+    pub fn new(mapping: HashMap<String, String>, target: PosMapTarget) -> Self {
+        Self { mapping, target }
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        serde_json::from_slice::<PosMapTokenFilterConfig>(data)
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+
+    pub fn from_value(value: &Value) -> LinderaResult<Self> {
+        serde_json::from_value::<PosMapTokenFilterConfig>(value.clone())
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+}
+
+impl Default for PosMapTokenFilterConfig {
+    fn default() -> Self {
+        Self::new(default_mapping(), PosMapTarget::default())
+    }
+}
+
+/// Map the part-of-speech tag of the tokens (the first detail) to a coarse tag, such as the
+/// Universal Dependencies tags `NOUN`, `VERB` or `ADP`, and write it into a detail or as a
+/// prefix of the token text.
+/// Tokens whose tag is not in the mapping are left untouched.
+///
+#[derive(Clone, Debug)]
+pub struct PosMapTokenFilter {
+    config: PosMapTokenFilterConfig,
+}
+
+impl PosMapTokenFilter {
+    pub fn new(config: PosMapTokenFilterConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        Ok(Self::new(PosMapTokenFilterConfig::from_slice(data)?))
+    }
+}
+
+impl TokenFilter for PosMapTokenFilter {
+    fn name(&self) -> &'static str {
+        POS_MAP_TOKEN_FILTER_NAME
+    }
+
+    fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
+        for token in tokens.iter_mut() {
+            let coarse_tag = match token
+                .details
+                .first()
+                .and_then(|tag| self.config.mapping.get(tag))
+            {
+                Some(coarse_tag) => coarse_tag.clone(),
+                None => continue,
+            };
+
+            match self.config.target {
+                PosMapTarget::Detail(index) => {
+                    if token.details.len() <= index {
+                        token.details.resize(index + 1, "*".to_string());
+                    }
+                    token.details[index] = coarse_tag;
+                }
+                PosMapTarget::TextPrefix => {
+                    token.text = format!("{}/{}", coarse_tag, token.text);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "ipadic")]
+    use lindera_core::word_entry::WordId;
+    use serde_json::json;
+
+    use crate::token_filter::pos_map::{PosMapTarget, PosMapTokenFilter, PosMapTokenFilterConfig};
+    #[cfg(feature = "ipadic")]
+    use crate::{token::Token, token_filter::TokenFilter};
+
+    #[test]
+    fn test_pos_map_token_filter_config_from_slice() {
+        let config_str = r#"
+            {
+                "mapping": {
+                    "名詞": "N",
+                    "動詞": "V"
+                },
+                "target": {
+                    "detail": 9
+                }
+            }
+            "#;
+        let config = PosMapTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
+
+        assert_eq!(config.mapping.len(), 2);
+        assert_eq!(config.mapping["名詞"], "N");
+        assert_eq!(config.target, PosMapTarget::Detail(9));
+
+        let config_str = r#"
+            {
+                "target": "text_prefix"
+            }
+            "#;
+        let config = PosMapTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
+
+        assert_eq!(config.mapping["名詞"], "NOUN");
+        assert_eq!(config.target, PosMapTarget::TextPrefix);
+
+        let config = PosMapTokenFilterConfig::from_value(&json!({})).unwrap();
+
+        assert_eq!(config.mapping["動詞"], "VERB");
+        assert_eq!(config.target, PosMapTarget::Detail(0));
+    }
+
+    #[test]
+    fn test_pos_map_token_filter_from_slice() {
+        let config_str = r#"
+            {}
+            "#;
+        let result = PosMapTokenFilter::from_slice(config_str.as_bytes());
+
+        assert_eq!(true, result.is_ok());
+    }
+
+    #[cfg(feature = "ipadic")]
+    fn ipadic_tokens() -> Vec<Token> {
+        vec![
+            Token {
+                text: "東京".to_string(),
+                byte_start: 0,
+                byte_end: 6,
+                position: 0,
+                position_length: 1,
+                word_id: WordId(74410, true),
+                details: vec![
+                    "名詞".to_string(),
+                    "固有名詞".to_string(),
+                    "地域".to_string(),
+                    "一般".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "東京".to_string(),
+                    "トウキョウ".to_string(),
+                    "トーキョー".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "へ".to_string(),
+                byte_start: 6,
+                byte_end: 9,
+                position: 1,
+                position_length: 1,
+                word_id: WordId(75930, true),
+                details: vec![
+                    "助詞".to_string(),
+                    "格助詞".to_string(),
+                    "一般".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "へ".to_string(),
+                    "ヘ".to_string(),
+                    "エ".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "行く".to_string(),
+                byte_start: 9,
+                byte_end: 15,
+                position: 2,
+                position_length: 1,
+                word_id: WordId(17055, true),
+                details: vec![
+                    "動詞".to_string(),
+                    "自立".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "五段・カ行促音便".to_string(),
+                    "基本形".to_string(),
+                    "行く".to_string(),
+                    "イク".to_string(),
+                    "イク".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+        ]
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_pos_map_token_filter_apply_ipadic() {
+        let filter = PosMapTokenFilter::new(PosMapTokenFilterConfig::default());
+
+        let mut tokens = ipadic_tokens();
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|t| t.details[0].as_str())
+                .collect::<Vec<_>>(),
+            vec!["NOUN", "ADP", "VERB"]
+        );
+        // the other details are kept
+        assert_eq!(&tokens[0].details[1], "固有名詞");
+        assert_eq!(&tokens[0].text, "東京");
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_pos_map_token_filter_apply_target_ipadic() {
+        let config_str = r#"
+            {
+                "target": {
+                    "detail": 9
+                }
+            }
+            "#;
+        let filter = PosMapTokenFilter::from_slice(config_str.as_bytes()).unwrap();
+
+        let mut tokens = ipadic_tokens();
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(&tokens[0].details[0], "名詞");
+        assert_eq!(&tokens[0].details[9], "NOUN");
+        assert_eq!(&tokens[2].details[9], "VERB");
+
+        let config_str = r#"
+            {
+                "mapping": {
+                    "名詞": "NOUN"
+                },
+                "target": "text_prefix"
+            }
+            "#;
+        let filter = PosMapTokenFilter::from_slice(config_str.as_bytes()).unwrap();
+
+        let mut tokens = ipadic_tokens();
+        filter.apply(&mut tokens).unwrap();
+
+        // the tags that are not in the mapping are left untouched
+        assert_eq!(
+            tokens.iter().map(|t| t.text.as_str()).collect::<Vec<_>>(),
+            vec!["NOUN/東京", "へ", "行く"]
+        );
+    }
+}
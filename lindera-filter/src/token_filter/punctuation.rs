@@ -0,0 +1,313 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use unicode_general_category::{get_general_category, GeneralCategory};
+
+use lindera_core::error::LinderaErrorKind;
+use lindera_core::LinderaResult;
+
+use crate::token::Token;
+use crate::token_filter::TokenFilter;
+
+pub const PUNCTUATION_TOKEN_FILTER_NAME: &str = "punctuation";
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PunctuationTokenFilterConfig {
+    /// The texts of the tokens to keep even though they consist only of punctuation or symbols.
+    #[serde(default)]
+    keep: HashSet<String>,
+}
+
+impl PunctuationTokenFilterConfig {
+    pub fn new(keep: HashSet<String>) -> Self {
+        Self { keep }
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        serde_json::from_slice::<PunctuationTokenFilterConfig>(data)
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+
+    pub fn from_value(value: &Value) -> LinderaResult<Self> {
+        serde_json::from_value::<PunctuationTokenFilterConfig>(value.clone())
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+}
+
+/// Remove the tokens whose text consists only of punctuation or symbol characters,
+/// such as `、`, `。`, `（` or `!?`.
+/// A character is a punctuation or symbol character if its Unicode general category is
+/// punctuation (P*) or symbol (S*), which covers the CJK and full-width punctuation.
+/// Tokens whose text is in `keep` are not removed.
+///
+#[derive(Clone, Debug)]
+pub struct PunctuationTokenFilter {
+    config: PunctuationTokenFilterConfig,
+}
+
+impl PunctuationTokenFilter {
+    pub fn new(config: PunctuationTokenFilterConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        Ok(Self::new(PunctuationTokenFilterConfig::from_slice(data)?))
+    }
+}
+
+impl TokenFilter for PunctuationTokenFilter {
+    fn name(&self) -> &'static str {
+        PUNCTUATION_TOKEN_FILTER_NAME
+    }
+
+    fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
+        tokens.retain(|token| {
+            !is_punctuation(&token.text) || self.config.keep.contains(token.text.as_str())
+        });
+
+        Ok(())
+    }
+}
+
+/// Return true if the text is not empty and consists only of punctuation or symbol characters.
+fn is_punctuation(text: &str) -> bool {
+    !text.is_empty()
+        && text.chars().all(|c| {
+            matches!(
+                get_general_category(c),
+                GeneralCategory::ConnectorPunctuation
+                    | GeneralCategory::DashPunctuation
+                    | GeneralCategory::OpenPunctuation
+                    | GeneralCategory::ClosePunctuation
+                    | GeneralCategory::InitialPunctuation
+                    | GeneralCategory::FinalPunctuation
+                    | GeneralCategory::OtherPunctuation
+                    | GeneralCategory::MathSymbol
+                    | GeneralCategory::CurrencySymbol
+                    | GeneralCategory::ModifierSymbol
+                    | GeneralCategory::OtherSymbol
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "ipadic")]
+    use lindera_core::word_entry::WordId;
+    use serde_json::json;
+
+    use crate::token_filter::punctuation::{
+        is_punctuation, PunctuationTokenFilter, PunctuationTokenFilterConfig,
+    };
+    #[cfg(feature = "ipadic")]
+    use crate::{token::Token, token_filter::TokenFilter};
+
+    #[test]
+    fn test_punctuation_token_filter_config_from_slice() {
+        let config_str = r#"
+            {
+                "keep": ["？"]
+            }
+            "#;
+        let config = PunctuationTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
+
+        assert_eq!(config.keep.len(), 1);
+        assert!(config.keep.contains("？"));
+
+        let config_str = r#"
+            {}
+            "#;
+        let config = PunctuationTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
+
+        assert!(config.keep.is_empty());
+
+        let config = PunctuationTokenFilterConfig::from_value(&json!({})).unwrap();
+
+        assert!(config.keep.is_empty());
+    }
+
+    #[test]
+    fn test_punctuation_token_filter_from_slice() {
+        let config_str = r#"
+            {
+                "keep": ["？"]
+            }
+            "#;
+        let result = PunctuationTokenFilter::from_slice(config_str.as_bytes());
+
+        assert_eq!(true, result.is_ok());
+    }
+
+    #[test]
+    fn test_is_punctuation() {
+        assert!(is_punctuation("、"));
+        assert!(is_punctuation("。"));
+        assert!(is_punctuation("（"));
+        assert!(is_punctuation("」"));
+        assert!(is_punctuation("!?"));
+        assert!(is_punctuation("・"));
+        assert!(is_punctuation("…"));
+        assert!(is_punctuation("＋"));
+        assert!(is_punctuation("¥"));
+
+        assert!(!is_punctuation("C++"));
+        assert!(!is_punctuation("東京"));
+        assert!(!is_punctuation("ー"));
+        assert!(!is_punctuation("１"));
+        assert!(!is_punctuation(" "));
+        assert!(!is_punctuation(""));
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_punctuation_token_filter_apply_ipadic() {
+        let config_str = r#"
+            {
+                "keep": ["？"]
+            }
+            "#;
+        let filter = PunctuationTokenFilter::from_slice(config_str.as_bytes()).unwrap();
+
+        let mut tokens: Vec<Token> = vec![
+            Token {
+                text: "「".to_string(),
+                byte_start: 0,
+                byte_end: 3,
+                position: 0,
+                position_length: 1,
+                word_id: WordId(4294967295, true),
+                details: vec![
+                    "記号".to_string(),
+                    "括弧開".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "「".to_string(),
+                    "「".to_string(),
+                    "「".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "東京".to_string(),
+                byte_start: 3,
+                byte_end: 9,
+                position: 1,
+                position_length: 1,
+                word_id: WordId(74410, true),
+                details: vec![
+                    "名詞".to_string(),
+                    "固有名詞".to_string(),
+                    "地域".to_string(),
+                    "一般".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "東京".to_string(),
+                    "トウキョウ".to_string(),
+                    "トーキョー".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "」".to_string(),
+                byte_start: 9,
+                byte_end: 12,
+                position: 2,
+                position_length: 1,
+                word_id: WordId(4294967295, true),
+                details: vec![
+                    "記号".to_string(),
+                    "括弧閉".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "」".to_string(),
+                    "」".to_string(),
+                    "」".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "へ".to_string(),
+                byte_start: 12,
+                byte_end: 15,
+                position: 3,
+                position_length: 1,
+                word_id: WordId(75930, true),
+                details: vec![
+                    "助詞".to_string(),
+                    "格助詞".to_string(),
+                    "一般".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "へ".to_string(),
+                    "ヘ".to_string(),
+                    "エ".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "？".to_string(),
+                byte_start: 15,
+                byte_end: 18,
+                position: 4,
+                position_length: 1,
+                word_id: WordId(4294967295, true),
+                details: vec![
+                    "記号".to_string(),
+                    "一般".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "？".to_string(),
+                    "？".to_string(),
+                    "？".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "。".to_string(),
+                byte_start: 18,
+                byte_end: 21,
+                position: 5,
+                position_length: 1,
+                word_id: WordId(4294967295, true),
+                details: vec![
+                    "記号".to_string(),
+                    "句点".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "。".to_string(),
+                    "。".to_string(),
+                    "。".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+        ];
+
+        filter.apply(&mut tokens).unwrap();
+
+        // "？" is in the keep list
+        assert_eq!(
+            tokens.iter().map(|t| t.text.as_str()).collect::<Vec<_>>(),
+            vec!["東京", "へ", "？"]
+        );
+        assert_eq!(
+            tokens.iter().map(|t| t.position).collect::<Vec<_>>(),
+            vec![1, 3, 4]
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use lindera_core::error::LinderaErrorKind;
+use lindera_core::LinderaResult;
+use lindera_dictionary::{DetailSchema, DictionaryKind};
+
+use crate::token::Token;
+use crate::token_filter::{check_detail_field, TokenFilter};
+
+pub const READING_AT_SAME_POSITION_TOKEN_FILTER_NAME: &str = "reading_at_same_position";
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ReadingAtSamePositionTokenFilterConfig {
+    kind: DictionaryKind,
+    /// The layout of the details, if it differs from the one of `kind`.
+    #[serde(default)]
+    detail_schema: Option<DetailSchema>,
+}
+
+impl ReadingAtSamePositionTokenFilterConfig {
+    pub fn new(kind: DictionaryKind, detail_schema: Option<DetailSchema>) -> Self {
+        Self {
+            kind,
+            detail_schema,
+        }
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        serde_json::from_slice::<ReadingAtSamePositionTokenFilterConfig>(data)
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+
+    pub fn from_value(value: &Value) -> LinderaResult<Self> {
+        serde_json::from_value::<ReadingAtSamePositionTokenFilterConfig>(value.clone())
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+}
+
+/// Add a token of the reading after each token, at the same position, so that both the surface
+/// and the reading are indexed for phonetic search.
+/// No token is added when the token has no registered reading (`*`), or when the reading is the
+/// same as the text, such as for a word written in katakana.
+///
+#[derive(Clone, Debug)]
+pub struct ReadingAtSamePositionTokenFilter {
+    detail_schema: DetailSchema,
+}
+
+impl ReadingAtSamePositionTokenFilter {
+    pub fn new(config: ReadingAtSamePositionTokenFilterConfig) -> LinderaResult<Self> {
+        let custom_schema = config.detail_schema.is_some();
+        let detail_schema = config
+            .detail_schema
+            .unwrap_or_else(|| config.kind.detail_schema());
+        check_detail_field(
+            READING_AT_SAME_POSITION_TOKEN_FILTER_NAME,
+            "reading",
+            detail_schema.reading,
+            &config.kind,
+            custom_schema,
+        )?;
+
+        Ok(Self { detail_schema })
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        Self::new(ReadingAtSamePositionTokenFilterConfig::from_slice(data)?)
+    }
+}
+
+impl TokenFilter for ReadingAtSamePositionTokenFilter {
+    fn name(&self) -> &'static str {
+        READING_AT_SAME_POSITION_TOKEN_FILTER_NAME
+    }
+
+    fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
+        let mut new_tokens = Vec::with_capacity(tokens.len() * 2);
+
+        for token in tokens.drain(..) {
+            let reading_token = match token.reading(&self.detail_schema) {
+                Some(reading) if reading != token.text => Some(Token {
+                    text: reading.to_string(),
+                    ..token.clone()
+                }),
+                _ => None,
+            };
+
+            new_tokens.push(token);
+            new_tokens.extend(reading_token);
+        }
+
+        *tokens = new_tokens;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "ipadic")]
+    use lindera_core::word_entry::WordId;
+    use lindera_dictionary::DictionaryKind;
+
+    use crate::token_filter::reading_at_same_position::{
+        ReadingAtSamePositionTokenFilter, ReadingAtSamePositionTokenFilterConfig,
+    };
+    #[cfg(feature = "ipadic")]
+    use crate::{token::Token, token_filter::TokenFilter};
+
+    #[test]
+    fn test_reading_at_same_position_token_filter_config_from_slice() {
+        let config_str = r#"
+            {
+                "kind": "ipadic"
+            }
+            "#;
+        let config =
+            ReadingAtSamePositionTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
+
+        assert_eq!(config.kind, DictionaryKind::IPADIC);
+        assert_eq!(config.detail_schema, None);
+    }
+
+    #[test]
+    fn test_reading_at_same_position_token_filter_from_slice() {
+        let config_str = r#"
+            {
+                "kind": "ipadic"
+            }
+            "#;
+        let result = ReadingAtSamePositionTokenFilter::from_slice(config_str.as_bytes());
+
+        assert_eq!(true, result.is_ok());
+    }
+
+    #[cfg(feature = "ipadic")]
+    fn token(
+        text: &str,
+        byte_start: usize,
+        position: usize,
+        position_length: usize,
+        details: &[&str],
+    ) -> Token {
+        Token {
+            text: text.to_string(),
+            byte_start,
+            byte_end: byte_start + text.len(),
+            position,
+            position_length,
+            word_id: WordId(0, true),
+            details: details.iter().map(|detail| detail.to_string()).collect(),
+            word_cost: None,
+            connection_cost: None,
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_reading_at_same_position_token_filter_apply_ipadic() {
+        let filter = ReadingAtSamePositionTokenFilter::new(
+            ReadingAtSamePositionTokenFilterConfig::new(DictionaryKind::IPADIC, None),
+        )
+        .unwrap();
+
+        let mut tokens: Vec<Token> = vec![
+            // a compound token spanning two positions
+            token(
+                "羽田空港",
+                0,
+                0,
+                2,
+                &[
+                    "名詞",
+                    "固有名詞",
+                    "一般",
+                    "*",
+                    "*",
+                    "*",
+                    "羽田空港",
+                    "ハネダクウコウ",
+                    "ハネダクーコー",
+                ],
+            ),
+            token(
+                "ターミナル",
+                12,
+                2,
+                1,
+                &[
+                    "名詞",
+                    "一般",
+                    "*",
+                    "*",
+                    "*",
+                    "*",
+                    "ターミナル",
+                    "ターミナル",
+                    "ターミナル",
+                ],
+            ),
+            token("ＡＢＣ", 27, 3, 1, &["UNK"]),
+        ];
+
+        filter.apply(&mut tokens).unwrap();
+
+        // the reading token follows the original token and shares its position, while the
+        // tokens whose reading is the text or unknown are left alone
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|t| (t.text.as_str(), t.position, t.position_length))
+                .collect::<Vec<_>>(),
+            vec![
+                ("羽田空港", 0, 2),
+                ("ハネダクウコウ", 0, 2),
+                ("ターミナル", 2, 1),
+                ("ＡＢＣ", 3, 1),
+            ]
+        );
+        assert_eq!(tokens[1].byte_start, 0);
+        assert_eq!(tokens[1].byte_end, 12);
+        assert_eq!(tokens[1].details, tokens[0].details);
+    }
+}
@@ -0,0 +1,176 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use lindera_core::error::LinderaErrorKind;
+use lindera_core::LinderaResult;
+
+use crate::token::Token;
+use crate::token_filter::TokenFilter;
+
+pub const REGEX_KEEP_TOKEN_FILTER_NAME: &str = "regex_keep";
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RegexKeepTokenFilterConfig {
+    pattern: String,
+    /// If true, the tokens matching the pattern are removed instead of kept.
+    #[serde(default)]
+    invert: bool,
+}
+
+impl RegexKeepTokenFilterConfig {
+    pub fn new(pattern: String, invert: bool) -> Self {
+        Self { pattern, invert }
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        serde_json::from_slice::<RegexKeepTokenFilterConfig>(data)
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+
+    pub fn from_value(value: &Value) -> LinderaResult<Self> {
+        serde_json::from_value::<RegexKeepTokenFilterConfig>(value.clone())
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+}
+
+/// Keep only the tokens whose text matches the regular expression, such as hashtags with `^#`.
+/// With `invert`, the tokens whose text matches are removed instead.
+///
+#[derive(Clone, Debug)]
+pub struct RegexKeepTokenFilter {
+    config: RegexKeepTokenFilterConfig,
+    regex: Regex,
+}
+
+impl RegexKeepTokenFilter {
+    pub fn new(config: RegexKeepTokenFilterConfig) -> LinderaResult<Self> {
+        let regex =
+            Regex::new(&config.pattern).map_err(|err| LinderaErrorKind::Args.with_error(err))?;
+
+        Ok(Self { config, regex })
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        Self::new(RegexKeepTokenFilterConfig::from_slice(data)?)
+    }
+}
+
+impl TokenFilter for RegexKeepTokenFilter {
+    fn name(&self) -> &'static str {
+        REGEX_KEEP_TOKEN_FILTER_NAME
+    }
+
+    fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
+        tokens.retain(|token| self.regex.is_match(&token.text) != self.config.invert);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "ipadic")]
+    use lindera_core::word_entry::WordId;
+
+    use crate::token_filter::regex_keep::{RegexKeepTokenFilter, RegexKeepTokenFilterConfig};
+    #[cfg(feature = "ipadic")]
+    use crate::{token::Token, token_filter::TokenFilter};
+
+    #[test]
+    fn test_regex_keep_token_filter_config_from_slice() {
+        let config_str = r#"
+            {
+                "pattern": "^#"
+            }
+            "#;
+        let config = RegexKeepTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
+
+        assert_eq!(config.pattern, "^#");
+        assert!(!config.invert);
+
+        let config_str = r#"
+            {
+                "pattern": "^#",
+                "invert": true
+            }
+            "#;
+        let config = RegexKeepTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
+
+        assert!(config.invert);
+    }
+
+    #[test]
+    fn test_regex_keep_token_filter_from_slice() {
+        let config_str = r#"
+            {
+                "pattern": "^#"
+            }
+            "#;
+        let result = RegexKeepTokenFilter::from_slice(config_str.as_bytes());
+
+        assert_eq!(true, result.is_ok());
+
+        // an invalid pattern is rejected when the filter is created
+        let config_str = r#"
+            {
+                "pattern": "^(#"
+            }
+            "#;
+        let result = RegexKeepTokenFilter::from_slice(config_str.as_bytes());
+
+        assert_eq!(true, result.is_err());
+    }
+
+    #[cfg(feature = "ipadic")]
+    fn ipadic_tokens() -> Vec<Token> {
+        // a "#" in the middle of the text does not match "^#"
+        ["#東京", "へ", "#旅行", "東京#"]
+            .iter()
+            .enumerate()
+            .map(|(position, text)| Token {
+                text: text.to_string(),
+                byte_start: position * 10,
+                byte_end: position * 10 + text.len(),
+                position,
+                position_length: 1,
+                word_id: WordId(4294967295, true),
+                details: vec!["UNK".to_string()],
+                word_cost: None,
+                connection_cost: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_regex_keep_token_filter_apply_ipadic() {
+        let filter =
+            RegexKeepTokenFilter::new(RegexKeepTokenFilterConfig::new("^#".to_string(), false))
+                .unwrap();
+
+        let mut tokens = ipadic_tokens();
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(
+            tokens.iter().map(|t| t.text.as_str()).collect::<Vec<_>>(),
+            vec!["#東京", "#旅行"]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_regex_keep_token_filter_apply_invert_ipadic() {
+        let filter =
+            RegexKeepTokenFilter::new(RegexKeepTokenFilterConfig::new("^#".to_string(), true))
+                .unwrap();
+
+        let mut tokens = ipadic_tokens();
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(
+            tokens.iter().map(|t| t.text.as_str()).collect::<Vec<_>>(),
+            vec!["へ", "東京#"]
+        );
+    }
+}
@@ -0,0 +1,197 @@
+use lindera_core::LinderaResult;
+
+use crate::check_args;
+use crate::token::Token;
+use crate::token_filter::TokenFilter;
+
+pub const SCRIPT_TAG_TOKEN_FILTER_NAME: &str = "script_tag";
+
+/// The scripts a token can be tagged with.
+/// When two scripts have as many characters in a token, the first one in this order wins.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Script {
+    Hiragana,
+    Katakana,
+    Kanji,
+    Latin,
+    Digit,
+    Other,
+}
+
+impl Script {
+    const ALL: [Script; 6] = [
+        Script::Hiragana,
+        Script::Katakana,
+        Script::Kanji,
+        Script::Latin,
+        Script::Digit,
+        Script::Other,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Script::Hiragana => "Hiragana",
+            Script::Katakana => "Katakana",
+            Script::Kanji => "Kanji",
+            Script::Latin => "Latin",
+            Script::Digit => "Digit",
+            Script::Other => "Other",
+        }
+    }
+
+    /// Classify a character. The full-width Latin letters and digits, and the half-width
+    /// katakana are classified like their ordinary forms.
+    pub fn of_char(c: char) -> Self {
+        match c {
+            '\u{3041}'..='\u{309F}' => Script::Hiragana,
+            '\u{30A0}'..='\u{30FF}' | '\u{31F0}'..='\u{31FF}' | '\u{FF66}'..='\u{FF9F}' => {
+                Script::Katakana
+            }
+            '\u{3005}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{20000}'..='\u{2FA1F}' => Script::Kanji,
+            'A'..='Z'
+            | 'a'..='z'
+            | '\u{00C0}'..='\u{024F}'
+            | '\u{FF21}'..='\u{FF3A}'
+            | '\u{FF41}'..='\u{FF5A}'
+                if c.is_alphabetic() =>
+            {
+                Script::Latin
+            }
+            '0'..='9' | '\u{FF10}'..='\u{FF19}' => Script::Digit,
+            _ => Script::Other,
+        }
+    }
+
+    /// Classify a text by the script of the majority of its characters.
+    /// An empty text is classified as `Other`.
+    pub fn of_text(text: &str) -> Self {
+        let mut counts = [0_usize; Script::ALL.len()];
+        for c in text.chars() {
+            counts[Script::of_char(c) as usize] += 1;
+        }
+
+        let mut script = Script::Other;
+        let mut max_count = 0;
+        for (candidate, count) in Script::ALL.iter().zip(counts) {
+            if count > max_count {
+                script = *candidate;
+                max_count = count;
+            }
+        }
+
+        script
+    }
+}
+
+/// Append the dominant script of the token text to the token details, one of `Hiragana`,
+/// `Katakana`, `Kanji`, `Latin`, `Digit` or `Other`, e.g. to route the tokens to different
+/// analyzers.
+///
+#[derive(Clone, Debug)]
+pub struct ScriptTagTokenFilter {}
+
+impl ScriptTagTokenFilter {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        check_args(data)?;
+
+        Ok(Self::new())
+    }
+}
+
+impl Default for ScriptTagTokenFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TokenFilter for ScriptTagTokenFilter {
+    fn name(&self) -> &'static str {
+        SCRIPT_TAG_TOKEN_FILTER_NAME
+    }
+
+    fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
+        for token in tokens.iter_mut() {
+            let script = Script::of_text(&token.text);
+            token.details.push(script.as_str().to_string());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "ipadic")]
+    use lindera_core::word_entry::WordId;
+
+    use crate::token_filter::script_tag::{Script, ScriptTagTokenFilter};
+    #[cfg(feature = "ipadic")]
+    use crate::{token::Token, token_filter::TokenFilter};
+
+    #[test]
+    fn test_script_tag_token_filter_from_slice() {
+        let config_str = r#"
+            {}
+            "#;
+        let result = ScriptTagTokenFilter::from_slice(config_str.as_bytes());
+
+        assert_eq!(true, result.is_ok());
+    }
+
+    #[test]
+    fn test_script_of_text() {
+        assert_eq!(Script::of_text("カタカナ"), Script::Katakana);
+        assert_eq!(Script::of_text("ｶﾀｶﾅ"), Script::Katakana);
+        assert_eq!(Script::of_text("ひらがな"), Script::Hiragana);
+        assert_eq!(Script::of_text("漢字"), Script::Kanji);
+        assert_eq!(Script::of_text("人々"), Script::Kanji);
+        assert_eq!(Script::of_text("１２３"), Script::Digit);
+        assert_eq!(Script::of_text("、。"), Script::Other);
+        assert_eq!(Script::of_text(""), Script::Other);
+
+        // the majority of the characters wins
+        assert_eq!(Script::of_text("東京へ"), Script::Kanji);
+        assert_eq!(Script::of_text("食べる"), Script::Hiragana);
+        assert_eq!(Script::of_text("ABC1234"), Script::Digit);
+
+        // on a tie, Latin comes before Digit
+        assert_eq!(Script::of_text("ABC123"), Script::Latin);
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_script_tag_token_filter_apply_ipadic() {
+        let filter = ScriptTagTokenFilter::default();
+
+        let mut tokens: Vec<Token> = ["カタカナ", "漢字", "ABC123"]
+            .iter()
+            .enumerate()
+            .map(|(position, text)| Token {
+                text: text.to_string(),
+                byte_start: position * 12,
+                byte_end: position * 12 + text.len(),
+                position,
+                position_length: 1,
+                word_id: WordId(4294967295, true),
+                details: vec!["UNK".to_string()],
+                word_cost: None,
+                connection_cost: None,
+            })
+            .collect();
+
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(tokens[0].details, vec!["UNK", "Katakana"]);
+        assert_eq!(tokens[1].details, vec!["UNK", "Kanji"]);
+        assert_eq!(tokens[2].details, vec!["UNK", "Latin"]);
+        assert_eq!(&tokens[2].text, "ABC123");
+    }
+}
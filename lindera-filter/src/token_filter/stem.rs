@@ -0,0 +1,443 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use lindera_core::error::LinderaErrorKind;
+use lindera_core::LinderaResult;
+use lindera_dictionary::{DetailSchema, DictionaryKind};
+
+use crate::token::Token;
+use crate::token_filter::{check_detail_field, TokenFilter};
+
+pub const STEM_TOKEN_FILTER_NAME: &str = "stem";
+
+/// The major parts-of-speech of the conjugatable words.
+const CONJUGATABLE_POS: [&str; 2] = ["動詞", "形容詞"];
+
+/// The major part-of-speech of the auxiliary verbs.
+const AUXILIARY_VERB_POS: &str = "助動詞";
+
+/// The part-of-speech subcategory of the suffix verbs and adjectives of IPADIC, such as
+/// `させる`, `られる` or `らしい`, which UniDic tags as auxiliary verbs.
+const SUFFIX_POS: &str = "接尾";
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct StemTokenFilterConfig {
+    kind: DictionaryKind,
+    /// The layout of the details, if it differs from the one of `kind`.
+    #[serde(default)]
+    detail_schema: Option<DetailSchema>,
+    /// If true, the auxiliary verbs immediately following a verb or an adjective are removed.
+    #[serde(default)]
+    drop_aux: bool,
+}
+
+impl StemTokenFilterConfig {
+    pub fn new(kind: DictionaryKind, detail_schema: Option<DetailSchema>, drop_aux: bool) -> Self {
+        Self {
+            kind,
+            detail_schema,
+            drop_aux,
+        }
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        serde_json::from_slice::<StemTokenFilterConfig>(data)
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+
+    pub fn from_value(value: &Value) -> LinderaResult<Self> {
+        serde_json::from_value::<StemTokenFilterConfig>(value.clone())
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+}
+
+/// Reduce the verbs and adjectives to their stem, the base form registered in the
+/// morphological dictionary, e.g. `食べ` to `食べる`. Other tokens are left unchanged.
+///
+/// If `drop_aux` is enabled, the auxiliary verbs (`助動詞`) immediately following a verb or
+/// an adjective are removed as part of its inflection, so `食べさせられた` gives `食べる` alone.
+/// The suffix verbs and adjectives of IPADIC (`動詞,接尾`, `形容詞,接尾`), such as `させる`, are
+/// removed in the same way. The offsets of the stem token are left unchanged.
+///
+#[derive(Clone, Debug)]
+pub struct StemTokenFilter {
+    detail_schema: DetailSchema,
+    drop_aux: bool,
+}
+
+impl StemTokenFilter {
+    pub fn new(config: StemTokenFilterConfig) -> LinderaResult<Self> {
+        let custom_schema = config.detail_schema.is_some();
+        let detail_schema = config
+            .detail_schema
+            .unwrap_or_else(|| config.kind.detail_schema());
+        check_detail_field(
+            STEM_TOKEN_FILTER_NAME,
+            "part-of-speech",
+            detail_schema.pos.first().copied(),
+            &config.kind,
+            custom_schema,
+        )?;
+        check_detail_field(
+            STEM_TOKEN_FILTER_NAME,
+            "base form",
+            detail_schema.base_form,
+            &config.kind,
+            custom_schema,
+        )?;
+
+        Ok(Self {
+            detail_schema,
+            drop_aux: config.drop_aux,
+        })
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        Self::new(StemTokenFilterConfig::from_slice(data)?)
+    }
+
+    fn is_conjugatable(&self, token: &Token) -> bool {
+        token
+            .pos(&self.detail_schema)
+            .is_some_and(|pos| CONJUGATABLE_POS.contains(&pos))
+    }
+
+    /// Whether the token is part of the inflection of a preceding verb or adjective.
+    fn is_inflection(&self, token: &Token) -> bool {
+        match token.pos(&self.detail_schema) {
+            Some(AUXILIARY_VERB_POS) => true,
+            Some(pos) if CONJUGATABLE_POS.contains(&pos) => self
+                .detail_schema
+                .pos
+                .get(1)
+                .and_then(|index| token.details.get(*index))
+                .is_some_and(|subcategory| subcategory == SUFFIX_POS),
+            _ => false,
+        }
+    }
+}
+
+impl TokenFilter for StemTokenFilter {
+    fn name(&self) -> &'static str {
+        STEM_TOKEN_FILTER_NAME
+    }
+
+    fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
+        let mut stemmed = Vec::with_capacity(tokens.len());
+        // Whether the previous token is a stem whose inflection may follow.
+        let mut after_stem = false;
+
+        for mut token in tokens.drain(..) {
+            if self.drop_aux && after_stem && self.is_inflection(&token) {
+                continue;
+            }
+
+            after_stem = self.is_conjugatable(&token);
+            if after_stem {
+                if let Some(base_form) = token.base_form(&self.detail_schema) {
+                    token.text = base_form.to_string();
+                }
+            }
+
+            stemmed.push(token);
+        }
+
+        *tokens = stemmed;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(all(feature = "ipadic", feature = "filter"))]
+    use lindera_dictionary::DictionaryKind;
+
+    #[cfg(all(feature = "ipadic", feature = "filter"))]
+    use crate::token::Token;
+    #[cfg(all(any(feature = "ipadic", feature = "unidic",), feature = "filter"))]
+    use crate::token_filter::make_token;
+    #[cfg(all(
+        any(feature = "ipadic", feature = "ko-dic", feature = "unidic",),
+        feature = "filter"
+    ))]
+    use crate::token_filter::stem::StemTokenFilter;
+    #[cfg(all(feature = "ipadic", feature = "filter"))]
+    use crate::token_filter::stem::StemTokenFilterConfig;
+    #[cfg(all(any(feature = "ipadic", feature = "unidic",), feature = "filter"))]
+    use crate::token_filter::TokenFilter;
+
+    /// The tokens of `食べさせられたので高かった` with IPADIC.
+    #[cfg(all(feature = "ipadic", feature = "filter"))]
+    fn ipadic_tokens() -> Vec<Token> {
+        vec![
+            make_token(
+                "食べ",
+                0,
+                0,
+                &[
+                    "動詞",
+                    "自立",
+                    "*",
+                    "*",
+                    "一段",
+                    "連用形",
+                    "食べる",
+                    "タベ",
+                    "タベ",
+                ],
+            ),
+            make_token(
+                "させ",
+                6,
+                1,
+                &[
+                    "動詞",
+                    "接尾",
+                    "*",
+                    "*",
+                    "一段",
+                    "未然形",
+                    "させる",
+                    "サセ",
+                    "サセ",
+                ],
+            ),
+            make_token(
+                "られ",
+                12,
+                2,
+                &[
+                    "動詞",
+                    "接尾",
+                    "*",
+                    "*",
+                    "一段",
+                    "連用形",
+                    "られる",
+                    "ラレ",
+                    "ラレ",
+                ],
+            ),
+            make_token(
+                "た",
+                18,
+                3,
+                &[
+                    "助動詞",
+                    "*",
+                    "*",
+                    "*",
+                    "特殊・タ",
+                    "基本形",
+                    "た",
+                    "タ",
+                    "タ",
+                ],
+            ),
+            make_token(
+                "ので",
+                21,
+                4,
+                &[
+                    "助詞",
+                    "接続助詞",
+                    "*",
+                    "*",
+                    "*",
+                    "*",
+                    "ので",
+                    "ノデ",
+                    "ノデ",
+                ],
+            ),
+            make_token(
+                "高かっ",
+                27,
+                5,
+                &[
+                    "形容詞",
+                    "自立",
+                    "*",
+                    "*",
+                    "形容詞・アウオ段",
+                    "連用タ接続",
+                    "高い",
+                    "タカカッ",
+                    "タカカッ",
+                ],
+            ),
+            make_token(
+                "た",
+                36,
+                6,
+                &[
+                    "助動詞",
+                    "*",
+                    "*",
+                    "*",
+                    "特殊・タ",
+                    "基本形",
+                    "た",
+                    "タ",
+                    "タ",
+                ],
+            ),
+        ]
+    }
+
+    #[cfg(all(feature = "ipadic", feature = "filter"))]
+    #[test]
+    fn test_stem_token_filter_config_from_slice_ipadic() {
+        let config_str = r#"
+        {
+            "kind": "ipadic",
+            "drop_aux": true
+        }
+        "#;
+        let config = StemTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
+
+        assert_eq!(config.kind, DictionaryKind::IPADIC);
+        assert!(config.drop_aux);
+    }
+
+    #[cfg(all(feature = "ipadic", feature = "filter"))]
+    #[test]
+    fn test_stem_token_filter_from_slice_ipadic() {
+        let config_str = r#"
+        {
+            "kind": "ipadic"
+        }
+        "#;
+        let result = StemTokenFilter::from_slice(config_str.as_bytes());
+
+        assert_eq!(true, result.is_ok());
+    }
+
+    #[cfg(all(feature = "ko-dic", feature = "filter"))]
+    #[test]
+    fn test_stem_token_filter_from_slice_ko_dic() {
+        let config_str = r#"
+        {
+            "kind": "ko-dic"
+        }
+        "#;
+        let result = StemTokenFilter::from_slice(config_str.as_bytes());
+
+        // ko-dic has no base form field.
+        assert_eq!(true, result.is_err());
+    }
+
+    #[cfg(all(feature = "ipadic", feature = "filter"))]
+    #[test]
+    fn test_stem_token_filter_apply_ipadic() {
+        let config_str = r#"
+        {
+            "kind": "ipadic",
+            "drop_aux": true
+        }
+        "#;
+        let filter = StemTokenFilter::from_slice(config_str.as_bytes()).unwrap();
+
+        let mut tokens = ipadic_tokens();
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(
+            tokens.iter().map(|t| t.text.as_str()).collect::<Vec<_>>(),
+            vec!["食べる", "ので", "高い"]
+        );
+        assert_eq!((tokens[0].byte_start, tokens[0].byte_end), (0, 6));
+        assert_eq!(tokens[1].position, 4);
+        assert_eq!(tokens[2].position, 5);
+    }
+
+    #[cfg(all(feature = "ipadic", feature = "filter"))]
+    #[test]
+    fn test_stem_token_filter_apply_without_drop_aux_ipadic() {
+        let config = StemTokenFilterConfig::new(DictionaryKind::IPADIC, None, false);
+        let filter = StemTokenFilter::new(config).unwrap();
+
+        let mut tokens = ipadic_tokens();
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(
+            tokens.iter().map(|t| t.text.as_str()).collect::<Vec<_>>(),
+            vec!["食べる", "させる", "られる", "た", "ので", "高い", "た"]
+        );
+    }
+
+    #[cfg(all(feature = "unidic", feature = "filter"))]
+    #[test]
+    fn test_stem_token_filter_apply_unidic() {
+        let config_str = r#"
+        {
+            "kind": "unidic",
+            "drop_aux": true
+        }
+        "#;
+        let filter = StemTokenFilter::from_slice(config_str.as_bytes()).unwrap();
+
+        let aux = |text: &str, byte_start: usize, position: usize, base_form: &str| {
+            make_token(
+                text,
+                byte_start,
+                position,
+                &[
+                    "助動詞",
+                    "*",
+                    "*",
+                    "*",
+                    "助動詞-*",
+                    "*",
+                    "*",
+                    "*",
+                    "*",
+                    "*",
+                    base_form,
+                    "*",
+                    "和",
+                    "*",
+                    "*",
+                    "*",
+                    "*",
+                ],
+            )
+        };
+        let mut tokens = vec![
+            make_token(
+                "食べ",
+                0,
+                0,
+                &[
+                    "動詞",
+                    "一般",
+                    "*",
+                    "*",
+                    "下一段-バ行",
+                    "連用形-一般",
+                    "タベル",
+                    "食べる",
+                    "食べ",
+                    "タベ",
+                    "食べる",
+                    "タベル",
+                    "和",
+                    "*",
+                    "*",
+                    "*",
+                    "*",
+                ],
+            ),
+            aux("させ", 6, 1, "させる"),
+            aux("られ", 12, 2, "られる"),
+            aux("た", 18, 3, "た"),
+        ];
+
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(
+            tokens.iter().map(|t| t.text.as_str()).collect::<Vec<_>>(),
+            vec!["食べる"]
+        );
+    }
+}
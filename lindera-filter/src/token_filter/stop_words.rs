@@ -131,6 +131,8 @@ mod tests {
                     "スモモ".to_string(),
                     "スモモ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "も".to_string(),
@@ -150,6 +152,8 @@ mod tests {
                     "モ".to_string(),
                     "モ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "もも".to_string(),
@@ -169,6 +173,8 @@ mod tests {
                     "モモ".to_string(),
                     "モモ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "も".to_string(),
@@ -188,6 +194,8 @@ mod tests {
                     "モ".to_string(),
                     "モ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "もも".to_string(),
@@ -207,6 +215,8 @@ mod tests {
                     "モモ".to_string(),
                     "モモ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "の".to_string(),
@@ -226,6 +236,8 @@ mod tests {
                     "ノ".to_string(),
                     "ノ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
             Token {
                 text: "うち".to_string(),
@@ -245,6 +257,8 @@ mod tests {
                     "ウチ".to_string(),
                     "ウチ".to_string(),
                 ],
+                word_cost: None,
+                connection_cost: None,
             },
         ];
 
@@ -256,4 +270,66 @@ mod tests {
         assert_eq!(&tokens[2].text, "もも");
         assert_eq!(&tokens[3].text, "うち");
     }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_stop_words_token_filter_apply_empty_words_ipadic() {
+        let config_str = r#"
+            {
+                "words": []
+            }
+            "#;
+        let filter = StopWordsTokenFilter::from_slice(config_str.as_bytes()).unwrap();
+
+        let mut tokens: Vec<Token> = vec![
+            Token {
+                text: "すもも".to_string(),
+                byte_start: 0,
+                byte_end: 9,
+                position: 0,
+                position_length: 1,
+                word_id: WordId(36165, true),
+                details: vec![
+                    "名詞".to_string(),
+                    "一般".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "すもも".to_string(),
+                    "スモモ".to_string(),
+                    "スモモ".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "も".to_string(),
+                byte_start: 9,
+                byte_end: 12,
+                position: 1,
+                position_length: 1,
+                word_id: WordId(73246, true),
+                details: vec![
+                    "助詞".to_string(),
+                    "係助詞".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "も".to_string(),
+                    "モ".to_string(),
+                    "モ".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+        ];
+
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(&tokens[0].text, "すもも");
+        assert_eq!(&tokens[1].text, "も");
+    }
 }
@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use lindera_core::error::LinderaErrorKind;
+use lindera_core::LinderaResult;
+
+use crate::token::Token;
+use crate::token_filter::TokenFilter;
+
+pub const SYNONYM_TOKEN_FILTER_NAME: &str = "synonym";
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct SynonymTokenFilterConfig {
+    synonyms: HashMap<String, Vec<String>>,
+}
+
+impl SynonymTokenFilterConfig {
+    pub fn new(synonyms: HashMap<String, Vec<String>>) -> Self {
+        Self { synonyms }
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        serde_json::from_slice::<SynonymTokenFilterConfig>(data)
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+
+    pub fn from_value(value: &Value) -> LinderaResult<Self> {
+        serde_json::from_value::<SynonymTokenFilterConfig>(value.clone())
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
+    }
+}
+
+/// Add the synonyms of the token text as tokens following the original token.
+/// The synonym tokens have the same offsets, position and position length as the original token,
+/// so that they overlay it.
+///
+#[derive(Clone, Debug)]
+pub struct SynonymTokenFilter {
+    config: SynonymTokenFilterConfig,
+}
+
+impl SynonymTokenFilter {
+    pub fn new(config: SynonymTokenFilterConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        Ok(Self::new(SynonymTokenFilterConfig::from_slice(data)?))
+    }
+}
+
+impl TokenFilter for SynonymTokenFilter {
+    fn name(&self) -> &'static str {
+        SYNONYM_TOKEN_FILTER_NAME
+    }
+
+    fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
+        let mut new_tokens = Vec::with_capacity(tokens.len());
+
+        for token in tokens.drain(..) {
+            let synonym_tokens = self
+                .config
+                .synonyms
+                .get(&token.text)
+                .into_iter()
+                .flatten()
+                .map(|synonym| Token {
+                    text: synonym.clone(),
+                    ..token.clone()
+                })
+                .collect::<Vec<_>>();
+
+            new_tokens.push(token);
+            new_tokens.extend(synonym_tokens);
+        }
+
+        *tokens = new_tokens;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "ipadic")]
+    use lindera_core::word_entry::WordId;
+
+    use crate::token_filter::synonym::{SynonymTokenFilter, SynonymTokenFilterConfig};
+    #[cfg(feature = "ipadic")]
+    use crate::{token::Token, token_filter::TokenFilter};
+
+    #[test]
+    fn test_synonym_token_filter_config_from_slice() {
+        let config_str = r#"
+            {
+                "synonyms": {
+                    "東京": ["トウキョウ", "Tokyo"],
+                    "大阪": ["Osaka"]
+                }
+            }
+            "#;
+        let config = SynonymTokenFilterConfig::from_slice(config_str.as_bytes()).unwrap();
+
+        assert_eq!(config.synonyms.len(), 2);
+        assert_eq!(
+            config.synonyms.get("東京").unwrap(),
+            &vec!["トウキョウ".to_string(), "Tokyo".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_synonym_token_filter_from_slice() {
+        let config_str = r#"
+            {
+                "synonyms": {
+                    "東京": ["トウキョウ", "Tokyo"]
+                }
+            }
+            "#;
+        let result = SynonymTokenFilter::from_slice(config_str.as_bytes());
+
+        assert_eq!(true, result.is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_synonym_token_filter_apply_ipadic() {
+        let config_str = r#"
+            {
+                "synonyms": {
+                    "東京": ["トウキョウ", "Tokyo"]
+                }
+            }
+            "#;
+        let filter = SynonymTokenFilter::from_slice(config_str.as_bytes()).unwrap();
+
+        let mut tokens: Vec<Token> = vec![
+            Token {
+                text: "東京".to_string(),
+                byte_start: 0,
+                byte_end: 6,
+                position: 0,
+                position_length: 1,
+                word_id: WordId(74410, true),
+                details: vec![
+                    "名詞".to_string(),
+                    "固有名詞".to_string(),
+                    "地域".to_string(),
+                    "一般".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "東京".to_string(),
+                    "トウキョウ".to_string(),
+                    "トーキョー".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "へ".to_string(),
+                byte_start: 6,
+                byte_end: 9,
+                position: 1,
+                position_length: 1,
+                word_id: WordId(75930, true),
+                details: vec![
+                    "助詞".to_string(),
+                    "格助詞".to_string(),
+                    "一般".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "へ".to_string(),
+                    "ヘ".to_string(),
+                    "エ".to_string(),
+                ],
+                word_cost: None,
+                connection_cost: None,
+            },
+        ];
+
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(tokens.len(), 4);
+        assert_eq!(
+            tokens.iter().map(|t| t.text.as_str()).collect::<Vec<_>>(),
+            vec!["東京", "トウキョウ", "Tokyo", "へ"]
+        );
+
+        // the synonyms overlay the original token
+        for token in tokens.iter().take(3) {
+            assert_eq!(token.byte_start, 0);
+            assert_eq!(token.byte_end, 6);
+            assert_eq!(token.position, 0);
+            assert_eq!(token.position_length, 1);
+        }
+
+        assert_eq!(tokens[3].byte_start, 6);
+        assert_eq!(tokens[3].byte_end, 9);
+        assert_eq!(tokens[3].position, 1);
+    }
+}
@@ -1,11 +1,14 @@
 use lindera_core::LinderaResult;
 
+use crate::check_args;
 use crate::token::Token;
 use crate::token_filter::TokenFilter;
 
 pub const UPPERCASE_TOKEN_FILTER_NAME: &str = "uppercase";
 
 /// Normalizes token text to uppercase.
+/// Only the text is replaced, so the byte offsets keep pointing at the original text
+/// even if a character expands to several characters (e.g. "ß" to "SS").
 ///
 #[derive(Clone, Debug)]
 pub struct UppercaseTokenFilter {}
@@ -14,6 +17,12 @@ impl UppercaseTokenFilter {
     pub fn new() -> Self {
         Self {}
     }
+
+    pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
+        check_args(data)?;
+
+        Ok(Self::new())
+    }
 }
 
 impl Default for UppercaseTokenFilter {
@@ -41,30 +50,56 @@ mod tests {
     #[cfg(feature = "ipadic")]
     use lindera_core::word_entry::WordId;
 
+    use crate::token_filter::uppercase::UppercaseTokenFilter;
     #[cfg(feature = "ipadic")]
-    use crate::{
-        token::Token,
-        token_filter::{uppercase::UppercaseTokenFilter, TokenFilter},
-    };
+    use crate::{token::Token, token_filter::TokenFilter};
+
+    #[test]
+    fn test_uppercase_token_filter_from_slice() {
+        let config_str = r#"
+            {}
+            "#;
+        let result = UppercaseTokenFilter::from_slice(config_str.as_bytes());
+
+        assert_eq!(true, result.is_ok());
+    }
 
     #[test]
     #[cfg(feature = "ipadic")]
-    fn test_uppercase_token_filter_apply() {
+    fn test_uppercase_token_filter_apply_ipadic() {
         let filter = UppercaseTokenFilter::default();
 
-        let mut tokens: Vec<Token> = vec![Token {
-            text: "Rust".to_string(),
-            byte_start: 0,
-            byte_end: 4,
-            position: 0,
-            position_length: 1,
-            word_id: WordId(4294967295, true),
-            details: vec!["UNK".to_string()],
-        }];
+        let mut tokens: Vec<Token> = vec![
+            Token {
+                text: "Rust".to_string(),
+                byte_start: 0,
+                byte_end: 4,
+                position: 0,
+                position_length: 1,
+                word_id: WordId(4294967295, true),
+                details: vec!["UNK".to_string()],
+                word_cost: None,
+                connection_cost: None,
+            },
+            Token {
+                text: "Maße".to_string(),
+                byte_start: 4,
+                byte_end: 9,
+                position: 1,
+                position_length: 1,
+                word_id: WordId(4294967295, true),
+                details: vec!["UNK".to_string()],
+                word_cost: None,
+                connection_cost: None,
+            },
+        ];
 
         filter.apply(&mut tokens).unwrap();
 
-        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens.len(), 2);
         assert_eq!(&tokens[0].text, "RUST");
+        assert_eq!(&tokens[1].text, "MASSE");
+        assert_eq!(tokens[1].byte_start, 4);
+        assert_eq!(tokens[1].byte_end, 9);
     }
 }
@@ -1,27 +1,75 @@
 use std::{fs, path::Path};
 
+use csv::StringRecord;
+
 use lindera_core::{
-    character_definition::CharacterDefinitions, dictionary::UserDictionary,
-    dictionary_builder::DictionaryBuilder, error::LinderaErrorKind, LinderaResult,
+    character_definition::CharacterDefinitions,
+    dictionary::UserDictionary,
+    dictionary_builder::{DictionaryBuilder, ValidationReport},
+    error::LinderaErrorKind,
+    LinderaResult,
 };
 use lindera_decompress::Algorithm;
 use lindera_dictionary_builder::{
-    build_user_dictionary, CharDefBuilderOptions, CostMatrixBuilderOptions, DictBuilderOptions,
-    UnkBuilderOptions, UserDictBuilderOptions,
+    build_user_dictionary, validate_source, write_dictionary_kind, CharDefBuilder,
+    CharDefBuilderOptions, CostMatrixBuilder, CostMatrixBuilderOptions, DictBuilder,
+    DictBuilderOptions, UnkBuilder, UnkBuilderOptions, UserDictBuilder, UserDictBuilderOptions,
 };
 
+const DICTIONARY_KIND: &str = "ko-dic";
 const SIMPLE_USERDIC_FIELDS_NUM: usize = 3;
 const SIMPLE_WORD_COST: i16 = -10000;
 const SIMPLE_CONTEXT_ID: u16 = 0;
 const DETAILED_USERDIC_FIELDS_NUM: usize = 12;
-const COMPRESS_ALGORITHM: Algorithm = Algorithm::Deflate;
+const DEFAULT_COMPRESS_ALGORITHM: Algorithm = Algorithm::Deflate;
 const UNK_FIELDS_NUM: usize = 12;
 
-pub struct KoDicBuilder {}
+pub struct KoDicBuilder {
+    compress_algorithm: Algorithm,
+}
 
 impl KoDicBuilder {
     pub fn new() -> Self {
-        KoDicBuilder {}
+        KoDicBuilder {
+            compress_algorithm: DEFAULT_COMPRESS_ALGORITHM,
+        }
+    }
+
+    /// Set the algorithm used to compress the dictionary files when the `compress` feature is
+    /// enabled. Defaults to `Algorithm::Deflate`.
+    pub fn compress_algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.compress_algorithm = algorithm;
+        self
+    }
+
+    fn chardef_builder(&self) -> CharDefBuilder {
+        CharDefBuilderOptions::default()
+            .compress_algorithm(self.compress_algorithm)
+            .builder()
+            .unwrap()
+    }
+
+    fn unk_builder(&self) -> UnkBuilder {
+        UnkBuilderOptions::default()
+            .compress_algorithm(self.compress_algorithm)
+            .unk_fields_num(UNK_FIELDS_NUM)
+            .builder()
+            .unwrap()
+    }
+
+    fn dict_builder(&self) -> DictBuilder {
+        DictBuilderOptions::default()
+            .flexible_csv(false)
+            .compress_algorithm(self.compress_algorithm)
+            .builder()
+            .unwrap()
+    }
+
+    fn cost_matrix_builder(&self) -> CostMatrixBuilder {
+        CostMatrixBuilderOptions::default()
+            .compress_algorithm(self.compress_algorithm)
+            .builder()
+            .unwrap()
     }
 }
 
@@ -40,10 +88,21 @@ impl DictionaryBuilder for KoDicBuilder {
         self.build_unk(input_dir, &chardef, output_dir).unwrap();
         self.build_dict(input_dir, output_dir).unwrap();
         self.build_cost_matrix(input_dir, output_dir).unwrap();
+        write_dictionary_kind(output_dir, DICTIONARY_KIND)?;
 
         Ok(())
     }
 
+    fn validate_dictionary(&self, input_dir: &Path) -> LinderaResult<ValidationReport> {
+        validate_source(
+            input_dir,
+            &self.chardef_builder(),
+            &self.unk_builder(),
+            &self.dict_builder(),
+            &self.cost_matrix_builder(),
+        )
+    }
+
     fn build_user_dictionary(&self, input_file: &Path, output_file: &Path) -> LinderaResult<()> {
         let user_dict = self.build_user_dict(input_file)?;
         build_user_dictionary(user_dict, output_file)
@@ -54,11 +113,7 @@ impl DictionaryBuilder for KoDicBuilder {
         input_dir: &Path,
         output_dir: &Path,
     ) -> LinderaResult<CharacterDefinitions> {
-        CharDefBuilderOptions::default()
-            .compress_algorithm(COMPRESS_ALGORITHM)
-            .builder()
-            .unwrap()
-            .build(input_dir, output_dir)
+        self.chardef_builder().build(input_dir, output_dir)
     }
 
     fn build_unk(
@@ -67,52 +122,56 @@ impl DictionaryBuilder for KoDicBuilder {
         chardef: &CharacterDefinitions,
         output_dir: &Path,
     ) -> LinderaResult<()> {
-        UnkBuilderOptions::default()
-            .compress_algorithm(COMPRESS_ALGORITHM)
-            .unk_fields_num(UNK_FIELDS_NUM)
-            .builder()
-            .unwrap()
-            .build(input_dir, chardef, output_dir)
+        self.unk_builder().build(input_dir, chardef, output_dir)
     }
 
     fn build_dict(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()> {
-        DictBuilderOptions::default()
-            .flexible_csv(false)
-            .compress_algorithm(COMPRESS_ALGORITHM)
-            .builder()
-            .unwrap()
-            .build(input_dir, output_dir)
+        self.dict_builder().build(input_dir, output_dir)
     }
 
     fn build_cost_matrix(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()> {
-        CostMatrixBuilderOptions::default()
-            .compress_algorithm(COMPRESS_ALGORITHM)
-            .builder()
-            .unwrap()
-            .build(&input_dir, output_dir)
+        self.cost_matrix_builder().build(input_dir, output_dir)
     }
 
     fn build_user_dict(&self, input_file: &Path) -> LinderaResult<UserDictionary> {
-        UserDictBuilderOptions::default()
-            .simple_userdic_fields_num(SIMPLE_USERDIC_FIELDS_NUM)
-            .detailed_userdic_fields_num(DETAILED_USERDIC_FIELDS_NUM)
-            .simple_word_cost(SIMPLE_WORD_COST)
-            .simple_context_id(SIMPLE_CONTEXT_ID)
-            .flexible_csv(false)
-            .simple_userdic_details_handler(Box::new(|row| {
-                Ok(vec![
-                    row[1].to_string(), //part-of-speech tag
-                    "*".to_string(),    // meaning
-                    "*".to_string(),    // presence or absence
-                    row[2].to_string(), // reading
-                    "*".to_string(),    // type
-                    "*".to_string(),    // first part-of-speech
-                    "*".to_string(),    // last part-of-speech
-                    "*".to_string(),    // expression
-                ])
-            }))
-            .builder()
-            .unwrap()
-            .build(input_file)
+        user_dict_builder().build(input_file)
+    }
+
+    fn build_user_dict_from_rows(&self, rows: Vec<StringRecord>) -> LinderaResult<UserDictionary> {
+        user_dict_builder().build_from_rows(rows)
     }
+
+    fn simple_userdic_details(&self, surface: &str, pos: &str, reading: &str) -> Vec<String> {
+        simple_userdic_details(surface, pos, reading)
+    }
+}
+
+/// The builder of the user dictionaries, for both the CSV files and the rows already read.
+fn user_dict_builder() -> UserDictBuilder {
+    UserDictBuilderOptions::default()
+        .simple_userdic_fields_num(SIMPLE_USERDIC_FIELDS_NUM)
+        .detailed_userdic_fields_num(DETAILED_USERDIC_FIELDS_NUM)
+        .simple_word_cost(SIMPLE_WORD_COST)
+        .simple_context_id(SIMPLE_CONTEXT_ID)
+        .flexible_csv(false)
+        .simple_userdic_details_handler(Box::new(|row| {
+            Ok(simple_userdic_details(&row[0], &row[1], &row[2]))
+        }))
+        .builder()
+        .unwrap()
+}
+
+/// Build the details of a simple row of a user dictionary CSV, i.e. a row with only the
+/// surface, the part-of-speech and the reading.
+fn simple_userdic_details(_surface: &str, pos: &str, reading: &str) -> Vec<String> {
+    vec![
+        pos.to_string(),     //part-of-speech tag
+        "*".to_string(),     // meaning
+        "*".to_string(),     // presence or absence
+        reading.to_string(), // reading
+        "*".to_string(),     // type
+        "*".to_string(),     // first part-of-speech
+        "*".to_string(),     // last part-of-speech
+        "*".to_string(),     // expression
+    ]
 }
@@ -3,8 +3,11 @@ use std::borrow::Cow;
 use std::env;
 
 use lindera_core::{
-    character_definition::CharacterDefinitions, connection::ConnectionCostMatrix,
-    dictionary::Dictionary, prefix_dict::PrefixDict, unknown_dictionary::UnknownDictionary,
+    character_definition::CharacterDefinitions,
+    connection::ConnectionCostMatrix,
+    dictionary::{strip_dictionary_header, Dictionary},
+    prefix_dict::PrefixDict,
+    unknown_dictionary::UnknownDictionary,
     LinderaResult,
 };
 #[cfg(feature = "compress")]
@@ -88,12 +91,12 @@ decompress_data!(WORDS_DATA, &[], "dict.words");
 
 pub fn load_dictionary() -> LinderaResult<Dictionary> {
     Ok(Dictionary {
-        dict: prefix_dict(),
-        cost_matrix: connection(),
+        dict: prefix_dict()?,
+        cost_matrix: connection()?,
         char_definitions: char_def()?,
         unknown_dictionary: unknown_dict()?,
-        words_idx_data: words_idx_data(),
-        words_data: words_data(),
+        words_idx_data: words_idx_data()?,
+        words_data: words_data()?,
     })
 }
 
@@ -102,20 +105,27 @@ pub fn char_def() -> LinderaResult<CharacterDefinitions> {
     CharacterDefinitions::load(&CHAR_DEFINITION_DATA)
 }
 
-pub fn connection() -> ConnectionCostMatrix {
+pub fn connection() -> LinderaResult<ConnectionCostMatrix> {
     #[cfg(feature = "compress")]
     {
-        ConnectionCostMatrix::load(&CONNECTION_DATA)
+        Ok(ConnectionCostMatrix::load(strip_dictionary_header(
+            &CONNECTION_DATA,
+        )?))
     }
     #[cfg(not(feature = "compress"))]
     {
-        ConnectionCostMatrix::load_static(CONNECTION_DATA)
+        Ok(ConnectionCostMatrix::load_static(strip_dictionary_header(
+            CONNECTION_DATA,
+        )?))
     }
 }
 
-pub fn prefix_dict() -> PrefixDict {
+pub fn prefix_dict() -> LinderaResult<PrefixDict> {
     #[allow(clippy::needless_borrow)]
-    PrefixDict::from_static_slice(&KO_DIC_DATA, &KO_DIC_VALS)
+    Ok(PrefixDict::from_static_slice(
+        strip_dictionary_header(&KO_DIC_DATA)?,
+        strip_dictionary_header(&KO_DIC_VALS)?,
+    ))
 }
 
 pub fn unknown_dict() -> LinderaResult<UnknownDictionary> {
@@ -123,24 +133,26 @@ pub fn unknown_dict() -> LinderaResult<UnknownDictionary> {
     UnknownDictionary::load(&UNKNOWN_DATA)
 }
 
-pub fn words_idx_data() -> Cow<'static, [u8]> {
+pub fn words_idx_data() -> LinderaResult<Cow<'static, [u8]>> {
     #[cfg(feature = "compress")]
     {
-        Cow::Owned(WORDS_IDX_DATA.to_vec())
+        Ok(Cow::Owned(
+            strip_dictionary_header(&WORDS_IDX_DATA)?.to_vec(),
+        ))
     }
     #[cfg(not(feature = "compress"))]
     {
-        Cow::Borrowed(WORDS_IDX_DATA)
+        Ok(Cow::Borrowed(strip_dictionary_header(WORDS_IDX_DATA)?))
     }
 }
 
-pub fn words_data() -> Cow<'static, [u8]> {
+pub fn words_data() -> LinderaResult<Cow<'static, [u8]>> {
     #[cfg(feature = "compress")]
     {
-        Cow::Owned(WORDS_DATA.to_vec())
+        Ok(Cow::Owned(strip_dictionary_header(&WORDS_DATA)?.to_vec()))
     }
     #[cfg(not(feature = "compress"))]
     {
-        Cow::Borrowed(WORDS_DATA)
+        Ok(Cow::Borrowed(strip_dictionary_header(WORDS_DATA)?))
     }
 }
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use once_cell::sync::Lazy;
 use serde::Serialize;
 
@@ -9,7 +11,9 @@ static UNK: Lazy<Vec<&str>> = Lazy::new(|| vec!["UNK"]);
 #[derive(Serialize, Clone)]
 pub struct Token<'a> {
     /// Text content of the token.
-    pub text: &'a str,
+    /// It borrows the tokenized text, except for the tokens of `Tokenizer::tokenize_reader`,
+    /// which own their text since the input is not held in memory.
+    pub text: Cow<'a, str>,
 
     /// Starting position of the token in bytes.
     pub byte_start: usize,
@@ -32,6 +36,12 @@ pub struct Token<'a> {
     /// Reference of user dictionary.
     pub user_dictionary: Option<&'a UserDictionary>,
 
+    /// The word cost of the token. Set only if the tokenizer has `debug_costs` enabled.
+    pub word_cost: Option<i32>,
+
+    /// The connection cost from the previous token. Set only if the tokenizer has `debug_costs` enabled.
+    pub connection_cost: Option<i32>,
+
     /// Detailes about the token.
     /// It contains metadata for tokens, such as part-of-speech information.
     details: Option<Vec<String>>,
@@ -39,7 +49,7 @@ pub struct Token<'a> {
 
 impl<'a> Token<'a> {
     pub fn new(
-        text: &'a str,
+        text: impl Into<Cow<'a, str>>,
         start: usize,
         end: usize,
         position: usize,
@@ -48,7 +58,7 @@ impl<'a> Token<'a> {
         user_dictionary: Option<&'a UserDictionary>,
     ) -> Self {
         Self {
-            text,
+            text: text.into(),
             details: None,
             byte_start: start,
             byte_end: end,
@@ -57,6 +67,8 @@ impl<'a> Token<'a> {
             word_id,
             dictionary,
             user_dictionary,
+            word_cost: None,
+            connection_cost: None,
         }
     }
 
@@ -74,7 +86,8 @@ impl<'a> Token<'a> {
         }
     }
 
-    // pub fn get_details(&mut self) -> Option<Vec<String>> {
+    /// Get the details of the token.
+    /// The details are looked up in the dictionary on the first call and cached in the token.
     pub fn get_details(&mut self) -> Option<Vec<&str>> {
         if self.details.is_some() {
             return self.details();
@@ -100,4 +113,28 @@ impl<'a> Token<'a> {
         self.details = details;
         self
     }
+
+    /// Move the token onto another text, keeping its position, word ID and details.
+    pub(crate) fn relocate<'b>(
+        self,
+        text: impl Into<Cow<'b, str>>,
+        start: usize,
+        end: usize,
+        dictionary: &'b Dictionary,
+        user_dictionary: Option<&'b UserDictionary>,
+    ) -> Token<'b> {
+        Token {
+            text: text.into(),
+            details: self.details,
+            byte_start: start,
+            byte_end: end,
+            position: self.position,
+            position_length: self.position_length,
+            word_id: self.word_id,
+            dictionary,
+            user_dictionary,
+            word_cost: self.word_cost,
+            connection_cost: self.connection_cost,
+        }
+    }
 }
@@ -1,17 +1,75 @@
+use std::borrow::Cow;
+use std::collections::{HashMap, VecDeque};
 use std::fmt;
+use std::io::BufRead;
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use serde::de::{self, MapAccess, SeqAccess, Visitor};
 use serde::{Deserialize, Deserializer, Serialize};
+use unicode_normalization::char::canonical_combining_class;
+use unicode_normalization::{is_nfc_quick, is_nfkc_quick, IsNormalized, UnicodeNormalization};
 
-use lindera_core::dictionary::{Dictionary, UserDictionary};
+use lindera_core::cost_model::{CostModel, DictionaryCostModel};
+use lindera_core::dictionary::{Dictionary, UserDictionary, UserWordOverlay};
+use lindera_core::error::LinderaErrorKind;
 use lindera_core::mode::Mode;
+use lindera_core::sentence_splitter::{SentenceSplitter, Sentences};
 use lindera_core::viterbi::Lattice;
+use lindera_core::word_entry::{WordEntry, WordId};
 use lindera_core::LinderaResult;
-use lindera_dictionary::{DictionaryConfig, DictionaryLoader, UserDictionaryConfig};
+use lindera_dictionary::{
+    DictionaryConfig, DictionaryKind, DictionaryLoader, UserDictionaryConfig,
+};
 
 use crate::token::Token;
 
+/// Characters at which the text is split into sentences before tokenization.
+const SENTENCE_DELIMITERS: [char; 4] = ['。', '、', '\n', '\t'];
+
+/// The maximum length in bytes of the text buffered by `Tokenizer::tokenize_reader` before it
+/// is tokenized. A sentence longer than this, without any terminator of the sentence splitter,
+/// is split at this length, at a character boundary.
+pub const MAX_BUFFERED_SENTENCE_LENGTH: usize = 1024 * 1024;
+
+/// The part-of-speech of the whitespace tokens emitted when `preserve_whitespace` is enabled.
+pub const WHITESPACE_POS: &str = "空白";
+
+/// The part-of-speech of the token emitted at the beginning of the text when `include_bos_eos`
+/// is enabled.
+pub const BOS_POS: &str = "BOS";
+
+/// The part-of-speech of the token emitted at the end of the text when `include_bos_eos` is
+/// enabled.
+pub const EOS_POS: &str = "EOS";
+
+/// The index of the type of the word in the details of ko-dic, `Compound` for compound words.
+const COMPOUND_TYPE_FIELD: usize = 4;
+
+/// The index of the decomposition of compound words in the details of ko-dic.
+const COMPOUND_EXPRESSION_FIELD: usize = 7;
+
+/// The default maximum length of a grouped unknown word, in characters.
+pub const DEFAULT_MAX_UNKNOWN_LENGTH: usize = 1024;
+
+/// Unicode normalization form applied to the text before tokenization.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum NormalizationForm {
+    #[serde(rename = "nfc")]
+    NFC,
+    #[serde(rename = "nfd")]
+    NFD,
+    #[serde(rename = "nfkc")]
+    NFKC,
+    #[serde(rename = "nfkd")]
+    NFKD,
+}
+
 /// Tokenizer config
+///
+/// Options are added to this struct over time. Set the ones you need and fill in the rest
+/// with `..Default::default()`, so that your code keeps compiling when new options are added.
+///
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 pub struct TokenizerConfig {
     /// The dictionary config to be used for tokenization.
@@ -22,6 +80,28 @@ pub struct TokenizerConfig {
 
     /// The tokenization mode.
     pub mode: Mode,
+
+    /// The Unicode normalization form applied to the text before tokenization. (Optional)
+    pub normalize: Option<NormalizationForm>,
+
+    /// Whether the tokens carry their word cost and connection cost, for debugging.
+    pub debug_costs: bool,
+
+    /// Whether runs of whitespace are emitted as tokens with the part-of-speech `空白`,
+    /// independently of how the dictionary handles whitespace.
+    pub preserve_whitespace: bool,
+
+    /// Whether zero-length tokens with the part-of-speech `BOS` and `EOS` are emitted at the
+    /// beginning and the end of the text.
+    pub include_bos_eos: bool,
+
+    /// The word cost of the unknown words, keyed by the character category name
+    /// (e.g. `ALPHA`, `NUMERIC`), replacing the cost in `unk.def`.
+    pub unknown_cost_overrides: HashMap<String, i16>,
+
+    /// The maximum length of a grouped unknown word, in characters.
+    /// Defaults to `DEFAULT_MAX_UNKNOWN_LENGTH`.
+    pub max_unknown_length: usize,
 }
 
 impl Default for TokenizerConfig {
@@ -29,12 +109,15 @@ impl Default for TokenizerConfig {
     /// default mode is Mode::Normal
     fn default() -> Self {
         Self {
-            dictionary: DictionaryConfig {
-                kind: None,
-                path: None,
-            },
+            dictionary: DictionaryConfig::default(),
             user_dictionary: None,
             mode: Mode::Normal,
+            normalize: None,
+            debug_costs: false,
+            preserve_whitespace: false,
+            include_bos_eos: false,
+            unknown_cost_overrides: HashMap::new(),
+            max_unknown_length: DEFAULT_MAX_UNKNOWN_LENGTH,
         }
     }
 }
@@ -48,6 +131,12 @@ impl<'de> Deserialize<'de> for TokenizerConfig {
             Dictionary,
             UserDictionary,
             Mode,
+            Normalize,
+            DebugCosts,
+            PreserveWhitespace,
+            IncludeBosEos,
+            UnknownCostOverrides,
+            MaxUnknownLength,
         }
 
         impl<'de> Deserialize<'de> for Field {
@@ -61,7 +150,9 @@ impl<'de> Deserialize<'de> for TokenizerConfig {
                     type Value = Field;
 
                     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                        formatter.write_str("`dictionary`, `user_dictionary`, or `mode`")
+                        formatter.write_str(
+                            "`dictionary`, `user_dictionary`, `mode`, `normalize`, `debug_costs`, `preserve_whitespace`, `include_bos_eos`, `unknown_cost_overrides`, or `max_unknown_length`",
+                        )
                     }
 
                     fn visit_str<E>(self, value: &str) -> Result<Field, E>
@@ -72,6 +163,12 @@ impl<'de> Deserialize<'de> for TokenizerConfig {
                             "dictionary" => Ok(Field::Dictionary),
                             "user_dictionary" => Ok(Field::UserDictionary),
                             "mode" => Ok(Field::Mode),
+                            "normalize" => Ok(Field::Normalize),
+                            "debug_costs" => Ok(Field::DebugCosts),
+                            "preserve_whitespace" => Ok(Field::PreserveWhitespace),
+                            "include_bos_eos" => Ok(Field::IncludeBosEos),
+                            "unknown_cost_overrides" => Ok(Field::UnknownCostOverrides),
+                            "max_unknown_length" => Ok(Field::MaxUnknownLength),
                             _ => Err(de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -99,11 +196,23 @@ impl<'de> Deserialize<'de> for TokenizerConfig {
                     .ok_or_else(|| de::Error::invalid_length(0, &self))?;
                 let user_dictionary = seq.next_element()?.unwrap_or(None);
                 let mode = seq.next_element()?.unwrap_or(Mode::Normal);
+                let normalize = seq.next_element()?.unwrap_or(None);
+                let debug_costs = seq.next_element()?.unwrap_or(false);
+                let preserve_whitespace = seq.next_element()?.unwrap_or(false);
+                let include_bos_eos = seq.next_element()?.unwrap_or(false);
+                let unknown_cost_overrides = seq.next_element()?.unwrap_or_default();
+                let max_unknown_length = seq.next_element()?.unwrap_or(DEFAULT_MAX_UNKNOWN_LENGTH);
 
                 Ok(TokenizerConfig {
                     dictionary,
                     user_dictionary,
                     mode,
+                    normalize,
+                    debug_costs,
+                    preserve_whitespace,
+                    include_bos_eos,
+                    unknown_cost_overrides,
+                    max_unknown_length,
                 })
             }
 
@@ -114,6 +223,12 @@ impl<'de> Deserialize<'de> for TokenizerConfig {
                 let mut dictionary = None;
                 let mut user_dictionary = None;
                 let mut mode = None;
+                let mut normalize = None;
+                let mut debug_costs = None;
+                let mut preserve_whitespace = None;
+                let mut include_bos_eos = None;
+                let mut unknown_cost_overrides = None;
+                let mut max_unknown_length = None;
                 while let Some(key) = map.next_key()? {
                     match key {
                         Field::Dictionary => {
@@ -134,26 +249,89 @@ impl<'de> Deserialize<'de> for TokenizerConfig {
                             }
                             mode = Some(map.next_value()?);
                         }
+                        Field::Normalize => {
+                            if normalize.is_some() {
+                                return Err(de::Error::duplicate_field("normalize"));
+                            }
+                            normalize = Some(map.next_value()?);
+                        }
+                        Field::DebugCosts => {
+                            if debug_costs.is_some() {
+                                return Err(de::Error::duplicate_field("debug_costs"));
+                            }
+                            debug_costs = Some(map.next_value()?);
+                        }
+                        Field::PreserveWhitespace => {
+                            if preserve_whitespace.is_some() {
+                                return Err(de::Error::duplicate_field("preserve_whitespace"));
+                            }
+                            preserve_whitespace = Some(map.next_value()?);
+                        }
+                        Field::IncludeBosEos => {
+                            if include_bos_eos.is_some() {
+                                return Err(de::Error::duplicate_field("include_bos_eos"));
+                            }
+                            include_bos_eos = Some(map.next_value()?);
+                        }
+                        Field::UnknownCostOverrides => {
+                            if unknown_cost_overrides.is_some() {
+                                return Err(de::Error::duplicate_field("unknown_cost_overrides"));
+                            }
+                            unknown_cost_overrides = Some(map.next_value()?);
+                        }
+                        Field::MaxUnknownLength => {
+                            if max_unknown_length.is_some() {
+                                return Err(de::Error::duplicate_field("max_unknown_length"));
+                            }
+                            max_unknown_length = Some(map.next_value()?);
+                        }
                     }
                 }
                 let dictionary =
                     dictionary.ok_or_else(|| de::Error::missing_field("dictionary"))?;
                 let mode = mode.unwrap_or(Mode::Normal);
+                let normalize = normalize.unwrap_or(None);
+                let debug_costs = debug_costs.unwrap_or(false);
+                let preserve_whitespace = preserve_whitespace.unwrap_or(false);
+                let include_bos_eos = include_bos_eos.unwrap_or(false);
+                let unknown_cost_overrides = unknown_cost_overrides.unwrap_or_default();
+                let max_unknown_length = max_unknown_length.unwrap_or(DEFAULT_MAX_UNKNOWN_LENGTH);
                 Ok(TokenizerConfig {
                     dictionary,
                     user_dictionary,
                     mode,
+                    normalize,
+                    debug_costs,
+                    preserve_whitespace,
+                    include_bos_eos,
+                    unknown_cost_overrides,
+                    max_unknown_length,
                 })
             }
         }
 
-        const FIELDS: &[&str] = &["dictionary", "user_dictionary", "mode", "with_details"];
+        const FIELDS: &[&str] = &[
+            "dictionary",
+            "user_dictionary",
+            "mode",
+            "normalize",
+            "debug_costs",
+            "preserve_whitespace",
+            "include_bos_eos",
+            "unknown_cost_overrides",
+            "max_unknown_length",
+            "with_details",
+        ];
         deserializer.deserialize_struct("TokenizerConfig", FIELDS, DurationVisitor)
     }
 }
 
 #[derive(Clone)]
 /// Tokenizer
+///
+/// The tokenizer holds no state specific to a call of `tokenize`; the lattice is allocated for
+/// each call. It is `Send` and `Sync`, so a single instance can be shared between threads with
+/// `Arc<Tokenizer>` instead of loading the dictionary for each thread.
 pub struct Tokenizer {
     /// The dictionary to be used for tokenization.
     pub dictionary: Dictionary,
@@ -161,8 +339,61 @@ pub struct Tokenizer {
     /// The user dictionary to be used for tokenization. (Optional)
     pub user_dictionary: Option<UserDictionary>,
 
+    /// The words added with `add_user_word`.
+    pub user_words: UserWordOverlay,
+
     /// The tokenization mode.
     pub mode: Mode,
+
+    /// The kind of the dictionary, which gives the layout of the details of the words added
+    /// with `add_user_word`. It is set by `from_config`, and is `None` for `new`, since the kind
+    /// of the dictionary is not known.
+    pub dictionary_kind: Option<DictionaryKind>,
+
+    /// Whether the compound words are split into their parts in decompose mode.
+    /// Only the details of ko-dic record the parts, so `from_config` enables it for ko-dic only.
+    /// It is disabled by `new`, since the kind of the dictionary is not known.
+    pub decompose_compounds: bool,
+
+    /// The Unicode normalization form applied to the text before tokenization. (Optional)
+    /// The offsets of the tokens point at the original text, and the token text is the
+    /// corresponding part of the original text.
+    /// It is applied by `tokenize`, `tokenize_into`, `tokenize_sentence`, `tokenize_sentences`,
+    /// `tokenize_iter` and `tokenize_reader`.
+    pub normalize: Option<NormalizationForm>,
+
+    /// The maximum length of a grouped unknown word, in characters.
+    /// Longer runs of unknown characters, such as base64 blobs, are split into several tokens.
+    pub max_unknown_length: usize,
+
+    /// Whether the tokens carry their word cost and the connection cost from the previous token.
+    /// This is useful for tuning a dictionary.
+    pub debug_costs: bool,
+
+    /// Whether runs of whitespace are emitted as tokens with the part-of-speech `空白`.
+    /// The whitespace is not passed to the dictionary, so the tokens are the same for all
+    /// dictionaries, and the text of the tokens can be concatenated back into the input text.
+    pub preserve_whitespace: bool,
+
+    /// Whether zero-length tokens with the part-of-speech `BOS` and `EOS` are emitted at the
+    /// beginning and the end of the text, like the virtual nodes of the lattice.
+    /// The BOS token has the position 0, so the positions of the other tokens start at 1.
+    /// It is applied by `tokenize`, `tokenize_into`, `tokenize_sentence`, `tokenize_sentences`,
+    /// `tokenize_iter` and `tokenize_reader`.
+    pub include_bos_eos: bool,
+
+    /// The sentence splitter used by `tokenize_sentences`, `tokenize_iter` and `tokenize_reader`.
+    pub sentence_splitter: SentenceSplitter,
+
+    /// The word cost of the unknown words, keyed by the character category name.
+    /// It replaces the cost of all the unknown word entries of the category, so a high cost
+    /// discourages grouping the characters of the category, and a low cost encourages it.
+    pub unknown_cost_overrides: HashMap<String, i16>,
+
+    /// The word costs and the connection costs of the Viterbi search.
+    /// By default, the costs of the dictionary. It is set by `with_cost_model`, as a cost model
+    /// cannot be described in the tokenizer config.
+    pub cost_model: Box<dyn CostModel>,
 }
 
 impl Tokenizer {
@@ -175,6 +406,10 @@ impl Tokenizer {
     /// returns: LinderaResult<Tokenizer>
     ///
     pub fn from_config(config: TokenizerConfig) -> LinderaResult<Self> {
+        let dictionary_kind = config.dictionary.kind.clone();
+        let decompose_compounds = dictionary_kind
+            .as_ref()
+            .is_some_and(DictionaryKind::has_compounds);
         let dictionary = DictionaryLoader::load_dictionary_from_config(config.dictionary)?;
 
         let user_dictionary = match config.user_dictionary {
@@ -184,7 +419,27 @@ impl Tokenizer {
             None => None,
         };
 
-        Ok(Self::new(dictionary, user_dictionary, config.mode))
+        let categories = dictionary.char_definitions.categories();
+        for category in config.unknown_cost_overrides.keys() {
+            if !categories.contains(category) {
+                return Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                    "unknown character category in unknown_cost_overrides: {}",
+                    category
+                )));
+            }
+        }
+
+        let mut tokenizer = Self::new(dictionary, user_dictionary, config.mode);
+        tokenizer.dictionary_kind = dictionary_kind;
+        tokenizer.decompose_compounds = decompose_compounds;
+        tokenizer.normalize = config.normalize;
+        tokenizer.debug_costs = config.debug_costs;
+        tokenizer.preserve_whitespace = config.preserve_whitespace;
+        tokenizer.include_bos_eos = config.include_bos_eos;
+        tokenizer.unknown_cost_overrides = config.unknown_cost_overrides;
+        tokenizer.max_unknown_length = config.max_unknown_length;
+
+        Ok(tokenizer)
     }
 
     /// Create a new tokenizer.
@@ -205,12 +460,154 @@ impl Tokenizer {
         Self {
             dictionary,
             user_dictionary,
+            user_words: UserWordOverlay::default(),
             mode,
+            dictionary_kind: None,
+            decompose_compounds: false,
+            normalize: None,
+            max_unknown_length: DEFAULT_MAX_UNKNOWN_LENGTH,
+            debug_costs: false,
+            preserve_whitespace: false,
+            include_bos_eos: false,
+            sentence_splitter: SentenceSplitter::default(),
+            unknown_cost_overrides: HashMap::new(),
+            cost_model: Box::new(DictionaryCostModel),
+        }
+    }
+
+    /// Set the cost model of the Viterbi search, in place of the costs of the dictionary.
+    ///
+    /// # Arguments
+    ///
+    /// * `cost_model`: The cost model.
+    ///
+    /// returns: Tokenizer
+    ///
+    pub fn with_cost_model(mut self, cost_model: Box<dyn CostModel>) -> Self {
+        self.cost_model = cost_model;
+        self
+    }
+
+    /// Add a word to the tokenizer without rebuilding the user dictionary.
+    ///
+    /// The word is kept in an in-memory overlay that is looked up along with the dictionaries
+    /// when the lattice is built. The overlay is not persisted: the word is lost when the
+    /// tokenizer is dropped. Adding a surface again replaces the previous word.
+    ///
+    /// Like the simple rows of a user dictionary CSV, the word uses the context ID 0, and its
+    /// details are built as those of a simple row for `dictionary_kind`, e.g. the 9 fields of
+    /// IPADIC with the surface as the base form. If the kind of the dictionary is not known, the
+    /// details are the part-of-speech and the reading.
+    ///
+    /// # Arguments
+    ///
+    /// * `surface`: The surface form of the word.
+    /// * `pos`: The part-of-speech of the word.
+    /// * `reading`: The reading of the word.
+    /// * `cost`: The word cost. The lower the cost, the more likely the word is chosen.
+    ///
+    pub fn add_user_word(&mut self, surface: &str, pos: &str, reading: &str, cost: i16) {
+        let details = match &self.dictionary_kind {
+            Some(kind) => kind.simple_userdic_details(surface, pos, reading),
+            None => vec![pos.to_string(), reading.to_string()],
+        };
+        self.user_words.insert(surface, cost, 0, details);
+    }
+
+    /// Look up the words whose surface form is exactly the given surface, without tokenizing.
+    ///
+    /// The double arrays of the dictionaries are queried directly, so this is much cheaper than
+    /// `tokenize`, e.g. to check the candidates of an autocomplete. The words added with
+    /// `add_user_word` come first, then those of the user dictionary and of the system
+    /// dictionary. Unknown words are not considered.
+    /// Use `Dictionary::word_details` or `UserDictionary::word_details` to get the details of
+    /// the returned entries.
+    ///
+    /// # Arguments
+    ///
+    /// * `surface`: The surface form of the word.
+    ///
+    /// returns: Vec<WordEntry>
+    ///
+    pub fn lookup(&self, surface: &str) -> Vec<WordEntry> {
+        let mut entries = Vec::new();
+        if surface.is_empty() {
+            return entries;
+        }
+
+        entries.extend(self.user_words.get(surface));
+        if let Some(user_dictionary) = &self.user_dictionary {
+            entries.extend(user_dictionary.dict.find_surface(surface));
+        }
+        entries.extend(self.dictionary.dict.find_surface(surface));
+
+        entries
+    }
+
+    /// Return whether a word with exactly the given surface form is in the dictionaries or was
+    /// added with `add_user_word`. See `lookup`.
+    ///
+    /// # Arguments
+    ///
+    /// * `surface`: The surface form of the word.
+    ///
+    /// returns: bool
+    ///
+    pub fn contains_word(&self, surface: &str) -> bool {
+        !self.lookup(surface).is_empty()
+    }
+
+    /// Look up the words whose surface form starts with the given prefix, e.g. to suggest the
+    /// completions of an autocomplete. The prefix itself is included if it is a word.
+    ///
+    /// The words added with `add_user_word` come first, then those of the user dictionary and
+    /// of the system dictionary, each in the byte order of their surface, i.e. the order of the
+    /// code points. The words are not ordered by frequency or cost, so the first words are not
+    /// necessarily the most likely completions. The search stops once `limit` words are found,
+    /// so a small limit keeps a short prefix cheap. Unknown words are not considered.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix`: The prefix of the surface forms.
+    /// * `limit`: The maximum number of words to return.
+    ///
+    /// returns: Vec<(String, WordEntry)>
+    ///
+    /// * String : The surface form of the word
+    /// * WordEntry : The word entry of the word
+    ///
+    pub fn prefix_search(&self, prefix: &str, limit: usize) -> Vec<(String, WordEntry)> {
+        // The overlay is a hash map of a few words, so its words are sorted here.
+        let mut words = self
+            .user_words
+            .predictive(prefix)
+            .map(|(surface, word_entry)| (surface.to_string(), word_entry))
+            .collect::<Vec<(String, WordEntry)>>();
+        words.sort_by(|(surface1, _), (surface2, _)| surface1.cmp(surface2));
+        words.truncate(limit);
+
+        if let Some(user_dictionary) = &self.user_dictionary {
+            words.extend(
+                user_dictionary
+                    .dict
+                    .predictive_search(prefix, limit - words.len()),
+            );
         }
+        words.extend(
+            self.dictionary
+                .dict
+                .predictive_search(prefix, limit - words.len()),
+        );
+
+        words
     }
 
     /// Tokenize the text
     ///
+    /// The details of the tokens (e.g. part-of-speech) are not looked up in the dictionary here.
+    /// They are fetched lazily when `Token::get_details` is called, so tokenization stays fast
+    /// if only the surface text and offsets are needed.
+    ///
     /// # Arguments
     ///
     /// * `text`: The text to be tokenized.
@@ -224,126 +621,1266 @@ impl Tokenizer {
         let mut tokens: Vec<Token> = Vec::new();
         let mut lattice = Lattice::default();
 
+        self.tokenize_into(text, &mut tokens, &mut lattice)?;
+
+        Ok(tokens)
+    }
+
+    /// Tokenize the texts in parallel.
+    ///
+    /// The texts are distributed over the rayon thread pool, and each thread reuses its lattice
+    /// across the texts it tokenizes. The tokens of each text are the same as those returned by
+    /// `tokenize`, and they are returned in the order of the texts.
+    ///
+    /// Distributing the texts has a cost, so for a handful of short texts a serial loop over
+    /// `tokenize_into` is faster. Run the `tokenize-batch-ipadic` benchmark to find the batch
+    /// size from which the parallel version wins on a given machine.
+    ///
+    /// # Arguments
+    ///
+    /// * `texts`: The texts to be tokenized.
+    ///
+    /// returns: LinderaResult<Vec<Vec<Token>>>
+    ///
+    /// * Vec<Vec<Token>> : The list of `Token` of each text if succeeded
+    /// * LinderaError : Error message with LinderaErrorKind, if any of the texts failed
+    ///
+    #[cfg(feature = "parallel")]
+    pub fn tokenize_batch<'a>(&'a self, texts: &'a [String]) -> LinderaResult<Vec<Vec<Token<'a>>>> {
+        texts
+            .par_iter()
+            .map_init(Lattice::default, |lattice, text| {
+                let mut tokens = Vec::new();
+                self.tokenize_into(text, &mut tokens, lattice)?;
+                Ok(tokens)
+            })
+            .collect()
+    }
+
+    /// Tokenize the text into the given buffers.
+    ///
+    /// The tokens vector is cleared and then filled with the tokens of the text.
+    /// Reusing the tokens vector and the lattice across calls avoids allocating them
+    /// for each text, which helps when tokenizing many short texts.
+    ///
+    /// # Arguments
+    ///
+    /// * `text`: The text to be tokenized.
+    /// * `tokens`: The vector to store the tokens in.
+    /// * `lattice`: The lattice to be used for tokenization.
+    ///
+    /// returns: LinderaResult<()>
+    ///
+    pub fn tokenize_into<'a>(
+        &'a self,
+        text: &'a str,
+        tokens: &mut Vec<Token<'a>>,
+        lattice: &mut Lattice,
+    ) -> LinderaResult<()> {
+        self.tokenize_normalized(text, tokens, lattice, true, &mut PathChoice::Best)
+    }
+
+    /// Tokenize the text as a single sentence.
+    ///
+    /// Unlike `tokenize`, the text is not split into chunks after `。`, `、`, newlines or tabs:
+    /// one lattice is built over the whole text. This is useful when the text has
+    /// already been split into sentences, so that the tokens follow that split exactly.
+    /// The newlines and the sentence delimiters inside the text are treated literally, like any
+    /// other character, so a word may span them.
+    /// Normalization and whitespace preservation are applied as in `tokenize`.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence`: The sentence to be tokenized.
+    ///
+    /// returns: LinderaResult<Vec<Token>>
+    ///
+    /// * Vec<Token> : The list of `Token` if succeeded
+    /// * LinderaError : Error message with LinderaErrorKind
+    ///
+    pub fn tokenize_sentence<'a>(&'a self, sentence: &'a str) -> LinderaResult<Vec<Token<'a>>> {
+        let mut tokens: Vec<Token> = Vec::new();
+        let mut lattice = Lattice::default();
+
+        self.tokenize_normalized(
+            sentence,
+            &mut tokens,
+            &mut lattice,
+            false,
+            &mut PathChoice::Best,
+        )?;
+
+        Ok(tokens)
+    }
+
+    fn tokenize_normalized<'a>(
+        &'a self,
+        text: &'a str,
+        tokens: &mut Vec<Token<'a>>,
+        lattice: &mut Lattice,
+        split_sentences: bool,
+        paths: &mut PathChoice,
+    ) -> LinderaResult<()> {
+        tokens.clear();
+
+        let mut position = 0;
+        if self.include_bos_eos {
+            tokens.push(self.boundary_token(0, position, BOS_POS));
+            position += 1;
+        }
+
+        self.tokenize_part(
+            text,
+            0,
+            &mut position,
+            lattice,
+            split_sentences,
+            paths,
+            tokens,
+        );
+
+        if self.include_bos_eos {
+            tokens.push(self.boundary_token(text.len(), position, EOS_POS));
+        }
+
+        Ok(())
+    }
+
+    /// Tokenize a part of the text, normalized if `normalize` is set, and append its tokens.
+    /// The byte offsets of the tokens are shifted by `byte_offset`, the start of the part in the
+    /// whole text, and their positions start at `position`, which is moved past them.
+    /// No BOS or EOS token is emitted.
+    #[allow(clippy::too_many_arguments)]
+    fn tokenize_part<'a>(
+        &'a self,
+        part: &'a str,
+        byte_offset: usize,
+        position: &mut usize,
+        lattice: &mut Lattice,
+        split_sentences: bool,
+        paths: &mut PathChoice,
+        tokens: &mut Vec<Token<'a>>,
+    ) {
+        let first = tokens.len();
+
+        match self.normalize {
+            Some(form) => {
+                let (normalized_text, origins) = normalize_with_origins(part, form);
+
+                let mut normalized_tokens = Vec::new();
+                self.tokenize_text(
+                    &normalized_text,
+                    &mut normalized_tokens,
+                    lattice,
+                    split_sentences,
+                    paths,
+                );
+
+                // Map the tokens back onto the original text.
+                for token in normalized_tokens {
+                    let start = origins[token.byte_start].0;
+                    let end = origins[token.byte_end - 1].1;
+                    tokens.push(token.relocate(
+                        &part[start..end],
+                        start,
+                        end,
+                        &self.dictionary,
+                        self.user_dictionary.as_ref(),
+                    ));
+                }
+            }
+            None => self.tokenize_text(part, tokens, lattice, split_sentences, paths),
+        }
+
+        for token in tokens[first..].iter_mut() {
+            token.byte_start += byte_offset;
+            token.byte_end += byte_offset;
+            token.position += *position;
+        }
+        if let Some(last) = tokens[first..].last() {
+            *position = last.position + last.position_length;
+        }
+    }
+
+    /// Make a zero-length token at the byte offset of the text, with the part-of-speech `pos`.
+    fn boundary_token<'a>(&'a self, offset: usize, position: usize, pos: &str) -> Token<'a> {
+        let mut token = Token::new(
+            "",
+            offset,
+            offset,
+            position,
+            WordId(u32::MAX, true),
+            &self.dictionary,
+            self.user_dictionary.as_ref(),
+        );
+        token.set_details(Some(vec![pos.to_string()]));
+        token
+    }
+
+    fn tokenize_text<'a>(
+        &'a self,
+        text: &'a str,
+        tokens: &mut Vec<Token<'a>>,
+        lattice: &mut Lattice,
+        split_sentences: bool,
+        paths: &mut PathChoice,
+    ) {
         let mut position = 0_usize;
         let mut byte_position = 0_usize;
 
+        if !split_sentences {
+            if !text.is_empty() {
+                self.tokenize_chunk(
+                    lattice,
+                    text,
+                    &mut position,
+                    &mut byte_position,
+                    paths,
+                    tokens,
+                );
+            }
+            return;
+        }
+
         // Split text into sentences using Japanese punctuation.
-        for sentence in text.split_inclusive(&['。', '、', '\n', '\t']) {
+        for sentence in text.split_inclusive(&SENTENCE_DELIMITERS) {
             if text.is_empty() {
                 continue;
             }
 
-            lattice.set_text(
-                &self.dictionary.dict,
-                &self.user_dictionary.as_ref().map(|d| &d.dict),
-                &self.dictionary.char_definitions,
-                &self.dictionary.unknown_dictionary,
+            self.tokenize_chunk(
+                lattice,
                 sentence,
-                &self.mode,
+                &mut position,
+                &mut byte_position,
+                paths,
+                tokens,
             );
-            lattice.calculate_path_costs(&self.dictionary.cost_matrix, &self.mode);
+        }
+    }
 
-            let offsets = lattice.tokens_offset();
+    /// Estimate the number of tokens of the text.
+    ///
+    /// This is a heuristic for preallocating buffers, e.g. with `Vec::with_capacity`, not an
+    /// exact count. It returns the number of characters of the text (after normalization, if
+    /// enabled), which is an upper bound of the number of tokens since every token is at least
+    /// one character long. It runs in O(n) without building the lattice.
+    ///
+    /// # Arguments
+    ///
+    /// * `text`: The text to be tokenized.
+    ///
+    /// returns: usize
+    ///
+    pub fn estimate_token_count(&self, text: &str) -> usize {
+        match self.normalize {
+            Some(NormalizationForm::NFC) => text.nfc().count(),
+            Some(NormalizationForm::NFD) => text.nfd().count(),
+            Some(NormalizationForm::NFKC) => text.nfkc().count(),
+            Some(NormalizationForm::NFKD) => text.nfkd().count(),
+            None => text.chars().count(),
+        }
+    }
 
-            for i in 0..offsets.len() {
-                let (byte_start, word_id) = offsets[i];
-                let byte_end = if i == offsets.len() - 1 {
-                    sentence.len()
-                } else {
-                    let (next_start, _word_id) = offsets[i + 1];
-                    next_start
-                };
+    /// Tokenize the text and group the tokens by sentence.
+    ///
+    /// The text is split into sentences by `sentence_splitter`, by default after `。`, `！`, `？`,
+    /// `!`, `?` and newlines.
+    /// The terminating character belongs to the sentence it ends.
+    /// If the text contains no terminating character, the whole text is returned as one sentence.
+    /// Tokens are the same as those returned by `tokenize`, and their byte offsets and positions
+    /// are relative to the start of the whole text. Each sentence is normalized separately if
+    /// `normalize` is set, and if `include_bos_eos` is enabled, the BOS token is the first token
+    /// of the first sentence and the EOS token the last token of the last sentence.
+    ///
+    /// # Arguments
+    ///
+    /// * `text`: The text to be tokenized.
+    ///
+    /// returns: LinderaResult<Vec<(usize, usize, Vec<Token>)>>
+    ///
+    /// * Vec<(usize, usize, Vec<Token>)> : The byte start, byte end and tokens of each sentence
+    /// * LinderaError : Error message with LinderaErrorKind
+    ///
+    pub fn tokenize_sentences<'a>(
+        &'a self,
+        text: &'a str,
+    ) -> LinderaResult<Vec<(usize, usize, Vec<Token<'a>>)>> {
+        let mut sentences: Vec<(usize, usize, Vec<Token>)> = Vec::new();
+        let mut lattice = Lattice::default();
 
-                // retrieve token from its sentence byte positions
-                let surface = &sentence[byte_start..byte_end];
+        let mut position = 0_usize;
+        let mut sentence_start = 0_usize;
 
-                // compute the token's absolute byte positions
-                let token_start = byte_position;
-                byte_position += surface.len();
-                let token_end = byte_position;
+        for sentence in self.sentence_splitter.split(text) {
+            let mut tokens: Vec<Token> = Vec::new();
+            if self.include_bos_eos && sentences.is_empty() {
+                tokens.push(self.boundary_token(0, position, BOS_POS));
+                position += 1;
+            }
 
-                tokens.push(Token::new(
-                    surface,
-                    token_start,
-                    token_end,
-                    position,
-                    word_id,
-                    &self.dictionary,
-                    self.user_dictionary.as_ref(),
-                ));
+            self.tokenize_part(
+                sentence,
+                sentence_start,
+                &mut position,
+                &mut lattice,
+                true,
+                &mut PathChoice::Best,
+                &mut tokens,
+            );
 
-                position += 1;
+            let sentence_end = sentence_start + sentence.len();
+            sentences.push((sentence_start, sentence_end, tokens));
+            sentence_start = sentence_end;
+        }
+
+        if self.include_bos_eos {
+            if let Some((_, _, tokens)) = sentences.last_mut() {
+                tokens.push(self.boundary_token(text.len(), position, EOS_POS));
             }
         }
 
-        Ok(tokens)
+        Ok(sentences)
     }
-}
-
-#[cfg(test)]
-mod tests {
-    #[allow(unused_imports)]
-    #[cfg(any(
-        feature = "ipadic",
-        feature = "ipadic-neologd",
-        feature = "unidic",
-        feature = "ko-dic",
-        feature = "cc-cedict"
-    ))]
-    use std::{
-        fs::File,
-        io::{BufReader, Read},
-        path::PathBuf,
-    };
 
-    #[allow(unused_imports)]
-    #[cfg(any(
-        feature = "ipadic",
-        feature = "ipadic-neologd",
-        feature = "unidic",
-        feature = "ko-dic",
-        feature = "cc-cedict"
-    ))]
-    use lindera_core::mode::{Mode, Penalty};
+    /// Tokenize the text lazily, sentence by sentence.
+    ///
+    /// The text is split into sentences by `sentence_splitter`, as in `tokenize_sentences`.
+    /// Unlike `tokenize`, only the lattice and the tokens of the sentence currently being
+    /// processed are held in memory, which makes it suitable for very large texts.
+    /// The tokens are the same as those returned by `tokenize_sentences`: each sentence is
+    /// normalized if `normalize` is set, the BOS and EOS tokens are emitted at the beginning and
+    /// the end of the whole text if `include_bos_eos` is enabled, and the byte offsets and
+    /// positions of the tokens are relative to the start of the whole text.
+    ///
+    /// # Arguments
+    ///
+    /// * `text`: The text to be tokenized.
+    ///
+    /// returns: impl Iterator<Item = LinderaResult<Token>>
+    ///
+    pub fn tokenize_iter<'a>(
+        &'a self,
+        text: &'a str,
+    ) -> impl Iterator<Item = LinderaResult<Token<'a>>> + 'a {
+        let mut tokens = VecDeque::new();
+        let mut position = 0;
+        if self.include_bos_eos {
+            tokens.push_back(self.boundary_token(0, position, BOS_POS));
+            position += 1;
+        }
 
-    #[cfg(any(
-        feature = "ipadic",
-        feature = "ipadic-neologd",
-        feature = "unidic",
-        feature = "ko-dic",
-        feature = "cc-cedict"
-    ))]
-    use lindera_dictionary::{DictionaryConfig, DictionaryKind, UserDictionaryConfig};
+        TextTokens {
+            tokenizer: self,
+            sentences: self.sentence_splitter.sentences(text),
+            tokens,
+            lattice: Lattice::default(),
+            position,
+            byte_position: 0,
+            done: false,
+        }
+    }
 
-    #[cfg(any(
-        feature = "ipadic",
-        feature = "ipadic-neologd",
-        feature = "unidic",
-        feature = "ko-dic",
-        feature = "cc-cedict"
-    ))]
-    use crate::tokenizer::{Tokenizer, TokenizerConfig};
+    /// Tokenize the text read from the reader, without holding the whole text in memory.
+    ///
+    /// The text is read in chunks of whole sentences, split by `sentence_splitter` as in
+    /// `tokenize_sentences`, and each sentence is tokenized on its own. A chunk is buffered
+    /// until a sentence terminator is read, up to `MAX_BUFFERED_SENTENCE_LENGTH` bytes, beyond
+    /// which it is split at that length. The tokens are the same as those returned by
+    /// `tokenize_sentences`, except that they own their text. The byte offsets and positions of
+    /// the tokens are relative to the start of the whole text, and if `include_bos_eos` is
+    /// enabled, the BOS and EOS tokens are emitted once, at the beginning and the end of the
+    /// whole text.
+    ///
+    /// Reading stops at the first error, such as an I/O error or text that is not valid UTF-8.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader`: The reader of the text to be tokenized.
+    ///
+    /// returns: impl Iterator<Item = LinderaResult<Token>>
+    ///
+    pub fn tokenize_reader<'a, R: BufRead + 'a>(
+        &'a self,
+        reader: R,
+    ) -> impl Iterator<Item = LinderaResult<Token<'a>>> + 'a {
+        let mut tokens = VecDeque::new();
+        let mut position = 0;
+        if self.include_bos_eos {
+            tokens.push_back(self.boundary_token(0, position, BOS_POS));
+            position += 1;
+        }
 
-    #[test]
-    #[cfg(feature = "ipadic")]
-    fn test_tokenize_config_ipadic_normal() {
-        let config_str = r#"
-        {
-            "dictionary": {
-                "kind": "ipadic"
-            },
-            "mode": "normal"
+        ReaderTokens {
+            tokenizer: self,
+            reader,
+            buffer: Vec::new(),
+            scanned_len: 0,
+            tokens,
+            lattice: Lattice::default(),
+            position,
+            byte_position: 0,
+            done: false,
         }
-        "#;
+    }
 
-        let config: TokenizerConfig = serde_json::from_str(config_str).unwrap();
-        assert_eq!(config.dictionary.kind, Some(DictionaryKind::IPADIC));
+    /// Build the lattice of the text, for inspecting the candidate words and their costs.
+    ///
+    /// The lattice holds every edge (word candidate) found in the dictionaries, with the cost
+    /// of the best path from the beginning of the text to it.
+    /// One lattice is built over the whole text as is: unlike `tokenize`, the text is neither
+    /// normalized nor split after `。`, `、`, newlines or tabs, and whitespace is not preserved.
+    /// So this is the lattice decoded by `tokenize` only for a text that `tokenize` does not
+    /// split, such as a single clause.
+    ///
+    /// # Arguments
+    ///
+    /// * `text`: The text to build the lattice of.
+    ///
+    /// returns: Lattice
+    ///
+    pub fn lattice(&self, text: &str) -> Lattice {
+        let mut lattice = Lattice::default();
+        self.build_lattice(&mut lattice, text);
+
+        lattice
     }
 
-    #[test]
-    #[cfg(feature = "ipadic")]
-    fn test_tokenize_config_ipadic_decompose() {
-        let config_str = r#"
+    /// Tokenize the text into its `n` best segmentations, in increasing order of cost.
+    ///
+    /// The text goes through the same steps as in `tokenize`: it is normalized if `normalize`
+    /// is set, split after `。`, `、`, newlines and tabs, and the runs of whitespace are emitted
+    /// as tokens if `preserve_whitespace` is enabled. The best segmentations of the whole text
+    /// are the best combinations of those of its parts, and the BOS and EOS tokens are emitted
+    /// in each of them if `include_bos_eos` is enabled.
+    /// So the first segmentation is the same as the tokens returned by `tokenize`.
+    /// Fewer than `n` segmentations are returned if the text does not have that many.
+    ///
+    /// # Arguments
+    ///
+    /// * `text`: The text to be tokenized.
+    /// * `n`: The number of segmentations.
+    ///
+    /// returns: LinderaResult<Vec<Vec<Token>>>
+    ///
+    /// * Vec<Vec<Token>> : The tokens of each segmentation if succeeded
+    /// * LinderaError : Error message with LinderaErrorKind
+    ///
+    pub fn n_best<'a>(&'a self, text: &'a str, n: usize) -> LinderaResult<Vec<Vec<Token<'a>>>> {
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut lattice = Lattice::default();
+        let mut tokens = Vec::new();
+
+        // The candidate paths of each segment passed to the lattice, in the order of the text.
+        let mut candidates = Vec::new();
+        self.tokenize_normalized(
+            text,
+            &mut tokens,
+            &mut lattice,
+            true,
+            &mut PathChoice::Collect {
+                n,
+                candidates: &mut candidates,
+            },
+        )?;
+
+        // The best combinations of the candidates: the cost and the index of the candidate of
+        // each segment.
+        let mut combinations: Vec<(i32, Vec<usize>)> = vec![(0, Vec::new())];
+        for paths in candidates.iter() {
+            // Both lists are sorted by cost, so the best `n` sums are among their products.
+            let mut next_combinations = Vec::new();
+            for (cost, indices) in combinations.iter() {
+                for (index, path) in paths.iter().enumerate() {
+                    let mut indices = indices.clone();
+                    indices.push(index);
+                    next_combinations.push((cost + path.cost, indices));
+                }
+            }
+            next_combinations.sort_by_key(|(cost, _)| *cost);
+            next_combinations.truncate(n);
+            combinations = next_combinations;
+        }
+
+        let mut segmentations = Vec::with_capacity(combinations.len());
+        for (_, indices) in combinations {
+            let mut tokens = Vec::new();
+            self.tokenize_normalized(
+                text,
+                &mut tokens,
+                &mut lattice,
+                true,
+                &mut PathChoice::Chosen {
+                    candidates: &candidates,
+                    indices: &indices,
+                    segment_index: 0,
+                },
+            )?;
+            segmentations.push(tokens);
+        }
+
+        Ok(segmentations)
+    }
+
+    fn tokenize_chunk<'a>(
+        &'a self,
+        lattice: &mut Lattice,
+        chunk: &'a str,
+        position: &mut usize,
+        byte_position: &mut usize,
+        paths: &mut PathChoice,
+        tokens: &mut Vec<Token<'a>>,
+    ) {
+        if !self.preserve_whitespace {
+            self.tokenize_segment(lattice, chunk, position, byte_position, paths, tokens);
+            return;
+        }
+
+        // Split the chunk into runs of whitespace and the segments between them.
+        // Only the segments are passed to the lattice.
+        let mut rest = chunk;
+        while let Some(first_char) = rest.chars().next() {
+            let is_whitespace = first_char.is_whitespace();
+            let run_len = rest
+                .find(|c: char| c.is_whitespace() != is_whitespace)
+                .unwrap_or(rest.len());
+            let (run, remainder) = rest.split_at(run_len);
+            rest = remainder;
+
+            if !is_whitespace {
+                self.tokenize_segment(lattice, run, position, byte_position, paths, tokens);
+                continue;
+            }
+
+            let token_start = *byte_position;
+            *byte_position += run.len();
+            let token_end = *byte_position;
+
+            let mut token = Token::new(
+                run,
+                token_start,
+                token_end,
+                *position,
+                WordId(u32::MAX, true),
+                &self.dictionary,
+                self.user_dictionary.as_ref(),
+            );
+            token.set_details(Some(vec![WHITESPACE_POS.to_string()]));
+            tokens.push(token);
+
+            *position += 1;
+        }
+    }
+
+    fn tokenize_segment<'a>(
+        &'a self,
+        lattice: &mut Lattice,
+        segment: &'a str,
+        position: &mut usize,
+        byte_position: &mut usize,
+        paths: &mut PathChoice,
+        tokens: &mut Vec<Token<'a>>,
+    ) {
+        match paths {
+            PathChoice::Best => {
+                self.build_lattice(lattice, segment);
+
+                let offsets = lattice.tokens_offset();
+                let costs = if self.debug_costs {
+                    Some(
+                        lattice
+                            .tokens_costs(&self.dictionary.cost_matrix, self.cost_model.as_ref()),
+                    )
+                } else {
+                    None
+                };
+
+                self.push_path_tokens(
+                    segment,
+                    &offsets,
+                    costs.as_deref(),
+                    position,
+                    byte_position,
+                    tokens,
+                );
+            }
+            PathChoice::Collect { n, candidates } => {
+                self.build_lattice(lattice, segment);
+
+                let paths = lattice
+                    .nbest_paths(
+                        &self.dictionary.cost_matrix,
+                        &self.mode,
+                        self.cost_model.as_ref(),
+                        *n,
+                    )
+                    .into_iter()
+                    .map(|(cost, path)| {
+                        let costs = if self.debug_costs {
+                            Some(lattice.path_costs(
+                                &path,
+                                &self.dictionary.cost_matrix,
+                                self.cost_model.as_ref(),
+                            ))
+                        } else {
+                            None
+                        };
+                        SegmentPath {
+                            cost,
+                            offsets: lattice.path_offsets(&path),
+                            costs,
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                // The segment still takes up its bytes and positions, so that the following
+                // segments are tokenized as in the other passes.
+                if let Some(path) = paths.first() {
+                    self.push_path_tokens(
+                        segment,
+                        &path.offsets,
+                        path.costs.as_deref(),
+                        position,
+                        byte_position,
+                        tokens,
+                    );
+                }
+                candidates.push(paths);
+            }
+            PathChoice::Chosen {
+                candidates,
+                indices,
+                segment_index,
+            } => {
+                let path = &candidates[*segment_index][indices[*segment_index]];
+                *segment_index += 1;
+
+                self.push_path_tokens(
+                    segment,
+                    &path.offsets,
+                    path.costs.as_deref(),
+                    position,
+                    byte_position,
+                    tokens,
+                );
+            }
+        }
+    }
+
+    fn build_lattice(&self, lattice: &mut Lattice, text: &str) {
+        lattice.set_text(
+            &self.dictionary.dict,
+            &self.user_dictionary.as_ref().map(|d| &d.dict),
+            &self.user_words,
+            &self.dictionary.char_definitions,
+            &self.dictionary.unknown_dictionary,
+            text,
+            &self.mode,
+            self.max_unknown_length,
+            &self.unknown_cost_overrides,
+        );
+        lattice.calculate_path_costs(
+            &self.dictionary.cost_matrix,
+            &self.mode,
+            self.cost_model.as_ref(),
+        );
+    }
+
+    /// Push the tokens of a path through the lattice of the segment, given as the byte offset
+    /// and the word ID of each token.
+    fn push_path_tokens<'a>(
+        &'a self,
+        segment: &'a str,
+        offsets: &[(usize, WordId)],
+        costs: Option<&[(i32, i32)]>,
+        position: &mut usize,
+        byte_position: &mut usize,
+        tokens: &mut Vec<Token<'a>>,
+    ) {
+        for i in 0..offsets.len() {
+            let (byte_start, word_id) = offsets[i];
+            let byte_end = if i == offsets.len() - 1 {
+                segment.len()
+            } else {
+                let (next_start, _word_id) = offsets[i + 1];
+                next_start
+            };
+
+            // retrieve token from its segment byte positions
+            let surface = &segment[byte_start..byte_end];
+
+            // compute the token's absolute byte positions
+            let token_start = *byte_position;
+            *byte_position += surface.len();
+            let token_end = *byte_position;
+
+            let mut token = Token::new(
+                surface,
+                token_start,
+                token_end,
+                *position,
+                word_id,
+                &self.dictionary,
+                self.user_dictionary.as_ref(),
+            );
+            if let Some(details) = self.user_words.word_details(word_id) {
+                token.set_details(Some(details));
+            }
+            if let Some(costs) = costs {
+                let (word_cost, connection_cost) = costs[i];
+                token.word_cost = Some(word_cost);
+                token.connection_cost = Some(connection_cost);
+            }
+
+            if self.mode.is_search() && self.decompose_compounds {
+                if let Some(sub_tokens) = self.decompose_compound(&token) {
+                    *position += sub_tokens.len();
+                    tokens.extend(sub_tokens);
+                    continue;
+                }
+            }
+
+            tokens.push(token);
+
+            *position += 1;
+        }
+    }
+
+    /// Split a ko-dic compound word into its parts.
+    ///
+    /// ko-dic records the decomposition of compound words in the 8th field of the details,
+    /// e.g. `한국/NNG/*+어/NNG/*` for `한국어`. Only the type and the decomposition fields are
+    /// read, so the details of the tokens are still looked up lazily.
+    /// The offsets of the parts are in the tokenized text, like those of the compound word, so
+    /// they are mapped onto the original text along with the other tokens if it is normalized.
+    /// The parts are not in the lattice, so they carry the costs of the compound word.
+    /// Returns `None` if the token is not a compound word or the parts do not make up its text,
+    /// in which case the token should be kept as is.
+    fn decompose_compound<'a>(&'a self, token: &Token<'a>) -> Option<Vec<Token<'a>>> {
+        let expression = self.compound_expression(token.word_id)?;
+
+        let mut parts = Vec::new();
+        for part in expression.split('+') {
+            let fields: Vec<&str> = part.split('/').collect();
+            if fields.len() != 3 || fields[0].is_empty() {
+                return None;
+            }
+            parts.push((fields[0], fields[1], fields[2]));
+        }
+        if parts.len() < 2
+            || parts
+                .iter()
+                .map(|(surface, _, _)| *surface)
+                .collect::<String>()
+                != token.text
+        {
+            return None;
+        }
+
+        let mut sub_tokens = Vec::with_capacity(parts.len());
+        let mut offset = 0;
+        for (i, (surface, pos, semantic)) in parts.into_iter().enumerate() {
+            let text = match &token.text {
+                Cow::Borrowed(text) => Cow::Borrowed(&text[offset..offset + surface.len()]),
+                Cow::Owned(text) => Cow::Owned(text[offset..offset + surface.len()].to_string()),
+            };
+            let mut sub_token = Token::new(
+                text,
+                token.byte_start + offset,
+                token.byte_start + offset + surface.len(),
+                token.position + i,
+                token.word_id,
+                token.dictionary,
+                token.user_dictionary,
+            );
+            sub_token.set_details(Some(vec![
+                pos.to_string(),
+                semantic.to_string(),
+                final_consonant(surface).to_string(),
+                surface.to_string(),
+                "*".to_string(),
+                "*".to_string(),
+                "*".to_string(),
+                "*".to_string(),
+            ]));
+            sub_token.word_cost = token.word_cost;
+            sub_token.connection_cost = token.connection_cost;
+            sub_tokens.push(sub_token);
+            offset += surface.len();
+        }
+
+        Some(sub_tokens)
+    }
+
+    /// Get the decomposition of the word if it is a compound word, reading only the fields
+    /// needed from the dictionary.
+    fn compound_expression(&self, word_id: WordId) -> Option<String> {
+        if word_id.is_unknown() {
+            return None;
+        }
+
+        if word_id.is_system() {
+            let id = word_id.0 as usize;
+            if self.dictionary.word_detail(id, COMPOUND_TYPE_FIELD)? != "Compound" {
+                return None;
+            }
+            return self
+                .dictionary
+                .word_detail(id, COMPOUND_EXPRESSION_FIELD)
+                .map(|expression| expression.to_string());
+        }
+
+        // The details of the user dictionary are stored together, so they are decoded at once,
+        // but not cached in the token.
+        let mut details = self
+            .user_dictionary
+            .as_ref()?
+            .word_details(word_id.0 as usize)?;
+        if details.len() <= COMPOUND_EXPRESSION_FIELD || details[COMPOUND_TYPE_FIELD] != "Compound"
+        {
+            return None;
+        }
+        Some(details.swap_remove(COMPOUND_EXPRESSION_FIELD))
+    }
+}
+
+/// A path through the lattice of a segment of the text.
+struct SegmentPath {
+    /// The cost of the path.
+    cost: i32,
+    /// The byte offset in the segment and the word ID of each token.
+    offsets: Vec<(usize, WordId)>,
+    /// The word cost and the connection cost of each token, if `debug_costs` is enabled.
+    costs: Option<Vec<(i32, i32)>>,
+}
+
+/// How the path through the lattice of each segment of the text is chosen, the segments being
+/// the parts of the text passed to the lattice, in the order they are tokenized.
+enum PathChoice<'p> {
+    /// The best path of each segment.
+    Best,
+    /// The best path of each segment, after adding its `n` best paths to `candidates`.
+    Collect {
+        n: usize,
+        candidates: &'p mut Vec<Vec<SegmentPath>>,
+    },
+    /// The path at `indices[i]` in `candidates[i]` for the i-th segment.
+    /// `segment_index` is the index of the next segment.
+    Chosen {
+        candidates: &'p [Vec<SegmentPath>],
+        indices: &'p [usize],
+        segment_index: usize,
+    },
+}
+
+/// The iterator returned by `Tokenizer::tokenize_iter`.
+struct TextTokens<'a> {
+    tokenizer: &'a Tokenizer,
+    /// The sentences of the text that have not been tokenized yet.
+    sentences: Sentences<'a>,
+    /// The tokens of the last sentence that have not been returned yet.
+    tokens: VecDeque<Token<'a>>,
+    lattice: Lattice,
+    position: usize,
+    byte_position: usize,
+    done: bool,
+}
+
+impl<'a> Iterator for TextTokens<'a> {
+    type Item = LinderaResult<Token<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(token) = self.tokens.pop_front() {
+                return Some(Ok(token));
+            }
+            if self.done {
+                return None;
+            }
+
+            let tokenizer = self.tokenizer;
+            match self.sentences.next() {
+                Some(sentence) => {
+                    let mut tokens = Vec::new();
+                    tokenizer.tokenize_part(
+                        sentence,
+                        self.byte_position,
+                        &mut self.position,
+                        &mut self.lattice,
+                        true,
+                        &mut PathChoice::Best,
+                        &mut tokens,
+                    );
+                    self.tokens.extend(tokens);
+                    self.byte_position += sentence.len();
+                }
+                None => {
+                    self.done = true;
+                    if tokenizer.include_bos_eos {
+                        self.tokens.push_back(tokenizer.boundary_token(
+                            self.byte_position,
+                            self.position,
+                            EOS_POS,
+                        ));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The iterator returned by `Tokenizer::tokenize_reader`.
+struct ReaderTokens<'a, R> {
+    tokenizer: &'a Tokenizer,
+    reader: R,
+    /// The bytes read but not tokenized yet.
+    buffer: Vec<u8>,
+    /// The length of the beginning of the buffer already searched for a sentence terminator.
+    scanned_len: usize,
+    /// The tokens of the last chunk that have not been returned yet.
+    tokens: VecDeque<Token<'a>>,
+    lattice: Lattice,
+    position: usize,
+    byte_position: usize,
+    done: bool,
+}
+
+impl<'a, R: BufRead> ReaderTokens<'a, R> {
+    /// Read the next chunk of text and tokenize it.
+    fn tokenize_next_chunk(&mut self) -> LinderaResult<()> {
+        let chunk_len = loop {
+            if let Some(end) = last_sentence_end(
+                &self.tokenizer.sentence_splitter,
+                &self.buffer,
+                self.scanned_len,
+            ) {
+                break end;
+            }
+            self.scanned_len = self.buffer.len();
+            if self.buffer.len() >= MAX_BUFFERED_SENTENCE_LENGTH {
+                break char_boundary(&self.buffer, MAX_BUFFERED_SENTENCE_LENGTH);
+            }
+
+            let available = self
+                .reader
+                .fill_buf()
+                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+            if available.is_empty() {
+                self.done = true;
+                break self.buffer.len();
+            }
+            let available_len = available.len();
+            self.buffer.extend_from_slice(available);
+            self.reader.consume(available_len);
+        };
+
+        let rest = self.buffer.split_off(chunk_len);
+        let chunk = String::from_utf8(std::mem::replace(&mut self.buffer, rest))
+            .map_err(|err| LinderaErrorKind::Decode.with_error(anyhow::anyhow!(err)))?;
+        self.scanned_len = 0;
+
+        let tokenizer = self.tokenizer;
+        let mut tokens = Vec::new();
+        let mut sentence_start = self.byte_position;
+        for sentence in tokenizer.sentence_splitter.split(&chunk) {
+            tokenizer.tokenize_part(
+                sentence,
+                sentence_start,
+                &mut self.position,
+                &mut self.lattice,
+                true,
+                &mut PathChoice::Best,
+                &mut tokens,
+            );
+            sentence_start += sentence.len();
+        }
+
+        // The tokens borrow the chunk, which is dropped, so they are moved onto their own text.
+        for token in tokens {
+            let text = token.text.to_string();
+            let (start, end) = (token.byte_start, token.byte_end);
+            self.tokens.push_back(token.relocate(
+                text,
+                start,
+                end,
+                &tokenizer.dictionary,
+                tokenizer.user_dictionary.as_ref(),
+            ));
+        }
+        self.byte_position += chunk.len();
+
+        if self.done && tokenizer.include_bos_eos {
+            self.tokens.push_back(tokenizer.boundary_token(
+                self.byte_position,
+                self.position,
+                EOS_POS,
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, R: BufRead> Iterator for ReaderTokens<'a, R> {
+    type Item = LinderaResult<Token<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(token) = self.tokens.pop_front() {
+                return Some(Ok(token));
+            }
+            if self.done {
+                return None;
+            }
+            if let Err(err) = self.tokenize_next_chunk() {
+                self.done = true;
+                return Some(Err(err));
+            }
+        }
+    }
+}
+
+/// Return the end of the last sentence terminator of the buffer, searching from `from` only
+/// since the beginning has already been searched. Only the valid UTF-8 text is searched, so a
+/// character cut off at the end of the buffer is searched once it has been read entirely.
+fn last_sentence_end(splitter: &SentenceSplitter, buffer: &[u8], from: usize) -> Option<usize> {
+    // A multi-byte terminator may have been cut off by the previous read.
+    let mut from = from.min(buffer.len());
+    while from > 0 && from < buffer.len() && buffer[from] & 0xC0 == 0x80 {
+        from -= 1;
+    }
+
+    let bytes = &buffer[from..];
+    let valid_len = match std::str::from_utf8(bytes) {
+        Ok(text) => text.len(),
+        Err(err) => err.valid_up_to(),
+    };
+    let text = std::str::from_utf8(&bytes[..valid_len]).ok()?;
+
+    splitter.last_sentence_end(text).map(|end| from + end)
+}
+
+/// Return the largest character boundary of the UTF-8 buffer that is not greater than `max_len`.
+/// A character is at most 4 bytes long, so if there is no boundary within the last 3 bytes, the
+/// buffer is not valid UTF-8 and `max_len` is returned for the decoding to fail.
+fn char_boundary(buffer: &[u8], max_len: usize) -> usize {
+    let max_len = max_len.min(buffer.len());
+    // Continuation bytes are of the form 0b10xxxxxx.
+    (max_len.saturating_sub(3)..=max_len)
+        .rev()
+        .find(|&len| len == buffer.len() || buffer[len] & 0xC0 != 0x80)
+        .filter(|&len| len > 0)
+        .unwrap_or(max_len)
+}
+
+/// Normalize the text and return it together with, for each byte of the normalized text,
+/// the byte range of the original text it was produced from.
+/// The text is normalized segment by segment, each segment starting at a normalization
+/// boundary, so that the segments can be mapped back independently and the result is the same
+/// as normalizing the whole text.
+fn normalize_with_origins(text: &str, form: NormalizationForm) -> (String, Vec<(usize, usize)>) {
+    let mut normalized_text = String::with_capacity(text.len());
+    let mut origins = Vec::with_capacity(text.len());
+
+    let mut segment_start = 0;
+    let mut char_indices = text.char_indices().peekable();
+    while char_indices.next().is_some() {
+        let segment_end = match char_indices.peek() {
+            // Characters that may be composed or reordered with the previous ones, such as
+            // combining marks or conjoining Hangul vowels, belong to the current segment.
+            Some(&(_, next_char)) if !is_normalization_boundary(next_char, form) => continue,
+            Some(&(next_index, _)) => next_index,
+            None => text.len(),
+        };
+
+        let segment = &text[segment_start..segment_end];
+        match form {
+            NormalizationForm::NFC => normalized_text.extend(segment.nfc()),
+            NormalizationForm::NFD => normalized_text.extend(segment.nfd()),
+            NormalizationForm::NFKC => normalized_text.extend(segment.nfkc()),
+            NormalizationForm::NFKD => normalized_text.extend(segment.nfkd()),
+        }
+        origins.resize(normalized_text.len(), (segment_start, segment_end));
+
+        segment_start = segment_end;
+    }
+
+    (normalized_text, origins)
+}
+
+/// Return whether the normalization never changes the text across the boundary before the
+/// character. The first character of its decomposition must be a starter (canonical combining
+/// class 0), so that it is not reordered with the characters before it, and for the composed
+/// forms it must not be composed with them either, which the quick check rules out.
+fn is_normalization_boundary(c: char, form: NormalizationForm) -> bool {
+    let first_char = match form {
+        NormalizationForm::NFC | NormalizationForm::NFD => std::iter::once(c).nfd().next(),
+        NormalizationForm::NFKC | NormalizationForm::NFKD => std::iter::once(c).nfkd().next(),
+    };
+
+    match first_char {
+        Some(first_char) if canonical_combining_class(first_char) == 0 => match form {
+            NormalizationForm::NFC => {
+                is_nfc_quick(std::iter::once(first_char)) == IsNormalized::Yes
+            }
+            NormalizationForm::NFKC => {
+                is_nfkc_quick(std::iter::once(first_char)) == IsNormalized::Yes
+            }
+            NormalizationForm::NFD | NormalizationForm::NFKD => true,
+        },
+        _ => false,
+    }
+}
+
+/// Return `T` if the last syllable of the text has a final consonant (jongseong), `F` otherwise.
+fn final_consonant(text: &str) -> &'static str {
+    match text.chars().last() {
+        Some(c)
+            if ('\u{AC00}'..='\u{D7A3}').contains(&c)
+                && !(c as u32 - 0xAC00).is_multiple_of(28) =>
+        {
+            "T"
+        }
+        _ => "F",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    #[cfg(any(
+        feature = "ipadic",
+        feature = "ipadic-neologd",
+        feature = "unidic",
+        feature = "ko-dic",
+        feature = "cc-cedict"
+    ))]
+    use std::{
+        collections::HashMap,
+        fs::File,
+        io::{BufReader, Read},
+        path::PathBuf,
+    };
+
+    #[allow(unused_imports)]
+    #[cfg(any(
+        feature = "ipadic",
+        feature = "ipadic-neologd",
+        feature = "unidic",
+        feature = "ko-dic",
+        feature = "cc-cedict"
+    ))]
+    use lindera_core::mode::{Mode, Penalty};
+
+    #[cfg(any(
+        feature = "ipadic",
+        feature = "ipadic-neologd",
+        feature = "unidic",
+        feature = "ko-dic",
+        feature = "cc-cedict"
+    ))]
+    use lindera_dictionary::{DictionaryConfig, DictionaryKind, UserDictionaryConfig};
+
+    #[cfg(feature = "ipadic")]
+    use std::{sync::Arc, thread};
+
+    use lindera_core::sentence_splitter::SentenceSplitter;
+
+    #[cfg(feature = "ipadic")]
+    use lindera_core::viterbi::Lattice;
+
+    #[cfg(feature = "ipadic")]
+    use lindera_core::LinderaResult;
+
+    #[cfg(feature = "ipadic")]
+    use crate::tokenizer::NormalizationForm;
+
+    #[cfg(any(
+        feature = "ipadic",
+        feature = "ipadic-neologd",
+        feature = "unidic",
+        feature = "ko-dic",
+        feature = "cc-cedict"
+    ))]
+    use crate::tokenizer::Tokenizer;
+
+    use crate::tokenizer::{char_boundary, last_sentence_end, normalize_with_origins};
+    use crate::tokenizer::{TokenizerConfig, DEFAULT_MAX_UNKNOWN_LENGTH};
+
+    #[test]
+    fn test_tokenizer_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<crate::tokenizer::Tokenizer>();
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_shared_between_threads_ipadic() {
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        };
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary: None,
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+
+        let tokenizer = Arc::new(Tokenizer::from_config(config).unwrap());
+        let text = "日本語の形態素解析を行うことができます。テストの文章です。";
+
+        let expected = tokenizer
+            .tokenize(text)
+            .unwrap()
+            .iter()
+            .map(|token| (token.text.to_string(), token.byte_start, token.byte_end))
+            .collect::<Vec<_>>();
+
+        let handles = (0..8)
+            .map(|_| {
+                let tokenizer = Arc::clone(&tokenizer);
+                thread::spawn(move || {
+                    tokenizer
+                        .tokenize(text)
+                        .unwrap()
+                        .iter()
+                        .map(|token| (token.text.to_string(), token.byte_start, token.byte_end))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_config_ipadic_normal() {
+        let config_str = r#"
+        {
+            "dictionary": {
+                "kind": "ipadic"
+            },
+            "mode": "normal"
+        }
+        "#;
+
+        let config: TokenizerConfig = serde_json::from_str(config_str).unwrap();
+        assert_eq!(config.dictionary.kind, Some(DictionaryKind::IPADIC));
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_config_ipadic_decompose() {
+        let config_str = r#"
         {
             "dictionary": {
                 "kind": "ipadic"
@@ -357,461 +1894,1403 @@ mod tests {
                 }
             }
         }
-        "#;
-
-        let config: TokenizerConfig = serde_json::from_str(config_str).unwrap();
-        assert_eq!(config.dictionary.kind, Some(DictionaryKind::IPADIC));
+        "#;
+
+        let config: TokenizerConfig = serde_json::from_str(config_str).unwrap();
+        assert_eq!(config.dictionary.kind, Some(DictionaryKind::IPADIC));
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_config_ipadic_normalize() {
+        let config_str = r#"
+        {
+            "dictionary": {
+                "kind": "ipadic"
+            },
+            "mode": "normal",
+            "normalize": "nfkc"
+        }
+        "#;
+
+        let config: TokenizerConfig = serde_json::from_str(config_str).unwrap();
+        assert_eq!(config.normalize, Some(NormalizationForm::NFKC));
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_config_ipadic_unknown_cost_overrides() {
+        let config_str = r#"
+        {
+            "dictionary": {
+                "kind": "ipadic"
+            },
+            "mode": "normal",
+            "unknown_cost_overrides": {
+                "ALPHA": 30000
+            }
+        }
+        "#;
+
+        let config: TokenizerConfig = serde_json::from_str(config_str).unwrap();
+        assert_eq!(config.unknown_cost_overrides.get("ALPHA"), Some(&30000));
+    }
+
+    #[test]
+    fn test_tokenize_config_max_unknown_length() {
+        let config_str = r#"
+        {
+            "dictionary": {
+                "kind": "ipadic"
+            },
+            "mode": "normal",
+            "max_unknown_length": 100
+        }
+        "#;
+
+        let config: TokenizerConfig = serde_json::from_str(config_str).unwrap();
+        assert_eq!(config.max_unknown_length, 100);
+
+        let config_str = r#"
+        {
+            "dictionary": {
+                "kind": "ipadic"
+            }
+        }
+        "#;
+
+        let config: TokenizerConfig = serde_json::from_str(config_str).unwrap();
+        assert_eq!(config.max_unknown_length, DEFAULT_MAX_UNKNOWN_LENGTH);
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_ipadic() {
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        };
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary: None,
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+
+        let tokenizer = Tokenizer::from_config(config).unwrap();
+        let mut tokens = tokenizer
+            .tokenize("日本語の形態素解析を行うことができます。テスト。")
+            .unwrap();
+        let mut tokens_iter = tokens.iter_mut();
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "日本語");
+            assert_eq!(token.byte_start, 0);
+            assert_eq!(token.byte_end, 9);
+            assert_eq!(token.position, 0);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec![
+                    "名詞",
+                    "一般",
+                    "*",
+                    "*",
+                    "*",
+                    "*",
+                    "日本語",
+                    "ニホンゴ",
+                    "ニホンゴ"
+                ]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "の");
+            assert_eq!(token.byte_start, 9);
+            assert_eq!(token.byte_end, 12);
+            assert_eq!(token.position, 1);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec!["助詞", "連体化", "*", "*", "*", "*", "の", "ノ", "ノ"]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "形態素");
+            assert_eq!(token.byte_start, 12);
+            assert_eq!(token.byte_end, 21);
+            assert_eq!(token.position, 2);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec![
+                    "名詞",
+                    "一般",
+                    "*",
+                    "*",
+                    "*",
+                    "*",
+                    "形態素",
+                    "ケイタイソ",
+                    "ケイタイソ"
+                ]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "解析");
+            assert_eq!(token.byte_start, 21);
+            assert_eq!(token.byte_end, 27);
+            assert_eq!(token.position, 3);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec![
+                    "名詞",
+                    "サ変接続",
+                    "*",
+                    "*",
+                    "*",
+                    "*",
+                    "解析",
+                    "カイセキ",
+                    "カイセキ"
+                ]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "を");
+            assert_eq!(token.byte_start, 27);
+            assert_eq!(token.byte_end, 30);
+            assert_eq!(token.position, 4);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec!["助詞", "格助詞", "一般", "*", "*", "*", "を", "ヲ", "ヲ"]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "行う");
+            assert_eq!(token.byte_start, 30);
+            assert_eq!(token.byte_end, 36);
+            assert_eq!(token.position, 5);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec![
+                    "動詞",
+                    "自立",
+                    "*",
+                    "*",
+                    "五段・ワ行促音便",
+                    "基本形",
+                    "行う",
+                    "オコナウ",
+                    "オコナウ"
+                ]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "こと");
+            assert_eq!(token.byte_start, 36);
+            assert_eq!(token.byte_end, 42);
+            assert_eq!(token.position, 6);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec![
+                    "名詞",
+                    "非自立",
+                    "一般",
+                    "*",
+                    "*",
+                    "*",
+                    "こと",
+                    "コト",
+                    "コト"
+                ]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "が");
+            assert_eq!(token.byte_start, 42);
+            assert_eq!(token.byte_end, 45);
+            assert_eq!(token.position, 7);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec!["助詞", "格助詞", "一般", "*", "*", "*", "が", "ガ", "ガ"]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "でき");
+            assert_eq!(token.byte_start, 45);
+            assert_eq!(token.byte_end, 51);
+            assert_eq!(token.position, 8);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec![
+                    "動詞",
+                    "自立",
+                    "*",
+                    "*",
+                    "一段",
+                    "連用形",
+                    "できる",
+                    "デキ",
+                    "デキ"
+                ]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "ます");
+            assert_eq!(token.byte_start, 51);
+            assert_eq!(token.byte_end, 57);
+            assert_eq!(token.position, 9);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec![
+                    "助動詞",
+                    "*",
+                    "*",
+                    "*",
+                    "特殊・マス",
+                    "基本形",
+                    "ます",
+                    "マス",
+                    "マス"
+                ]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "。");
+            assert_eq!(token.byte_start, 57);
+            assert_eq!(token.byte_end, 60);
+            assert_eq!(token.position, 10);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec!["記号", "句点", "*", "*", "*", "*", "。", "。", "。"]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "テスト");
+            assert_eq!(token.byte_start, 60);
+            assert_eq!(token.byte_end, 69);
+            assert_eq!(token.position, 11);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec![
+                    "名詞",
+                    "サ変接続",
+                    "*",
+                    "*",
+                    "*",
+                    "*",
+                    "テスト",
+                    "テスト",
+                    "テスト"
+                ]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "。");
+            assert_eq!(token.byte_start, 69);
+            assert_eq!(token.byte_end, 72);
+            assert_eq!(token.position, 12);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec!["記号", "句点", "*", "*", "*", "*", "。", "。", "。"]
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "unidic")]
+    fn test_tokenize_unidic() {
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::UniDic),
+            path: None,
+            ..Default::default()
+        };
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary: None,
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+
+        let tokenizer = Tokenizer::from_config(config).unwrap();
+        let mut tokens = tokenizer
+            .tokenize("日本語の形態素解析を行うことができます。")
+            .unwrap();
+        let mut tokens_iter = tokens.iter_mut();
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "日本");
+            assert_eq!(token.byte_start, 0);
+            assert_eq!(token.byte_end, 6);
+            assert_eq!(token.position, 0);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec![
+                    "名詞",
+                    "固有名詞",
+                    "地名",
+                    "国",
+                    "*",
+                    "*",
+                    "ニッポン",
+                    "日本",
+                    "日本",
+                    "ニッポン",
+                    "日本",
+                    "ニッポン",
+                    "固",
+                    "*",
+                    "*",
+                    "*",
+                    "*"
+                ]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "語");
+            assert_eq!(token.byte_start, 6);
+            assert_eq!(token.byte_end, 9);
+            assert_eq!(token.position, 1);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec![
+                    "名詞",
+                    "普通名詞",
+                    "一般",
+                    "*",
+                    "*",
+                    "*",
+                    "ゴ",
+                    "語",
+                    "語",
+                    "ゴ",
+                    "語",
+                    "ゴ",
+                    "漢",
+                    "*",
+                    "*",
+                    "*",
+                    "*"
+                ]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "の");
+            assert_eq!(token.byte_start, 9);
+            assert_eq!(token.byte_end, 12);
+            assert_eq!(token.position, 2);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec![
+                    "助詞",
+                    "格助詞",
+                    "*",
+                    "*",
+                    "*",
+                    "*",
+                    "ノ",
+                    "の",
+                    "の",
+                    "ノ",
+                    "の",
+                    "ノ",
+                    "和",
+                    "*",
+                    "*",
+                    "*",
+                    "*"
+                ]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "形態");
+            assert_eq!(token.byte_start, 12);
+            assert_eq!(token.byte_end, 18);
+            assert_eq!(token.position, 3);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec![
+                    "名詞",
+                    "普通名詞",
+                    "一般",
+                    "*",
+                    "*",
+                    "*",
+                    "ケイタイ",
+                    "形態",
+                    "形態",
+                    "ケータイ",
+                    "形態",
+                    "ケータイ",
+                    "漢",
+                    "*",
+                    "*",
+                    "*",
+                    "*"
+                ]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "素");
+            assert_eq!(token.byte_start, 18);
+            assert_eq!(token.byte_end, 21);
+            assert_eq!(token.position, 4);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec![
+                    "接尾辞",
+                    "名詞的",
+                    "一般",
+                    "*",
+                    "*",
+                    "*",
+                    "ソ",
+                    "素",
+                    "素",
+                    "ソ",
+                    "素",
+                    "ソ",
+                    "漢",
+                    "*",
+                    "*",
+                    "*",
+                    "*"
+                ]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "解析");
+            assert_eq!(token.byte_start, 21);
+            assert_eq!(token.byte_end, 27);
+            assert_eq!(token.position, 5);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec![
+                    "名詞",
+                    "普通名詞",
+                    "サ変可能",
+                    "*",
+                    "*",
+                    "*",
+                    "カイセキ",
+                    "解析",
+                    "解析",
+                    "カイセキ",
+                    "解析",
+                    "カイセキ",
+                    "漢",
+                    "*",
+                    "*",
+                    "*",
+                    "*"
+                ]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "を");
+            assert_eq!(token.byte_start, 27);
+            assert_eq!(token.byte_end, 30);
+            assert_eq!(token.position, 6);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec![
+                    "助詞",
+                    "格助詞",
+                    "*",
+                    "*",
+                    "*",
+                    "*",
+                    "ヲ",
+                    "を",
+                    "を",
+                    "オ",
+                    "を",
+                    "オ",
+                    "和",
+                    "*",
+                    "*",
+                    "*",
+                    "*"
+                ]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "行う");
+            assert_eq!(token.byte_start, 30);
+            assert_eq!(token.byte_end, 36);
+            assert_eq!(token.position, 7);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec![
+                    "動詞",
+                    "一般",
+                    "*",
+                    "*",
+                    "五段-ワア行",
+                    "連体形-一般",
+                    "オコナウ",
+                    "行う",
+                    "行う",
+                    "オコナウ",
+                    "行う",
+                    "オコナウ",
+                    "和",
+                    "*",
+                    "*",
+                    "*",
+                    "*"
+                ]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "こと");
+            assert_eq!(token.byte_start, 36);
+            assert_eq!(token.byte_end, 42);
+            assert_eq!(token.position, 8);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec![
+                    "名詞",
+                    "普通名詞",
+                    "一般",
+                    "*",
+                    "*",
+                    "*",
+                    "コト",
+                    "事",
+                    "こと",
+                    "コト",
+                    "こと",
+                    "コト",
+                    "和",
+                    "コ濁",
+                    "基本形",
+                    "*",
+                    "*"
+                ]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "が");
+            assert_eq!(token.byte_start, 42);
+            assert_eq!(token.byte_end, 45);
+            assert_eq!(token.position, 9);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec![
+                    "助詞",
+                    "格助詞",
+                    "*",
+                    "*",
+                    "*",
+                    "*",
+                    "ガ",
+                    "が",
+                    "が",
+                    "ガ",
+                    "が",
+                    "ガ",
+                    "和",
+                    "*",
+                    "*",
+                    "*",
+                    "*"
+                ]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "でき");
+            assert_eq!(token.byte_start, 45);
+            assert_eq!(token.byte_end, 51);
+            assert_eq!(token.position, 10);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec![
+                    "動詞",
+                    "非自立可能",
+                    "*",
+                    "*",
+                    "上一段-カ行",
+                    "連用形-一般",
+                    "デキル",
+                    "出来る",
+                    "でき",
+                    "デキ",
+                    "できる",
+                    "デキル",
+                    "和",
+                    "*",
+                    "*",
+                    "*",
+                    "*"
+                ]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "ます");
+            assert_eq!(token.byte_start, 51);
+            assert_eq!(token.byte_end, 57);
+            assert_eq!(token.position, 11);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec![
+                    "助動詞",
+                    "*",
+                    "*",
+                    "*",
+                    "助動詞-マス",
+                    "終止形-一般",
+                    "マス",
+                    "ます",
+                    "ます",
+                    "マス",
+                    "ます",
+                    "マス",
+                    "和",
+                    "*",
+                    "*",
+                    "*",
+                    "*"
+                ]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "。");
+            assert_eq!(token.byte_start, 57);
+            assert_eq!(token.byte_end, 60);
+            assert_eq!(token.position, 12);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec![
+                    "補助記号",
+                    "句点",
+                    "*",
+                    "*",
+                    "*",
+                    "*",
+                    "",
+                    "。",
+                    "。",
+                    "",
+                    "。",
+                    "",
+                    "記号",
+                    "*",
+                    "*",
+                    "*",
+                    "*"
+                ]
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "ko-dic")]
+    fn test_tokenize_ko_dic() {
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::KoDic),
+            path: None,
+            ..Default::default()
+        };
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary: None,
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+
+        let tokenizer = Tokenizer::from_config(config).unwrap();
+        let mut tokens = tokenizer
+            .tokenize("한국어의형태해석을실시할수있습니다.")
+            .unwrap();
+        let mut tokens_iter = tokens.iter_mut();
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "한국어");
+            assert_eq!(token.byte_start, 0);
+            assert_eq!(token.byte_end, 9);
+            assert_eq!(token.position, 0);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec![
+                    "NNG",
+                    "*",
+                    "F",
+                    "한국어",
+                    "Compound",
+                    "*",
+                    "*",
+                    "한국/NNG/*+어/NNG/*"
+                ]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "의");
+            assert_eq!(token.byte_start, 9);
+            assert_eq!(token.byte_end, 12);
+            assert_eq!(token.position, 1);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec!["JKG", "*", "F", "의", "*", "*", "*", "*"]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "형태");
+            assert_eq!(token.byte_start, 12);
+            assert_eq!(token.byte_end, 18);
+            assert_eq!(token.position, 2);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec!["NNG", "*", "F", "형태", "*", "*", "*", "*"]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "해석");
+            assert_eq!(token.byte_start, 18);
+            assert_eq!(token.byte_end, 24);
+            assert_eq!(token.position, 3);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec!["NNG", "행위", "T", "해석", "*", "*", "*", "*"]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "을");
+            assert_eq!(token.byte_start, 24);
+            assert_eq!(token.byte_end, 27);
+            assert_eq!(token.position, 4);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec!["JKO", "*", "T", "을", "*", "*", "*", "*"]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "실시");
+            assert_eq!(token.byte_start, 27);
+            assert_eq!(token.byte_end, 33);
+            assert_eq!(token.position, 5);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec!["NNG", "행위", "F", "실시", "*", "*", "*", "*"]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "할");
+            assert_eq!(token.byte_start, 33);
+            assert_eq!(token.byte_end, 36);
+            assert_eq!(token.position, 6);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec![
+                    "XSV+ETM",
+                    "*",
+                    "T",
+                    "할",
+                    "Inflect",
+                    "XSV",
+                    "ETM",
+                    "하/XSV/*+ᆯ/ETM/*"
+                ]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "수");
+            assert_eq!(token.byte_start, 36);
+            assert_eq!(token.byte_end, 39);
+            assert_eq!(token.position, 7);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec!["NNB", "*", "F", "수", "*", "*", "*", "*"]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "있");
+            assert_eq!(token.byte_start, 39);
+            assert_eq!(token.byte_end, 42);
+            assert_eq!(token.position, 8);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec!["VV", "*", "T", "있", "*", "*", "*", "*"]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "습니다");
+            assert_eq!(token.byte_start, 42);
+            assert_eq!(token.byte_end, 51);
+            assert_eq!(token.position, 9);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec!["EF", "*", "F", "습니다", "*", "*", "*", "*"]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, ".");
+            assert_eq!(token.byte_start, 51);
+            assert_eq!(token.byte_end, 52);
+            assert_eq!(token.position, 10);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec!["SF", "*", "*", "*", "*", "*", "*", "*"]
+            );
+        }
     }
 
     #[test]
-    #[cfg(feature = "ipadic")]
-    fn test_tokenize_ipadic() {
+    #[cfg(feature = "ko-dic")]
+    fn test_tokenize_ko_dic_with_decompose_mode() {
         let dictionary = DictionaryConfig {
-            kind: Some(DictionaryKind::IPADIC),
+            kind: Some(DictionaryKind::KoDic),
             path: None,
+            ..Default::default()
         };
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary: None,
-            mode: Mode::Normal,
+            mode: Mode::Decompose(Penalty::default()),
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
         let mut tokens = tokenizer
-            .tokenize("日本語の形態素解析を行うことができます。テスト。")
+            .tokenize("한국어의형태해석을실시할수있습니다.")
             .unwrap();
         let mut tokens_iter = tokens.iter_mut();
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "日本語");
+            assert_eq!(token.text, "한국");
             assert_eq!(token.byte_start, 0);
-            assert_eq!(token.byte_end, 9);
+            assert_eq!(token.byte_end, 6);
             assert_eq!(token.position, 0);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
-                vec![
-                    "名詞",
-                    "一般",
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "日本語",
-                    "ニホンゴ",
-                    "ニホンゴ"
-                ]
+                vec!["NNG", "*", "T", "한국", "*", "*", "*", "*"]
             );
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "の");
-            assert_eq!(token.byte_start, 9);
-            assert_eq!(token.byte_end, 12);
+            assert_eq!(token.text, "어");
+            assert_eq!(token.byte_start, 6);
+            assert_eq!(token.byte_end, 9);
             assert_eq!(token.position, 1);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
-                vec!["助詞", "連体化", "*", "*", "*", "*", "の", "ノ", "ノ"]
+                vec!["NNG", "*", "F", "어", "*", "*", "*", "*"]
             );
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "形態素");
-            assert_eq!(token.byte_start, 12);
-            assert_eq!(token.byte_end, 21);
+            assert_eq!(token.text, "의");
+            assert_eq!(token.byte_start, 9);
+            assert_eq!(token.byte_end, 12);
             assert_eq!(token.position, 2);
             assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec![
-                    "名詞",
-                    "一般",
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "形態素",
-                    "ケイタイソ",
-                    "ケイタイソ"
-                ]
-            );
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "解析");
-            assert_eq!(token.byte_start, 21);
-            assert_eq!(token.byte_end, 27);
+            assert_eq!(token.text, "형태");
             assert_eq!(token.position, 3);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec![
-                    "名詞",
-                    "サ変接続",
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "解析",
-                    "カイセキ",
-                    "カイセキ"
-                ]
-            );
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "を");
-            assert_eq!(token.byte_start, 27);
-            assert_eq!(token.byte_end, 30);
+            assert_eq!(token.text, "해석");
             assert_eq!(token.position, 4);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec!["助詞", "格助詞", "一般", "*", "*", "*", "を", "ヲ", "ヲ"]
-            );
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "行う");
-            assert_eq!(token.byte_start, 30);
-            assert_eq!(token.byte_end, 36);
+            assert_eq!(token.text, "을");
             assert_eq!(token.position, 5);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec![
-                    "動詞",
-                    "自立",
-                    "*",
-                    "*",
-                    "五段・ワ行促音便",
-                    "基本形",
-                    "行う",
-                    "オコナウ",
-                    "オコナウ"
-                ]
-            );
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "こと");
-            assert_eq!(token.byte_start, 36);
-            assert_eq!(token.byte_end, 42);
+            assert_eq!(token.text, "실시");
             assert_eq!(token.position, 6);
+        }
+        {
+            // Inflected words are not decomposed.
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "할");
+            assert_eq!(token.byte_start, 33);
+            assert_eq!(token.byte_end, 36);
+            assert_eq!(token.position, 7);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "ko-dic")]
+    fn test_tokenize_ko_dic_with_decompose_mode_and_normalize() {
+        use unicode_normalization::UnicodeNormalization;
+
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::KoDic),
+            path: None,
+            ..Default::default()
+        };
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary: None,
+            mode: Mode::Decompose(Penalty::default()),
+            normalize: Some(crate::tokenizer::NormalizationForm::NFC),
+            debug_costs: true,
+            ..Default::default()
+        };
+
+        let tokenizer = Tokenizer::from_config(config).unwrap();
+        assert!(tokenizer.decompose_compounds);
+
+        // The syllables are written as conjoining jamo, which are longer than the syllables.
+        let text = "한국어의형태해석".nfd().collect::<String>();
+        let mut tokens = tokenizer.tokenize(&text).unwrap();
+
+        // The parts of the compound word point at the original text.
+        let hanguk = "한국".nfd().collect::<String>();
+        assert_eq!(tokens[0].text, hanguk);
+        assert_eq!(
+            (tokens[0].byte_start, tokens[0].byte_end),
+            (0, hanguk.len())
+        );
+        let eo = "어".nfd().collect::<String>();
+        assert_eq!(tokens[1].text, eo);
+        assert_eq!(
+            (tokens[1].byte_start, tokens[1].byte_end),
+            (hanguk.len(), hanguk.len() + eo.len())
+        );
+        assert_eq!(tokens[1].position, 1);
+        assert_eq!(
+            tokens[1].get_details().unwrap(),
+            vec!["NNG", "*", "F", "어", "*", "*", "*", "*"]
+        );
+
+        // The parts carry the costs of the compound word.
+        assert!(tokens[0].word_cost.is_some());
+        assert_eq!(tokens[0].word_cost, tokens[1].word_cost);
+        assert_eq!(tokens[0].connection_cost, tokens[1].connection_cost);
+
+        for token in tokens.iter() {
+            assert_eq!(&text[token.byte_start..token.byte_end], token.text);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "cc-cedict")]
+    fn test_tokenize_cc_cedict() {
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::CcCedict),
+            path: None,
+            ..Default::default()
+        };
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary: None,
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+
+        let tokenizer = Tokenizer::from_config(config).unwrap();
+        let mut tokens = tokenizer.tokenize("可以进行中文形态学分析。").unwrap();
+        let mut tokens_iter = tokens.iter_mut();
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "可以");
+            assert_eq!(token.byte_start, 0);
+            assert_eq!(token.byte_end, 6);
+            assert_eq!(token.position, 0);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
                 vec![
-                    "名詞",
-                    "非自立",
-                    "一般",
                     "*",
                     "*",
                     "*",
-                    "こと",
-                    "コト",
-                    "コト"
+                    "*",
+                    "ke3 yi3",
+                    "可以",
+                    "可以",
+                    "can/may/possible/able to/not bad/pretty good/"
                 ]
             );
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "が");
-            assert_eq!(token.byte_start, 42);
-            assert_eq!(token.byte_end, 45);
-            assert_eq!(token.position, 7);
+            assert_eq!(token.text, "进行");
+            assert_eq!(token.byte_start, 6);
+            assert_eq!(token.byte_end, 12);
+            assert_eq!(token.position, 1);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
-                vec!["助詞", "格助詞", "一般", "*", "*", "*", "が", "ガ", "ガ"]
+                vec![
+                    "*", 
+                    "*", 
+                    "*", 
+                    "*", 
+                    "jin4 xing2", 
+                    "進行", 
+                    "进行", 
+                    "to advance/to conduct/underway/in progress/to do/to carry out/to carry on/to execute/"
+                ]
             );
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "でき");
-            assert_eq!(token.byte_start, 45);
-            assert_eq!(token.byte_end, 51);
-            assert_eq!(token.position, 8);
+            assert_eq!(token.text, "中文");
+            assert_eq!(token.byte_start, 12);
+            assert_eq!(token.byte_end, 18);
+            assert_eq!(token.position, 2);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
                 vec![
-                    "動詞",
-                    "自立",
                     "*",
                     "*",
-                    "一段",
-                    "連用形",
-                    "できる",
-                    "デキ",
-                    "デキ"
+                    "*",
+                    "*",
+                    "Zhong1 wen2",
+                    "中文",
+                    "中文",
+                    "Chinese language/"
                 ]
             );
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "ます");
-            assert_eq!(token.byte_start, 51);
-            assert_eq!(token.byte_end, 57);
-            assert_eq!(token.position, 9);
+            assert_eq!(token.text, "形态学");
+            assert_eq!(token.byte_start, 18);
+            assert_eq!(token.byte_end, 27);
+            assert_eq!(token.position, 3);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
                 vec![
-                    "助動詞",
                     "*",
                     "*",
                     "*",
-                    "特殊・マス",
-                    "基本形",
-                    "ます",
-                    "マス",
-                    "マス"
+                    "*",
+                    "xing2 tai4 xue2",
+                    "形態學",
+                    "形态学",
+                    "morphology (in biology or linguistics)/"
                 ]
             );
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "。");
-            assert_eq!(token.byte_start, 57);
-            assert_eq!(token.byte_end, 60);
-            assert_eq!(token.position, 10);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec!["記号", "句点", "*", "*", "*", "*", "。", "。", "。"]
-            );
-        }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "テスト");
-            assert_eq!(token.byte_start, 60);
-            assert_eq!(token.byte_end, 69);
-            assert_eq!(token.position, 11);
+            assert_eq!(token.text, "分析");
+            assert_eq!(token.byte_start, 27);
+            assert_eq!(token.byte_end, 33);
+            assert_eq!(token.position, 4);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
                 vec![
-                    "名詞",
-                    "サ変接続",
                     "*",
                     "*",
                     "*",
                     "*",
-                    "テスト",
-                    "テスト",
-                    "テスト"
+                    "fen1 xi1",
+                    "分析",
+                    "分析",
+                    "to analyze/analysis/CL:個|个[ge4]/"
                 ]
             );
         }
         {
             let token = tokens_iter.next().unwrap();
             assert_eq!(token.text, "。");
-            assert_eq!(token.byte_start, 69);
-            assert_eq!(token.byte_end, 72);
-            assert_eq!(token.position, 12);
+            assert_eq!(token.byte_start, 33);
+            assert_eq!(token.byte_end, 36);
+            assert_eq!(token.position, 5);
             assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec!["記号", "句点", "*", "*", "*", "*", "。", "。", "。"]
-            );
+            assert_eq!(token.get_details().unwrap(), vec!["UNK"]);
         }
     }
 
     #[test]
-    #[cfg(feature = "unidic")]
-    fn test_tokenize_unidic() {
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_with_simple_userdic_ipadic() {
         let dictionary = DictionaryConfig {
-            kind: Some(DictionaryKind::UniDic),
+            kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
+        let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../resources")
+            .join("ipadic_simple_userdic.csv");
+
+        let user_dictionary = Some(UserDictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: userdic_file,
+        });
+
         let config = TokenizerConfig {
             dictionary,
-            user_dictionary: None,
+            user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
         let mut tokens = tokenizer
-            .tokenize("日本語の形態素解析を行うことができます。")
+            .tokenize("東京スカイツリーの最寄り駅はとうきょうスカイツリー駅です。")
             .unwrap();
         let mut tokens_iter = tokens.iter_mut();
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "日本");
+            assert_eq!(token.text, "東京スカイツリー");
             assert_eq!(token.byte_start, 0);
-            assert_eq!(token.byte_end, 6);
+            assert_eq!(token.byte_end, 24);
             assert_eq!(token.position, 0);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
                 vec![
-                    "名詞",
-                    "固有名詞",
-                    "地名",
-                    "国",
+                    "カスタム名詞",
                     "*",
                     "*",
-                    "ニッポン",
-                    "日本",
-                    "日本",
-                    "ニッポン",
-                    "日本",
-                    "ニッポン",
-                    "固",
                     "*",
                     "*",
                     "*",
+                    "東京スカイツリー",
+                    "トウキョウスカイツリー",
                     "*"
                 ]
             );
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "語");
-            assert_eq!(token.byte_start, 6);
-            assert_eq!(token.byte_end, 9);
+            assert_eq!(token.text, "の");
+            assert_eq!(token.byte_start, 24);
+            assert_eq!(token.byte_end, 27);
             assert_eq!(token.position, 1);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
-                vec![
-                    "名詞",
-                    "普通名詞",
-                    "一般",
-                    "*",
-                    "*",
-                    "*",
-                    "ゴ",
-                    "語",
-                    "語",
-                    "ゴ",
-                    "語",
-                    "ゴ",
-                    "漢",
-                    "*",
-                    "*",
-                    "*",
-                    "*"
-                ]
+                vec!["助詞", "連体化", "*", "*", "*", "*", "の", "ノ", "ノ"]
             );
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "の");
-            assert_eq!(token.byte_start, 9);
-            assert_eq!(token.byte_end, 12);
+            assert_eq!(token.text, "最寄り駅");
+            assert_eq!(token.byte_start, 27);
+            assert_eq!(token.byte_end, 39);
             assert_eq!(token.position, 2);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
                 vec![
-                    "助詞",
-                    "格助詞",
-                    "*",
-                    "*",
-                    "*",
+                    "名詞",
+                    "一般",
                     "*",
-                    "ノ",
-                    "の",
-                    "の",
-                    "ノ",
-                    "の",
-                    "ノ",
-                    "和",
                     "*",
                     "*",
                     "*",
-                    "*"
+                    "最寄り駅",
+                    "モヨリエキ",
+                    "モヨリエキ"
                 ]
             );
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "形態");
-            assert_eq!(token.byte_start, 12);
-            assert_eq!(token.byte_end, 18);
+            assert_eq!(token.text, "は");
+            assert_eq!(token.byte_start, 39);
+            assert_eq!(token.byte_end, 42);
             assert_eq!(token.position, 3);
             assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec!["助詞", "係助詞", "*", "*", "*", "*", "は", "ハ", "ワ"]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "とうきょうスカイツリー駅");
+            assert_eq!(token.byte_start, 42);
+            assert_eq!(token.byte_end, 78);
+            assert_eq!(token.position, 4);
+            assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
                 vec![
-                    "名詞",
-                    "普通名詞",
-                    "一般",
-                    "*",
+                    "カスタム名詞",
                     "*",
                     "*",
-                    "ケイタイ",
-                    "形態",
-                    "形態",
-                    "ケータイ",
-                    "形態",
-                    "ケータイ",
-                    "漢",
                     "*",
                     "*",
                     "*",
+                    "とうきょうスカイツリー駅",
+                    "トウキョウスカイツリーエキ",
                     "*"
                 ]
             );
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "素");
-            assert_eq!(token.byte_start, 18);
-            assert_eq!(token.byte_end, 21);
-            assert_eq!(token.position, 4);
+            assert_eq!(token.text, "です");
+            assert_eq!(token.byte_start, 78);
+            assert_eq!(token.byte_end, 84);
+            assert_eq!(token.position, 5);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
                 vec![
-                    "接尾辞",
-                    "名詞的",
-                    "一般",
-                    "*",
-                    "*",
-                    "*",
-                    "ソ",
-                    "素",
-                    "素",
-                    "ソ",
-                    "素",
-                    "ソ",
-                    "漢",
+                    "助動詞",
                     "*",
                     "*",
                     "*",
-                    "*"
+                    "特殊・デス",
+                    "基本形",
+                    "です",
+                    "デス",
+                    "デス"
                 ]
             );
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "解析");
-            assert_eq!(token.byte_start, 21);
-            assert_eq!(token.byte_end, 27);
-            assert_eq!(token.position, 5);
+            assert_eq!(token.text, "。");
+            assert_eq!(token.byte_start, 84);
+            assert_eq!(token.byte_end, 87);
+            assert_eq!(token.position, 6);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec!["記号", "句点", "*", "*", "*", "*", "。", "。", "。"]
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "unidic")]
+    fn test_tokenize_with_simple_userdic_unidic() {
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::UniDic),
+            path: None,
+            ..Default::default()
+        };
+
+        let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../resources")
+            .join("unidic_simple_userdic.csv");
+
+        let user_dictionary = Some(UserDictionaryConfig {
+            kind: Some(DictionaryKind::UniDic),
+            path: userdic_file,
+        });
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary,
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+
+        let tokenizer = Tokenizer::from_config(config).unwrap();
+        let mut tokens = tokenizer
+            .tokenize("東京スカイツリーの最寄り駅はとうきょうスカイツリー駅です。")
+            .unwrap();
+        let mut tokens_iter = tokens.iter_mut();
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "東京スカイツリー");
+            assert_eq!(token.byte_start, 0);
+            assert_eq!(token.byte_end, 24);
+            assert_eq!(token.position, 0);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
                 vec![
-                    "名詞",
-                    "普通名詞",
-                    "サ変可能",
+                    "カスタム名詞",
+                    "*",
+                    "*",
+                    "*",
+                    "*",
+                    "*",
+                    "トウキョウスカイツリー",
+                    "*",
+                    "*",
+                    "*",
                     "*",
                     "*",
                     "*",
-                    "カイセキ",
-                    "解析",
-                    "解析",
-                    "カイセキ",
-                    "解析",
-                    "カイセキ",
-                    "漢",
                     "*",
                     "*",
                     "*",
@@ -821,10 +3300,10 @@ mod tests {
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "を");
-            assert_eq!(token.byte_start, 27);
-            assert_eq!(token.byte_end, 30);
-            assert_eq!(token.position, 6);
+            assert_eq!(token.text, "の");
+            assert_eq!(token.byte_start, 24);
+            assert_eq!(token.byte_end, 27);
+            assert_eq!(token.position, 1);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
@@ -835,12 +3314,12 @@ mod tests {
                     "*",
                     "*",
                     "*",
-                    "ヲ",
-                    "を",
-                    "を",
-                    "オ",
-                    "を",
-                    "オ",
+                    "ノ",
+                    "の",
+                    "の",
+                    "ノ",
+                    "の",
+                    "ノ",
                     "和",
                     "*",
                     "*",
@@ -851,26 +3330,26 @@ mod tests {
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "行う");
-            assert_eq!(token.byte_start, 30);
+            assert_eq!(token.text, "最寄り");
+            assert_eq!(token.byte_start, 27);
             assert_eq!(token.byte_end, 36);
-            assert_eq!(token.position, 7);
+            assert_eq!(token.position, 2);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
                 vec![
-                    "動詞",
+                    "名詞",
+                    "普通名詞",
                     "一般",
                     "*",
                     "*",
-                    "五段-ワア行",
-                    "連体形-一般",
-                    "オコナウ",
-                    "行う",
-                    "行う",
-                    "オコナウ",
-                    "行う",
-                    "オコナウ",
+                    "*",
+                    "モヨリ",
+                    "最寄り",
+                    "最寄り",
+                    "モヨリ",
+                    "最寄り",
+                    "モヨリ",
                     "和",
                     "*",
                     "*",
@@ -881,10 +3360,10 @@ mod tests {
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "こと");
+            assert_eq!(token.text, "駅");
             assert_eq!(token.byte_start, 36);
-            assert_eq!(token.byte_end, 42);
-            assert_eq!(token.position, 8);
+            assert_eq!(token.byte_end, 39);
+            assert_eq!(token.position, 3);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
@@ -895,15 +3374,15 @@ mod tests {
                     "*",
                     "*",
                     "*",
-                    "コト",
-                    "事",
-                    "こと",
-                    "コト",
-                    "こと",
-                    "コト",
-                    "和",
-                    "コ濁",
-                    "基本形",
+                    "エキ",
+                    "駅",
+                    "駅",
+                    "エキ",
+                    "駅",
+                    "エキ",
+                    "漢",
+                    "*",
+                    "*",
                     "*",
                     "*"
                 ]
@@ -911,26 +3390,26 @@ mod tests {
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "が");
-            assert_eq!(token.byte_start, 42);
-            assert_eq!(token.byte_end, 45);
-            assert_eq!(token.position, 9);
+            assert_eq!(token.text, "は");
+            assert_eq!(token.byte_start, 39);
+            assert_eq!(token.byte_end, 42);
+            assert_eq!(token.position, 4);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
                 vec![
                     "助詞",
-                    "格助詞",
+                    "係助詞",
                     "*",
                     "*",
                     "*",
                     "*",
-                    "ガ",
-                    "が",
-                    "が",
-                    "ガ",
-                    "が",
-                    "ガ",
+                    "ハ",
+                    "は",
+                    "は",
+                    "ワ",
+                    "は",
+                    "ワ",
                     "和",
                     "*",
                     "*",
@@ -941,27 +3420,27 @@ mod tests {
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "でき");
-            assert_eq!(token.byte_start, 45);
-            assert_eq!(token.byte_end, 51);
-            assert_eq!(token.position, 10);
+            assert_eq!(token.text, "とうきょうスカイツリー駅");
+            assert_eq!(token.byte_start, 42);
+            assert_eq!(token.byte_end, 78);
+            assert_eq!(token.position, 5);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
                 vec![
-                    "動詞",
-                    "非自立可能",
+                    "カスタム名詞",
+                    "*",
+                    "*",
+                    "*",
+                    "*",
+                    "*",
+                    "トウキョウスカイツリーエキ",
+                    "*",
+                    "*",
+                    "*",
+                    "*",
                     "*",
                     "*",
-                    "上一段-カ行",
-                    "連用形-一般",
-                    "デキル",
-                    "出来る",
-                    "でき",
-                    "デキ",
-                    "できる",
-                    "デキル",
-                    "和",
                     "*",
                     "*",
                     "*",
@@ -971,10 +3450,10 @@ mod tests {
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "ます");
-            assert_eq!(token.byte_start, 51);
-            assert_eq!(token.byte_end, 57);
-            assert_eq!(token.position, 11);
+            assert_eq!(token.text, "です");
+            assert_eq!(token.byte_start, 78);
+            assert_eq!(token.byte_end, 84);
+            assert_eq!(token.position, 6);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
@@ -983,14 +3462,14 @@ mod tests {
                     "*",
                     "*",
                     "*",
-                    "助動詞-マス",
+                    "助動詞-デス",
                     "終止形-一般",
-                    "マス",
-                    "ます",
-                    "ます",
-                    "マス",
-                    "ます",
-                    "マス",
+                    "デス",
+                    "です",
+                    "です",
+                    "デス",
+                    "です",
+                    "デス",
                     "和",
                     "*",
                     "*",
@@ -1002,9 +3481,9 @@ mod tests {
         {
             let token = tokens_iter.next().unwrap();
             assert_eq!(token.text, "。");
-            assert_eq!(token.byte_start, 57);
-            assert_eq!(token.byte_end, 60);
-            assert_eq!(token.position, 12);
+            assert_eq!(token.byte_start, 84);
+            assert_eq!(token.byte_end, 87);
+            assert_eq!(token.position, 7);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
@@ -1017,183 +3496,99 @@ mod tests {
                     "*",
                     "",
                     "。",
-                    "。",
-                    "",
-                    "。",
-                    "",
-                    "記号",
-                    "*",
-                    "*",
-                    "*",
-                    "*"
-                ]
-            );
-        }
-    }
-
-    #[test]
-    #[cfg(feature = "ko-dic")]
-    fn test_tokenize_ko_dic() {
-        let dictionary = DictionaryConfig {
-            kind: Some(DictionaryKind::KoDic),
-            path: None,
-        };
-
-        let config = TokenizerConfig {
-            dictionary,
-            user_dictionary: None,
-            mode: Mode::Normal,
-        };
-
-        let tokenizer = Tokenizer::from_config(config).unwrap();
-        let mut tokens = tokenizer
-            .tokenize("한국어의형태해석을실시할수있습니다.")
-            .unwrap();
-        let mut tokens_iter = tokens.iter_mut();
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "한국어");
-            assert_eq!(token.byte_start, 0);
-            assert_eq!(token.byte_end, 9);
-            assert_eq!(token.position, 0);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec![
-                    "NNG",
-                    "*",
-                    "F",
-                    "한국어",
-                    "Compound",
-                    "*",
-                    "*",
-                    "한국/NNG/*+어/NNG/*"
-                ]
-            );
-        }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "의");
-            assert_eq!(token.byte_start, 9);
-            assert_eq!(token.byte_end, 12);
-            assert_eq!(token.position, 1);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec!["JKG", "*", "F", "의", "*", "*", "*", "*"]
-            );
-        }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "형태");
-            assert_eq!(token.byte_start, 12);
-            assert_eq!(token.byte_end, 18);
-            assert_eq!(token.position, 2);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec!["NNG", "*", "F", "형태", "*", "*", "*", "*"]
-            );
-        }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "해석");
-            assert_eq!(token.byte_start, 18);
-            assert_eq!(token.byte_end, 24);
-            assert_eq!(token.position, 3);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec!["NNG", "행위", "T", "해석", "*", "*", "*", "*"]
-            );
-        }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "을");
-            assert_eq!(token.byte_start, 24);
-            assert_eq!(token.byte_end, 27);
-            assert_eq!(token.position, 4);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec!["JKO", "*", "T", "을", "*", "*", "*", "*"]
-            );
-        }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "실시");
-            assert_eq!(token.byte_start, 27);
-            assert_eq!(token.byte_end, 33);
-            assert_eq!(token.position, 5);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec!["NNG", "행위", "F", "실시", "*", "*", "*", "*"]
-            );
-        }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "할");
-            assert_eq!(token.byte_start, 33);
-            assert_eq!(token.byte_end, 36);
-            assert_eq!(token.position, 6);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec![
-                    "XSV+ETM",
+                    "。",
+                    "",
+                    "。",
+                    "",
+                    "記号",
                     "*",
-                    "T",
-                    "할",
-                    "Inflect",
-                    "XSV",
-                    "ETM",
-                    "하/XSV/*+ᆯ/ETM/*"
+                    "*",
+                    "*",
+                    "*"
                 ]
             );
         }
+    }
+
+    #[test]
+    #[cfg(feature = "ko-dic")]
+    fn test_tokenize_with_simple_userdic_ko_dic() {
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::KoDic),
+            path: None,
+            ..Default::default()
+        };
+
+        let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../resources")
+            .join("ko-dic_simple_userdic.csv");
+
+        let user_dictionary = Some(UserDictionaryConfig {
+            kind: Some(DictionaryKind::KoDic),
+            path: userdic_file,
+        });
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary,
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+
+        let tokenizer = Tokenizer::from_config(config).unwrap();
+        let mut tokens = tokenizer.tokenize("하네다공항한정토트백.").unwrap();
+        let mut tokens_iter = tokens.iter_mut();
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "수");
-            assert_eq!(token.byte_start, 36);
-            assert_eq!(token.byte_end, 39);
-            assert_eq!(token.position, 7);
+            assert_eq!(token.text, "하네다공항");
+            assert_eq!(token.byte_start, 0);
+            assert_eq!(token.byte_end, 15);
+            assert_eq!(token.position, 0);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
-                vec!["NNB", "*", "F", "수", "*", "*", "*", "*"]
+                vec!["NNP", "*", "*", "하네다공항", "*", "*", "*", "*"]
             );
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "있");
-            assert_eq!(token.byte_start, 39);
-            assert_eq!(token.byte_end, 42);
-            assert_eq!(token.position, 8);
+            assert_eq!(token.text, "한정");
+            assert_eq!(token.byte_start, 15);
+            assert_eq!(token.byte_end, 21);
+            assert_eq!(token.position, 1);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
-                vec!["VV", "*", "T", "있", "*", "*", "*", "*"]
+                vec!["NNG", "*", "T", "한정", "*", "*", "*", "*"]
             );
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "습니다");
-            assert_eq!(token.byte_start, 42);
-            assert_eq!(token.byte_end, 51);
-            assert_eq!(token.position, 9);
+            assert_eq!(token.text, "토트백");
+            assert_eq!(token.byte_start, 21);
+            assert_eq!(token.byte_end, 30);
+            assert_eq!(token.position, 2);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
-                vec!["EF", "*", "F", "습니다", "*", "*", "*", "*"]
+                vec![
+                    "NNG",
+                    "*",
+                    "T",
+                    "토트백",
+                    "Compound",
+                    "*",
+                    "*",
+                    "토트/NNP/인명+백/NNG/*"
+                ]
             );
         }
         {
             let token = tokens_iter.next().unwrap();
             assert_eq!(token.text, ".");
-            assert_eq!(token.byte_start, 51);
-            assert_eq!(token.byte_end, 52);
-            assert_eq!(token.position, 10);
+            assert_eq!(token.byte_start, 30);
+            assert_eq!(token.byte_end, 31);
+            assert_eq!(token.position, 3);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
@@ -1204,69 +3599,50 @@ mod tests {
 
     #[test]
     #[cfg(feature = "cc-cedict")]
-    fn test_tokenize_cc_cedict() {
+    fn test_tokenize_with_simple_userdic_cc_cedict() {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::CcCedict),
             path: None,
+            ..Default::default()
         };
 
+        let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../resources")
+            .join("cc-cedict_simple_userdic.csv");
+
+        let user_dictionary = Some(UserDictionaryConfig {
+            kind: Some(DictionaryKind::CcCedict),
+            path: userdic_file,
+        });
+
         let config = TokenizerConfig {
             dictionary,
-            user_dictionary: None,
+            user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
-        let mut tokens = tokenizer.tokenize("可以进行中文形态学分析。").unwrap();
+        let mut tokens = tokenizer.tokenize("羽田机场限定托特包。").unwrap();
         let mut tokens_iter = tokens.iter_mut();
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "可以");
+            assert_eq!(token.text, "羽田机场");
             assert_eq!(token.byte_start, 0);
-            assert_eq!(token.byte_end, 6);
-            assert_eq!(token.position, 0);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec![
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "ke3 yi3",
-                    "可以",
-                    "可以",
-                    "can/may/possible/able to/not bad/pretty good/"
-                ]
-            );
-        }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "进行");
-            assert_eq!(token.byte_start, 6);
             assert_eq!(token.byte_end, 12);
-            assert_eq!(token.position, 1);
+            assert_eq!(token.position, 0);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
-                vec![
-                    "*", 
-                    "*", 
-                    "*", 
-                    "*", 
-                    "jin4 xing2", 
-                    "進行", 
-                    "进行", 
-                    "to advance/to conduct/underway/in progress/to do/to carry out/to carry on/to execute/"
-                ]
+                vec!["*", "*", "*", "*", "Yu3 tian2 ji1 chang3", "*", "*", "*"]
             );
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "中文");
+            assert_eq!(token.text, "限定");
             assert_eq!(token.byte_start, 12);
             assert_eq!(token.byte_end, 18);
-            assert_eq!(token.position, 2);
+            assert_eq!(token.position, 1);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
@@ -1275,19 +3651,19 @@ mod tests {
                     "*",
                     "*",
                     "*",
-                    "Zhong1 wen2",
-                    "中文",
-                    "中文",
-                    "Chinese language/"
+                    "xian4 ding4",
+                    "限定",
+                    "限定",
+                    "to restrict to/to limit/"
                 ]
             );
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "形态学");
+            assert_eq!(token.text, "托特");
             assert_eq!(token.byte_start, 18);
-            assert_eq!(token.byte_end, 27);
-            assert_eq!(token.position, 3);
+            assert_eq!(token.byte_end, 24);
+            assert_eq!(token.position, 2);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
@@ -1296,40 +3672,41 @@ mod tests {
                     "*",
                     "*",
                     "*",
-                    "xing2 tai4 xue2",
-                    "形態學",
-                    "形态学",
-                    "morphology (in biology or linguistics)/"
+                    "tuo1 te4",
+                    "托特",
+                    "托特",
+                    "(loanword) tote (bag)/"
                 ]
             );
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "分析");
-            assert_eq!(token.byte_start, 27);
-            assert_eq!(token.byte_end, 33);
-            assert_eq!(token.position, 4);
+            assert_eq!(token.text, "包");
+            assert_eq!(token.byte_start, 24);
+            assert_eq!(token.byte_end, 27);
+            assert_eq!(token.position, 3);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
                 vec![
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "fen1 xi1",
-                    "分析",
-                    "分析",
-                    "to analyze/analysis/CL:個|个[ge4]/"
+                    "*", 
+                    "*", 
+                    "*", 
+                    "*", 
+                    "bao1", 
+                    "包", 
+                    "包", 
+                    "to cover/to wrap/to hold/to include/to take charge of/to contract (to or for)/package/wrapper/container/bag/to hold or embrace/bundle/packet/CL:個|个[ge4]", 
+                    "隻|只[zhi1]/"
                 ]
             );
         }
         {
             let token = tokens_iter.next().unwrap();
             assert_eq!(token.text, "。");
-            assert_eq!(token.byte_start, 33);
-            assert_eq!(token.byte_end, 36);
-            assert_eq!(token.position, 5);
+            assert_eq!(token.byte_start, 27);
+            assert_eq!(token.byte_end, 30);
+            assert_eq!(token.position, 4);
             assert_eq!(token.position_length, 1);
             assert_eq!(token.get_details().unwrap(), vec!["UNK"]);
         }
@@ -1337,15 +3714,16 @@ mod tests {
 
     #[test]
     #[cfg(feature = "ipadic")]
-    fn test_tokenize_with_simple_userdic_ipadic() {
+    fn test_tokenize_with_simple_userdic_bin_ipadic() {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
             .join("../resources")
-            .join("ipadic_simple_userdic.csv");
+            .join("ipadic_simple_userdic.bin");
 
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
@@ -1356,6 +3734,7 @@ mod tests {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -1491,15 +3870,16 @@ mod tests {
 
     #[test]
     #[cfg(feature = "unidic")]
-    fn test_tokenize_with_simple_userdic_unidic() {
+    fn test_tokenize_with_simple_userdic_bin_unidic() {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::UniDic),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
             .join("../resources")
-            .join("unidic_simple_userdic.csv");
+            .join("unidic_simple_userdic.bin");
 
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::UniDic),
@@ -1510,6 +3890,7 @@ mod tests {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -1761,15 +4142,16 @@ mod tests {
 
     #[test]
     #[cfg(feature = "ko-dic")]
-    fn test_tokenize_with_simple_userdic_ko_dic() {
+    fn test_tokenize_with_simple_userdic_bin_ko_dic() {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::KoDic),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
             .join("../resources")
-            .join("ko-dic_simple_userdic.csv");
+            .join("ko-dic_simple_userdic.bin");
 
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::KoDic),
@@ -1780,6 +4162,7 @@ mod tests {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -1846,15 +4229,16 @@ mod tests {
 
     #[test]
     #[cfg(feature = "cc-cedict")]
-    fn test_tokenize_with_simple_userdic_cc_cedict() {
+    fn test_tokenize_with_simple_userdic_bin_cc_cedict() {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::CcCedict),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
             .join("../resources")
-            .join("cc-cedict_simple_userdic.csv");
+            .join("cc-cedict_simple_userdic.bin");
 
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::CcCedict),
@@ -1865,6 +4249,7 @@ mod tests {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -1959,15 +4344,16 @@ mod tests {
 
     #[test]
     #[cfg(feature = "ipadic")]
-    fn test_tokenize_with_simple_userdic_bin_ipadic() {
+    fn test_tokenize_with_detailed_userdic_ipadic() {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
             .join("../resources")
-            .join("ipadic_simple_userdic.bin");
+            .join("ipadic_detailed_userdic.csv");
 
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
@@ -1978,6 +4364,7 @@ mod tests {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -1995,15 +4382,15 @@ mod tests {
             assert_eq!(
                 token.get_details().unwrap(),
                 vec![
+                    "名詞",
+                    "固有名詞",
+                    "一般",
                     "カスタム名詞",
                     "*",
                     "*",
-                    "*",
-                    "*",
-                    "*",
                     "東京スカイツリー",
                     "トウキョウスカイツリー",
-                    "*"
+                    "トウキョウスカイツリー"
                 ]
             );
         }
@@ -2063,15 +4450,15 @@ mod tests {
             assert_eq!(
                 token.get_details().unwrap(),
                 vec![
+                    "名詞",
+                    "固有名詞",
+                    "一般",
                     "カスタム名詞",
                     "*",
                     "*",
-                    "*",
-                    "*",
-                    "*",
                     "とうきょうスカイツリー駅",
                     "トウキョウスカイツリーエキ",
-                    "*"
+                    "トウキョウスカイツリーエキ"
                 ]
             );
         }
@@ -2112,19 +4499,20 @@ mod tests {
     }
 
     #[test]
-    #[cfg(feature = "unidic")]
-    fn test_tokenize_with_simple_userdic_bin_unidic() {
+    #[cfg(feature = "ipadic")]
+    fn test_mixed_user_dict() {
         let dictionary = DictionaryConfig {
-            kind: Some(DictionaryKind::UniDic),
+            kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
             .join("../resources")
-            .join("unidic_simple_userdic.bin");
+            .join("ipadic_mixed_userdic.csv");
 
         let user_dictionary = Some(UserDictionaryConfig {
-            kind: Some(DictionaryKind::UniDic),
+            kind: Some(DictionaryKind::IPADIC),
             path: userdic_file,
         });
 
@@ -2132,6 +4520,7 @@ mod tests {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -2148,24 +4537,16 @@ mod tests {
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
-                vec![
-                    "カスタム名詞",
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "トウキョウスカイツリー",
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "*",
+                vec![
+                    "名詞",
+                    "固有名詞",
+                    "一般",
+                    "カスタム名詞",
                     "*",
                     "*",
-                    "*"
+                    "東京スカイツリー",
+                    "トウキョウスカイツリー",
+                    "トウキョウスカイツリー"
                 ]
             );
         }
@@ -2178,84 +4559,28 @@ mod tests {
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
-                vec![
-                    "助詞",
-                    "格助詞",
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "ノ",
-                    "の",
-                    "の",
-                    "ノ",
-                    "の",
-                    "ノ",
-                    "和",
-                    "*",
-                    "*",
-                    "*",
-                    "*"
-                ]
+                vec!["助詞", "連体化", "*", "*", "*", "*", "の", "ノ", "ノ"]
             );
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "最寄り");
+            assert_eq!(token.text, "最寄り駅");
             assert_eq!(token.byte_start, 27);
-            assert_eq!(token.byte_end, 36);
-            assert_eq!(token.position, 2);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec![
-                    "名詞",
-                    "普通名詞",
-                    "一般",
-                    "*",
-                    "*",
-                    "*",
-                    "モヨリ",
-                    "最寄り",
-                    "最寄り",
-                    "モヨリ",
-                    "最寄り",
-                    "モヨリ",
-                    "和",
-                    "*",
-                    "*",
-                    "*",
-                    "*"
-                ]
-            );
-        }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "駅");
-            assert_eq!(token.byte_start, 36);
             assert_eq!(token.byte_end, 39);
-            assert_eq!(token.position, 3);
+            assert_eq!(token.position, 2);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
                 vec![
                     "名詞",
-                    "普通名詞",
                     "一般",
                     "*",
                     "*",
                     "*",
-                    "エキ",
-                    "駅",
-                    "駅",
-                    "エキ",
-                    "駅",
-                    "エキ",
-                    "漢",
-                    "*",
                     "*",
-                    "*",
-                    "*"
+                    "最寄り駅",
+                    "モヨリエキ",
+                    "モヨリエキ"
                 ]
             );
         }
@@ -2264,29 +4589,11 @@ mod tests {
             assert_eq!(token.text, "は");
             assert_eq!(token.byte_start, 39);
             assert_eq!(token.byte_end, 42);
-            assert_eq!(token.position, 4);
+            assert_eq!(token.position, 3);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
-                vec![
-                    "助詞",
-                    "係助詞",
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "ハ",
-                    "は",
-                    "は",
-                    "ワ",
-                    "は",
-                    "ワ",
-                    "和",
-                    "*",
-                    "*",
-                    "*",
-                    "*"
-                ]
+                vec!["助詞", "係助詞", "*", "*", "*", "*", "は", "ハ", "ワ"]
             );
         }
         {
@@ -2294,7 +4601,7 @@ mod tests {
             assert_eq!(token.text, "とうきょうスカイツリー駅");
             assert_eq!(token.byte_start, 42);
             assert_eq!(token.byte_end, 78);
-            assert_eq!(token.position, 5);
+            assert_eq!(token.position, 4);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
@@ -2305,16 +4612,8 @@ mod tests {
                     "*",
                     "*",
                     "*",
+                    "とうきょうスカイツリー駅",
                     "トウキョウスカイツリーエキ",
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "*",
                     "*"
                 ]
             );
@@ -2324,7 +4623,7 @@ mod tests {
             assert_eq!(token.text, "です");
             assert_eq!(token.byte_start, 78);
             assert_eq!(token.byte_end, 84);
-            assert_eq!(token.position, 6);
+            assert_eq!(token.position, 5);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
@@ -2333,19 +4632,11 @@ mod tests {
                     "*",
                     "*",
                     "*",
-                    "助動詞-デス",
-                    "終止形-一般",
-                    "デス",
-                    "です",
-                    "です",
-                    "デス",
+                    "特殊・デス",
+                    "基本形",
                     "です",
                     "デス",
-                    "和",
-                    "*",
-                    "*",
-                    "*",
-                    "*"
+                    "デス"
                 ]
             );
         }
@@ -2354,787 +4645,1626 @@ mod tests {
             assert_eq!(token.text, "。");
             assert_eq!(token.byte_start, 84);
             assert_eq!(token.byte_end, 87);
-            assert_eq!(token.position, 7);
+            assert_eq!(token.position, 6);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec!["記号", "句点", "*", "*", "*", "*", "。", "。", "。"]
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    #[should_panic(expected = "failed to parse word cost")]
+    fn test_user_dict_invalid_word_cost() {
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        };
+
+        let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../resources")
+            .join("ipadic_userdic_invalid_word_cost.csv");
+
+        let user_dictionary = Some(UserDictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: userdic_file,
+        });
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary,
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+
+        Tokenizer::from_config(config).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    #[should_panic(expected = "user dictionary should be a CSV with 3 or 13+ fields")]
+    fn test_user_dict_number_of_fields_is_11() {
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        };
+
+        let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../resources")
+            .join("ipadic_userdic_insufficient_number_of_fields.csv");
+
+        let user_dictionary = Some(UserDictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: userdic_file,
+        });
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary,
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+
+        Tokenizer::from_config(config).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_with_nomal_mode() {
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        };
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary: None,
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+
+        let tokenizer = Tokenizer::from_config(config).unwrap();
+        let mut tokens = tokenizer.tokenize("羽田空港限定トートバッグ").unwrap();
+        let mut tokens_iter = tokens.iter_mut();
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "羽田空港");
+            assert_eq!(token.byte_start, 0);
+            assert_eq!(token.byte_end, 12);
+            assert_eq!(token.position, 0);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
                 vec![
-                    "補助記号",
-                    "句点",
+                    "名詞",
+                    "固有名詞",
+                    "一般",
                     "*",
                     "*",
                     "*",
+                    "羽田空港",
+                    "ハネダクウコウ",
+                    "ハネダクーコー"
+                ]
+            );
+        }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "限定");
+            assert_eq!(token.byte_start, 12);
+            assert_eq!(token.byte_end, 18);
+            assert_eq!(token.position, 1);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec![
+                    "名詞",
+                    "サ変接続",
                     "*",
-                    "",
-                    "。",
-                    "。",
-                    "",
-                    "。",
-                    "",
-                    "記号",
                     "*",
                     "*",
                     "*",
-                    "*"
+                    "限定",
+                    "ゲンテイ",
+                    "ゲンテイ"
                 ]
             );
         }
+        {
+            let token = tokens_iter.next().unwrap();
+            assert_eq!(token.text, "トートバッグ");
+            assert_eq!(token.byte_start, 18);
+            assert_eq!(token.byte_end, 36);
+            assert_eq!(token.position, 2);
+            assert_eq!(token.position_length, 1);
+            assert_eq!(token.get_details().unwrap(), vec!["UNK"]);
+        }
     }
 
     #[test]
-    #[cfg(feature = "ko-dic")]
-    fn test_tokenize_with_simple_userdic_bin_ko_dic() {
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_with_decompose_mode() {
         let dictionary = DictionaryConfig {
-            kind: Some(DictionaryKind::KoDic),
+            kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
-        let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("../resources")
-            .join("ko-dic_simple_userdic.bin");
-
-        let user_dictionary = Some(UserDictionaryConfig {
-            kind: Some(DictionaryKind::KoDic),
-            path: userdic_file,
-        });
-
         let config = TokenizerConfig {
             dictionary,
-            user_dictionary,
-            mode: Mode::Normal,
+            user_dictionary: None,
+            mode: Mode::Decompose(Penalty::default()),
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
-        let mut tokens = tokenizer.tokenize("하네다공항한정토트백.").unwrap();
+        let mut tokens = tokenizer.tokenize("羽田空港限定トートバッグ").unwrap();
         let mut tokens_iter = tokens.iter_mut();
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "하네다공항");
+            assert_eq!(token.text, "羽田");
             assert_eq!(token.byte_start, 0);
-            assert_eq!(token.byte_end, 15);
+            assert_eq!(token.byte_end, 6);
             assert_eq!(token.position, 0);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
-                vec!["NNP", "*", "*", "하네다공항", "*", "*", "*", "*"]
+                vec![
+                    "名詞",
+                    "固有名詞",
+                    "人名",
+                    "姓",
+                    "*",
+                    "*",
+                    "羽田",
+                    "ハタ",
+                    "ハタ"
+                ]
             );
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "한정");
-            assert_eq!(token.byte_start, 15);
-            assert_eq!(token.byte_end, 21);
+            assert_eq!(token.text, "空港");
+            assert_eq!(token.byte_start, 6);
+            assert_eq!(token.byte_end, 12);
             assert_eq!(token.position, 1);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
-                vec!["NNG", "*", "T", "한정", "*", "*", "*", "*"]
+                vec![
+                    "名詞",
+                    "一般",
+                    "*",
+                    "*",
+                    "*",
+                    "*",
+                    "空港",
+                    "クウコウ",
+                    "クーコー"
+                ]
             );
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "토트백");
-            assert_eq!(token.byte_start, 21);
-            assert_eq!(token.byte_end, 30);
+            assert_eq!(token.text, "限定");
+            assert_eq!(token.byte_start, 12);
+            assert_eq!(token.byte_end, 18);
             assert_eq!(token.position, 2);
             assert_eq!(token.position_length, 1);
             assert_eq!(
                 token.get_details().unwrap(),
                 vec![
-                    "NNG",
+                    "名詞",
+                    "サ変接続",
                     "*",
-                    "T",
-                    "토트백",
-                    "Compound",
                     "*",
                     "*",
-                    "토트/NNP/인명+백/NNG/*"
+                    "*",
+                    "限定",
+                    "ゲンテイ",
+                    "ゲンテイ"
                 ]
             );
         }
         {
             let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, ".");
-            assert_eq!(token.byte_start, 30);
-            assert_eq!(token.byte_end, 31);
+            assert_eq!(token.text, "トートバッグ");
+            assert_eq!(token.byte_start, 18);
+            assert_eq!(token.byte_end, 36);
             assert_eq!(token.position, 3);
             assert_eq!(token.position_length, 1);
+            assert_eq!(token.get_details().unwrap(), vec!["UNK"]);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_sentences_ipadic() {
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        };
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary: None,
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+
+        let tokenizer = Tokenizer::from_config(config).unwrap();
+        let text = "東京へ行きます。京都は遠いですか？\n大阪、神戸";
+        let sentences = tokenizer.tokenize_sentences(text).unwrap();
+
+        assert_eq!(
+            sentences
+                .iter()
+                .map(|(start, end, _)| &text[*start..*end])
+                .collect::<Vec<_>>(),
+            vec!["東京へ行きます。", "京都は遠いですか？", "\n", "大阪、神戸"]
+        );
+
+        // the tokens are the same as those of `tokenize`, grouped by sentence
+        let tokens = tokenizer.tokenize(text).unwrap();
+        let sentence_tokens = sentences
+            .iter()
+            .flat_map(|(_, _, tokens)| tokens.iter())
+            .collect::<Vec<_>>();
+        assert_eq!(sentence_tokens.len(), tokens.len());
+        for (a, b) in sentence_tokens.iter().zip(tokens.iter()) {
+            assert_eq!(a.text, b.text);
+            assert_eq!(a.byte_start, b.byte_start);
+            assert_eq!(a.byte_end, b.byte_end);
+            assert_eq!(a.position, b.position);
+        }
+
+        for (start, end, tokens) in sentences.iter() {
+            assert_eq!(tokens.first().unwrap().byte_start, *start);
+            assert_eq!(tokens.last().unwrap().byte_end, *end);
+        }
+
+        // text without terminating punctuation is a single sentence
+        let sentences = tokenizer.tokenize_sentences("東京へ行きます").unwrap();
+        assert_eq!(sentences.len(), 1);
+        assert_eq!(sentences[0].0, 0);
+        assert_eq!(sentences[0].1, "東京へ行きます".len());
+
+        // the normalization and the BOS and EOS tokens are applied as in `tokenize`
+        let mut tokenizer = tokenizer;
+        tokenizer.normalize = Some(NormalizationForm::NFKC);
+        tokenizer.include_bos_eos = true;
+        let text = "ﾄｳｷｮｳへ行きます。京都";
+        let mut sentences = tokenizer.tokenize_sentences(text).unwrap();
+        assert_eq!(sentences.len(), 2);
+
+        let tokens = tokenizer.tokenize(text).unwrap();
+        let sentence_tokens = sentences
+            .iter()
+            .flat_map(|(_, _, tokens)| tokens.iter())
+            .collect::<Vec<_>>();
+        assert_eq!(sentence_tokens.len(), tokens.len());
+        for (a, b) in sentence_tokens.iter().zip(tokens.iter()) {
+            assert_eq!(a.text, b.text);
+            assert_eq!(a.byte_start, b.byte_start);
+            assert_eq!(a.byte_end, b.byte_end);
+            assert_eq!(a.position, b.position);
+        }
+        // the text of the tokens is that of the original text
+        assert_eq!(sentences[0].2[1].byte_start, 0);
+        assert!(sentences[0].2[1].text.starts_with('ﾄ'));
+
+        let first = sentences[0].2.first_mut().unwrap();
+        assert_eq!(first.get_details().unwrap(), vec!["BOS"]);
+        let last = sentences[1].2.last_mut().unwrap();
+        assert_eq!(last.get_details().unwrap(), vec!["EOS"]);
+        assert_eq!(last.byte_start, text.len());
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_sentences_custom_terminators_ipadic() {
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        };
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary: None,
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+
+        let mut tokenizer = Tokenizer::from_config(config).unwrap();
+        tokenizer.sentence_splitter = SentenceSplitter::builder()
+            .terminators(&['。'])
+            .terminator('!')
+            .terminator('?')
+            .split_on_newline(false)
+            .build();
+
+        let text = "東京へ行きます。Hello!京都は?\n大阪";
+        let sentences = tokenizer.tokenize_sentences(text).unwrap();
+
+        assert_eq!(
+            sentences
+                .iter()
+                .map(|(start, end, _)| &text[*start..*end])
+                .collect::<Vec<_>>(),
+            vec!["東京へ行きます。", "Hello!", "京都は?", "\n大阪"]
+        );
+        for (start, end, tokens) in sentences.iter() {
+            assert_eq!(tokens.first().unwrap().byte_start, *start);
+            assert_eq!(tokens.last().unwrap().byte_end, *end);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_n_best_ipadic() {
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        };
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary: None,
+            mode: Mode::Normal,
+            debug_costs: true,
+            ..Default::default()
+        };
+
+        let tokenizer = Tokenizer::from_config(config).unwrap();
+        let text = "すもももももももものうち、東京都に住む";
+
+        // the best segmentation is the same as the tokens of `tokenize`
+        let tokens = tokenizer.tokenize(text).unwrap();
+        let n_best = tokenizer.n_best(text, 1).unwrap();
+        assert_eq!(n_best.len(), 1);
+        assert_eq!(n_best[0].len(), tokens.len());
+        for (a, b) in n_best[0].iter().zip(tokens.iter()) {
+            assert_eq!(a.text, b.text);
+            assert_eq!(a.byte_start, b.byte_start);
+            assert_eq!(a.byte_end, b.byte_end);
+            assert_eq!(a.position, b.position);
+            assert_eq!(a.word_id, b.word_id);
+            assert_eq!(a.word_cost, b.word_cost);
+            assert_eq!(a.connection_cost, b.connection_cost);
+        }
+
+        // the segmentations are distinct and cover the whole text
+        let n_best = tokenizer.n_best(text, 5).unwrap();
+        assert_eq!(n_best.len(), 5);
+        let paths = n_best
+            .iter()
+            .map(|tokens| {
+                tokens
+                    .iter()
+                    .map(|t| (t.byte_start, t.word_id))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        for (i, tokens) in n_best.iter().enumerate() {
             assert_eq!(
-                token.get_details().unwrap(),
-                vec!["SF", "*", "*", "*", "*", "*", "*", "*"]
+                tokens.iter().map(|t| t.text.as_ref()).collect::<String>(),
+                text
+            );
+            assert!(!paths[i + 1..].contains(&paths[i]));
+        }
+        assert_eq!(n_best[0].len(), tokens.len());
+
+        assert!(tokenizer.n_best(text, 0).unwrap().is_empty());
+
+        // the best segmentation is still the same as the tokens of `tokenize` when the text is
+        // normalized, the whitespace is preserved and the BOS and EOS tokens are emitted
+        let config = TokenizerConfig {
+            dictionary: DictionaryConfig {
+                kind: Some(DictionaryKind::IPADIC),
+                path: None,
+                ..Default::default()
+            },
+            user_dictionary: None,
+            mode: Mode::Normal,
+            normalize: Some(NormalizationForm::NFKC),
+            debug_costs: true,
+            preserve_whitespace: true,
+            include_bos_eos: true,
+            ..Default::default()
+        };
+        let tokenizer = Tokenizer::from_config(config).unwrap();
+        let text = "ﾄｰﾄﾊﾞｯｸﾞ  すもももももももものうち、東京都に住む";
+
+        let mut tokens = tokenizer.tokenize(text).unwrap();
+        let mut n_best = tokenizer.n_best(text, 3).unwrap();
+        assert_eq!(n_best.len(), 3);
+        assert_eq!(n_best[0].len(), tokens.len());
+        for (a, b) in n_best[0].iter_mut().zip(tokens.iter_mut()) {
+            assert_eq!(a.text, b.text);
+            assert_eq!(a.byte_start, b.byte_start);
+            assert_eq!(a.byte_end, b.byte_end);
+            assert_eq!(a.position, b.position);
+            assert_eq!(a.word_id, b.word_id);
+            assert_eq!(a.word_cost, b.word_cost);
+            assert_eq!(a.connection_cost, b.connection_cost);
+            assert_eq!(a.get_details(), b.get_details());
+        }
+        assert_eq!(
+            tokens.first_mut().unwrap().get_details().unwrap(),
+            vec!["BOS"]
+        );
+        assert_eq!(
+            tokens.last_mut().unwrap().get_details().unwrap(),
+            vec!["EOS"]
+        );
+
+        // every segmentation has the BOS and EOS tokens, and covers the original text
+        for tokens in n_best.iter() {
+            assert_eq!(tokens.first().unwrap().text, "");
+            assert_eq!(tokens.last().unwrap().byte_start, text.len());
+            assert_eq!(
+                tokens.iter().map(|t| t.text.as_ref()).collect::<String>(),
+                text
             );
         }
     }
 
     #[test]
-    #[cfg(feature = "cc-cedict")]
-    fn test_tokenize_with_simple_userdic_bin_cc_cedict() {
+    #[cfg(feature = "ipadic")]
+    fn test_lattice_ipadic() {
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        };
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary: None,
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+
+        let tokenizer = Tokenizer::from_config(config).unwrap();
+        let text = "東京都に住む";
+        let lattice = tokenizer.lattice(text);
+
+        // both "東京" and "東京都" are candidates at the beginning of the text
+        let surfaces = lattice
+            .edges_starting_at(0)
+            .iter()
+            .map(|&edge_id| {
+                let edge = lattice.edge(edge_id);
+                &text[edge.start_index as usize..edge.stop_index as usize]
+            })
+            .collect::<Vec<_>>();
+        assert!(surfaces.contains(&"東京"));
+        assert!(surfaces.contains(&"東京都"));
+
+        // the best path of the lattice is the segmentation of `tokenize`
+        let offsets = lattice.path_offsets(&lattice.best_path());
+        let tokens = tokenizer.tokenize(text).unwrap();
+        assert_eq!(
+            offsets,
+            tokens
+                .iter()
+                .map(|t| (t.byte_start, t.word_id))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_normal_and_decompose_mode() {
+        let text = "関西国際空港限定トートバッグ";
+
+        let normal = Tokenizer::from_config(TokenizerConfig {
+            dictionary: DictionaryConfig {
+                kind: Some(DictionaryKind::IPADIC),
+                path: None,
+                ..Default::default()
+            },
+            user_dictionary: None,
+            mode: Mode::Normal,
+            ..Default::default()
+        })
+        .unwrap();
+        let tokens = normal.tokenize(text).unwrap();
+        assert_eq!(
+            tokens.iter().map(|t| t.text.as_ref()).collect::<Vec<_>>(),
+            vec!["関西国際空港", "限定", "トートバッグ"]
+        );
+
+        let decompose = Tokenizer::from_config(TokenizerConfig {
+            dictionary: DictionaryConfig {
+                kind: Some(DictionaryKind::IPADIC),
+                path: None,
+                ..Default::default()
+            },
+            user_dictionary: None,
+            mode: Mode::Decompose(Penalty::default()),
+            ..Default::default()
+        })
+        .unwrap();
+        let tokens = decompose.tokenize(text).unwrap();
+        assert_eq!(
+            tokens.iter().map(|t| t.text.as_ref()).collect::<Vec<_>>(),
+            vec!["関西", "国際", "空港", "限定", "トートバッグ"]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_with_cost_model_ipadic() {
+        use lindera_core::connection::ConnectionCostMatrix;
+        use lindera_core::cost_model::CostModel;
+        use lindera_core::viterbi::Edge;
+
+        // A cost model preferring the segmentations with the fewest, thus longest, tokens.
+        #[derive(Clone)]
+        struct FewestTokensCostModel;
+
+        impl CostModel for FewestTokensCostModel {
+            fn word_cost(&self, _edge: &Edge) -> i32 {
+                1
+            }
+
+            fn connection_cost(
+                &self,
+                _cost_matrix: &ConnectionCostMatrix,
+                _left_edge: &Edge,
+                _right_edge: &Edge,
+            ) -> i32 {
+                0
+            }
+        }
+
+        let config = TokenizerConfig {
+            dictionary: DictionaryConfig {
+                kind: Some(DictionaryKind::IPADIC),
+                path: None,
+                ..Default::default()
+            },
+            user_dictionary: None,
+            mode: Mode::Normal,
+            debug_costs: true,
+            ..Default::default()
+        };
+        let text = "すもももももももものうち";
+
+        let tokenizer = Tokenizer::from_config(config.clone()).unwrap();
+        let tokens = tokenizer.tokenize(text).unwrap();
+        let texts = tokens.iter().map(|t| t.text.as_ref()).collect::<Vec<_>>();
+        assert_eq!(
+            texts,
+            vec!["すもも", "も", "もも", "も", "もも", "の", "うち"]
+        );
+
+        let tokenizer = Tokenizer::from_config(config)
+            .unwrap()
+            .with_cost_model(Box::new(FewestTokensCostModel));
+        let custom_tokens = tokenizer.tokenize(text).unwrap();
+        let custom_texts = custom_tokens
+            .iter()
+            .map(|t| t.text.as_ref())
+            .collect::<Vec<_>>();
+
+        // the segmentation changes to longer tokens covering the same text
+        assert_ne!(custom_texts, texts);
+        assert!(custom_texts.len() < texts.len());
+        assert_eq!(custom_texts.concat(), text);
+
+        // the costs of the tokens are those of the cost model
+        for token in custom_tokens.iter() {
+            assert_eq!(token.word_cost, Some(1));
+            assert_eq!(token.connection_cost, Some(0));
+        }
+
+        // the cost model is kept by a clone of the tokenizer
+        let cloned_tokenizer = tokenizer.clone();
+        assert_eq!(
+            cloned_tokenizer
+                .tokenize(text)
+                .unwrap()
+                .iter()
+                .map(|t| t.text.as_ref())
+                .collect::<Vec<_>>(),
+            custom_texts
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_long_text() {
+        let mut large_file = BufReader::new(
+            File::open(
+                PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                    .join("../resources")
+                    .join("bocchan.txt"),
+            )
+            .unwrap(),
+        );
+        let mut large_text = String::new();
+        let _size = large_file.read_to_string(&mut large_text).unwrap();
+
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        };
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary: None,
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+
+        let tokenizer = Tokenizer::from_config(config).unwrap();
+
+        let tokens = tokenizer.tokenize(large_text.as_str()).unwrap();
+        assert!(!tokens.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_unknown_word_ipadic() {
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        };
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary: None,
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+
+        let tokenizer = Tokenizer::from_config(config).unwrap();
+        let tokens = tokenizer.tokenize("限定トートバッグ").unwrap();
+
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0].text, "限定");
+        assert!(tokens[0].word_id.is_known());
+        assert_eq!(tokens[1].text, "トートバッグ");
+        assert!(!tokens[1].word_id.is_known());
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_into_ipadic() {
         let dictionary = DictionaryConfig {
-            kind: Some(DictionaryKind::CcCedict),
+            kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
-        let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("../resources")
-            .join("cc-cedict_simple_userdic.bin");
-
-        let user_dictionary = Some(UserDictionaryConfig {
-            kind: Some(DictionaryKind::CcCedict),
-            path: userdic_file,
-        });
-
         let config = TokenizerConfig {
             dictionary,
-            user_dictionary,
+            user_dictionary: None,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
-        let mut tokens = tokenizer.tokenize("羽田机场限定托特包。").unwrap();
-        let mut tokens_iter = tokens.iter_mut();
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "羽田机场");
-            assert_eq!(token.byte_start, 0);
-            assert_eq!(token.byte_end, 12);
-            assert_eq!(token.position, 0);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec!["*", "*", "*", "*", "Yu3 tian2 ji1 chang3", "*", "*", "*"]
-            );
-        }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "限定");
-            assert_eq!(token.byte_start, 12);
-            assert_eq!(token.byte_end, 18);
-            assert_eq!(token.position, 1);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec![
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "xian4 ding4",
-                    "限定",
-                    "限定",
-                    "to restrict to/to limit/"
-                ]
-            );
-        }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "托特");
-            assert_eq!(token.byte_start, 18);
-            assert_eq!(token.byte_end, 24);
-            assert_eq!(token.position, 2);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec![
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "tuo1 te4",
-                    "托特",
-                    "托特",
-                    "(loanword) tote (bag)/"
-                ]
-            );
-        }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "包");
-            assert_eq!(token.byte_start, 24);
-            assert_eq!(token.byte_end, 27);
-            assert_eq!(token.position, 3);
-            assert_eq!(token.position_length, 1);
+
+        let mut tokens = Vec::new();
+        let mut lattice = Lattice::default();
+        for text in [
+            "日本語の形態素解析",
+            "東京スカイツリー",
+            "すもももももももものうち",
+        ] {
+            tokenizer
+                .tokenize_into(text, &mut tokens, &mut lattice)
+                .unwrap();
+
+            let expected = tokenizer.tokenize(text).unwrap();
             assert_eq!(
-                token.get_details().unwrap(),
-                vec![
-                    "*", 
-                    "*", 
-                    "*", 
-                    "*", 
-                    "bao1", 
-                    "包", 
-                    "包", 
-                    "to cover/to wrap/to hold/to include/to take charge of/to contract (to or for)/package/wrapper/container/bag/to hold or embrace/bundle/packet/CL:個|个[ge4]", 
-                    "隻|只[zhi1]/"
-                ]
+                tokens
+                    .iter()
+                    .map(|token| (
+                        token.text.as_ref(),
+                        token.byte_start,
+                        token.byte_end,
+                        token.position
+                    ))
+                    .collect::<Vec<_>>(),
+                expected
+                    .iter()
+                    .map(|token| (
+                        token.text.as_ref(),
+                        token.byte_start,
+                        token.byte_end,
+                        token.position
+                    ))
+                    .collect::<Vec<_>>()
             );
         }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "。");
-            assert_eq!(token.byte_start, 27);
-            assert_eq!(token.byte_end, 30);
-            assert_eq!(token.position, 4);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(token.get_details().unwrap(), vec!["UNK"]);
-        }
     }
 
     #[test]
     #[cfg(feature = "ipadic")]
-    fn test_tokenize_with_detailed_userdic_ipadic() {
+    fn test_tokenize_with_normalize_ipadic() {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
-        let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("../resources")
-            .join("ipadic_detailed_userdic.csv");
-
-        let user_dictionary = Some(UserDictionaryConfig {
-            kind: Some(DictionaryKind::IPADIC),
-            path: userdic_file,
-        });
-
         let config = TokenizerConfig {
             dictionary,
-            user_dictionary,
+            user_dictionary: None,
             mode: Mode::Normal,
+            normalize: Some(NormalizationForm::NFKC),
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
-        let mut tokens = tokenizer
-            .tokenize("東京スカイツリーの最寄り駅はとうきょうスカイツリー駅です。")
-            .unwrap();
-        let mut tokens_iter = tokens.iter_mut();
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "東京スカイツリー");
+        let mut tokens = tokenizer.tokenize("㍿ＡＩ").unwrap();
+
+        // "㍿" is normalized to "株式会社", whose tokens all point at the original character.
+        let company_len = tokens.len() - 1;
+        let (company_tokens, ai_tokens) = tokens.split_at_mut(company_len);
+        assert!(!company_tokens.is_empty());
+        for token in company_tokens.iter_mut() {
+            assert_eq!(token.text, "㍿");
             assert_eq!(token.byte_start, 0);
-            assert_eq!(token.byte_end, 24);
-            assert_eq!(token.position, 0);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec![
-                    "名詞",
-                    "固有名詞",
-                    "一般",
-                    "カスタム名詞",
-                    "*",
-                    "*",
-                    "東京スカイツリー",
-                    "トウキョウスカイツリー",
-                    "トウキョウスカイツリー"
-                ]
-            );
-        }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "の");
-            assert_eq!(token.byte_start, 24);
-            assert_eq!(token.byte_end, 27);
-            assert_eq!(token.position, 1);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec!["助詞", "連体化", "*", "*", "*", "*", "の", "ノ", "ノ"]
-            );
-        }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "最寄り駅");
-            assert_eq!(token.byte_start, 27);
-            assert_eq!(token.byte_end, 39);
-            assert_eq!(token.position, 2);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec![
-                    "名詞",
-                    "一般",
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "最寄り駅",
-                    "モヨリエキ",
-                    "モヨリエキ"
-                ]
-            );
-        }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "は");
-            assert_eq!(token.byte_start, 39);
-            assert_eq!(token.byte_end, 42);
-            assert_eq!(token.position, 3);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec!["助詞", "係助詞", "*", "*", "*", "*", "は", "ハ", "ワ"]
-            );
-        }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "とうきょうスカイツリー駅");
-            assert_eq!(token.byte_start, 42);
-            assert_eq!(token.byte_end, 78);
-            assert_eq!(token.position, 4);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec![
-                    "名詞",
-                    "固有名詞",
-                    "一般",
-                    "カスタム名詞",
-                    "*",
-                    "*",
-                    "とうきょうスカイツリー駅",
-                    "トウキョウスカイツリーエキ",
-                    "トウキョウスカイツリーエキ"
-                ]
-            );
-        }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "です");
-            assert_eq!(token.byte_start, 78);
-            assert_eq!(token.byte_end, 84);
-            assert_eq!(token.position, 5);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec![
-                    "助動詞",
-                    "*",
-                    "*",
-                    "*",
-                    "特殊・デス",
-                    "基本形",
-                    "です",
-                    "デス",
-                    "デス"
-                ]
-            );
-        }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "。");
-            assert_eq!(token.byte_start, 84);
-            assert_eq!(token.byte_end, 87);
-            assert_eq!(token.position, 6);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec!["記号", "句点", "*", "*", "*", "*", "。", "。", "。"]
-            );
+            assert_eq!(token.byte_end, 3);
+            assert_ne!(token.get_details().unwrap(), vec!["UNK"]);
         }
+
+        // "ＡＩ" is tokenized as "AI" but keeps the original text and offsets.
+        let token = &ai_tokens[0];
+        assert_eq!(token.text, "ＡＩ");
+        assert_eq!(token.byte_start, 3);
+        assert_eq!(token.byte_end, 9);
+        assert_eq!(token.position, company_tokens.len());
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_long_unknown_word_ipadic() {
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        };
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary: None,
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+
+        let mut tokenizer = Tokenizer::from_config(config).unwrap();
+        let text = "x".repeat(5000);
+
+        let tokens = tokenizer.tokenize(&text).unwrap();
+        assert!(tokens
+            .iter()
+            .all(|token| token.text.chars().count() <= DEFAULT_MAX_UNKNOWN_LENGTH));
+        assert_eq!(tokens.last().unwrap().byte_end, 5000);
+
+        tokenizer.max_unknown_length = 100;
+        let tokens = tokenizer.tokenize(&text).unwrap();
+        assert!(tokens.iter().all(|token| token.text.chars().count() <= 100));
+        assert_eq!(tokens.len(), 50);
+        assert_eq!(tokens.last().unwrap().byte_end, 5000);
     }
 
     #[test]
     #[cfg(feature = "ipadic")]
-    fn test_mixed_user_dict() {
+    fn test_tokenize_unknown_cost_overrides_ipadic() {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
-        let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("../resources")
-            .join("ipadic_mixed_userdic.csv");
+        let config = TokenizerConfig {
+            dictionary: dictionary.clone(),
+            user_dictionary: None,
+            mode: Mode::Normal,
+            ..Default::default()
+        };
 
-        let user_dictionary = Some(UserDictionaryConfig {
-            kind: Some(DictionaryKind::IPADIC),
-            path: userdic_file,
-        });
+        let tokenizer = Tokenizer::from_config(config).unwrap();
+        let text = "２０２４年";
+
+        // the full-width digits are in the dictionary, so they are split one by one
+        let tokens = tokenizer.tokenize(text).unwrap();
+        assert_eq!(tokens[0].text, "２");
+        assert!(tokens[0].word_id.is_known());
 
         let config = TokenizerConfig {
             dictionary,
-            user_dictionary,
+            user_dictionary: None,
             mode: Mode::Normal,
+            debug_costs: true,
+            unknown_cost_overrides: HashMap::from([("NUMERIC".to_string(), -10000)]),
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
-        let mut tokens = tokenizer
-            .tokenize("東京スカイツリーの最寄り駅はとうきょうスカイツリー駅です。")
-            .unwrap();
-        let mut tokens_iter = tokens.iter_mut();
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "東京スカイツリー");
-            assert_eq!(token.byte_start, 0);
-            assert_eq!(token.byte_end, 24);
-            assert_eq!(token.position, 0);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec![
-                    "名詞",
-                    "固有名詞",
-                    "一般",
-                    "カスタム名詞",
-                    "*",
-                    "*",
-                    "東京スカイツリー",
-                    "トウキョウスカイツリー",
-                    "トウキョウスカイツリー"
-                ]
-            );
+
+        // a cheap unknown word groups the digits into one token
+        let mut tokens = tokenizer.tokenize(text).unwrap();
+        assert_eq!(
+            tokens.iter().map(|t| t.text.as_ref()).collect::<Vec<_>>(),
+            vec!["２０２４", "年"]
+        );
+        assert!(!tokens[0].word_id.is_known());
+        assert_eq!(tokens[0].word_cost, Some(-10000));
+        assert_eq!(tokens[0].get_details().unwrap()[1], "数");
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_unknown_cost_overrides_invalid_category_ipadic() {
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        };
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary: None,
+            mode: Mode::Normal,
+            unknown_cost_overrides: HashMap::from([("NO_SUCH_CATEGORY".to_string(), 0)]),
+            ..Default::default()
+        };
+
+        assert!(Tokenizer::from_config(config).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_estimate_token_count_ipadic() {
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        };
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary: None,
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+
+        let mut tokenizer = Tokenizer::from_config(config).unwrap();
+
+        for text in [
+            "",
+            "日本語の形態素解析を行うことができます。",
+            "すもももももももものうち",
+            "Rust is fast.",
+            "㍿ＡＩ",
+        ] {
+            let count = tokenizer.tokenize(text).unwrap().len();
+            assert!(tokenizer.estimate_token_count(text) >= count);
+
+            tokenizer.normalize = Some(NormalizationForm::NFKC);
+            let count = tokenizer.tokenize(text).unwrap().len();
+            assert!(tokenizer.estimate_token_count(text) >= count);
+            tokenizer.normalize = None;
         }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "の");
-            assert_eq!(token.byte_start, 24);
-            assert_eq!(token.byte_end, 27);
-            assert_eq!(token.position, 1);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec!["助詞", "連体化", "*", "*", "*", "*", "の", "ノ", "ノ"]
-            );
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_debug_costs_ipadic() {
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        };
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary: None,
+            mode: Mode::Normal,
+            debug_costs: true,
+            ..Default::default()
+        };
+
+        let mut tokenizer = Tokenizer::from_config(config).unwrap();
+
+        let tokens = tokenizer.tokenize("日本語の形態素解析").unwrap();
+        assert_eq!(
+            tokens.iter().map(|t| t.text.as_ref()).collect::<Vec<_>>(),
+            vec!["日本語", "の", "形態素", "解析"]
+        );
+        for token in tokens.iter() {
+            assert!(token.word_cost.is_some());
+            assert!(token.connection_cost.is_some());
         }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "最寄り駅");
-            assert_eq!(token.byte_start, 27);
-            assert_eq!(token.byte_end, 39);
-            assert_eq!(token.position, 2);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec![
-                    "名詞",
-                    "一般",
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "最寄り駅",
-                    "モヨリエキ",
-                    "モヨリエキ"
-                ]
-            );
+
+        tokenizer.debug_costs = false;
+        let tokens = tokenizer.tokenize("日本語の形態素解析").unwrap();
+        for token in tokens.iter() {
+            assert_eq!(token.word_cost, None);
+            assert_eq!(token.connection_cost, None);
         }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "は");
-            assert_eq!(token.byte_start, 39);
-            assert_eq!(token.byte_end, 42);
-            assert_eq!(token.position, 3);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec!["助詞", "係助詞", "*", "*", "*", "*", "は", "ハ", "ワ"]
-            );
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_preserve_whitespace_ipadic() {
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        };
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary: None,
+            mode: Mode::Normal,
+            preserve_whitespace: true,
+            ..Default::default()
+        };
+
+        let tokenizer = Tokenizer::from_config(config).unwrap();
+        let text = "今日 は 晴れ";
+
+        let mut tokens = tokenizer.tokenize(text).unwrap();
+        assert_eq!(
+            tokens.iter().map(|t| t.text.as_ref()).collect::<Vec<_>>(),
+            vec!["今日", " ", "は", " ", "晴れ"]
+        );
+        assert_eq!(
+            tokens.iter().map(|t| t.position).collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+
+        for index in [1, 3] {
+            let token = &mut tokens[index];
+            assert_eq!(&text[token.byte_start..token.byte_end], " ");
+            assert_eq!(token.get_details().unwrap(), vec!["空白"]);
         }
+        assert_eq!((tokens[1].byte_start, tokens[1].byte_end), (6, 7));
+        assert_eq!((tokens[3].byte_start, tokens[3].byte_end), (10, 11));
+
+        // The tokens cover the whole text.
+        assert_eq!(
+            tokens.iter().map(|t| t.text.as_ref()).collect::<String>(),
+            text
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_include_bos_eos_ipadic() {
+        let config_str = r#"
         {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "とうきょうスカイツリー駅");
-            assert_eq!(token.byte_start, 42);
-            assert_eq!(token.byte_end, 78);
-            assert_eq!(token.position, 4);
-            assert_eq!(token.position_length, 1);
+            "dictionary": {
+                "kind": "ipadic"
+            },
+            "mode": "normal",
+            "include_bos_eos": true
+        }
+        "#;
+        let config: TokenizerConfig = serde_json::from_str(config_str).unwrap();
+        assert!(config.include_bos_eos);
+
+        let mut tokenizer = Tokenizer::from_config(config).unwrap();
+        let text = "日本語の形態素解析";
+
+        let words = {
+            let mut tokens = tokenizer.tokenize(text).unwrap();
             assert_eq!(
-                token.get_details().unwrap(),
-                vec![
-                    "カスタム名詞",
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "とうきょうスカイツリー駅",
-                    "トウキョウスカイツリーエキ",
-                    "*"
-                ]
+                tokens.iter().map(|t| t.text.as_ref()).collect::<Vec<_>>(),
+                vec!["", "日本語", "の", "形態素", "解析", ""]
             );
-        }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "です");
-            assert_eq!(token.byte_start, 78);
-            assert_eq!(token.byte_end, 84);
-            assert_eq!(token.position, 5);
-            assert_eq!(token.position_length, 1);
             assert_eq!(
-                token.get_details().unwrap(),
-                vec![
-                    "助動詞",
-                    "*",
-                    "*",
-                    "*",
-                    "特殊・デス",
-                    "基本形",
-                    "です",
-                    "デス",
-                    "デス"
-                ]
+                tokens.iter().map(|t| t.position).collect::<Vec<_>>(),
+                vec![0, 1, 2, 3, 4, 5]
             );
-        }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "。");
-            assert_eq!(token.byte_start, 84);
-            assert_eq!(token.byte_end, 87);
-            assert_eq!(token.position, 6);
-            assert_eq!(token.position_length, 1);
+
+            let bos = tokens.first_mut().unwrap();
+            assert_eq!(bos.byte_start, 0);
+            assert_eq!(bos.byte_end, 0);
+            assert_eq!(bos.get_details().unwrap(), vec!["BOS"]);
+
+            let eos = tokens.last_mut().unwrap();
+            assert_eq!(eos.byte_start, text.len());
+            assert_eq!(eos.byte_end, text.len());
+            assert_eq!(eos.get_details().unwrap(), vec!["EOS"]);
+
+            tokens[1..tokens.len() - 1]
+                .iter()
+                .map(|t| (t.byte_start, t.byte_end, t.position))
+                .collect::<Vec<_>>()
+        };
+
+        // The word tokens are the same as without the BOS and EOS tokens, shifted by one position.
+        tokenizer.include_bos_eos = false;
+        assert_eq!(
+            tokenizer
+                .tokenize(text)
+                .unwrap()
+                .iter()
+                .map(|t| (t.byte_start, t.byte_end, t.position + 1))
+                .collect::<Vec<_>>(),
+            words
+        );
+
+        // An empty text has only the BOS and EOS tokens.
+        tokenizer.include_bos_eos = true;
+        let mut tokens = tokenizer.tokenize("").unwrap();
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(
+            tokens.iter().map(|t| t.position).collect::<Vec<_>>(),
+            vec![0, 1]
+        );
+        assert_eq!(tokens[0].get_details().unwrap(), vec!["BOS"]);
+        assert_eq!(tokens[1].get_details().unwrap(), vec!["EOS"]);
+    }
+
+    #[test]
+    #[cfg(all(feature = "ipadic", feature = "parallel"))]
+    fn test_tokenize_batch_ipadic() {
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        };
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary: None,
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+
+        let tokenizer = Tokenizer::from_config(config).unwrap();
+
+        let texts = [
+            "日本語の形態素解析を行うことができます。",
+            "すもももももももものうち",
+            "",
+            "関西国際空港限定トートバッグ",
+            "東京スカイツリーの最寄り駅はとうきょうスカイツリー駅です。",
+        ]
+        .iter()
+        .cycle()
+        .take(100)
+        .map(|text| text.to_string())
+        .collect::<Vec<_>>();
+
+        let batch_tokens = tokenizer.tokenize_batch(&texts).unwrap();
+
+        assert_eq!(batch_tokens.len(), texts.len());
+        for (text, tokens) in texts.iter().zip(batch_tokens.iter()) {
+            let serial_tokens = tokenizer.tokenize(text).unwrap();
             assert_eq!(
-                token.get_details().unwrap(),
-                vec!["記号", "句点", "*", "*", "*", "*", "。", "。", "。"]
+                tokens
+                    .iter()
+                    .map(|t| (
+                        t.text.as_ref(),
+                        t.byte_start,
+                        t.byte_end,
+                        t.position,
+                        t.word_id
+                    ))
+                    .collect::<Vec<_>>(),
+                serial_tokens
+                    .iter()
+                    .map(|t| (
+                        t.text.as_ref(),
+                        t.byte_start,
+                        t.byte_end,
+                        t.position,
+                        t.word_id
+                    ))
+                    .collect::<Vec<_>>()
             );
         }
     }
 
     #[test]
     #[cfg(feature = "ipadic")]
-    #[should_panic(expected = "failed to parse word cost")]
-    fn test_user_dict_invalid_word_cost() {
+    fn test_add_user_word_ipadic() {
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        };
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary: None,
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+
+        let mut tokenizer = Tokenizer::from_config(config).unwrap();
+        let text = "東京スカイツリーの最寄り駅";
+
+        // The word is not in the system dictionary.
+        let tokens = tokenizer.tokenize(text).unwrap();
+        assert_eq!(tokens[0].text, "東京");
+
+        tokenizer.add_user_word(
+            "東京スカイツリー",
+            "カスタム名詞",
+            "トウキョウスカイツリー",
+            -10000,
+        );
+
+        let mut tokens = tokenizer.tokenize(text).unwrap();
+        assert_eq!(
+            tokens.iter().map(|t| t.text.as_ref()).collect::<Vec<_>>(),
+            vec!["東京スカイツリー", "の", "最寄り駅"]
+        );
+        assert_eq!(tokens[0].byte_start, 0);
+        assert_eq!(tokens[0].byte_end, 24);
+        assert!(tokens[0].word_id.is_known());
+        assert!(!tokens[0].word_id.is_system());
+        // The details have the layout of IPADIC, like a simple row of a user dictionary CSV.
+        assert_eq!(
+            tokens[0].get_details().unwrap(),
+            vec![
+                "カスタム名詞",
+                "*",
+                "*",
+                "*",
+                "*",
+                "*",
+                "東京スカイツリー",
+                "トウキョウスカイツリー",
+                "*"
+            ]
+        );
+        assert_eq!(tokens[1].get_details().unwrap()[0], "助詞");
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_lookup_ipadic() {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
-        let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("../resources")
-            .join("ipadic_userdic_invalid_word_cost.csv");
-
-        let user_dictionary = Some(UserDictionaryConfig {
-            kind: Some(DictionaryKind::IPADIC),
-            path: userdic_file,
-        });
-
         let config = TokenizerConfig {
             dictionary,
-            user_dictionary,
+            user_dictionary: None,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
-        Tokenizer::from_config(config).unwrap();
+        let mut tokenizer = Tokenizer::from_config(config).unwrap();
+
+        assert!(tokenizer.contains_word("東京"));
+        assert!(tokenizer.contains_word("形態素"));
+        assert!(!tokenizer.contains_word("ぬぽぬぽぬぽ"));
+        assert!(!tokenizer.contains_word(""));
+
+        // Only exact matches are found, not the words that are a prefix of the surface.
+        assert!(!tokenizer.contains_word("東京スカイツリー"));
+
+        let entries = tokenizer.lookup("東京");
+        assert!(!entries.is_empty());
+        for entry in entries.iter() {
+            assert!(entry.word_id.is_system());
+            let details = tokenizer
+                .dictionary
+                .word_details(entry.word_id.0 as usize)
+                .unwrap();
+            assert_eq!(details[6], "東京");
+        }
+
+        tokenizer.add_user_word(
+            "東京スカイツリー",
+            "カスタム名詞",
+            "トウキョウスカイツリー",
+            -10000,
+        );
+        assert!(tokenizer.contains_word("東京スカイツリー"));
+        let entries = tokenizer.lookup("東京スカイツリー");
+        assert_eq!(entries.len(), 1);
+        assert!(!entries[0].word_id.is_system());
     }
 
     #[test]
     #[cfg(feature = "ipadic")]
-    #[should_panic(expected = "user dictionary should be a CSV with 3 or 13+ fields")]
-    fn test_user_dict_number_of_fields_is_11() {
+    fn test_prefix_search_ipadic() {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
-        let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("../resources")
-            .join("ipadic_userdic_insufficient_number_of_fields.csv");
-
-        let user_dictionary = Some(UserDictionaryConfig {
-            kind: Some(DictionaryKind::IPADIC),
-            path: userdic_file,
-        });
-
         let config = TokenizerConfig {
             dictionary,
-            user_dictionary,
+            user_dictionary: None,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
-        Tokenizer::from_config(config).unwrap();
+        let mut tokenizer = Tokenizer::from_config(config).unwrap();
+
+        let words = tokenizer.prefix_search("東京", 1000);
+        assert!(words.len() > 1);
+        for (surface, word_entry) in words.iter() {
+            assert!(surface.starts_with("東京"));
+            assert!(tokenizer.lookup(surface).contains(word_entry));
+        }
+        let surfaces = words
+            .iter()
+            .map(|(surface, _)| surface.as_str())
+            .collect::<Vec<&str>>();
+        assert!(surfaces.contains(&"東京"));
+        assert!(surfaces.contains(&"東京都"));
+        assert!(words.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+
+        let limited_words = tokenizer.prefix_search("東京", 3);
+        assert_eq!(limited_words, words[..3].to_vec());
+        assert!(tokenizer.prefix_search("東京", 0).is_empty());
+        assert!(tokenizer.prefix_search("ぬぽぬぽぬぽ", 10).is_empty());
+
+        tokenizer.add_user_word(
+            "東京スカイツリー",
+            "カスタム名詞",
+            "トウキョウスカイツリー",
+            -10000,
+        );
+        let words = tokenizer.prefix_search("東京", 3);
+        assert_eq!(words.len(), 3);
+        assert_eq!(words[0].0, "東京スカイツリー");
+        assert!(!words[0].1.word_id.is_system());
     }
 
     #[test]
     #[cfg(feature = "ipadic")]
-    fn test_tokenize_with_nomal_mode() {
+    fn test_tokenize_sentence_ipadic() {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
-        let tokenizer = Tokenizer::from_config(config).unwrap();
-        let mut tokens = tokenizer.tokenize("羽田空港限定トートバッグ").unwrap();
-        let mut tokens_iter = tokens.iter_mut();
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "羽田空港");
-            assert_eq!(token.byte_start, 0);
-            assert_eq!(token.byte_end, 12);
-            assert_eq!(token.position, 0);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec![
-                    "名詞",
-                    "固有名詞",
-                    "一般",
-                    "*",
-                    "*",
-                    "*",
-                    "羽田空港",
-                    "ハネダクウコウ",
-                    "ハネダクーコー"
-                ]
-            );
-        }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "限定");
-            assert_eq!(token.byte_start, 12);
-            assert_eq!(token.byte_end, 18);
-            assert_eq!(token.position, 1);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec![
-                    "名詞",
-                    "サ変接続",
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "限定",
-                    "ゲンテイ",
-                    "ゲンテイ"
-                ]
-            );
-        }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "トートバッグ");
-            assert_eq!(token.byte_start, 18);
-            assert_eq!(token.byte_end, 36);
-            assert_eq!(token.position, 2);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(token.get_details().unwrap(), vec!["UNK"]);
-        }
+        let mut tokenizer = Tokenizer::from_config(config).unwrap();
+        // A word spanning the sentence delimiter can only be found in a single lattice.
+        tokenizer.add_user_word("東京。大阪", "カスタム名詞", "トウキョウオオサカ", -10000);
+        let text = "東京。大阪へ行く";
+
+        // `tokenize` splits the text after `。`.
+        let tokens = tokenizer.tokenize(text).unwrap();
+        assert_eq!(
+            tokens.iter().map(|t| t.text.as_ref()).collect::<Vec<_>>(),
+            vec!["東京", "。", "大阪", "へ", "行く"]
+        );
+
+        let tokens = tokenizer.tokenize_sentence(text).unwrap();
+        assert_eq!(
+            tokens.iter().map(|t| t.text.as_ref()).collect::<Vec<_>>(),
+            vec!["東京。大阪", "へ", "行く"]
+        );
+        assert_eq!(tokens[0].byte_start, 0);
+        assert_eq!(tokens[0].byte_end, 15);
+        assert_eq!(tokens[1].byte_start, 15);
+        assert_eq!(tokens[2].position, 2);
+
+        // Without such a word, the tokens are the same as those of `tokenize`.
+        let text = "東京へ行きます。京都は遠いです。";
+        assert_eq!(
+            tokenizer
+                .tokenize_sentence(text)
+                .unwrap()
+                .iter()
+                .map(|t| t.text.as_ref())
+                .collect::<Vec<_>>(),
+            tokenizer
+                .tokenize(text)
+                .unwrap()
+                .iter()
+                .map(|t| t.text.as_ref())
+                .collect::<Vec<_>>()
+        );
+
+        assert!(tokenizer.tokenize_sentence("").unwrap().is_empty());
     }
 
     #[test]
     #[cfg(feature = "ipadic")]
-    fn test_tokenize_with_decompose_mode() {
+    fn test_tokenize_iter_ipadic() {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary: None,
-            mode: Mode::Decompose(Penalty::default()),
+            mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
-        let mut tokens = tokenizer.tokenize("羽田空港限定トートバッグ").unwrap();
-        let mut tokens_iter = tokens.iter_mut();
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "羽田");
-            assert_eq!(token.byte_start, 0);
-            assert_eq!(token.byte_end, 6);
-            assert_eq!(token.position, 0);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec![
-                    "名詞",
-                    "固有名詞",
-                    "人名",
-                    "姓",
-                    "*",
-                    "*",
-                    "羽田",
-                    "ハタ",
-                    "ハタ"
-                ]
-            );
-        }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "空港");
-            assert_eq!(token.byte_start, 6);
-            assert_eq!(token.byte_end, 12);
-            assert_eq!(token.position, 1);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(
-                token.get_details().unwrap(),
-                vec![
-                    "名詞",
-                    "一般",
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "空港",
-                    "クウコウ",
-                    "クーコー"
-                ]
-            );
-        }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "限定");
-            assert_eq!(token.byte_start, 12);
-            assert_eq!(token.byte_end, 18);
-            assert_eq!(token.position, 2);
-            assert_eq!(token.position_length, 1);
+        let text = "日本語の形態素解析を行うことができます。テスト。";
+
+        let tokens = tokenizer.tokenize(text).unwrap();
+        let iter_tokens = tokenizer
+            .tokenize_iter(text)
+            .collect::<LinderaResult<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(tokens.len(), iter_tokens.len());
+        for (token, iter_token) in tokens.iter().zip(iter_tokens.iter()) {
+            assert_eq!(token.text, iter_token.text);
+            assert_eq!(token.byte_start, iter_token.byte_start);
+            assert_eq!(token.byte_end, iter_token.byte_end);
+            assert_eq!(token.position, iter_token.position);
             assert_eq!(
-                token.get_details().unwrap(),
-                vec![
-                    "名詞",
-                    "サ変接続",
-                    "*",
-                    "*",
-                    "*",
-                    "*",
-                    "限定",
-                    "ゲンテイ",
-                    "ゲンテイ"
-                ]
+                &text[iter_token.byte_start..iter_token.byte_end],
+                iter_token.text
             );
         }
-        {
-            let token = tokens_iter.next().unwrap();
-            assert_eq!(token.text, "トートバッグ");
-            assert_eq!(token.byte_start, 18);
-            assert_eq!(token.byte_end, 36);
-            assert_eq!(token.position, 3);
-            assert_eq!(token.position_length, 1);
-            assert_eq!(token.get_details().unwrap(), vec!["UNK"]);
+
+        // Offsets of the tokens in the second sentence are relative to the whole text.
+        let last = iter_tokens.last().unwrap();
+        assert_eq!(last.text, "。");
+        assert_eq!(last.byte_end, text.len());
+
+        // The normalization and the BOS and EOS tokens are applied as in `tokenize`.
+        let mut tokenizer = tokenizer;
+        tokenizer.normalize = Some(NormalizationForm::NFKC);
+        tokenizer.include_bos_eos = true;
+        let text = "ﾃｽﾄを行います。ﾃｽﾄ";
+
+        let mut tokens = tokenizer.tokenize(text).unwrap();
+        let mut iter_tokens = tokenizer
+            .tokenize_iter(text)
+            .collect::<LinderaResult<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(tokens.len(), iter_tokens.len());
+        for (token, iter_token) in tokens.iter_mut().zip(iter_tokens.iter_mut()) {
+            assert_eq!(token.text, iter_token.text);
+            assert_eq!(token.byte_start, iter_token.byte_start);
+            assert_eq!(token.byte_end, iter_token.byte_end);
+            assert_eq!(token.position, iter_token.position);
+            assert_eq!(token.get_details(), iter_token.get_details());
         }
+        assert_eq!(iter_tokens[0].get_details().unwrap(), vec!["BOS"]);
+        assert!(iter_tokens[1].text.starts_with('ﾃ'));
+        let last = iter_tokens.last_mut().unwrap();
+        assert_eq!(last.get_details().unwrap(), vec!["EOS"]);
+        assert_eq!(last.byte_start, text.len());
     }
 
     #[test]
     #[cfg(feature = "ipadic")]
-    fn test_long_text() {
-        let mut large_file = BufReader::new(
-            File::open(
-                PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-                    .join("../resources")
-                    .join("bocchan.txt"),
-            )
-            .unwrap(),
-        );
-        let mut large_text = String::new();
-        let _size = large_file.read_to_string(&mut large_text).unwrap();
+    fn test_tokenize_reader_ipadic() {
+        use std::io::Cursor;
 
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
 
-        let tokens = tokenizer.tokenize(large_text.as_str()).unwrap();
-        assert!(!tokens.is_empty());
+        // A large text read through a tiny buffer, so that the sentences and the characters
+        // are split across the reads.
+        let text = (0..2000)
+            .map(|i| match i % 3 {
+                0 => "東京スカイツリーの最寄り駅はとうきょうスカイツリー駅です。",
+                1 => "すもももももももものうち\n",
+                _ => "関西国際空港限定トートバッグ、",
+            })
+            .collect::<String>();
+        let reader = BufReader::with_capacity(7, Cursor::new(text.as_bytes()));
+
+        let reader_tokens = tokenizer
+            .tokenize_reader(reader)
+            .collect::<LinderaResult<Vec<_>>>()
+            .unwrap();
+        let tokens = tokenizer.tokenize(&text).unwrap();
+
+        assert_eq!(reader_tokens.len(), tokens.len());
+        for (token, reader_token) in tokens.iter().zip(reader_tokens.iter()) {
+            assert_eq!(token.text, reader_token.text);
+            assert_eq!(token.byte_start, reader_token.byte_start);
+            assert_eq!(token.byte_end, reader_token.byte_end);
+            assert_eq!(token.position, reader_token.position);
+            assert_eq!(token.word_id, reader_token.word_id);
+        }
+        assert_eq!(reader_tokens.last().unwrap().byte_end, text.len());
+
+        // The reading stops at the first invalid byte sequence.
+        let mut results = tokenizer.tokenize_reader(Cursor::new(b"\xFF\xFE\n".to_vec()));
+        assert!(results.next().unwrap().is_err());
+        assert!(results.next().is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "ipadic")]
+    fn test_tokenize_reader_include_bos_eos_ipadic() {
+        use std::io::Cursor;
+
+        let config_str = r#"
+        {
+            "dictionary": {
+                "kind": "ipadic"
+            },
+            "mode": "normal",
+            "include_bos_eos": true
+        }
+        "#;
+        let config: TokenizerConfig = serde_json::from_str(config_str).unwrap();
+        let tokenizer = Tokenizer::from_config(config).unwrap();
+
+        // Several sentences read through a tiny buffer, so that they are tokenized in several
+        // chunks, the last of which is empty.
+        let text = "東京スカイツリーの最寄り駅は、とうきょうスカイツリー駅です。\nすもももももももものうち\n";
+        let reader = BufReader::with_capacity(7, Cursor::new(text.as_bytes()));
+
+        let mut reader_tokens = tokenizer
+            .tokenize_reader(reader)
+            .collect::<LinderaResult<Vec<_>>>()
+            .unwrap();
+        let tokens = tokenizer.tokenize(text).unwrap();
+
+        assert_eq!(reader_tokens.len(), tokens.len());
+        for (token, reader_token) in tokens.iter().zip(reader_tokens.iter()) {
+            assert_eq!(token.text, reader_token.text);
+            assert_eq!(token.byte_start, reader_token.byte_start);
+            assert_eq!(token.byte_end, reader_token.byte_end);
+            assert_eq!(token.position, reader_token.position);
+        }
+
+        // A single BOS token and a single EOS token surround the tokens of all the chunks.
+        let boundaries = reader_tokens
+            .iter_mut()
+            .enumerate()
+            .filter(|(_, token)| token.text.is_empty())
+            .map(|(index, token)| (index, token.get_details().unwrap()[0].to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            boundaries,
+            vec![
+                (0, "BOS".to_string()),
+                (tokens.len() - 1, "EOS".to_string())
+            ]
+        );
+        assert_eq!(reader_tokens.last().unwrap().byte_start, text.len());
+
+        // An empty text has only the BOS and EOS tokens.
+        let reader_tokens = tokenizer
+            .tokenize_reader(Cursor::new(Vec::new()))
+            .collect::<LinderaResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            reader_tokens
+                .iter()
+                .map(|token| token.position)
+                .collect::<Vec<_>>(),
+            vec![0, 1]
+        );
+    }
+
+    #[test]
+    fn test_last_sentence_end() {
+        let splitter = SentenceSplitter::default();
+
+        let buffer = "東京。京都！大阪、".as_bytes();
+        assert_eq!(
+            last_sentence_end(&splitter, buffer, 0),
+            Some("東京。京都！".len())
+        );
+        assert_eq!(
+            last_sentence_end(&splitter, "東京\n京都".as_bytes(), 0),
+            Some(7)
+        );
+        assert_eq!(last_sentence_end(&splitter, "東京京都".as_bytes(), 0), None);
+
+        // The terminator cut off by the previous read is found.
+        assert_eq!(
+            last_sentence_end(&splitter, "東京。".as_bytes(), 7),
+            Some(9)
+        );
+
+        // A character cut off at the end of the buffer is not searched yet.
+        let buffer = "東京。京".as_bytes();
+        assert_eq!(
+            last_sentence_end(&splitter, &buffer[..11], 0),
+            Some("東京。".len())
+        );
+    }
+
+    #[test]
+    fn test_char_boundary() {
+        let buffer = "あいう".as_bytes();
+        assert_eq!(char_boundary(buffer, 3), 3);
+        assert_eq!(char_boundary(buffer, 4), 3);
+        assert_eq!(char_boundary(buffer, 5), 3);
+        assert_eq!(char_boundary(buffer, 6), 6);
+        assert_eq!(char_boundary(buffer, 100), 9);
+    }
+
+    #[test]
+    fn test_normalize_with_origins() {
+        use unicode_normalization::UnicodeNormalization;
+
+        use crate::tokenizer::NormalizationForm as Form;
+
+        // A combining mark is composed with the preceding character.
+        let text = "か\u{3099}き";
+        let (normalized_text, origins) = normalize_with_origins(text, Form::NFC);
+        assert_eq!(normalized_text, "がき");
+        assert_eq!(
+            origins,
+            vec![(0, 6), (0, 6), (0, 6), (6, 9), (6, 9), (6, 9)]
+        );
+
+        // Conjoining Hangul jamo (leading consonant, vowel and final consonant) all have the
+        // combining class 0, but are composed into syllables.
+        let text = "\u{1112}\u{1161}\u{11AB}\u{1100}\u{116E}\u{11A8}\u{110B}\u{1165}";
+        for form in [Form::NFC, Form::NFKC] {
+            let (normalized_text, origins) = normalize_with_origins(text, form);
+            assert_eq!(normalized_text, "한국어");
+            assert_eq!(normalized_text, text.nfc().collect::<String>());
+            assert_eq!(
+                origins,
+                vec![
+                    (0, 9),
+                    (0, 9),
+                    (0, 9),
+                    (9, 18),
+                    (9, 18),
+                    (9, 18),
+                    (18, 24),
+                    (18, 24),
+                    (18, 24)
+                ]
+            );
+        }
+
+        // A half-width voiced sound mark is composed with the preceding half-width katakana.
+        let (normalized_text, origins) = normalize_with_origins("ｶﾞｷ", Form::NFKC);
+        assert_eq!(normalized_text, "ガキ");
+        assert_eq!(origins[..3], vec![(0, 6); 3]);
+        assert_eq!(origins[3..], vec![(6, 9); 3]);
+
+        // A syllable is decomposed into jamo, each of which comes from the whole syllable.
+        let (normalized_text, origins) = normalize_with_origins("한국", Form::NFD);
+        assert_eq!(normalized_text, text[..18]);
+        assert_eq!(origins[..9], vec![(0, 3); 9]);
+        assert_eq!(origins[9..], vec![(3, 6); 9]);
     }
 }
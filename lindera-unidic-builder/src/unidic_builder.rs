@@ -1,28 +1,76 @@
 use std::{fs, path::Path};
 
+use csv::StringRecord;
+
 use lindera_dictionary_builder::{
-    build_user_dictionary, CharDefBuilderOptions, CostMatrixBuilderOptions, DictBuilderOptions,
-    UnkBuilderOptions, UserDictBuilderOptions,
+    build_user_dictionary, validate_source, write_dictionary_kind, CharDefBuilder,
+    CharDefBuilderOptions, CostMatrixBuilder, CostMatrixBuilderOptions, DictBuilder,
+    DictBuilderOptions, UnkBuilder, UnkBuilderOptions, UserDictBuilder, UserDictBuilderOptions,
 };
 
 use lindera_core::{
-    character_definition::CharacterDefinitions, dictionary::UserDictionary,
-    dictionary_builder::DictionaryBuilder, error::LinderaErrorKind, LinderaResult,
+    character_definition::CharacterDefinitions,
+    dictionary::UserDictionary,
+    dictionary_builder::{DictionaryBuilder, ValidationReport},
+    error::LinderaErrorKind,
+    LinderaResult,
 };
 use lindera_decompress::Algorithm;
 
+const DICTIONARY_KIND: &str = "unidic";
 const SIMPLE_USERDIC_FIELDS_NUM: usize = 3;
 const SIMPLE_WORD_COST: i16 = -10000;
 const SIMPLE_CONTEXT_ID: u16 = 0;
 const DETAILED_USERDIC_FIELDS_NUM: usize = 21;
-const COMPRESS_ALGORITHM: Algorithm = Algorithm::Deflate;
+const DEFAULT_COMPRESS_ALGORITHM: Algorithm = Algorithm::Deflate;
 const UNK_FIELDS_NUM: usize = 10;
 
-pub struct UnidicBuilder {}
+pub struct UnidicBuilder {
+    compress_algorithm: Algorithm,
+}
 
 impl UnidicBuilder {
     pub fn new() -> Self {
-        UnidicBuilder {}
+        UnidicBuilder {
+            compress_algorithm: DEFAULT_COMPRESS_ALGORITHM,
+        }
+    }
+
+    /// Set the algorithm used to compress the dictionary files when the `compress` feature is
+    /// enabled. Defaults to `Algorithm::Deflate`.
+    pub fn compress_algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.compress_algorithm = algorithm;
+        self
+    }
+
+    fn chardef_builder(&self) -> CharDefBuilder {
+        CharDefBuilderOptions::default()
+            .compress_algorithm(self.compress_algorithm)
+            .builder()
+            .unwrap()
+    }
+
+    fn unk_builder(&self) -> UnkBuilder {
+        UnkBuilderOptions::default()
+            .compress_algorithm(self.compress_algorithm)
+            .unk_fields_num(UNK_FIELDS_NUM)
+            .builder()
+            .unwrap()
+    }
+
+    fn dict_builder(&self) -> DictBuilder {
+        DictBuilderOptions::default()
+            .flexible_csv(false)
+            .compress_algorithm(self.compress_algorithm)
+            .builder()
+            .unwrap()
+    }
+
+    fn cost_matrix_builder(&self) -> CostMatrixBuilder {
+        CostMatrixBuilderOptions::default()
+            .compress_algorithm(self.compress_algorithm)
+            .builder()
+            .unwrap()
     }
 }
 
@@ -41,10 +89,21 @@ impl DictionaryBuilder for UnidicBuilder {
         self.build_unk(input_dir, &chardef, output_dir)?;
         self.build_dict(input_dir, output_dir)?;
         self.build_cost_matrix(input_dir, output_dir)?;
+        write_dictionary_kind(output_dir, DICTIONARY_KIND)?;
 
         Ok(())
     }
 
+    fn validate_dictionary(&self, input_dir: &Path) -> LinderaResult<ValidationReport> {
+        validate_source(
+            input_dir,
+            &self.chardef_builder(),
+            &self.unk_builder(),
+            &self.dict_builder(),
+            &self.cost_matrix_builder(),
+        )
+    }
+
     fn build_user_dictionary(&self, input_file: &Path, output_file: &Path) -> LinderaResult<()> {
         let user_dict = self.build_user_dict(input_file)?;
         build_user_dictionary(user_dict, output_file)
@@ -55,11 +114,7 @@ impl DictionaryBuilder for UnidicBuilder {
         input_dir: &Path,
         output_dir: &Path,
     ) -> LinderaResult<CharacterDefinitions> {
-        CharDefBuilderOptions::default()
-            .compress_algorithm(COMPRESS_ALGORITHM)
-            .builder()
-            .unwrap()
-            .build(input_dir, output_dir)
+        self.chardef_builder().build(input_dir, output_dir)
     }
 
     fn build_unk(
@@ -68,61 +123,260 @@ impl DictionaryBuilder for UnidicBuilder {
         chardef: &CharacterDefinitions,
         output_dir: &Path,
     ) -> LinderaResult<()> {
-        UnkBuilderOptions::default()
-            .compress_algorithm(COMPRESS_ALGORITHM)
-            .unk_fields_num(UNK_FIELDS_NUM)
-            .builder()
-            .unwrap()
-            .build(input_dir, chardef, output_dir)
+        self.unk_builder().build(input_dir, chardef, output_dir)
     }
 
     fn build_dict(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()> {
-        DictBuilderOptions::default()
-            .flexible_csv(false)
-            .compress_algorithm(COMPRESS_ALGORITHM)
-            .builder()
-            .unwrap()
-            .build(input_dir, output_dir)
+        self.dict_builder().build(input_dir, output_dir)
     }
 
     fn build_cost_matrix(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()> {
-        CostMatrixBuilderOptions::default()
-            .compress_algorithm(COMPRESS_ALGORITHM)
-            .builder()
-            .unwrap()
-            .build(&input_dir, output_dir)
+        self.cost_matrix_builder().build(input_dir, output_dir)
     }
 
     fn build_user_dict(&self, input_file: &Path) -> LinderaResult<UserDictionary> {
-        UserDictBuilderOptions::default()
-            .simple_userdic_fields_num(SIMPLE_USERDIC_FIELDS_NUM)
-            .detailed_userdic_fields_num(DETAILED_USERDIC_FIELDS_NUM)
-            .simple_word_cost(SIMPLE_WORD_COST)
-            .simple_context_id(SIMPLE_CONTEXT_ID)
-            .flexible_csv(false)
-            .simple_userdic_details_handler(Box::new(|row| {
-                Ok(vec![
-                    row[1].to_string(), //Major POS classification
-                    "*".to_string(),    // Middle POS classification
-                    "*".to_string(),    // Small POS classification
-                    "*".to_string(),    // Fine POS classification
-                    "*".to_string(),    // Conjugation form
-                    "*".to_string(),    // Conjugation type
-                    row[2].to_string(), //Lexeme reading
-                    "*".to_string(),    // Lexeme
-                    "*".to_string(),    // Orthography appearance type
-                    "*".to_string(),    // Pronunciation appearance type
-                    "*".to_string(),    // Orthography basic type
-                    "*".to_string(),    // Pronunciation basic type
-                    "*".to_string(),    // Word type
-                    "*".to_string(),    // Prefix of a word form
-                    "*".to_string(),    // Prefix of a word type
-                    "*".to_string(),    // Suffix of a word form
-                    "*".to_string(),    // Suffix of a word type
-                ])
-            }))
-            .builder()
-            .unwrap()
-            .build(input_file)
+        user_dict_builder().build(input_file)
+    }
+
+    fn build_user_dict_from_rows(&self, rows: Vec<StringRecord>) -> LinderaResult<UserDictionary> {
+        user_dict_builder().build_from_rows(rows)
+    }
+
+    fn simple_userdic_details(&self, surface: &str, pos: &str, reading: &str) -> Vec<String> {
+        simple_userdic_details(surface, pos, reading)
+    }
+}
+
+/// The builder of the user dictionaries, for both the CSV files and the rows already read.
+fn user_dict_builder() -> UserDictBuilder {
+    UserDictBuilderOptions::default()
+        .simple_userdic_fields_num(SIMPLE_USERDIC_FIELDS_NUM)
+        .detailed_userdic_fields_num(DETAILED_USERDIC_FIELDS_NUM)
+        .simple_word_cost(SIMPLE_WORD_COST)
+        .simple_context_id(SIMPLE_CONTEXT_ID)
+        .flexible_csv(false)
+        .simple_userdic_details_handler(Box::new(|row| {
+            Ok(simple_userdic_details(&row[0], &row[1], &row[2]))
+        }))
+        .builder()
+        .unwrap()
+}
+
+/// Build the details of a simple row of a user dictionary CSV, i.e. a row with only the
+/// surface, the part-of-speech and the reading.
+fn simple_userdic_details(_surface: &str, pos: &str, reading: &str) -> Vec<String> {
+    vec![
+        pos.to_string(),     //Major POS classification
+        "*".to_string(),     // Middle POS classification
+        "*".to_string(),     // Small POS classification
+        "*".to_string(),     // Fine POS classification
+        "*".to_string(),     // Conjugation form
+        "*".to_string(),     // Conjugation type
+        reading.to_string(), //Lexeme reading
+        "*".to_string(),     // Lexeme
+        "*".to_string(),     // Orthography appearance type
+        "*".to_string(),     // Pronunciation appearance type
+        "*".to_string(),     // Orthography basic type
+        "*".to_string(),     // Pronunciation basic type
+        "*".to_string(),     // Word type
+        "*".to_string(),     // Prefix of a word form
+        "*".to_string(),     // Prefix of a word type
+        "*".to_string(),     // Suffix of a word form
+        "*".to_string(),     // Suffix of a word type
+    ]
+}
+
+#[cfg(test)]
+#[cfg(not(feature = "compress"))]
+mod tests {
+    use std::borrow::Cow;
+    use std::fs;
+    use std::path::Path;
+
+    use lindera_core::character_definition::CharacterDefinitions;
+    use lindera_core::connection::ConnectionCostMatrix;
+    use lindera_core::dictionary::{strip_dictionary_header, Dictionary, UserDictionary};
+    use lindera_core::dictionary_builder::DictionaryBuilder;
+    use lindera_core::prefix_dict::PrefixDict;
+    use lindera_core::unknown_dictionary::UnknownDictionary;
+
+    use crate::unidic_builder::UnidicBuilder;
+
+    const CHAR_DEF: &str = "DEFAULT 0 1 0
+SPACE 0 1 0
+KANJI 0 0 2
+0x0020 SPACE
+0x4E00..0x9FA5 KANJI
+";
+
+    const UNK_DEF: &str = "DEFAULT,1,1,1000,補助記号,一般,*,*,*,*
+SPACE,1,1,1000,空白,*,*,*,*,*
+KANJI,1,1,1000,名詞,普通名詞,一般,*,*,*
+";
+
+    const MATRIX_DEF: &str = "2 2
+0 0 0
+0 1 0
+1 0 0
+1 1 0
+";
+
+    const LEX_CSV: &str = "東京,1,1,100,名詞,固有名詞,地名,一般,*,*,トウキョウ,トウキョウ,東京,トーキョー,東京,トーキョー,固,*,*,*,*
+都,1,1,200,名詞,普通名詞,一般,*,*,*,ト,都,都,ト,都,ト,漢,*,*,*,*
+";
+
+    fn load_dictionary(dir: &Path) -> Dictionary {
+        Dictionary {
+            dict: PrefixDict::from_static_slice(
+                strip_dictionary_header(&fs::read(dir.join("dict.da")).unwrap()).unwrap(),
+                strip_dictionary_header(&fs::read(dir.join("dict.vals")).unwrap()).unwrap(),
+            ),
+            cost_matrix: ConnectionCostMatrix::load(
+                strip_dictionary_header(&fs::read(dir.join("matrix.mtx")).unwrap()).unwrap(),
+            ),
+            char_definitions: CharacterDefinitions::load(
+                &fs::read(dir.join("char_def.bin")).unwrap(),
+            )
+            .unwrap(),
+            unknown_dictionary: UnknownDictionary::load(&fs::read(dir.join("unk.bin")).unwrap())
+                .unwrap(),
+            words_idx_data: Cow::Owned(
+                strip_dictionary_header(&fs::read(dir.join("dict.wordsidx")).unwrap())
+                    .unwrap()
+                    .to_vec(),
+            ),
+            words_data: Cow::Owned(
+                strip_dictionary_header(&fs::read(dir.join("dict.words")).unwrap())
+                    .unwrap()
+                    .to_vec(),
+            ),
+        }
+    }
+
+    #[test]
+    fn test_build_dictionary() {
+        let base_dir = std::env::temp_dir().join("lindera-unidic-builder-test-build-dictionary");
+        let input_dir = base_dir.join("input");
+        let output_dir = base_dir.join("output");
+        fs::create_dir_all(&input_dir).unwrap();
+        fs::write(input_dir.join("char.def"), CHAR_DEF).unwrap();
+        fs::write(input_dir.join("unk.def"), UNK_DEF).unwrap();
+        fs::write(input_dir.join("matrix.def"), MATRIX_DEF).unwrap();
+        fs::write(input_dir.join("lex.csv"), LEX_CSV).unwrap();
+
+        let builder = UnidicBuilder::new();
+        builder.build_dictionary(&input_dir, &output_dir).unwrap();
+
+        let dictionary = load_dictionary(&output_dir);
+
+        let entries = dictionary.dict.find_surface("東京");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].word_cost, 100);
+        let details = dictionary
+            .word_details(entries[0].word_id.0 as usize)
+            .unwrap();
+        assert_eq!(details.len(), 17);
+        assert_eq!(
+            details,
+            vec![
+                "名詞",
+                "固有名詞",
+                "地名",
+                "一般",
+                "*",
+                "*",
+                "トウキョウ",
+                "トウキョウ",
+                "東京",
+                "トーキョー",
+                "東京",
+                "トーキョー",
+                "固",
+                "*",
+                "*",
+                "*",
+                "*"
+            ]
+        );
+
+        let entries = dictionary.dict.find_surface("都");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].word_cost, 200);
+
+        fs::remove_dir_all(&base_dir).unwrap();
+    }
+
+    #[test]
+    fn test_build_dictionary_without_cost_matrix() {
+        let base_dir =
+            std::env::temp_dir().join("lindera-unidic-builder-test-build-without-cost-matrix");
+        let input_dir = base_dir.join("input");
+        let output_dir = base_dir.join("output");
+        fs::create_dir_all(&input_dir).unwrap();
+        fs::write(input_dir.join("char.def"), CHAR_DEF).unwrap();
+        fs::write(input_dir.join("unk.def"), UNK_DEF).unwrap();
+        fs::write(input_dir.join("matrix.def"), MATRIX_DEF).unwrap();
+        fs::write(input_dir.join("lex.csv"), LEX_CSV).unwrap();
+
+        let builder = UnidicBuilder::new();
+
+        // there is no cost matrix to reuse yet
+        let result = builder.build_dictionary_without_cost_matrix(&input_dir, &output_dir);
+        assert!(result.is_err());
+
+        builder.build_dictionary(&input_dir, &output_dir).unwrap();
+        let matrix = fs::read(output_dir.join("matrix.mtx")).unwrap();
+
+        // the lexicon changes, but the matrix.def is not needed anymore
+        fs::write(
+            input_dir.join("lex.csv"),
+            format!(
+                "{}京都,1,1,300,名詞,固有名詞,地名,一般,*,*,キョウト,キョウト,京都,キョート,京都,キョート,固,*,*,*,*\n",
+                LEX_CSV
+            ),
+        )
+        .unwrap();
+        fs::remove_file(input_dir.join("matrix.def")).unwrap();
+        builder
+            .build_dictionary_without_cost_matrix(&input_dir, &output_dir)
+            .unwrap();
+
+        assert_eq!(fs::read(output_dir.join("matrix.mtx")).unwrap(), matrix);
+        let dictionary = load_dictionary(&output_dir);
+        assert_eq!(dictionary.dict.find_surface("京都").len(), 1);
+        assert_eq!(dictionary.dict.find_surface("東京").len(), 1);
+
+        fs::remove_dir_all(&base_dir).unwrap();
+    }
+
+    #[test]
+    fn test_build_user_dictionary() {
+        let base_dir =
+            std::env::temp_dir().join("lindera-unidic-builder-test-build-user-dictionary");
+        fs::create_dir_all(&base_dir).unwrap();
+        let input_file = base_dir.join("userdic.csv");
+        let output_file = base_dir.join("userdic.bin");
+        fs::write(
+            &input_file,
+            "東京スカイツリー,カスタム名詞,トウキョウスカイツリー\n",
+        )
+        .unwrap();
+
+        let builder = UnidicBuilder::new();
+        builder
+            .build_user_dictionary(&input_file, &output_file)
+            .unwrap();
+
+        let user_dictionary = UserDictionary::load(&fs::read(&output_file).unwrap()).unwrap();
+        let entries = user_dictionary.dict.find_surface("東京スカイツリー");
+        assert_eq!(entries.len(), 1);
+        let details = user_dictionary
+            .word_details(entries[0].word_id.0 as usize)
+            .unwrap();
+        assert_eq!(details.len(), 17);
+        assert_eq!(details[0], "カスタム名詞");
+        assert_eq!(details[6], "トウキョウスカイツリー");
+
+        fs::remove_dir_all(&base_dir).unwrap();
     }
 }
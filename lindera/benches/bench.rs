@@ -13,6 +13,8 @@ use std::{
 use std::path::PathBuf;
 
 use criterion::{criterion_group, criterion_main, Criterion};
+#[cfg(all(feature = "ipadic", feature = "parallel"))]
+use criterion::{BenchmarkId, Throughput};
 
 #[cfg(any(
     feature = "ipadic",
@@ -24,6 +26,9 @@ use lindera::{
     DictionaryConfig, DictionaryKind, Mode, Tokenizer, TokenizerConfig, UserDictionaryConfig,
 };
 
+#[cfg(feature = "ipadic")]
+use lindera::Lattice;
+
 #[allow(unused_variables)]
 fn bench_constructor(c: &mut Criterion) {
     #[cfg(feature = "ipadic")]
@@ -33,12 +38,14 @@ fn bench_constructor(c: &mut Criterion) {
                 let dictionary = DictionaryConfig {
                     kind: Some(DictionaryKind::IPADIC),
                     path: None,
+                    ..Default::default()
                 };
 
                 let config = TokenizerConfig {
                     dictionary,
                     user_dictionary: None,
                     mode: Mode::Normal,
+                    ..Default::default()
                 };
                 Tokenizer::from_config(config).unwrap()
             })
@@ -52,12 +59,14 @@ fn bench_constructor(c: &mut Criterion) {
                 let dictionary = DictionaryConfig {
                     kind: Some(DictionaryKind::UniDic),
                     path: None,
+                    ..Default::default()
                 };
 
                 let config = TokenizerConfig {
                     dictionary,
                     user_dictionary: None,
                     mode: Mode::Normal,
+                    ..Default::default()
                 };
                 Tokenizer::from_config(config).unwrap()
             })
@@ -71,12 +80,14 @@ fn bench_constructor(c: &mut Criterion) {
                 let dictionary = DictionaryConfig {
                     kind: Some(DictionaryKind::KoDic),
                     path: None,
+                    ..Default::default()
                 };
 
                 let config = TokenizerConfig {
                     dictionary,
                     user_dictionary: None,
                     mode: Mode::Normal,
+                    ..Default::default()
                 };
                 Tokenizer::from_config(config).unwrap()
             })
@@ -90,12 +101,14 @@ fn bench_constructor(c: &mut Criterion) {
                 let dictionary = DictionaryConfig {
                     kind: Some(DictionaryKind::CcCedict),
                     path: None,
+                    ..Default::default()
                 };
 
                 let config = TokenizerConfig {
                     dictionary,
                     user_dictionary: None,
                     mode: Mode::Normal,
+                    ..Default::default()
                 };
                 Tokenizer::from_config(config).unwrap()
             })
@@ -112,6 +125,7 @@ fn bench_constructor_with_simple_userdic(c: &mut Criterion) {
                 let dictionary = DictionaryConfig {
                     kind: Some(DictionaryKind::IPADIC),
                     path: None,
+                    ..Default::default()
                 };
 
                 let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -127,6 +141,7 @@ fn bench_constructor_with_simple_userdic(c: &mut Criterion) {
                     dictionary,
                     user_dictionary,
                     mode: Mode::Normal,
+                    ..Default::default()
                 };
                 Tokenizer::from_config(config).unwrap()
             })
@@ -140,6 +155,7 @@ fn bench_constructor_with_simple_userdic(c: &mut Criterion) {
                 let dictionary = DictionaryConfig {
                     kind: Some(DictionaryKind::UniDic),
                     path: None,
+                    ..Default::default()
                 };
 
                 let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -155,6 +171,7 @@ fn bench_constructor_with_simple_userdic(c: &mut Criterion) {
                     dictionary,
                     user_dictionary,
                     mode: Mode::Normal,
+                    ..Default::default()
                 };
                 Tokenizer::from_config(config).unwrap()
             })
@@ -168,6 +185,7 @@ fn bench_constructor_with_simple_userdic(c: &mut Criterion) {
                 let dictionary = DictionaryConfig {
                     kind: Some(DictionaryKind::KoDic),
                     path: None,
+                    ..Default::default()
                 };
 
                 let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -183,6 +201,7 @@ fn bench_constructor_with_simple_userdic(c: &mut Criterion) {
                     dictionary,
                     user_dictionary,
                     mode: Mode::Normal,
+                    ..Default::default()
                 };
                 Tokenizer::from_config(config).unwrap()
             })
@@ -196,6 +215,7 @@ fn bench_constructor_with_simple_userdic(c: &mut Criterion) {
                 let dictionary = DictionaryConfig {
                     kind: Some(DictionaryKind::CcCedict),
                     path: None,
+                    ..Default::default()
                 };
 
                 let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -211,6 +231,7 @@ fn bench_constructor_with_simple_userdic(c: &mut Criterion) {
                     dictionary,
                     user_dictionary,
                     mode: Mode::Normal,
+                    ..Default::default()
                 };
                 Tokenizer::from_config(config).unwrap()
             })
@@ -225,12 +246,14 @@ fn bench_tokenize(c: &mut Criterion) {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -245,12 +268,14 @@ fn bench_tokenize(c: &mut Criterion) {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::UniDic),
             path: None,
+            ..Default::default()
         };
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -265,12 +290,14 @@ fn bench_tokenize(c: &mut Criterion) {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::KoDic),
             path: None,
+            ..Default::default()
         };
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -285,12 +312,14 @@ fn bench_tokenize(c: &mut Criterion) {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::CcCedict),
             path: None,
+            ..Default::default()
         };
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -308,6 +337,7 @@ fn bench_tokenize_with_simple_userdic(c: &mut Criterion) {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -323,6 +353,7 @@ fn bench_tokenize_with_simple_userdic(c: &mut Criterion) {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -338,6 +369,7 @@ fn bench_tokenize_with_simple_userdic(c: &mut Criterion) {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::UniDic),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -353,6 +385,7 @@ fn bench_tokenize_with_simple_userdic(c: &mut Criterion) {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -368,6 +401,7 @@ fn bench_tokenize_with_simple_userdic(c: &mut Criterion) {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::KoDic),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -383,6 +417,7 @@ fn bench_tokenize_with_simple_userdic(c: &mut Criterion) {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -396,6 +431,7 @@ fn bench_tokenize_with_simple_userdic(c: &mut Criterion) {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::CcCedict),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -411,6 +447,7 @@ fn bench_tokenize_with_simple_userdic(c: &mut Criterion) {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -438,6 +475,7 @@ fn bench_tokenize_long_text(c: &mut Criterion) {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -453,6 +491,7 @@ fn bench_tokenize_long_text(c: &mut Criterion) {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
         let tokenizer = Tokenizer::from_config(config).unwrap();
 
@@ -481,6 +520,7 @@ fn bench_tokenize_long_text(c: &mut Criterion) {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::UniDic),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -496,6 +536,7 @@ fn bench_tokenize_long_text(c: &mut Criterion) {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
         let tokenizer = Tokenizer::from_config(config).unwrap();
 
@@ -527,6 +568,7 @@ fn bench_tokenize_details_long_text(c: &mut Criterion) {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -542,12 +584,22 @@ fn bench_tokenize_details_long_text(c: &mut Criterion) {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
         let tokenizer = Tokenizer::from_config(config).unwrap();
 
         // Using benchmark_group for changing sample_size
+        // The details are read lazily, so tokenizing without reading them should be cheaper.
         let mut group = c.benchmark_group("tokenize-details-long-text-ipadic");
         group.sample_size(20);
+        group.bench_function("bench-tokenize-without-details-long-text-ipadic", |b| {
+            b.iter(|| {
+                let tokens = tokenizer.tokenize(long_text.as_str()).unwrap();
+                for token in tokens.iter() {
+                    let _text = &token.text;
+                }
+            });
+        });
         group.bench_function("bench-tokenize-details-long-text-ipadic", |b| {
             b.iter(|| {
                 let mut tokens = tokenizer.tokenize(long_text.as_str()).unwrap();
@@ -575,6 +627,7 @@ fn bench_tokenize_details_long_text(c: &mut Criterion) {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::UniDic),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -590,12 +643,22 @@ fn bench_tokenize_details_long_text(c: &mut Criterion) {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
         let tokenizer = Tokenizer::from_config(config).unwrap();
 
         // Using benchmark_group for changing sample_size
+        // The details are read lazily, so tokenizing without reading them should be cheaper.
         let mut group = c.benchmark_group("tokenize-details-long-text-unidic");
         group.sample_size(20);
+        group.bench_function("bench-tokenize-without-details-long-text-unidic", |b| {
+            b.iter(|| {
+                let tokens = tokenizer.tokenize(long_text.as_str()).unwrap();
+                for token in tokens.iter() {
+                    let _text = &token.text;
+                }
+            });
+        });
         group.bench_function("bench-tokenize-details-long-text-unidic", |b| {
             b.iter(|| {
                 let mut tokens = tokenizer.tokenize(long_text.as_str()).unwrap();
@@ -608,6 +671,131 @@ fn bench_tokenize_details_long_text(c: &mut Criterion) {
     }
 }
 
+#[allow(unused_variables)]
+fn bench_tokenize_short_texts(c: &mut Criterion) {
+    #[cfg(feature = "ipadic")]
+    {
+        let mut long_text_file = BufReader::new(
+            File::open(
+                PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                    .join("../resources")
+                    .join("bocchan.txt"),
+            )
+            .unwrap(),
+        );
+        let mut long_text = String::new();
+        let _size = long_text_file.read_to_string(&mut long_text).unwrap();
+
+        // Use the sentences of the text as a corpus of short queries.
+        let short_texts = long_text
+            .split(['。', '\n'])
+            .filter(|text| !text.is_empty())
+            .take(1000)
+            .collect::<Vec<_>>();
+
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        };
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary: None,
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+        let tokenizer = Tokenizer::from_config(config).unwrap();
+
+        let mut group = c.benchmark_group("tokenize-short-texts-ipadic");
+        group.bench_function("bench-tokenize-short-texts-ipadic", |b| {
+            b.iter(|| {
+                for text in short_texts.iter() {
+                    let _tokens = tokenizer.tokenize(text).unwrap();
+                }
+            });
+        });
+        group.bench_function("bench-tokenize-into-short-texts-ipadic", |b| {
+            let mut tokens = Vec::new();
+            let mut lattice = Lattice::default();
+            b.iter(|| {
+                for text in short_texts.iter().copied() {
+                    tokenizer
+                        .tokenize_into(text, &mut tokens, &mut lattice)
+                        .unwrap();
+                }
+            });
+        });
+        group.finish();
+    }
+}
+
+#[allow(unused_variables)]
+fn bench_tokenize_batch(c: &mut Criterion) {
+    #[cfg(all(feature = "ipadic", feature = "parallel"))]
+    {
+        let mut long_text_file = BufReader::new(
+            File::open(
+                PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                    .join("../resources")
+                    .join("bocchan.txt"),
+            )
+            .unwrap(),
+        );
+        let mut long_text = String::new();
+        let _size = long_text_file.read_to_string(&mut long_text).unwrap();
+
+        // Use the lines of the text as a corpus of documents.
+        let documents = long_text
+            .lines()
+            .filter(|text| !text.is_empty())
+            .map(|text| text.to_string())
+            .collect::<Vec<_>>();
+
+        let dictionary = DictionaryConfig {
+            kind: Some(DictionaryKind::IPADIC),
+            path: None,
+            ..Default::default()
+        };
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary: None,
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+        let tokenizer = Tokenizer::from_config(config).unwrap();
+
+        // Compare a serial loop with `tokenize_batch` for growing batch sizes to find the
+        // crossover point.
+        let mut group = c.benchmark_group("tokenize-batch-ipadic");
+        for size in [1, 4, 16, 64, 256, 1024] {
+            let texts = documents
+                .iter()
+                .cycle()
+                .take(size)
+                .cloned()
+                .collect::<Vec<_>>();
+            group.throughput(Throughput::Elements(size as u64));
+            group.bench_with_input(BenchmarkId::new("serial", size), &texts, |b, texts| {
+                let mut tokens = Vec::new();
+                let mut lattice = Lattice::default();
+                b.iter(|| {
+                    for text in texts.iter() {
+                        tokenizer
+                            .tokenize_into(text, &mut tokens, &mut lattice)
+                            .unwrap();
+                    }
+                });
+            });
+            group.bench_with_input(BenchmarkId::new("parallel", size), &texts, |b, texts| {
+                b.iter(|| tokenizer.tokenize_batch(texts).unwrap());
+            });
+        }
+        group.finish();
+    }
+}
+
 criterion_group!(
     benches,
     bench_constructor,
@@ -616,5 +804,7 @@ criterion_group!(
     bench_tokenize_with_simple_userdic,
     bench_tokenize_long_text,
     bench_tokenize_details_long_text,
+    bench_tokenize_short_texts,
+    bench_tokenize_batch,
 );
 criterion_main!(benches);
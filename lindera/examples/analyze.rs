@@ -35,12 +35,14 @@ fn main() -> LinderaResult<()> {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
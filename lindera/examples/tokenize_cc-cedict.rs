@@ -8,12 +8,14 @@ fn main() -> LinderaResult<()> {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::CcCedict),
             path: None,
+            ..Default::default()
         };
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         #[allow(unused_variables)]
@@ -13,6 +13,7 @@ fn main() -> LinderaResult<()> {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::UniDic),
             path: None,
+            ..Default::default()
         };
 
         let user_dictionary = Some(UserDictionaryConfig {
@@ -26,6 +27,7 @@ fn main() -> LinderaResult<()> {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         #[allow(unused_variables)]
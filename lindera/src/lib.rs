@@ -5,14 +5,24 @@ pub type Mode = lindera_core::mode::Mode;
 pub type Penalty = lindera_core::mode::Penalty;
 pub type DictionaryConfig = lindera_dictionary::DictionaryConfig;
 pub type DictionaryKind = lindera_dictionary::DictionaryKind;
+pub type DetailSchema = lindera_dictionary::DetailSchema;
 pub type UserDictionaryConfig = lindera_dictionary::UserDictionaryConfig;
 pub type Tokenizer = lindera_tokenizer::tokenizer::Tokenizer;
 pub type TokenizerConfig = lindera_tokenizer::tokenizer::TokenizerConfig;
+pub type NormalizationForm = lindera_tokenizer::tokenizer::NormalizationForm;
+pub use lindera_tokenizer::tokenizer::DEFAULT_MAX_UNKNOWN_LENGTH;
 pub type Token<'a> = lindera_tokenizer::token::Token<'a>;
+pub type Lattice = lindera_core::viterbi::Lattice;
+pub type DictionaryCostModel = lindera_core::cost_model::DictionaryCostModel;
+pub use lindera_core::cost_model::CostModel;
+pub type SentenceSplitter = lindera_core::sentence_splitter::SentenceSplitter;
+pub type SentenceSplitterBuilder = lindera_core::sentence_splitter::SentenceSplitterBuilder;
 pub type DictionaryBuilderResolver = lindera_dictionary::DictionaryBuilderResolver;
 pub type DictionaryLoader = lindera_dictionary::DictionaryLoader;
 pub type Dictionary = lindera_core::dictionary::Dictionary;
+pub type DictionaryInfo = lindera_core::dictionary::DictionaryInfo;
 pub type UserDictionary = lindera_core::dictionary::UserDictionary;
+pub type ValidationReport = lindera_core::dictionary_builder::ValidationReport;
 pub type Analyzer = lindera_analyzer::analyzer::Analyzer;
 #[cfg(feature = "filter")]
 pub type AnalyzerConfig = lindera_analyzer::analyzer::AnalyzerConfig;
@@ -20,8 +30,13 @@ pub type AnalyzerConfig = lindera_analyzer::analyzer::AnalyzerConfig;
 pub type FilteredToken = lindera_filter::token::Token;
 #[cfg(feature = "filter")]
 pub type CharacterFilterLoader = lindera_filter::character_filter::CharacterFilterLoader;
+#[cfg(feature = "filter")]
+pub type FilterDescription = lindera_filter::FilterDescription;
 pub type BoxCharacterFilter = lindera_filter::character_filter::BoxCharacterFilter;
 #[cfg(feature = "filter")]
+pub type CombiningSoundMarkCharacterFilter =
+    lindera_filter::character_filter::combining_sound_mark::CombiningSoundMarkCharacterFilter;
+#[cfg(feature = "filter")]
 pub type JapaneseIterationMarkCharacterFilter =
     lindera_filter::character_filter::japanese_iteration_mark::JapaneseIterationMarkCharacterFilter;
 #[cfg(feature = "filter")]
@@ -37,6 +52,9 @@ pub type RegexCharacterFilter = lindera_filter::character_filter::regex::RegexCh
 pub type RegexCharacterFilterConfig =
     lindera_filter::character_filter::regex::RegexCharacterFilterConfig;
 #[cfg(feature = "filter")]
+pub type TrimWhitespaceCharacterFilter =
+    lindera_filter::character_filter::trim_whitespace::TrimWhitespaceCharacterFilter;
+#[cfg(feature = "filter")]
 pub type UnicodeNormalizeCharacterFilter =
     lindera_filter::character_filter::unicode_normalize::UnicodeNormalizeCharacterFilter;
 #[cfg(feature = "filter")]
@@ -49,6 +67,21 @@ pub type UnicodeNormalizeKind =
 pub type TokenFilterLoader = lindera_filter::token_filter::TokenFilterLoader;
 pub type BoxTokenFilter = lindera_filter::token_filter::BoxTokenFilter;
 #[cfg(feature = "filter")]
+pub type CostThresholdTokenFilter =
+    lindera_filter::token_filter::cost_threshold::CostThresholdTokenFilter;
+#[cfg(feature = "filter")]
+pub type CostThresholdTokenFilterConfig =
+    lindera_filter::token_filter::cost_threshold::CostThresholdTokenFilterConfig;
+#[cfg(feature = "filter")]
+pub type DedupeTokenFilter = lindera_filter::token_filter::dedupe::DedupeTokenFilter;
+#[cfg(feature = "filter")]
+pub type DedupeTokenFilterConfig = lindera_filter::token_filter::dedupe::DedupeTokenFilterConfig;
+#[cfg(feature = "filter")]
+pub type ElongationTokenFilter = lindera_filter::token_filter::elongation::ElongationTokenFilter;
+#[cfg(feature = "filter")]
+pub type ElongationTokenFilterConfig =
+    lindera_filter::token_filter::elongation::ElongationTokenFilterConfig;
+#[cfg(feature = "filter")]
 pub type JapaneseBaseFormTokenFilter =
     lindera_filter::token_filter::japanese_base_form::JapaneseBaseFormTokenFilter;
 #[cfg(feature = "filter")]
@@ -97,6 +130,21 @@ pub type JapaneseStopTagsTokenFilter =
 pub type JapaneseStopTagsTokenFilterConfig =
     lindera_filter::token_filter::japanese_stop_tags::JapaneseStopTagsTokenFilterConfig;
 #[cfg(feature = "filter")]
+pub type JapaneseWidthTokenFilter =
+    lindera_filter::token_filter::japanese_width::JapaneseWidthTokenFilter;
+#[cfg(feature = "filter")]
+pub type JapaneseWidthTokenFilterConfig =
+    lindera_filter::token_filter::japanese_width::JapaneseWidthTokenFilterConfig;
+#[cfg(feature = "filter")]
+pub type KanaConvertTokenFilter =
+    lindera_filter::token_filter::kana_convert::KanaConvertTokenFilter;
+#[cfg(feature = "filter")]
+pub type KanaConvertTokenFilterConfig =
+    lindera_filter::token_filter::kana_convert::KanaConvertTokenFilterConfig;
+#[cfg(feature = "filter")]
+pub type KeepLongestTokenFilter =
+    lindera_filter::token_filter::keep_longest::KeepLongestTokenFilter;
+#[cfg(feature = "filter")]
 pub type KeepWordsTokenFilter = lindera_filter::token_filter::keep_words::KeepWordsTokenFilter;
 #[cfg(feature = "filter")]
 pub type KeepWordsTokenFilterConfig =
@@ -123,15 +171,60 @@ pub type LengthTokenFilterConfig = lindera_filter::token_filter::length::LengthT
 #[cfg(feature = "filter")]
 pub type LowercaseTokenFilter = lindera_filter::token_filter::lowercase::LowercaseTokenFilter;
 #[cfg(feature = "filter")]
+pub type LowercaseTokenFilterConfig =
+    lindera_filter::token_filter::lowercase::LowercaseTokenFilterConfig;
+#[cfg(feature = "filter")]
 pub type MappingTokenFilter = lindera_filter::token_filter::mapping::MappingTokenFilter;
 #[cfg(feature = "filter")]
 pub type MappingTokenFilterConfig = lindera_filter::token_filter::mapping::MappingTokenFilterConfig;
 #[cfg(feature = "filter")]
+pub type NgramTokenFilter = lindera_filter::token_filter::ngram::NgramTokenFilter;
+#[cfg(feature = "filter")]
+pub type NgramTokenFilterConfig = lindera_filter::token_filter::ngram::NgramTokenFilterConfig;
+#[cfg(feature = "filter")]
+pub type NumberCounterTokenFilter =
+    lindera_filter::token_filter::number_counter::NumberCounterTokenFilter;
+#[cfg(feature = "filter")]
+pub type NumberCounterTokenFilterConfig =
+    lindera_filter::token_filter::number_counter::NumberCounterTokenFilterConfig;
+#[cfg(feature = "filter")]
+pub type PosMapTokenFilter = lindera_filter::token_filter::pos_map::PosMapTokenFilter;
+#[cfg(feature = "filter")]
+pub type PosMapTokenFilterConfig = lindera_filter::token_filter::pos_map::PosMapTokenFilterConfig;
+#[cfg(feature = "filter")]
+pub type PosMapTarget = lindera_filter::token_filter::pos_map::PosMapTarget;
+#[cfg(feature = "filter")]
+pub type PunctuationTokenFilter = lindera_filter::token_filter::punctuation::PunctuationTokenFilter;
+#[cfg(feature = "filter")]
+pub type PunctuationTokenFilterConfig =
+    lindera_filter::token_filter::punctuation::PunctuationTokenFilterConfig;
+#[cfg(feature = "filter")]
+pub type ReadingAtSamePositionTokenFilter =
+    lindera_filter::token_filter::reading_at_same_position::ReadingAtSamePositionTokenFilter;
+#[cfg(feature = "filter")]
+pub type ReadingAtSamePositionTokenFilterConfig =
+    lindera_filter::token_filter::reading_at_same_position::ReadingAtSamePositionTokenFilterConfig;
+#[cfg(feature = "filter")]
+pub type RegexKeepTokenFilter = lindera_filter::token_filter::regex_keep::RegexKeepTokenFilter;
+#[cfg(feature = "filter")]
+pub type RegexKeepTokenFilterConfig =
+    lindera_filter::token_filter::regex_keep::RegexKeepTokenFilterConfig;
+#[cfg(feature = "filter")]
+pub type ScriptTagTokenFilter = lindera_filter::token_filter::script_tag::ScriptTagTokenFilter;
+#[cfg(feature = "filter")]
+pub type StemTokenFilter = lindera_filter::token_filter::stem::StemTokenFilter;
+#[cfg(feature = "filter")]
+pub type StemTokenFilterConfig = lindera_filter::token_filter::stem::StemTokenFilterConfig;
+#[cfg(feature = "filter")]
 pub type StopWordsTokenFilter = lindera_filter::token_filter::stop_words::StopWordsTokenFilter;
 #[cfg(feature = "filter")]
 pub type StopWordsTokenFilterConfig =
     lindera_filter::token_filter::stop_words::StopWordsTokenFilterConfig;
 #[cfg(feature = "filter")]
+pub type SynonymTokenFilter = lindera_filter::token_filter::synonym::SynonymTokenFilter;
+#[cfg(feature = "filter")]
+pub type SynonymTokenFilterConfig = lindera_filter::token_filter::synonym::SynonymTokenFilterConfig;
+#[cfg(feature = "filter")]
 pub type UppercaseTokenFilter = lindera_filter::token_filter::uppercase::UppercaseTokenFilter;
 
 #[cfg(test)]
@@ -211,12 +304,14 @@ mod tests {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -468,12 +563,14 @@ mod tests {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary: None,
             mode: Mode::Decompose(Penalty::default()),
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -562,6 +659,7 @@ mod tests {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -577,6 +675,7 @@ mod tests {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -716,6 +815,7 @@ mod tests {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -731,6 +831,7 @@ mod tests {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -881,12 +982,14 @@ mod tests {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -940,12 +1043,14 @@ mod tests {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::UniDic),
             path: None,
+            ..Default::default()
         };
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -1351,6 +1456,7 @@ mod tests {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::UniDic),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -1366,6 +1472,7 @@ mod tests {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -1621,6 +1728,7 @@ mod tests {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::UniDic),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -1636,6 +1744,7 @@ mod tests {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -1930,12 +2039,14 @@ mod tests {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::KoDic),
             path: None,
+            ..Default::default()
         };
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -2101,6 +2212,7 @@ mod tests {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::KoDic),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -2116,6 +2228,7 @@ mod tests {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -2186,6 +2299,7 @@ mod tests {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::KoDic),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -2201,6 +2315,7 @@ mod tests {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -2310,12 +2425,14 @@ mod tests {
         let dictionary = lindera_dictionary::DictionaryConfig {
             kind: Some(DictionaryKind::CcCedict),
             path: None,
+            ..Default::default()
         };
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -2437,12 +2554,49 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(feature = "cc-cedict")]
+    fn test_analyze_pinyin_cc_cedict() {
+        let dictionary = lindera_dictionary::DictionaryConfig {
+            kind: Some(DictionaryKind::CcCedict),
+            path: None,
+            ..Default::default()
+        };
+
+        let config = TokenizerConfig {
+            dictionary,
+            user_dictionary: None,
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+
+        let tokenizer = Tokenizer::from_config(config).unwrap();
+        let analyzer = crate::Analyzer::new(Vec::new(), tokenizer, Vec::new());
+
+        let tokens = analyzer.analyze("可以进行中文形态学分析。").unwrap();
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|token| (token.text.as_str(), token.pinyin()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("可以", Some("ke3 yi3")),
+                ("进行", Some("jin4 xing2")),
+                ("中文", Some("Zhong1 wen2")),
+                ("形态学", Some("xing2 tai4 xue2")),
+                ("分析", Some("fen1 xi1")),
+                ("。", None),
+            ]
+        );
+    }
+
     #[test]
     #[cfg(feature = "cc-cedict")]
     fn test_tokenize_with_simple_userdic_cc_cedict() {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::CcCedict),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -2458,6 +2612,7 @@ mod tests {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -2556,6 +2711,7 @@ mod tests {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::CcCedict),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -2571,6 +2727,7 @@ mod tests {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -2669,6 +2826,7 @@ mod tests {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -2684,6 +2842,7 @@ mod tests {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -2824,6 +2983,7 @@ mod tests {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -2839,6 +2999,7 @@ mod tests {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         Tokenizer::from_config(config).unwrap();
@@ -2851,6 +3012,7 @@ mod tests {
         let dictionary = DictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: None,
+            ..Default::default()
         };
 
         let userdic_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -2866,6 +3028,7 @@ mod tests {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..Default::default()
         };
 
         Tokenizer::from_config(config).unwrap();